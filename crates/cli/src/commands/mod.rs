@@ -13,6 +13,7 @@ pub mod info;
 pub mod inspect;
 pub mod migrate;
 pub mod optimize;
+pub mod route;
 pub mod status;
 pub mod test;
 pub mod update;
@@ -23,7 +24,6 @@ pub mod module;
 // These modules contain unused code and will be re-enabled as needed
 pub mod new;
 // pub mod generate;
-// pub mod route;
 // pub mod model;
 // pub mod resource;
 pub mod make;
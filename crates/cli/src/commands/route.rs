@@ -1,6 +1,11 @@
 use elif_core::ElifError;
+use elif_web::http::RouteLimits;
+use quote::ToTokens;
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
+use syn::visit::Visit;
+use syn::{Attribute, Expr, ExprMethodCall, ImplItem, Item, Lit, LitStr};
 
 pub async fn add_route(method: &str, path: &str, controller: &str) -> Result<(), ElifError> {
     // Validate method
@@ -24,28 +29,451 @@ pub async fn add_route(method: &str, path: &str, controller: &str) -> Result<(),
     Ok(())
 }
 
+
 pub async fn list_routes() -> Result<(), ElifError> {
-    let routes_file = "src/routes/mod.rs";
-    
-    if !Path::new(routes_file).exists() {
+    let routes_file = Path::new("src/routes/mod.rs");
+    let controllers_dir = Path::new("src/controllers");
+
+    if !routes_file.exists() && !controllers_dir.exists() {
         println!("No routes found. Create an elif app first with: elif new <app_name>");
         return Ok(());
     }
-    
-    let content = fs::read_to_string(routes_file).map_err(|e| ElifError::Io(e))?;
-    
-    println!("📋 Current Routes:");
-    
-    // Parse routes from the file (simple parsing for now)
-    for line in content.lines() {
-        if line.trim().starts_with(".route(") {
-            println!("  {}", line.trim());
+
+    let mut routes = Vec::new();
+    if routes_file.exists() {
+        if let Some(file) = read_source(routes_file)? {
+            routes.extend(parse_router_routes(&file));
         }
     }
-    
+    if controllers_dir.exists() {
+        let mut files: Vec<_> = fs::read_dir(controllers_dir)
+            .map_err(ElifError::Io)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+            .collect();
+        files.sort();
+        for path in files {
+            if let Some(file) = read_source(&path)? {
+                routes.extend(parse_controller_routes(&file));
+            }
+        }
+    }
+
+    if routes.is_empty() {
+        println!("No routes found.");
+        return Ok(());
+    }
+
+    println!("📋 Current Routes:");
+    let path_width = routes
+        .iter()
+        .map(|route| route.path.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    println!(
+        "  {:<7} {:<width$} LIMITS",
+        "METHOD",
+        "PATH",
+        width = path_width
+    );
+    for route in &routes {
+        println!(
+            "  {:<7} {:<width$} {}",
+            route.method,
+            route.path,
+            route.describe_limits(),
+            width = path_width
+        );
+    }
+
     Ok(())
 }
 
+/// Parse a source file, skipping it with a warning when it is not valid Rust
+fn read_source(path: &Path) -> Result<Option<syn::File>, ElifError> {
+    let content = fs::read_to_string(path).map_err(ElifError::Io)?;
+    match syn::parse_file(&content) {
+        Ok(file) => Ok(Some(file)),
+        Err(error) => {
+            eprintln!("⚠️  Skipping {}: {}", path.display(), error);
+            Ok(None)
+        }
+    }
+}
+
+/// A route found in the application sources
+#[derive(Debug, Default, PartialEq)]
+struct ListedRoute {
+    method: String,
+    path: String,
+    limits: RouteLimits,
+    /// Limits whose value is only known at runtime, e.g. `timeout=UPLOAD_TIMEOUT`
+    unresolved: Vec<String>,
+}
+
+impl ListedRoute {
+    fn describe_limits(&self) -> String {
+        if self.unresolved.is_empty() {
+            return self.limits.to_string();
+        }
+
+        let mut limits = Vec::new();
+        if !self.limits.is_empty() {
+            limits.push(self.limits.to_string());
+        }
+        limits.extend(self.unresolved.iter().cloned());
+        limits.join(" ")
+    }
+
+    /// Apply a limit set from code, as in `.timeout(Duration::from_secs(30))`
+    fn apply_limit(&mut self, name: &str, value: &Expr) {
+        let applied = match name {
+            "timeout" => eval_duration(value).map(|timeout| self.limits.timeout = Some(timeout)),
+            "max_body_size" => {
+                eval_integer(value).map(|bytes| self.limits.max_body_size = Some(bytes))
+            }
+            "max_concurrency" => {
+                eval_integer(value).map(|requests| self.limits.max_concurrency = Some(requests))
+            }
+            "limits" => self.apply_route_limits(value),
+            _ => return,
+        };
+        if applied.is_none() {
+            self.unresolved
+                .push(format!("{}={}", name, source_text(value)));
+        }
+    }
+
+    /// Apply a `RouteLimits::new().timeout(..)...` expression
+    fn apply_route_limits(&mut self, value: &Expr) -> Option<()> {
+        let (root, chain) = flatten_method_chain(value);
+        let Expr::Call(constructor) = root else {
+            return None;
+        };
+        let Expr::Path(path) = &*constructor.func else {
+            return None;
+        };
+        let segments: Vec<_> = path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        if !matches!(
+            segments
+                .iter()
+                .rev()
+                .take(2)
+                .map(String::as_str)
+                .collect::<Vec<_>>()[..],
+            ["new" | "default", "RouteLimits"]
+        ) {
+            return None;
+        }
+
+        self.limits = RouteLimits::default();
+        for call in chain {
+            if let [value] = call.args.iter().collect::<Vec<_>>()[..] {
+                self.apply_limit(&call.method.to_string(), value);
+            }
+        }
+        Some(())
+    }
+}
+
+const HTTP_METHODS: [&str; 7] = ["get", "post", "put", "delete", "patch", "head", "options"];
+
+/// Find routes registered on the router in `src/routes/mod.rs`
+///
+/// Understands `.route("/path")` builders with limits (`.route("/p").timeout(..).get(h)`),
+/// direct registrations (`.get("/p", h)`) and axum-style `.route("/p", get(h))` calls.
+fn parse_router_routes(file: &syn::File) -> Vec<ListedRoute> {
+    let mut visitor = RouterVisitor::default();
+    visitor.visit_file(file);
+    visitor.routes
+}
+
+#[derive(Default)]
+struct RouterVisitor {
+    routes: Vec<ListedRoute>,
+}
+
+impl<'ast> Visit<'ast> for RouterVisitor {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        // Only the outermost call of a chain is visited, so each chain is read once
+        let mut chain = vec![call];
+        let mut receiver = &*call.receiver;
+        while let Expr::MethodCall(inner) = receiver {
+            chain.push(inner);
+            receiver = &inner.receiver;
+        }
+        chain.reverse();
+
+        self.routes.extend(routes_in_chain(&chain));
+
+        self.visit_expr(receiver);
+        for call in chain {
+            for arg in &call.args {
+                self.visit_expr(arg);
+            }
+        }
+    }
+}
+
+/// Read the routes registered by a chain of router method calls
+fn routes_in_chain(chain: &[&ExprMethodCall]) -> Vec<ListedRoute> {
+    let mut routes = Vec::new();
+    let mut builder: Option<ListedRoute> = None;
+
+    for call in chain {
+        let name = call.method.to_string();
+        let args: Vec<&Expr> = call.args.iter().collect();
+        match (name.as_str(), &args[..]) {
+            ("route", [path]) => {
+                builder = string_literal(path).map(|path| ListedRoute {
+                    path,
+                    ..Default::default()
+                })
+            }
+            ("route", [path, method_router]) => {
+                builder = None;
+                if let Some(path) = string_literal(path) {
+                    routes.extend(
+                        method_router_methods(method_router)
+                            .into_iter()
+                            .map(|method| ListedRoute {
+                                method,
+                                path: path.clone(),
+                                ..Default::default()
+                            }),
+                    );
+                }
+            }
+            (_, [value]) if builder.is_some() => {
+                if HTTP_METHODS.contains(&name.as_str()) || name == "any" {
+                    // A method call registers the route and ends the builder
+                    let mut route = builder.take().unwrap();
+                    route.method = name.to_uppercase();
+                    routes.push(route);
+                } else if let Some(route) = builder.as_mut() {
+                    route.apply_limit(&name, value);
+                }
+            }
+            (_, [path, _handler]) if HTTP_METHODS.contains(&name.as_str()) => {
+                builder = None;
+                if let Some(path) = string_literal(path) {
+                    routes.push(ListedRoute {
+                        method: name.to_uppercase(),
+                        path,
+                        ..Default::default()
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    routes
+}
+
+/// Methods of an axum method router such as `get(show).post(update)`
+fn method_router_methods(expr: &Expr) -> Vec<String> {
+    let (root, chain) = flatten_method_chain(expr);
+
+    let mut methods = Vec::new();
+    if let Expr::Call(call) = root {
+        if let Expr::Path(path) = &*call.func {
+            if let Some(segment) = path.path.segments.last() {
+                methods.push(segment.ident.to_string());
+            }
+        }
+    }
+    methods.extend(chain.iter().map(|call| call.method.to_string()));
+
+    methods
+        .into_iter()
+        .filter(|method| HTTP_METHODS.contains(&method.as_str()))
+        .map(|method| method.to_uppercase())
+        .collect()
+}
+
+/// Split `root.a(..).b(..)` into `root` and the calls in order
+fn flatten_method_chain(expr: &Expr) -> (&Expr, Vec<&ExprMethodCall>) {
+    let mut chain = Vec::new();
+    let mut current = strip_parens(expr);
+    while let Expr::MethodCall(call) = current {
+        chain.push(call);
+        current = strip_parens(&call.receiver);
+    }
+    chain.reverse();
+    (current, chain)
+}
+
+fn strip_parens(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(inner) => strip_parens(&inner.expr),
+        Expr::Group(inner) => strip_parens(&inner.expr),
+        expr => expr,
+    }
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match strip_parens(expr) {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) => Some(value.value()),
+        _ => None,
+    }
+}
+
+/// Evaluate `Duration::from_secs(..)` and `Duration::from_millis(..)` with constant amounts
+fn eval_duration(expr: &Expr) -> Option<Duration> {
+    let Expr::Call(call) = strip_parens(expr) else {
+        return None;
+    };
+    let Expr::Path(path) = &*call.func else {
+        return None;
+    };
+    let [amount] = call.args.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let amount = u64::try_from(eval_integer(amount)?).ok()?;
+    match path.path.segments.last()?.ident.to_string().as_str() {
+        "from_secs" => Some(Duration::from_secs(amount)),
+        "from_millis" => Some(Duration::from_millis(amount)),
+        _ => None,
+    }
+}
+
+/// Evaluate constant integer expressions such as `50 * 1024 * 1024`
+fn eval_integer(expr: &Expr) -> Option<usize> {
+    match strip_parens(expr) {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }) => value.base10_parse().ok(),
+        Expr::Binary(binary) => {
+            let left = eval_integer(&binary.left)?;
+            let right = eval_integer(&binary.right)?;
+            match binary.op {
+                syn::BinOp::Mul(_) => left.checked_mul(right),
+                syn::BinOp::Add(_) => left.checked_add(right),
+                syn::BinOp::Shl(_) => left.checked_shl(u32::try_from(right).ok()?),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Source of an expression without token spacing, e.g. `config::UPLOAD_TIMEOUT`
+fn source_text(expr: &Expr) -> String {
+    expr.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Find routes declared with `#[controller]` and HTTP method attributes in a controller file
+fn parse_controller_routes(file: &syn::File) -> Vec<ListedRoute> {
+    let base_path = file.items.iter().find_map(|item| match item {
+        Item::Struct(item) => controller_path(&item.attrs),
+        Item::Impl(item) => controller_path(&item.attrs),
+        _ => None,
+    });
+    let Some(base_path) = base_path else {
+        return Vec::new();
+    };
+    let base_path = base_path.trim_end_matches('/');
+
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Impl(item) => Some(item),
+            _ => None,
+        })
+        .flat_map(|item| &item.items)
+        .filter_map(|item| match item {
+            ImplItem::Fn(method) => controller_route(base_path, &method.attrs),
+            _ => None,
+        })
+        .collect()
+}
+
+fn controller_path(attrs: &[Attribute]) -> Option<String> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("controller"))?;
+    attr.parse_args::<LitStr>().ok().map(|path| path.value())
+}
+
+/// Read the route of a controller method from its attributes, in whatever order they come
+fn controller_route(base_path: &str, attrs: &[Attribute]) -> Option<ListedRoute> {
+    let (method, route_path) = attrs.iter().find_map(|attr| {
+        let method = attr.path().get_ident()?.to_string();
+        if !HTTP_METHODS.contains(&method.as_str()) {
+            return None;
+        }
+        let route_path = match &attr.meta {
+            syn::Meta::Path(_) => String::new(),
+            _ => attr.parse_args::<LitStr>().ok()?.value(),
+        };
+        Some((method, route_path))
+    })?;
+
+    let path = match (base_path, route_path.as_str()) {
+        ("", "") => "/".to_string(),
+        (base, "") => base.to_string(),
+        (base, path) if path.starts_with('/') => format!("{}{}", base, path),
+        (base, path) => format!("{}/{}", base, path),
+    };
+
+    let mut route = ListedRoute {
+        method: method.to_uppercase(),
+        path,
+        ..Default::default()
+    };
+    if let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("limits")) {
+        apply_attribute_limits(&mut route, attr);
+    }
+    Some(route)
+}
+
+/// Read the arguments of `#[limits(timeout = "30s", max_body_size = "10MB", max_concurrency = 4)]`
+fn apply_attribute_limits(route: &mut ListedRoute, attr: &Attribute) {
+    let _ = attr.parse_nested_meta(|meta| {
+        let Some(name) = meta.path.get_ident().map(|name| name.to_string()) else {
+            return Ok(());
+        };
+        let value: Lit = meta.value()?.parse()?;
+        // Bare integers are seconds for timeouts and bytes for body sizes
+        let applied = match (name.as_str(), &value) {
+            ("timeout", Lit::Int(secs)) => secs
+                .base10_parse()
+                .ok()
+                .map(|secs| route.limits.timeout = Some(Duration::from_secs(secs))),
+            ("timeout", Lit::Str(value)) => RouteLimits::parse_duration(&value.value())
+                .map(|timeout| route.limits.timeout = Some(timeout)),
+            ("max_body_size", Lit::Int(bytes)) => bytes
+                .base10_parse()
+                .ok()
+                .map(|bytes| route.limits.max_body_size = Some(bytes)),
+            ("max_body_size", Lit::Str(value)) => RouteLimits::parse_size(&value.value())
+                .map(|bytes| route.limits.max_body_size = Some(bytes)),
+            ("max_concurrency", Lit::Int(requests)) => requests
+                .base10_parse()
+                .ok()
+                .map(|requests| route.limits.max_concurrency = Some(requests)),
+            _ => Some(()),
+        };
+        if applied.is_none() {
+            route
+                .unresolved
+                .push(format!("{}={}", name, value.to_token_stream()));
+        }
+        Ok(())
+    });
+}
+
 async fn create_controller_if_missing(controller_name: &str) -> Result<(), ElifError> {
     let controller_path = format!("src/controllers/{}.rs", controller_name);
     
@@ -72,7 +500,7 @@ pub async fn {}() -> Result<Json<Value>, StatusCode> {{
 // <<<ELIF:END agent-editable:{}>>>
 "#, controller_name, controller_name, controller_name, controller_name);
     
-    fs::write(&controller_path, controller_content).map_err(|e| ElifError::Io(e))?;
+    fs::write(&controller_path, controller_content).map_err(ElifError::Io)?;
     
     // Update controllers/mod.rs
     update_controllers_mod(controller_name)?;
@@ -84,7 +512,7 @@ pub async fn {}() -> Result<Json<Value>, StatusCode> {{
 
 fn update_controllers_mod(controller_name: &str) -> Result<(), ElifError> {
     let mod_path = "src/controllers/mod.rs";
-    let mut content = fs::read_to_string(mod_path).map_err(|e| ElifError::Io(e))?;
+    let mut content = fs::read_to_string(mod_path).map_err(ElifError::Io)?;
     
     // Add module declaration if not present
     let mod_declaration = format!("pub mod {};", controller_name);
@@ -112,7 +540,7 @@ fn update_controllers_mod(controller_name: &str) -> Result<(), ElifError> {
             content = format!("{}\n{}", mod_declaration, content);
         }
         
-        fs::write(mod_path, content).map_err(|e| ElifError::Io(e))?;
+        fs::write(mod_path, content).map_err(ElifError::Io)?;
     }
     
     Ok(())
@@ -120,7 +548,7 @@ fn update_controllers_mod(controller_name: &str) -> Result<(), ElifError> {
 
 async fn add_route_to_router(method: &str, path: &str, controller: &str) -> Result<(), ElifError> {
     let routes_path = "src/routes/mod.rs";
-    let content = fs::read_to_string(routes_path).map_err(|e| ElifError::Io(e))?;
+    let content = fs::read_to_string(routes_path).map_err(ElifError::Io)?;
     
     let axum_method = match method {
         "GET" => "get",
@@ -142,10 +570,94 @@ async fn add_route_to_router(method: &str, path: &str, controller: &str) -> Resu
             &format!("Router::new()\n{}", route_line)
         );
         
-        fs::write(routes_path, new_content).map_err(|e| ElifError::Io(e))?;
+        fs::write(routes_path, new_content).map_err(ElifError::Io)?;
     } else {
         return Err(ElifError::Validation { message: "Could not find Router::new() in src/routes/mod.rs".to_string() });
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> syn::File {
+        syn::parse_file(content).unwrap()
+    }
+
+    #[test]
+    fn test_parse_router_routes_with_limits() {
+        let content = r#"
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub fn router() -> ElifRouter {
+    ElifRouter::new()
+        .get("/health", health)
+        .route("/uploads")
+            .timeout(Duration::from_secs(120))
+            .max_body_size(50 * 1024 * 1024)
+            .max_concurrency(4)
+            .post(upload)
+        .route("/imports")
+            .timeout(UPLOAD_TIMEOUT)
+            .put(import)
+        .route("/exports")
+            .limits(RouteLimits::new().timeout(Duration::from_millis(1500)))
+            .get(export)
+        .route("/legacy", get(legacy).post(legacy))
+}
+"#;
+        let routes = parse_router_routes(&parse(content));
+        assert_eq!(routes.len(), 6);
+        assert_eq!(routes[0].path, "/health");
+        assert_eq!(routes[0].describe_limits(), "-");
+        assert_eq!(routes[1].method, "POST");
+        assert_eq!(
+            routes[1].describe_limits(),
+            "timeout=120s body=50MB concurrency=4"
+        );
+        assert_eq!(routes[2].method, "PUT");
+        assert_eq!(routes[2].describe_limits(), "timeout=UPLOAD_TIMEOUT");
+        assert_eq!(routes[3].describe_limits(), "timeout=1.5s");
+        assert_eq!(
+            (routes[4].method.as_str(), routes[4].path.as_str()),
+            ("GET", "/legacy")
+        );
+        assert_eq!(routes[5].method, "POST");
+    }
+
+    #[test]
+    fn test_parse_controller_routes_with_limits() {
+        let content = r#"
+#[controller("/api/files")]
+impl FileController {
+    #[get("")]
+    async fn index(&self) -> HttpResult<ElifResponse> { todo!() }
+
+    #[post("/upload")]
+    #[limits(timeout = "2m", max_body_size = "10MB", max_concurrency = 2)]
+    async fn upload(&self) -> HttpResult<ElifResponse> { todo!() }
+
+    #[limits(timeout = 30)]
+    #[middleware("auth")]
+    #[put("/{id}")]
+    async fn replace(&self) -> HttpResult<ElifResponse> { todo!() }
+
+    fn helper(&self) {}
+}
+"#;
+        let routes = parse_controller_routes(&parse(content));
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].path, "/api/files");
+        assert!(routes[0].limits.is_empty());
+        assert_eq!(routes[1].path, "/api/files/upload");
+        assert_eq!(
+            routes[1].describe_limits(),
+            "timeout=120s body=10MB concurrency=2"
+        );
+        assert_eq!(routes[2].method, "PUT");
+        assert_eq!(routes[2].path, "/api/files/{id}");
+        assert_eq!(routes[2].describe_limits(), "timeout=30s");
+    }
+}
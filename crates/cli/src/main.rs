@@ -275,6 +275,12 @@ enum Commands {
         #[command(subcommand)]
         make_command: MakeCommands,
     },
+
+    /// Route management
+    Route {
+        #[command(subcommand)]
+        route_command: RouteCommands,
+    },
 }
 
 // ========== Original Elif.rs Command Structure ==========
@@ -409,6 +415,24 @@ enum InspectCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum RouteCommands {
+    /// Add a route and its handler
+    Add {
+        /// HTTP method (GET, POST, PUT, DELETE, PATCH)
+        method: String,
+
+        /// Route path
+        path: String,
+
+        /// Handler name
+        controller: String,
+    },
+
+    /// List routes with their methods, paths and limits
+    List,
+}
+
 #[derive(Subcommand)]
 enum DeployCommands {
     /// Prepare for deployment (validation, optimization, packaging)
@@ -572,6 +596,13 @@ enum MigrateCommands {
         /// Migration name
         name: String,
     },
+
+    /// Squash applied migrations into a schema snapshot
+    Squash {
+        /// Remove the squashed migration files
+        #[arg(long)]
+        prune: bool,
+    },
 }
 
 // ========== Legacy Command Support ==========
@@ -981,6 +1012,9 @@ async fn main() -> Result<(), ElifError> {
             MigrateCommands::Create { name } => {
                 commands::migrate::create(&name).await?;
             }
+            MigrateCommands::Squash { prune } => {
+                commands::migrate::squash(prune).await?;
+            }
         },
 
         Commands::Build {
@@ -1000,6 +1034,19 @@ async fn main() -> Result<(), ElifError> {
             commands::optimize::run(routes, assets, config, force).await?;
         }
 
+        Commands::Route { route_command } => match route_command {
+            RouteCommands::Add {
+                method,
+                path,
+                controller,
+            } => {
+                commands::route::add_route(&method, &path, &controller).await?;
+            }
+            RouteCommands::List => {
+                commands::route::list_routes().await?;
+            }
+        },
+
         Commands::Deploy { deploy_command } => match deploy_command {
             DeployCommands::Prepare { target, env } => {
                 commands::deploy::prepare(&target, &env).await?;
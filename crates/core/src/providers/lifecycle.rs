@@ -64,6 +64,16 @@ impl ProviderLifecycleManager {
         Ok(container)
     }
 
+    /// Shut down all providers in reverse dependency order
+    pub fn shutdown(&mut self, container: &Container) -> Result<(), ProviderError> {
+        let start_time = Instant::now();
+        tracing::info!("Shutting down providers...");
+
+        let result = self.registry.shutdown_all(container);
+        self.lifecycle_stats.shutdown_time = start_time.elapsed();
+        result
+    }
+
     /// Get lifecycle statistics
     pub fn lifecycle_stats(&self) -> &ProviderLifecycleStats {
         &self.lifecycle_stats
@@ -90,6 +100,7 @@ pub struct ProviderLifecycleStats {
     pub registration_time: std::time::Duration,
     pub container_build_time: std::time::Duration,
     pub boot_time: std::time::Duration,
+    pub shutdown_time: std::time::Duration,
 }
 
 impl ProviderLifecycleStats {
@@ -102,6 +113,7 @@ impl ProviderLifecycleStats {
             registration_time: std::time::Duration::ZERO,
             container_build_time: std::time::Duration::ZERO,
             boot_time: std::time::Duration::ZERO,
+            shutdown_time: std::time::Duration::ZERO,
         }
     }
 }
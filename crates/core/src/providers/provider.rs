@@ -19,6 +19,9 @@ pub enum ProviderError {
     #[error("Provider boot failed: {message}")]
     BootFailed { message: String },
 
+    #[error("Provider shutdown failed: {message}")]
+    ShutdownFailed { message: String },
+
     #[error("Container error: {0}")]
     Container(#[from] CoreError),
 }
@@ -40,6 +43,13 @@ pub trait ServiceProvider: Send + Sync {
         Ok(())
     }
 
+    /// Release the provider's resources when the application shuts down
+    /// This is called in reverse boot order, so dependencies are still available
+    fn shutdown(&self, container: &Container) -> Result<(), ProviderError> {
+        let _ = container;
+        Ok(())
+    }
+
     /// Provider dependencies (other providers that must be registered first)
     fn dependencies(&self) -> Vec<&'static str> {
         vec![]
@@ -193,6 +203,7 @@ macro_rules! provider {
         $(optional: $optional:expr,)?
         register: |$builder:ident| $register:block
         $(, boot: |$container:ident| $boot:block)?
+        $(, shutdown: |$shutdown_container:ident| $shutdown:block)?
     ) => {
         {
             struct CustomProvider;
@@ -233,6 +244,12 @@ macro_rules! provider {
                 {
                     $boot
                 })?
+
+                $(fn shutdown(&self, $shutdown_container: &$crate::container::Container)
+                    -> Result<(), $crate::providers::ProviderError>
+                {
+                    $shutdown
+                })?
             }
 
             CustomProvider
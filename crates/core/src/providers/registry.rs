@@ -161,6 +161,28 @@ impl ProviderRegistry {
         }
         Ok(())
     }
+
+    /// Shut down all providers in reverse boot order
+    ///
+    /// Every provider is shut down even when an earlier one fails; the first failure is
+    /// returned.
+    pub fn shutdown_all(
+        &self,
+        container: &crate::container::Container,
+    ) -> Result<(), ProviderError> {
+        let mut first_error = None;
+        for &index in self.boot_order.iter().rev() {
+            let provider = &self.providers[index];
+            tracing::info!("Shutting down provider: {}", provider.name());
+            if let Err(e) = provider.shutdown(container) {
+                tracing::error!("Failed to shut down provider '{}': {}", provider.name(), e);
+                first_error.get_or_insert(ProviderError::ShutdownFailed {
+                    message: format!("Failed to shut down provider '{}': {}", provider.name(), e),
+                });
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
 }
 
 impl Default for ProviderRegistry {
@@ -168,3 +190,66 @@ impl Default for ProviderRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::Container;
+    use std::sync::{Arc, Mutex};
+
+    struct RecordingProvider {
+        name: &'static str,
+        dependencies: Vec<&'static str>,
+        fail: bool,
+        shutdowns: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl ServiceProvider for RecordingProvider {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn register(&self, builder: ContainerBuilder) -> Result<ContainerBuilder, ProviderError> {
+            Ok(builder)
+        }
+
+        fn shutdown(&self, _container: &Container) -> Result<(), ProviderError> {
+            self.shutdowns.lock().unwrap().push(self.name);
+            if self.fail {
+                return Err(ProviderError::RegistrationFailed {
+                    message: "connection lost".to_string(),
+                });
+            }
+            Ok(())
+        }
+
+        fn dependencies(&self) -> Vec<&'static str> {
+            self.dependencies.clone()
+        }
+    }
+
+    #[test]
+    fn test_shutdown_in_reverse_dependency_order() {
+        let shutdowns = Arc::new(Mutex::new(Vec::new()));
+        let provider = |name, dependencies, fail| RecordingProvider {
+            name,
+            dependencies,
+            fail,
+            shutdowns: shutdowns.clone(),
+        };
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider("cache", vec!["database"], true));
+        registry.register(provider("database", vec!["config"], false));
+        registry.register(provider("config", vec![], false));
+        registry.resolve_dependencies().unwrap();
+
+        let result = registry.shutdown_all(&Container::new());
+        assert!(matches!(result, Err(ProviderError::ShutdownFailed { .. })));
+        // A failing provider does not stop the others from shutting down
+        assert_eq!(
+            *shutdowns.lock().unwrap(),
+            vec!["cache", "database", "config"]
+        );
+    }
+}
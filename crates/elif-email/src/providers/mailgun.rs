@@ -1,3 +1,4 @@
+use super::{inject_trace_context, request_span};
 use crate::{config::MailgunConfig, Email, EmailError, EmailProvider, EmailResult};
 use async_trait::async_trait;
 use reqwest::{
@@ -7,7 +8,7 @@ use reqwest::{
 };
 use serde::Deserialize;
 use std::time::Duration;
-use tracing::{debug, error};
+use tracing::{debug, error, Instrument};
 
 /// Mailgun email provider using reqwest HTTP client
 #[derive(Clone)]
@@ -166,15 +167,18 @@ impl EmailProvider for MailgunProvider {
         );
 
         let form = self.convert_email(email)?;
-        let headers = self.build_headers()?;
+        let mut headers = self.build_headers()?;
         let endpoint = self.get_endpoint();
 
+        let span = request_span("mailgun", email);
+        inject_trace_context(&span, &mut headers);
         let response = self
             .client
             .post(&endpoint)
             .headers(headers)
             .multipart(form)
             .send()
+            .instrument(span)
             .await?;
 
         let status = response.status();
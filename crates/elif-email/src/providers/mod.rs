@@ -7,8 +7,41 @@ pub use sendgrid::*;
 pub use smtp::*;
 
 use crate::{Email, EmailError, EmailProvider, EmailResult};
+use reqwest::header::HeaderMap;
 use std::sync::Arc;
 
+/// Create the span of an outgoing provider API request
+pub(crate) fn request_span(provider: &'static str, email: &Email) -> tracing::Span {
+    tracing::info_span!("email.send", email.provider = provider, email.id = %email.id)
+}
+
+/// Add the W3C trace context of a request span to the request headers
+///
+/// With the `otel` feature, provider calls continue the trace of the code sending the email,
+/// using the globally installed OpenTelemetry propagator. Without it, nothing is added.
+#[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+pub(crate) fn inject_trace_context(span: &tracing::Span, headers: &mut HeaderMap) {
+    #[cfg(feature = "otel")]
+    {
+        use reqwest::header::{HeaderName, HeaderValue};
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let context = span.context();
+        let mut carrier = std::collections::HashMap::new();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut carrier)
+        });
+        for (key, value) in carrier {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(key.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+    }
+}
+
 // Test providers for internal testing
 #[cfg(test)]
 #[allow(dead_code)]
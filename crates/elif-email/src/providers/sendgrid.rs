@@ -1,3 +1,4 @@
+use super::{inject_trace_context, request_span};
 use crate::{config::SendGridConfig, Email, EmailError, EmailProvider, EmailResult};
 use async_trait::async_trait;
 use reqwest::{
@@ -6,7 +7,7 @@ use reqwest::{
 };
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use tracing::{debug, error};
+use tracing::{debug, error, Instrument};
 
 /// SendGrid email provider using reqwest HTTP client
 #[derive(Clone)]
@@ -267,15 +268,18 @@ impl EmailProvider for SendGridProvider {
         );
 
         let sendgrid_email = self.convert_email(email)?;
-        let headers = self.build_headers()?;
+        let mut headers = self.build_headers()?;
         let endpoint = self.get_endpoint();
 
+        let span = request_span("sendgrid", email);
+        inject_trace_context(&span, &mut headers);
         let response = self
             .client
             .post(&endpoint)
             .headers(headers)
             .json(&sendgrid_email)
             .send()
+            .instrument(span)
             .await?;
 
         let status = response.status();
@@ -415,4 +419,57 @@ mod tests {
         );
         assert_eq!(sendgrid_email.subject, "Test Email");
     }
+
+    #[cfg(feature = "otel")]
+    #[tokio::test]
+    async fn test_request_carries_trace_context() {
+        use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        opentelemetry::global::set_text_map_propagator(
+            opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+        );
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // SendGrid stand-in answering every request with 202 and returning its head
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 4096];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let read = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            socket
+                .write_all(
+                    b"HTTP/1.1 202 Accepted\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request).to_lowercase()
+        });
+
+        let mut config = SendGridConfig::new("test-api-key");
+        config.endpoint = Some(format!("http://{}/v3/mail/send", addr));
+        let provider = SendGridProvider::new(config).unwrap();
+        let email = Email::new()
+            .from("sender@example.com")
+            .to("recipient@example.com")
+            .subject("Traced")
+            .text_body("Hello");
+
+        let span = tracing::info_span!("signup");
+        let trace_id = span.context().span().span_context().trace_id();
+        provider.send(&email).instrument(span).await.unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.contains(&format!("traceparent: 00-{}-", trace_id)));
+    }
 }
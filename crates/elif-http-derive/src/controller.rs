@@ -6,6 +6,7 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, ImplItem, ItemImpl, ItemStruct, LitStr};
 
+use crate::limits::extract_limits_from_attrs;
 use crate::utils::{
     extract_http_method_info, extract_middleware_from_attrs, extract_param_types_from_attrs,
     extract_path_parameters,
@@ -85,6 +86,12 @@ pub fn controller_impl(args: TokenStream, input: TokenStream) -> TokenStream {
                     let middleware = extract_middleware_from_attrs(&method.attrs);
                     let middleware_vec = quote! { vec![#(#middleware.to_string()),*] };
 
+                    // Extract route limits from #[limits]
+                    let limits = match extract_limits_from_attrs(&method.attrs) {
+                        Ok(limits) => limits.to_tokens(),
+                        Err(err) => return err.to_compile_error().into(),
+                    };
+
                     // Extract path parameters from the route path
                     let path_params = extract_path_parameters(&path);
 
@@ -118,6 +125,7 @@ pub fn controller_impl(args: TokenStream, input: TokenStream) -> TokenStream {
                             handler_name: #handler_name.to_string(),
                             middleware: #middleware_vec,
                             params: vec![#(#param_tokens),*],
+                            limits: #limits,
                         }
                     });
 
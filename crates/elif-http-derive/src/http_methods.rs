@@ -184,13 +184,12 @@ fn generate_injected_method(
                         } else if path_params.contains(&param_name) {
                             // This is a path parameter - generate extraction code
                             let param_ident = &pat_ident.ident;
-                            let extraction_method =
-                                get_extraction_method(&param_name, param_types, &param_type_str);
-
-                            param_extractions.push(quote! {
-                            let #param_ident = request.#extraction_method(#param_name)
-                                .map_err(|e| ::elif_http::HttpError::bad_request(format!("Invalid parameter '{}': {:?}", #param_name, e)))?;
-                        });
+                            param_extractions.push(path_param_extraction(
+                                param_ident,
+                                param_type,
+                                &param_name,
+                                param_types,
+                            ));
                             call_args.push(quote! { #param_ident });
                             modified_inputs.push(input.clone());
                         } else if let Some((body_param_name, _)) = &body_param {
@@ -324,6 +323,7 @@ fn generate_injected_method(
                 && !attr.path().is_ident("head")
                 && !attr.path().is_ident("options")
                 && !attr.path().is_ident("param")
+                && !attr.path().is_ident("limits")
                 && !attr.path().is_ident("request")
                 && !attr.path().is_ident("body")
         })
@@ -443,6 +443,30 @@ enum ReturnTypeCategory {
     SerializableType,       // T - serialize to JSON
 }
 
+/// Generate the code binding a path parameter to a handler argument
+pub(crate) fn path_param_extraction(
+    param_ident: &syn::Ident,
+    param_type: &syn::Type,
+    param_name: &str,
+    param_types: &HashMap<String, String>,
+) -> proc_macro2::TokenStream {
+    if param_types.get(param_name).map(String::as_str) == Some("Model") {
+        // Route model binding: look the model up by its route key
+        return quote! {
+            let #param_ident = ::elif_http::ElifModel::<#param_type>::from_request(&request, #param_name)
+                .await?
+                .into_inner();
+        };
+    }
+
+    let param_type_str = quote! { #param_type }.to_string();
+    let extraction_method = get_extraction_method(param_name, param_types, &param_type_str);
+    quote! {
+        let #param_ident = request.#extraction_method(#param_name)
+            .map_err(|e| ::elif_http::HttpError::bad_request(format!("Invalid parameter '{}': {:?}", #param_name, e)))?;
+    }
+}
+
 /// Get the appropriate extraction method name based on parameter type
 fn get_extraction_method(
     param_name: &str,
@@ -6,6 +6,7 @@
 //! - `#[controller]`: Define controller base path and metadata
 //! - `#[get]`, `#[post]`, etc.: HTTP method routing macros
 //! - `#[middleware]`: Apply middleware to controllers and methods
+//! - `#[limits]`: Per-route timeout, body size and concurrency limits
 //! - `#[param]`: Route parameter specifications
 //! - `#[body]`: Request body type specifications
 //! - `#[request]`: Automatic ElifRequest parameter injection
@@ -26,6 +27,7 @@ mod debug;
 mod groups;
 mod http_methods;
 mod inject;
+mod limits;
 mod middleware;
 mod module;
 mod params;
@@ -89,6 +91,12 @@ pub fn middleware(args: TokenStream, input: TokenStream) -> TokenStream {
     middleware::middleware_impl(args, input)
 }
 
+/// Route limits macro for per-route timeouts, body size and concurrency limits
+#[proc_macro_attribute]
+pub fn limits(args: TokenStream, input: TokenStream) -> TokenStream {
+    limits::limits_impl(args, input)
+}
+
 /// Route parameter specification macro
 #[proc_macro_attribute]
 pub fn param(args: TokenStream, input: TokenStream) -> TokenStream {
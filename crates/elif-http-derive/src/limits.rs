@@ -0,0 +1,167 @@
+//! Route limits macro
+//!
+//! Provides the #[limits] attribute for declaring per-route timeouts, body size limits
+//! and concurrency limits on controller methods.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Ident, Lit, LitInt, LitStr, Token};
+
+/// Parsed `#[limits(...)]` specification
+///
+/// Durations and sizes written as strings are parsed by `RouteLimits::parse_duration` and
+/// `RouteLimits::parse_size` in a constant, so invalid values still fail to compile.
+#[derive(Debug, Clone, Default)]
+pub struct LimitsSpec {
+    pub timeout: Option<LimitValue>,
+    pub max_body_size: Option<LimitValue>,
+    pub max_concurrency: Option<LitInt>,
+}
+
+/// Value of a duration or size limit
+#[derive(Debug, Clone)]
+pub enum LimitValue {
+    /// Seconds for timeouts, bytes for body sizes
+    Int(LitInt),
+    /// Duration like `"30s"` or size like `"10MB"`
+    Str(LitStr),
+}
+
+struct LimitArg {
+    name: Ident,
+    value: Lit,
+}
+
+impl Parse for LimitArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: Lit = input.parse()?;
+        Ok(LimitArg { name, value })
+    }
+}
+
+impl Parse for LimitsSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let args = Punctuated::<LimitArg, Token![,]>::parse_terminated(input)?;
+        let mut spec = LimitsSpec::default();
+
+        for arg in args {
+            let invalid = || {
+                syn::Error::new_spanned(
+                    &arg.value,
+                    format!(
+                        "Invalid value for route limit '{}'. \
+                        Hint: use a duration like \"500ms\", \"30s\" or \"2m\" for timeout, a size like \"10MB\" for max_body_size \
+                        and a positive integer for max_concurrency.",
+                        arg.name
+                    ),
+                )
+            };
+
+            match arg.name.to_string().as_str() {
+                "timeout" | "max_body_size" => {
+                    let value = match &arg.value {
+                        Lit::Int(int) => {
+                            int.base10_parse::<u64>()?;
+                            LimitValue::Int(int.clone())
+                        }
+                        Lit::Str(text) => LimitValue::Str(text.clone()),
+                        _ => return Err(invalid()),
+                    };
+                    if arg.name == "timeout" {
+                        spec.timeout = Some(value);
+                    } else {
+                        spec.max_body_size = Some(value);
+                    }
+                }
+                "max_concurrency" => match &arg.value {
+                    Lit::Int(int) if int.base10_parse::<u64>()? > 0 => {
+                        spec.max_concurrency = Some(int.clone());
+                    }
+                    _ => return Err(invalid()),
+                },
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &arg.name,
+                        format!(
+                            "Unknown route limit '{}'. Supported limits: timeout, max_body_size, max_concurrency. \
+                            Hint: #[limits(timeout = \"30s\", max_body_size = \"10MB\", max_concurrency = 4)]",
+                            other
+                        ),
+                    ))
+                }
+            }
+        }
+
+        Ok(spec)
+    }
+}
+
+impl LimitsSpec {
+    /// Generate the `RouteLimits` expression for these limits
+    pub fn to_tokens(&self) -> TokenStream2 {
+        let timeout = self.timeout.as_ref().map(|value| {
+            let timeout = match value {
+                LimitValue::Int(secs) => quote! { ::std::time::Duration::from_secs(#secs) },
+                LimitValue::Str(text) => quote_spanned! {text.span()=>
+                    const {
+                        match ::elif_http::RouteLimits::parse_duration(#text) {
+                            ::std::option::Option::Some(timeout) => timeout,
+                            ::std::option::Option::None => panic!(
+                                "Invalid route timeout; use a duration like \"500ms\", \"30s\" or \"2m\""
+                            ),
+                        }
+                    }
+                },
+            };
+            quote! { .timeout(#timeout) }
+        });
+        let max_body_size = self.max_body_size.as_ref().map(|value| {
+            let bytes = match value {
+                LimitValue::Int(bytes) => quote! { #bytes },
+                LimitValue::Str(text) => quote_spanned! {text.span()=>
+                    const {
+                        match ::elif_http::RouteLimits::parse_size(#text) {
+                            ::std::option::Option::Some(bytes) => bytes,
+                            ::std::option::Option::None => panic!(
+                                "Invalid route body size; use a size like \"512KB\" or \"10MB\""
+                            ),
+                        }
+                    }
+                },
+            };
+            quote! { .max_body_size(#bytes) }
+        });
+        let max_concurrency = self
+            .max_concurrency
+            .as_ref()
+            .map(|requests| quote! { .max_concurrency(#requests) });
+
+        quote! {
+            ::elif_http::RouteLimits::new() #timeout #max_body_size #max_concurrency
+        }
+    }
+}
+
+/// Extract the route limits declared with #[limits] on a method
+pub fn extract_limits_from_attrs(attrs: &[Attribute]) -> syn::Result<LimitsSpec> {
+    match attrs.iter().find(|attr| attr.path().is_ident("limits")) {
+        Some(attr) => attr.parse_args::<LimitsSpec>(),
+        None => Ok(LimitsSpec::default()),
+    }
+}
+
+/// Route limits macro
+///
+/// Only validates the limits; #[controller] reads them when generating route metadata.
+/// Usage: #[limits(timeout = "30s", max_body_size = "10MB", max_concurrency = 4)]
+pub fn limits_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    if let Err(err) = syn::parse::<LimitsSpec>(args) {
+        return err.to_compile_error().into();
+    }
+    input
+}
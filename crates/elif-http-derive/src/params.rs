@@ -32,6 +32,7 @@ pub enum ParamType {
     Float,
     Bool,
     Uuid,
+    Model, // ORM model resolved by route model binding
 }
 
 /// Supported body parameter types for injection
@@ -51,6 +52,7 @@ impl std::fmt::Display for ParamType {
             ParamType::Float => write!(f, "float"),
             ParamType::Bool => write!(f, "bool"),
             ParamType::Uuid => write!(f, "uuid"),
+            ParamType::Model => write!(f, "model"),
         }
     }
 }
@@ -75,11 +77,12 @@ impl Parse for ParamSpec {
             "f64" => ParamType::Float,
             "bool" => ParamType::Bool,
             "uuid" => ParamType::Uuid,
+            "model" => ParamType::Model,
             _ => {
                 return Err(syn::Error::new_spanned(
                     type_ident,
                     format!(
-                        "Unsupported parameter type '{}'. Supported types: string, int, i32, i64, uint, u32, u64, float, f32, f64, bool, uuid. \
+                        "Unsupported parameter type '{}'. Supported types: string, int, i32, i64, uint, u32, u64, float, f32, f64, bool, uuid, model. \
                         Hint: Use #[param({}: string)] for string parameters or #[param({}: u32)] for u32 parameters.", 
                         type_name, 
                         name, 
@@ -157,6 +160,11 @@ pub fn validate_param_consistency(param_spec: &ParamSpec, sig: &Signature) -> Re
         )),
     };
 
+    // Any model type can be bound; the Model bound is checked where it is resolved
+    if param_spec.param_type == ParamType::Model {
+        return Ok(());
+    }
+
     // Validate type compatibility
     let type_str = quote! { #param_type }.to_string().replace(" ", "");
     let expected_types = get_compatible_rust_types(&param_spec.param_type);
@@ -188,6 +196,7 @@ pub fn get_compatible_rust_types(param_type: &ParamType) -> Vec<&'static str> {
         ParamType::Float => vec!["f32", "f64"],
         ParamType::Bool => vec!["bool"],
         ParamType::Uuid => vec!["Uuid", "uuid::Uuid"],
+        ParamType::Model => vec![],
     }
 }
 
@@ -200,6 +209,7 @@ pub fn get_recommended_param_type(param_type: &ParamType) -> &'static str {
         ParamType::Float => "float",
         ParamType::Bool => "bool",
         ParamType::Uuid => "uuid",
+        ParamType::Model => "model",
     }
 }
 
@@ -349,4 +349,94 @@ mod tests {
             _ => panic!("Expected Custom body type"),
         }
     }
+
+    #[test]
+    fn test_model_param_binding() {
+        use crate::params::{validate_param_consistency, ParamSpec, ParamType};
+        use crate::utils::extract_param_types_from_attrs;
+        use syn::{parse_quote, Attribute, Signature};
+
+        let spec: ParamSpec = syn::parse2(quote::quote!(post: model)).unwrap();
+        assert_eq!(spec.param_type, ParamType::Model);
+
+        // Any model type is accepted for a model parameter
+        let sig: Signature = parse_quote!(fn show(&self, post: Post) -> HttpResult<ElifResponse>);
+        assert!(validate_param_consistency(&spec, &sig).is_ok());
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[param(post: model, id: int)])];
+        let param_types = extract_param_types_from_attrs(&attrs);
+        assert_eq!(param_types.get("post").map(String::as_str), Some("Model"));
+        assert_eq!(param_types.get("id").map(String::as_str), Some("Integer"));
+
+        // Model parameters are resolved by route model binding, others parsed from the path
+        let extraction = crate::http_methods::path_param_extraction(
+            &parse_quote!(post),
+            &parse_quote!(Post),
+            "post",
+            &param_types,
+        );
+        assert_eq!(
+            extraction.to_string(),
+            quote::quote! {
+                let post = ::elif_http::ElifModel::<Post>::from_request(&request, "post")
+                    .await?
+                    .into_inner();
+            }
+            .to_string()
+        );
+        let extraction = crate::http_methods::path_param_extraction(
+            &parse_quote!(id),
+            &parse_quote!(i32),
+            "id",
+            &param_types,
+        )
+        .to_string();
+        assert!(extraction.contains("request . path_param_int (\"id\")"));
+        assert!(!extraction.contains("ElifModel"));
+    }
+
+    #[test]
+    fn test_route_limits_parsing() {
+        use crate::limits::{extract_limits_from_attrs, LimitsSpec};
+        use syn::{parse_quote, Attribute};
+
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[post("/upload")]),
+            parse_quote!(#[limits(timeout = "2m", max_body_size = "50MB", max_concurrency = 4)]),
+        ];
+        let tokens = extract_limits_from_attrs(&attrs)
+            .unwrap()
+            .to_tokens()
+            .to_string();
+        assert!(tokens.contains("RouteLimits :: parse_duration (\"2m\")"));
+        assert!(tokens.contains("RouteLimits :: parse_size (\"50MB\")"));
+        assert!(tokens.contains(". max_concurrency (4)"));
+
+        // Bare integers are seconds and bytes
+        let spec: LimitsSpec =
+            syn::parse2(quote::quote!(timeout = 30, max_body_size = 1024)).unwrap();
+        let tokens = spec.to_tokens().to_string();
+        assert!(tokens.contains("Duration :: from_secs (30)"));
+        assert!(tokens.contains(". max_body_size (1024)"));
+
+        // The method attribute is found after other route attributes
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[limits(timeout = 30)]),
+            parse_quote!(#[middleware("auth")]),
+            parse_quote!(#[put("/{id}")]),
+        ];
+        let (method, path) = crate::utils::extract_http_method_info(&attrs).unwrap();
+        assert_eq!(method, "put");
+        assert_eq!(path, "/{id}");
+
+        let tokens = extract_limits_from_attrs(&[]).unwrap().to_tokens();
+        assert_eq!(
+            tokens.to_string(),
+            quote::quote!(::elif_http::RouteLimits::new()).to_string()
+        );
+        assert!(syn::parse2::<LimitsSpec>(quote::quote!(timeout = true)).is_err());
+        assert!(syn::parse2::<LimitsSpec>(quote::quote!(max_concurrency = 0)).is_err());
+        assert!(syn::parse2::<LimitsSpec>(quote::quote!(max_concurrency = "4")).is_err());
+        assert!(syn::parse2::<LimitsSpec>(quote::quote!(rate = 10)).is_err());
+    }
 }
@@ -6,40 +6,29 @@ use syn::{Attribute, Meta};
 
 /// Extract HTTP method and path from method attributes
 pub fn extract_http_method_info(attrs: &[Attribute]) -> Option<(proc_macro2::Ident, String)> {
-    for attr in attrs {
-        if let Meta::List(meta_list) = &attr.meta {
-            let path_name = meta_list.path.get_ident()?.to_string();
-            let method_ident = match path_name.as_str() {
-                "get" => Some(quote::format_ident!("get")),
-                "post" => Some(quote::format_ident!("post")),
-                "put" => Some(quote::format_ident!("put")),
-                "delete" => Some(quote::format_ident!("delete")),
-                "patch" => Some(quote::format_ident!("patch")),
-                "head" => Some(quote::format_ident!("head")),
-                "options" => Some(quote::format_ident!("options")),
-                _ => None,
-            }?;
-
+    // Other attributes such as #[limits] or #[middleware] may come before the method one
+    attrs.iter().find_map(|attr| {
+        let method_ident = match attr.path().get_ident()?.to_string().as_str() {
+            "get" => quote::format_ident!("get"),
+            "post" => quote::format_ident!("post"),
+            "put" => quote::format_ident!("put"),
+            "delete" => quote::format_ident!("delete"),
+            "patch" => quote::format_ident!("patch"),
+            "head" => quote::format_ident!("head"),
+            "options" => quote::format_ident!("options"),
+            _ => return None,
+        };
+
+        match &attr.meta {
             // Extract path from the attribute arguments using proper syn parsing
-            let path = extract_path_from_meta_list_robust(&meta_list.tokens);
-            return Some((method_ident, path));
-        } else if let Meta::Path(path) = &attr.meta {
-            let path_name = path.get_ident()?.to_string();
-            let method_ident = match path_name.as_str() {
-                "get" => Some(quote::format_ident!("get")),
-                "post" => Some(quote::format_ident!("post")),
-                "put" => Some(quote::format_ident!("put")),
-                "delete" => Some(quote::format_ident!("delete")),
-                "patch" => Some(quote::format_ident!("patch")),
-                "head" => Some(quote::format_ident!("head")),
-                "options" => Some(quote::format_ident!("options")),
-                _ => None,
-            }?;
-
-            return Some((method_ident, "".to_string()));
+            Meta::List(meta_list) => Some((
+                method_ident,
+                extract_path_from_meta_list_robust(&meta_list.tokens),
+            )),
+            Meta::Path(_) => Some((method_ident, "".to_string())),
+            Meta::NameValue(_) => None,
         }
-    }
-    None
+    })
 }
 
 /// Extract resource path from method attributes  
@@ -173,7 +162,6 @@ pub fn extract_path_parameters(path: &str) -> Vec<String> {
     params
 }
 
-
 /// Extract middleware names from method attributes
 pub fn extract_middleware_from_attrs(attrs: &[Attribute]) -> Vec<String> {
     let mut middleware = Vec::new();
@@ -221,11 +209,12 @@ pub fn convert_param_type_to_routing(param_type: &str) -> String {
         "u64" => "Integer".to_string(),
         "uuid" => "Uuid".to_string(),
         "Uuid" => "Uuid".to_string(),
+        "model" => "Model".to_string(), // Resolved by route model binding
         "float" => "String".to_string(), // Float not in routing::ParamType yet
         "f32" => "String".to_string(),
         "f64" => "String".to_string(),
-        "bool" => "String".to_string(),  // Bool not in routing::ParamType yet
-        _ => "String".to_string(),       // Default fallback
+        "bool" => "String".to_string(), // Bool not in routing::ParamType yet
+        _ => "String".to_string(),      // Default fallback
     }
 }
 
@@ -297,6 +297,7 @@ mod tests {
                     handler_name: "index".to_string(),
                     middleware: vec![],
                     params: vec![],
+                    limits: Default::default(),
                 }
             ]
         }
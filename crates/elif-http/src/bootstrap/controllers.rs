@@ -389,6 +389,7 @@ mod tests {
                 required: true,
                 default: None,
             }],
+            limits: Default::default(),
         };
 
         let route_metadata: RouteMetadata = controller_route.into();
@@ -496,6 +496,7 @@ mod tests {
                 id: format!("{}::{}", controller, handler),
                 method,
                 path: path.to_string(),
+                host: None,
             },
         }
     }
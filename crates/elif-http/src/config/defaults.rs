@@ -10,5 +10,12 @@ impl HttpDefaults {
     pub const MAX_REQUEST_SIZE: usize = DEFAULT_MAX_REQUEST_SIZE;
     pub const ENABLE_TRACING: bool = true;
     pub const HEALTH_CHECK_PATH: &'static str = DEFAULT_HEALTH_CHECK_PATH;
+    pub const LIVENESS_PATH: &'static str = "/health/live";
+    pub const READINESS_PATH: &'static str = "/health/ready";
+    pub const METRICS_PATH: &'static str = "/metrics";
     pub const SHUTDOWN_TIMEOUT_SECS: u64 = DEFAULT_SHUTDOWN_TIMEOUT_SECS as u64;
+    pub const PRE_STOP_DELAY_SECS: u64 = 0;
+    pub const TLS_ENABLE_HTTP2: bool = true;
+    pub const TLS_RELOAD_INTERVAL_SECS: u64 = 10;
+    pub const TLS_HANDSHAKE_TIMEOUT_SECS: u64 = 10;
 }
@@ -4,6 +4,9 @@
 //! the elif-core configuration system.
 
 use super::defaults::HttpDefaults;
+use super::tls_config::TlsConfig;
+use super::trusted_proxies::TrustedProxies;
+use crate::errors::ErrorFormat;
 use elif_core::{AppConfigTrait, ConfigError, ConfigSource};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -23,8 +26,38 @@ pub struct HttpConfig {
     pub enable_tracing: bool,
     /// Health check endpoint path
     pub health_check_path: String,
+    /// Liveness probe path, running only the health checks that opt into liveness
+    #[serde(default = "default_liveness_path")]
+    pub liveness_path: String,
+    /// Readiness probe path, running every health check
+    #[serde(default = "default_readiness_path")]
+    pub readiness_path: String,
     /// Server shutdown timeout in seconds
     pub shutdown_timeout_secs: u64,
+    /// Seconds the readiness probe fails before the listener closes on shutdown, giving
+    /// load balancers time to stop routing new connections to the server
+    #[serde(default)]
+    pub pre_stop_delay_secs: u64,
+    /// HTTPS settings, serves plain HTTP when absent
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// Proxy addresses or CIDR networks whose forwarding headers are trusted
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    /// Path serving Prometheus metrics, disabled when absent
+    #[serde(default)]
+    pub metrics_path: Option<String>,
+    /// Body format of error responses, unless the request negotiates problem details
+    #[serde(default)]
+    pub error_format: ErrorFormat,
+}
+
+fn default_liveness_path() -> String {
+    HttpDefaults::LIVENESS_PATH.to_string()
+}
+
+fn default_readiness_path() -> String {
+    HttpDefaults::READINESS_PATH.to_string()
 }
 
 impl Default for HttpConfig {
@@ -35,7 +68,14 @@ impl Default for HttpConfig {
             max_request_size: HttpDefaults::MAX_REQUEST_SIZE,
             enable_tracing: HttpDefaults::ENABLE_TRACING,
             health_check_path: HttpDefaults::HEALTH_CHECK_PATH.to_string(),
+            liveness_path: default_liveness_path(),
+            readiness_path: default_readiness_path(),
             shutdown_timeout_secs: HttpDefaults::SHUTDOWN_TIMEOUT_SECS,
+            pre_stop_delay_secs: HttpDefaults::PRE_STOP_DELAY_SECS,
+            tls: None,
+            trusted_proxies: Vec::new(),
+            metrics_path: None,
+            error_format: ErrorFormat::default(),
         }
     }
 }
@@ -61,6 +101,12 @@ impl AppConfigTrait for HttpConfig {
             ));
         }
 
+        if self.pre_stop_delay_secs >= self.shutdown_timeout_secs {
+            return Err(ConfigError::validation_failed(
+                "Pre-stop delay must be shorter than the shutdown timeout",
+            ));
+        }
+
         // Validate request size limits
         if self.max_request_size == 0 {
             return Err(ConfigError::validation_failed(
@@ -75,6 +121,26 @@ impl AppConfigTrait for HttpConfig {
             ));
         }
 
+        if !self.liveness_path.starts_with('/') || !self.readiness_path.starts_with('/') {
+            return Err(ConfigError::validation_failed(
+                "Liveness and readiness paths must start with '/'",
+            ));
+        }
+
+        if let Some(metrics_path) = &self.metrics_path {
+            if !metrics_path.starts_with('/') || *metrics_path == self.health_check_path {
+                return Err(ConfigError::validation_failed(
+                    "Metrics path must start with '/' and differ from the health check path",
+                ));
+            }
+        }
+
+        if let Some(tls) = &self.tls {
+            tls.validate()?;
+        }
+
+        self.trusted_proxies()?;
+
         Ok(())
     }
 
@@ -125,6 +191,9 @@ impl AppConfigTrait for HttpConfig {
 
         let health_check_path =
             get_env_or_default("HTTP_HEALTH_CHECK_PATH", HttpDefaults::HEALTH_CHECK_PATH)?;
+        let liveness_path = get_env_or_default("HTTP_LIVENESS_PATH", HttpDefaults::LIVENESS_PATH)?;
+        let readiness_path =
+            get_env_or_default("HTTP_READINESS_PATH", HttpDefaults::READINESS_PATH)?;
 
         let shutdown_timeout_secs = get_env_or_default(
             "HTTP_SHUTDOWN_TIMEOUT",
@@ -137,13 +206,55 @@ impl AppConfigTrait for HttpConfig {
             expected: "valid number of seconds".to_string(),
         })?;
 
+        let pre_stop_delay_secs = get_env_or_default(
+            "HTTP_PRE_STOP_DELAY",
+            &HttpDefaults::PRE_STOP_DELAY_SECS.to_string(),
+        )?
+        .parse::<u64>()
+        .map_err(|_| ConfigError::InvalidValue {
+            field: "pre_stop_delay_secs".to_string(),
+            value: env::var("HTTP_PRE_STOP_DELAY").unwrap_or_default(),
+            expected: "valid number of seconds".to_string(),
+        })?;
+
+        let tls = TlsConfig::from_env()?;
+
+        let trusted_proxies = get_env_or_default("HTTP_TRUSTED_PROXIES", "")?
+            .split(',')
+            .map(str::trim)
+            .filter(|proxy| !proxy.is_empty())
+            .map(String::from)
+            .collect();
+
+        let metrics_path = env::var("HTTP_METRICS_PATH")
+            .ok()
+            .filter(|path| !path.is_empty());
+
+        let error_format = match env::var("HTTP_ERROR_FORMAT") {
+            Ok(value) if !value.is_empty() => {
+                ErrorFormat::parse(&value).ok_or_else(|| ConfigError::InvalidValue {
+                    field: "error_format".to_string(),
+                    value,
+                    expected: "json or problem".to_string(),
+                })?
+            }
+            _ => ErrorFormat::default(),
+        };
+
         Ok(HttpConfig {
             request_timeout_secs,
             keep_alive_timeout_secs,
             max_request_size,
             enable_tracing,
             health_check_path,
+            liveness_path,
+            readiness_path,
             shutdown_timeout_secs,
+            pre_stop_delay_secs,
+            tls,
+            trusted_proxies,
+            metrics_path,
+            error_format,
         })
     }
 
@@ -169,10 +280,46 @@ impl AppConfigTrait for HttpConfig {
             "health_check_path".to_string(),
             ConfigSource::EnvVar("HTTP_HEALTH_CHECK_PATH".to_string()),
         );
+        sources.insert(
+            "liveness_path".to_string(),
+            ConfigSource::EnvVar("HTTP_LIVENESS_PATH".to_string()),
+        );
+        sources.insert(
+            "readiness_path".to_string(),
+            ConfigSource::EnvVar("HTTP_READINESS_PATH".to_string()),
+        );
         sources.insert(
             "shutdown_timeout_secs".to_string(),
             ConfigSource::EnvVar("HTTP_SHUTDOWN_TIMEOUT".to_string()),
         );
+        sources.insert(
+            "pre_stop_delay_secs".to_string(),
+            ConfigSource::EnvVar("HTTP_PRE_STOP_DELAY".to_string()),
+        );
+        sources.insert(
+            "tls.cert_path".to_string(),
+            ConfigSource::EnvVar("HTTP_TLS_CERT".to_string()),
+        );
+        sources.insert(
+            "tls.key_path".to_string(),
+            ConfigSource::EnvVar("HTTP_TLS_KEY".to_string()),
+        );
+        sources.insert(
+            "tls.client_ca_path".to_string(),
+            ConfigSource::EnvVar("HTTP_TLS_CLIENT_CA".to_string()),
+        );
+        sources.insert(
+            "trusted_proxies".to_string(),
+            ConfigSource::EnvVar("HTTP_TRUSTED_PROXIES".to_string()),
+        );
+        sources.insert(
+            "metrics_path".to_string(),
+            ConfigSource::EnvVar("HTTP_METRICS_PATH".to_string()),
+        );
+        sources.insert(
+            "error_format".to_string(),
+            ConfigSource::EnvVar("HTTP_ERROR_FORMAT".to_string()),
+        );
         sources
     }
 }
@@ -192,6 +339,49 @@ impl HttpConfig {
     pub fn shutdown_timeout(&self) -> Duration {
         Duration::from_secs(self.shutdown_timeout_secs)
     }
+
+    /// Get the pre-stop delay as Duration
+    pub fn pre_stop_delay(&self) -> Duration {
+        Duration::from_secs(self.pre_stop_delay_secs)
+    }
+
+    /// Serve HTTPS with the given TLS settings
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Trust forwarding headers from the given proxy addresses or CIDR networks
+    pub fn with_trusted_proxies<I, S>(mut self, proxies: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.trusted_proxies = proxies.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Serve Prometheus metrics at `/metrics` and record them for every route
+    pub fn with_metrics(self) -> Self {
+        self.with_metrics_path(HttpDefaults::METRICS_PATH)
+    }
+
+    /// Serve Prometheus metrics at the given path and record them for every route
+    pub fn with_metrics_path<P: Into<String>>(mut self, path: P) -> Self {
+        self.metrics_path = Some(path.into());
+        self
+    }
+
+    /// Render errors as RFC 9457 `application/problem+json` documents
+    pub fn with_problem_details(mut self) -> Self {
+        self.error_format = ErrorFormat::Problem;
+        self
+    }
+
+    /// Parse the trusted proxy networks
+    pub fn trusted_proxies(&self) -> Result<TrustedProxies, ConfigError> {
+        TrustedProxies::parse(&self.trusted_proxies)
+    }
 }
 
 // Helper function for environment variable handling
@@ -203,6 +393,7 @@ fn get_env_or_default(key: &str, default: &str) -> Result<String, ConfigError> {
 mod tests {
     use super::*;
     use std::env;
+    use std::path::PathBuf;
     use std::sync::Mutex;
 
     // Global test lock to prevent concurrent environment modifications
@@ -215,6 +406,7 @@ mod tests {
         env::set_var("HTTP_ENABLE_TRACING", "false");
         env::set_var("HTTP_HEALTH_CHECK_PATH", "/api/health");
         env::set_var("HTTP_SHUTDOWN_TIMEOUT", "15");
+        env::set_var("HTTP_PRE_STOP_DELAY", "5");
     }
 
     fn clean_test_env() {
@@ -224,6 +416,7 @@ mod tests {
         env::remove_var("HTTP_ENABLE_TRACING");
         env::remove_var("HTTP_HEALTH_CHECK_PATH");
         env::remove_var("HTTP_SHUTDOWN_TIMEOUT");
+        env::remove_var("HTTP_PRE_STOP_DELAY");
     }
 
     #[test]
@@ -241,6 +434,8 @@ mod tests {
         assert_eq!(config.max_request_size, HttpDefaults::MAX_REQUEST_SIZE);
         assert_eq!(config.enable_tracing, HttpDefaults::ENABLE_TRACING);
         assert_eq!(config.health_check_path, HttpDefaults::HEALTH_CHECK_PATH);
+        assert_eq!(config.liveness_path, HttpDefaults::LIVENESS_PATH);
+        assert_eq!(config.readiness_path, HttpDefaults::READINESS_PATH);
         assert_eq!(
             config.shutdown_timeout_secs,
             HttpDefaults::SHUTDOWN_TIMEOUT_SECS
@@ -260,10 +455,123 @@ mod tests {
         assert!(!config.enable_tracing);
         assert_eq!(config.health_check_path, "/api/health");
         assert_eq!(config.shutdown_timeout_secs, 15);
+        assert_eq!(config.pre_stop_delay(), Duration::from_secs(5));
 
         clean_test_env();
     }
 
+    #[test]
+    fn test_tls_config() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        env::set_var("HTTP_TLS_CERT", "certs/server.pem");
+        env::set_var("HTTP_TLS_KEY", "certs/server.key");
+        env::set_var("HTTP_TLS_HTTP2", "false");
+
+        let config = HttpConfig::from_env().unwrap();
+        let tls = config.tls.as_ref().unwrap();
+        assert_eq!(tls.cert_path, PathBuf::from("certs/server.pem"));
+        assert!(!tls.enable_http2);
+        assert_eq!(
+            tls.reload_interval(),
+            Some(Duration::from_secs(HttpDefaults::TLS_RELOAD_INTERVAL_SECS))
+        );
+        assert!(config.validate().is_ok());
+
+        env::remove_var("HTTP_TLS_CERT");
+        env::remove_var("HTTP_TLS_KEY");
+        env::remove_var("HTTP_TLS_HTTP2");
+
+        let config = HttpConfig::default()
+            .with_tls(TlsConfig::new("server.pem", "server.key").require_client_cert(true));
+        assert!(config.validate().is_err());
+        assert!(HttpConfig::from_env().unwrap().tls.is_none());
+    }
+
+    #[test]
+    fn test_trusted_proxies_config() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        env::set_var("HTTP_TRUSTED_PROXIES", "10.0.0.0/8, 127.0.0.1");
+
+        let config = HttpConfig::from_env().unwrap();
+        assert_eq!(config.trusted_proxies, vec!["10.0.0.0/8", "127.0.0.1"]);
+        assert!(config
+            .trusted_proxies()
+            .unwrap()
+            .contains(&"10.1.2.3".parse().unwrap()));
+        assert!(config.validate().is_ok());
+
+        env::remove_var("HTTP_TRUSTED_PROXIES");
+
+        let config = HttpConfig::default().with_trusted_proxies(["10.0.0.0/40"]);
+        assert!(config.validate().is_err());
+        assert!(HttpConfig::from_env().unwrap().trusted_proxies.is_empty());
+    }
+
+    #[test]
+    fn test_metrics_config() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        assert!(HttpConfig::from_env().unwrap().metrics_path.is_none());
+
+        env::set_var("HTTP_METRICS_PATH", "/internal/metrics");
+        let config = HttpConfig::from_env().unwrap();
+        assert_eq!(config.metrics_path.as_deref(), Some("/internal/metrics"));
+        assert!(config.validate().is_ok());
+        env::remove_var("HTTP_METRICS_PATH");
+
+        let config = HttpConfig::default().with_metrics();
+        assert_eq!(config.metrics_path.as_deref(), Some("/metrics"));
+        assert!(config.validate().is_ok());
+        assert!(HttpConfig::default()
+            .with_metrics_path("/health")
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_probe_paths_config() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        env::set_var("HTTP_READINESS_PATH", "/ready");
+        let config = HttpConfig::from_env().unwrap();
+        assert_eq!(config.liveness_path, "/health/live");
+        assert_eq!(config.readiness_path, "/ready");
+        env::remove_var("HTTP_READINESS_PATH");
+
+        let config = HttpConfig {
+            liveness_path: "live".to_string(),
+            ..HttpConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = HttpConfig {
+            pre_stop_delay_secs: HttpDefaults::SHUTDOWN_TIMEOUT_SECS,
+            ..HttpConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_error_format_config() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        assert_eq!(
+            HttpConfig::from_env().unwrap().error_format,
+            ErrorFormat::Json
+        );
+
+        env::set_var("HTTP_ERROR_FORMAT", "problem");
+        assert_eq!(
+            HttpConfig::from_env().unwrap().error_format,
+            ErrorFormat::Problem
+        );
+        env::set_var("HTTP_ERROR_FORMAT", "xml");
+        assert!(HttpConfig::from_env().is_err());
+        env::remove_var("HTTP_ERROR_FORMAT");
+
+        assert_eq!(
+            HttpConfig::default().with_problem_details().error_format,
+            ErrorFormat::Problem
+        );
+    }
+
     #[test]
     fn test_duration_helpers() {
         let config = HttpConfig::default();
@@ -1,5 +1,9 @@
 pub mod defaults;
 pub mod http_config;
+pub mod tls_config;
+pub mod trusted_proxies;
 
 pub use defaults::*;
 pub use http_config::*;
+pub use tls_config::*;
+pub use trusted_proxies::*;
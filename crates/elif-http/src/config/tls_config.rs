@@ -0,0 +1,176 @@
+//! TLS configuration for the HTTP server
+
+use super::defaults::HttpDefaults;
+use elif_core::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// HTTPS settings: certificate, key, optional client authentication and HTTP/2
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// Path to the PEM certificate chain
+    pub cert_path: PathBuf,
+    /// Path to the PEM private key
+    pub key_path: PathBuf,
+    /// Path to PEM CA certificates used to verify client certificates (enables mutual TLS)
+    #[serde(default)]
+    pub client_ca_path: Option<PathBuf>,
+    /// Reject clients that don't present a certificate signed by the client CA
+    #[serde(default)]
+    pub require_client_cert: bool,
+    /// Offer HTTP/2 via ALPN
+    #[serde(default = "default_enable_http2")]
+    pub enable_http2: bool,
+    /// How often certificate files are checked for changes, 0 disables reloading
+    #[serde(default = "default_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+    /// How long a client may take to complete the TLS handshake
+    #[serde(default = "default_handshake_timeout_secs")]
+    pub handshake_timeout_secs: u64,
+}
+
+fn default_enable_http2() -> bool {
+    HttpDefaults::TLS_ENABLE_HTTP2
+}
+
+fn default_reload_interval_secs() -> u64 {
+    HttpDefaults::TLS_RELOAD_INTERVAL_SECS
+}
+
+fn default_handshake_timeout_secs() -> u64 {
+    HttpDefaults::TLS_HANDSHAKE_TIMEOUT_SECS
+}
+
+impl TlsConfig {
+    /// Create TLS config from certificate and key paths
+    pub fn new<C: Into<PathBuf>, K: Into<PathBuf>>(cert_path: C, key_path: K) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            client_ca_path: None,
+            require_client_cert: false,
+            enable_http2: HttpDefaults::TLS_ENABLE_HTTP2,
+            reload_interval_secs: HttpDefaults::TLS_RELOAD_INTERVAL_SECS,
+            handshake_timeout_secs: HttpDefaults::TLS_HANDSHAKE_TIMEOUT_SECS,
+        }
+    }
+
+    /// Verify client certificates against the CA certificates at a path
+    pub fn client_ca<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.client_ca_path = Some(path.into());
+        self
+    }
+
+    /// Require clients to present a valid certificate
+    pub fn require_client_cert(mut self, require: bool) -> Self {
+        self.require_client_cert = require;
+        self
+    }
+
+    /// Enable or disable HTTP/2
+    pub fn http2(mut self, enabled: bool) -> Self {
+        self.enable_http2 = enabled;
+        self
+    }
+
+    /// Set how often certificate files are checked for changes (0 disables reloading)
+    pub fn reload_interval_secs(mut self, secs: u64) -> Self {
+        self.reload_interval_secs = secs;
+        self
+    }
+
+    /// Get reload interval as Duration, if reloading is enabled
+    pub fn reload_interval(&self) -> Option<Duration> {
+        (self.reload_interval_secs > 0).then(|| Duration::from_secs(self.reload_interval_secs))
+    }
+
+    /// Set how long clients may take to complete the TLS handshake
+    pub fn handshake_timeout_secs(mut self, secs: u64) -> Self {
+        self.handshake_timeout_secs = secs;
+        self
+    }
+
+    /// Get handshake timeout as Duration
+    pub fn handshake_timeout(&self) -> Duration {
+        Duration::from_secs(self.handshake_timeout_secs)
+    }
+
+    /// Validate the TLS settings
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.cert_path.as_os_str().is_empty() || self.key_path.as_os_str().is_empty() {
+            return Err(ConfigError::validation_failed(
+                "TLS certificate and key paths must be non-empty",
+            ));
+        }
+
+        if self.require_client_cert && self.client_ca_path.is_none() {
+            return Err(ConfigError::validation_failed(
+                "Requiring client certificates needs a client CA path",
+            ));
+        }
+
+        if self.handshake_timeout_secs == 0 {
+            return Err(ConfigError::validation_failed(
+                "TLS handshake timeout must be greater than 0",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Load TLS config from environment, if a certificate and key are configured
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let (Ok(cert_path), Ok(key_path)) = (env::var("HTTP_TLS_CERT"), env::var("HTTP_TLS_KEY"))
+        else {
+            return Ok(None);
+        };
+
+        let mut config = Self::new(cert_path, key_path);
+        if let Ok(client_ca_path) = env::var("HTTP_TLS_CLIENT_CA") {
+            config.client_ca_path = Some(client_ca_path.into());
+        }
+        if let Ok(value) = env::var("HTTP_TLS_REQUIRE_CLIENT_CERT") {
+            config.require_client_cert =
+                value
+                    .parse::<bool>()
+                    .map_err(|_| ConfigError::InvalidValue {
+                        field: "tls.require_client_cert".to_string(),
+                        value,
+                        expected: "true or false".to_string(),
+                    })?;
+        }
+        if let Ok(value) = env::var("HTTP_TLS_HTTP2") {
+            config.enable_http2 = value
+                .parse::<bool>()
+                .map_err(|_| ConfigError::InvalidValue {
+                    field: "tls.enable_http2".to_string(),
+                    value,
+                    expected: "true or false".to_string(),
+                })?;
+        }
+        if let Ok(value) = env::var("HTTP_TLS_RELOAD_INTERVAL") {
+            config.reload_interval_secs =
+                value
+                    .parse::<u64>()
+                    .map_err(|_| ConfigError::InvalidValue {
+                        field: "tls.reload_interval_secs".to_string(),
+                        value,
+                        expected: "valid number of seconds".to_string(),
+                    })?;
+        }
+        if let Ok(value) = env::var("HTTP_TLS_HANDSHAKE_TIMEOUT") {
+            config.handshake_timeout_secs =
+                value
+                    .parse::<u64>()
+                    .map_err(|_| ConfigError::InvalidValue {
+                        field: "tls.handshake_timeout_secs".to_string(),
+                        value,
+                        expected: "valid number of seconds".to_string(),
+                    })?;
+        }
+
+        Ok(Some(config))
+    }
+}
@@ -0,0 +1,191 @@
+//! Trusted proxy networks
+//!
+//! Forwarding headers (`Forwarded`, `X-Forwarded-For`, ...) can be set by anyone, so they
+//! are only honoured when the connected peer belongs to one of these networks.
+
+use elif_core::ConfigError;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// An IP network in CIDR notation, e.g. `10.0.0.0/8` or `fd00::/8`
+///
+/// A bare address is treated as a single-host network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// Create a network from an address and prefix length
+    pub fn new(address: IpAddr, prefix_len: u8) -> Result<Self, ConfigError> {
+        let max = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max {
+            return Err(ConfigError::InvalidValue {
+                field: "trusted_proxies".to_string(),
+                value: format!("{}/{}", address, prefix_len),
+                expected: format!("prefix length of at most {}", max),
+            });
+        }
+
+        Ok(Self {
+            network: mask(address, prefix_len),
+            prefix_len,
+        })
+    }
+
+    /// Check whether an address belongs to this network
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        let address = match (self.network, address) {
+            (IpAddr::V4(_), IpAddr::V6(v6)) => match v6.to_ipv4_mapped() {
+                Some(v4) => IpAddr::V4(v4),
+                None => return false,
+            },
+            _ => *address,
+        };
+
+        match (self.network, address) {
+            (IpAddr::V4(_), IpAddr::V4(_)) | (IpAddr::V6(_), IpAddr::V6(_)) => {
+                mask(address, self.prefix_len) == self.network
+            }
+            _ => false,
+        }
+    }
+}
+
+fn mask(address: IpAddr, prefix_len: u8) -> IpAddr {
+    match address {
+        IpAddr::V4(v4) => {
+            let bits = u32::from(v4);
+            let mask = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0);
+            IpAddr::V4((bits & mask).into())
+        }
+        IpAddr::V6(v6) => {
+            let bits = u128::from(v6);
+            let mask = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0);
+            IpAddr::V6((bits & mask).into())
+        }
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ConfigError::InvalidValue {
+            field: "trusted_proxies".to_string(),
+            value: s.to_string(),
+            expected: "IP address or CIDR network".to_string(),
+        };
+
+        let s = s.trim();
+        match s.split_once('/') {
+            Some((address, prefix_len)) => {
+                let address = address.parse::<IpAddr>().map_err(|_| invalid())?;
+                let prefix_len = prefix_len.parse::<u8>().map_err(|_| invalid())?;
+                Self::new(address, prefix_len)
+            }
+            None => {
+                let address = s.parse::<IpAddr>().map_err(|_| invalid())?;
+                let prefix_len = if address.is_ipv4() { 32 } else { 128 };
+                Self::new(address, prefix_len)
+            }
+        }
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// The set of networks whose forwarding headers are trusted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    networks: Vec<IpCidr>,
+}
+
+impl TrustedProxies {
+    /// Trust no proxies
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Parse networks in CIDR notation
+    pub fn parse<I, S>(networks: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let networks = networks
+            .into_iter()
+            .map(|network| network.as_ref().parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { networks })
+    }
+
+    /// Trust an additional network
+    pub fn with(mut self, network: IpCidr) -> Self {
+        self.networks.push(network);
+        self
+    }
+
+    /// Check whether an address belongs to a trusted network
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        self.networks
+            .iter()
+            .any(|network| network.contains(address))
+    }
+
+    /// Whether no networks are trusted
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    /// Trusted networks
+    pub fn networks(&self) -> &[IpCidr] {
+        &self.networks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cidr_contains() {
+        let network: IpCidr = "10.0.0.0/8".parse().unwrap();
+        assert!(network.contains(&"10.20.30.40".parse().unwrap()));
+        assert!(!network.contains(&"11.0.0.1".parse().unwrap()));
+        assert!(network.contains(&"::ffff:10.0.0.1".parse().unwrap()));
+        assert_eq!(network.to_string(), "10.0.0.0/8");
+
+        let host: IpCidr = "192.168.1.5".parse().unwrap();
+        assert!(host.contains(&"192.168.1.5".parse().unwrap()));
+        assert!(!host.contains(&"192.168.1.6".parse().unwrap()));
+
+        let v6: IpCidr = "fd00::/8".parse().unwrap();
+        assert!(v6.contains(&"fd12::1".parse().unwrap()));
+        assert!(!v6.contains(&"10.0.0.1".parse().unwrap()));
+
+        let any: IpCidr = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(&"203.0.113.9".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_invalid_cidrs() {
+        assert!("10.0.0.0/33".parse::<IpCidr>().is_err());
+        assert!("not-an-ip".parse::<IpCidr>().is_err());
+        assert!("10.0.0.0/abc".parse::<IpCidr>().is_err());
+        assert!(TrustedProxies::parse(["127.0.0.1", "::1/200"]).is_err());
+
+        let proxies = TrustedProxies::parse(["127.0.0.1", "172.16.0.0/12"]).unwrap();
+        assert_eq!(proxies.networks().len(), 2);
+        assert!(proxies.contains(&"172.20.1.1".parse().unwrap()));
+    }
+}
@@ -6,7 +6,7 @@
 use crate::{
     request::{ElifPath, ElifQuery, ElifRequest, ElifState},
     response::{ElifJson, ElifResponse},
-    routing::{params::ParamType, HttpMethod},
+    routing::{params::ParamType, HttpMethod, RouteLimits},
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -194,6 +194,8 @@ pub struct ControllerRoute {
     pub handler_name: String,
     pub middleware: Vec<String>,
     pub params: Vec<RouteParam>,
+    /// Timeout, body size and concurrency limits of the route
+    pub limits: RouteLimits,
 }
 
 impl ControllerRoute {
@@ -204,6 +206,7 @@ impl ControllerRoute {
             handler_name: handler_name.to_string(),
             middleware: vec![],
             params: vec![],
+            limits: RouteLimits::default(),
         }
     }
 
@@ -221,6 +224,11 @@ impl ControllerRoute {
         self.params.push(param);
         self
     }
+
+    pub fn with_limits(mut self, limits: RouteLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 }
 
 /// Main trait for controllers with automatic route registration
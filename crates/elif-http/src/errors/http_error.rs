@@ -255,25 +255,34 @@ impl From<orm::ModelError> for HttpError {
             orm::ModelError::Event(msg) => HttpError::InternalError {
                 message: format!("Event error: {}", msg),
             },
+            orm::ModelError::Configuration(msg) => HttpError::InternalError {
+                message: format!("Configuration error: {}", msg),
+            },
+            orm::ModelError::InvalidKey(msg) => HttpError::BadRequest {
+                message: format!("Invalid key: {}", msg),
+            },
+            orm::ModelError::ColumnNotFound(column) => HttpError::InternalError {
+                message: format!("Column not found: {}", column),
+            },
         }
     }
 }
 
 // Convert from ORM QueryError to HttpError
 #[cfg(feature = "orm")]
-impl From<orm::QueryError> for HttpError {
-    fn from(err: orm::QueryError) -> Self {
+impl From<orm::error::QueryError> for HttpError {
+    fn from(err: orm::error::QueryError) -> Self {
         match err {
-            orm::QueryError::InvalidSql(msg) => HttpError::BadRequest {
+            orm::error::QueryError::InvalidSql(msg) => HttpError::BadRequest {
                 message: format!("Invalid SQL query: {}", msg),
             },
-            orm::QueryError::MissingFields(msg) => HttpError::BadRequest {
+            orm::error::QueryError::MissingFields(msg) => HttpError::BadRequest {
                 message: format!("Missing required fields: {}", msg),
             },
-            orm::QueryError::InvalidParameter(msg) => HttpError::BadRequest {
+            orm::error::QueryError::InvalidParameter(msg) => HttpError::BadRequest {
                 message: format!("Invalid query parameter: {}", msg),
             },
-            orm::QueryError::UnsupportedOperation(msg) => HttpError::BadRequest {
+            orm::error::QueryError::UnsupportedOperation(msg) => HttpError::BadRequest {
                 message: format!("Unsupported operation: {}", msg),
             },
         }
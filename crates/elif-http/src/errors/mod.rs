@@ -1,8 +1,12 @@
 pub mod http_error;
 pub mod parse_error;
+pub mod problem;
 pub mod responses;
 pub mod versioned;
 
 pub use http_error::*;
 pub use parse_error::*;
+pub use problem::{
+    ErrorContext, ErrorFormat, ProblemDetails, ABOUT_BLANK, PROBLEM_JSON_CONTENT_TYPE,
+};
 pub use versioned::*;
@@ -0,0 +1,475 @@
+//! RFC 9457 problem details
+//!
+//! Errors render either in the framework's JSON shape (`{"error": {"code", "message",
+//! "hint"}}`) or as `application/problem+json` documents. The server selects the
+//! [`ErrorFormat`] from [`HttpConfig::error_format`](crate::config::HttpConfig) and
+//! hands it to its requests as an extension; [`ErrorFormat::set_global`] sets the
+//! default used outside of such a server. Clients can ask for problem details per
+//! request with an `Accept` header listing `application/problem+json`.
+
+use super::HttpError;
+use crate::foundation::constants::HEADER_REQUEST_ID;
+use crate::request::ElifRequest;
+use crate::response::{ElifResponse, ElifStatusCode, IntoElifResponse};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Media type of problem details documents
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Problem type of problems described by their status code alone
+pub const ABOUT_BLANK: &str = "about:blank";
+
+static ERROR_FORMAT: AtomicU8 = AtomicU8::new(ErrorFormat::Json as u8);
+
+tokio::task_local! {
+    static REQUEST_ERROR_FORMAT: ErrorFormat;
+}
+
+/// Body format of error responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorFormat {
+    /// `{"error": {"code", "message", "hint"}}` documents
+    #[default]
+    Json = 0,
+    /// RFC 9457 `application/problem+json` documents
+    Problem = 1,
+}
+
+impl ErrorFormat {
+    /// Get the default format of requests that weren't given one by the server
+    pub fn global() -> Self {
+        match ERROR_FORMAT.load(Ordering::Relaxed) {
+            1 => Self::Problem,
+            _ => Self::Json,
+        }
+    }
+
+    /// Set the default format of requests that weren't given one by the server
+    pub fn set_global(self) {
+        ERROR_FORMAT.store(self as u8, Ordering::Relaxed);
+    }
+
+    /// Get the format of the request being handled, or the global format
+    ///
+    /// Response helpers that don't see the request, such as
+    /// [`validation_error`](crate::response::validation_error), render in this format.
+    pub fn current() -> Self {
+        REQUEST_ERROR_FORMAT
+            .try_with(|format| *format)
+            .unwrap_or_else(|_| Self::global())
+    }
+
+    /// Run a request's handler with this as its [`current`](Self::current) format
+    pub(crate) async fn scope<F: std::future::Future>(self, handler: F) -> F::Output {
+        REQUEST_ERROR_FORMAT.scope(self, handler).await
+    }
+
+    /// Select the format for a request's `Accept` header
+    ///
+    /// Problem details are used when the header accepts `application/problem+json`,
+    /// the [`current`](Self::current) format otherwise.
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        Self::negotiate_or(accept, Self::current())
+    }
+
+    /// Select the format for a request's `Accept` header, falling back to `default`
+    pub fn negotiate_or(accept: Option<&str>, default: Self) -> Self {
+        let accepts_problem = accept.is_some_and(|accept| {
+            accept.split(',').any(|range| {
+                let mut params = range.split(';').map(str::trim);
+                params
+                    .next()
+                    .is_some_and(|media| media.eq_ignore_ascii_case(PROBLEM_JSON_CONTENT_TYPE))
+                    && !params.any(|param| {
+                        param
+                            .strip_prefix("q=")
+                            .and_then(|q| q.parse::<f32>().ok())
+                            .is_some_and(|q| q == 0.0)
+                    })
+            })
+        });
+
+        if accepts_problem {
+            Self::Problem
+        } else {
+            default
+        }
+    }
+
+    /// Parse a format name, `json` or `problem`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "problem" | "problem+json" => Some(Self::Problem),
+            _ => None,
+        }
+    }
+}
+
+/// RFC 9457 problem details document
+///
+/// Extension members, such as field errors or the request id, are serialized next to
+/// the standard members.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type
+    #[serde(rename = "type", default = "about_blank")]
+    pub problem_type: String,
+    /// Short summary of the problem type
+    pub title: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Explanation specific to this occurrence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// URI reference identifying this occurrence, usually the request path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Extension members
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+fn about_blank() -> String {
+    ABOUT_BLANK.to_string()
+}
+
+impl ProblemDetails {
+    /// Create an `about:blank` problem titled after the status code
+    pub fn new(status: ElifStatusCode) -> Self {
+        Self {
+            problem_type: about_blank(),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail: None,
+            instance: None,
+            extensions: Map::new(),
+        }
+    }
+
+    /// Create a 422 problem listing the errors of each field
+    pub fn validation<T: Serialize>(errors: &T) -> Self {
+        Self::new(ElifStatusCode::UNPROCESSABLE_ENTITY)
+            .with_title("Validation failed")
+            .with_extension("errors", errors)
+    }
+
+    /// Set the problem type URI
+    pub fn with_type<T: Into<String>>(mut self, problem_type: T) -> Self {
+        self.problem_type = problem_type.into();
+        self
+    }
+
+    /// Set the title
+    pub fn with_title<T: Into<String>>(mut self, title: T) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the occurrence specific explanation
+    pub fn with_detail<T: Into<String>>(mut self, detail: T) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Set the occurrence URI
+    pub fn with_instance<T: Into<String>>(mut self, instance: T) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Add an extension member, replacing one of the same name
+    ///
+    /// Values that fail to serialize are recorded as `null`.
+    pub fn with_extension<T: Serialize>(mut self, name: &str, value: T) -> Self {
+        self.extensions.insert(
+            name.to_string(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+        self
+    }
+
+    /// Add the id of the request the problem occurred in
+    pub fn with_request_id<T: Into<String>>(self, request_id: T) -> Self {
+        self.with_extension("request_id", request_id.into())
+    }
+
+    /// Get the status code
+    pub fn status_code(&self) -> ElifStatusCode {
+        ElifStatusCode::from_u16(self.status).unwrap_or(ElifStatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+impl IntoElifResponse for ProblemDetails {
+    fn into_response(self) -> ElifResponse {
+        let status = self.status_code();
+        match serde_json::to_value(&self) {
+            Ok(body) => ElifResponse::with_status(status)
+                .json_value(body)
+                .with_header("content-type", PROBLEM_JSON_CONTENT_TYPE),
+            Err(e) => {
+                tracing::error!("Problem details serialization failed: {}", e);
+                ElifResponse::internal_server_error()
+            }
+        }
+    }
+}
+
+impl HttpError {
+    /// Describe this error as problem details
+    ///
+    /// The framework error code and hint become the `code` and `hint` extensions.
+    pub fn to_problem(&self) -> ProblemDetails {
+        let problem = ProblemDetails::new(self.status_code())
+            .with_detail(self.to_string())
+            .with_extension("code", self.error_code());
+
+        match self.error_hint() {
+            Some(hint) => problem.with_extension("hint", hint),
+            None => problem,
+        }
+    }
+}
+
+/// What error responses of a request are rendered with
+///
+/// Captured from the request before it is handed to the handler, so that errors can be
+/// rendered in the negotiated format and refer to the request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// Negotiated body format
+    pub format: ErrorFormat,
+    /// Request path, the `instance` of problems
+    pub instance: Option<String>,
+    /// Request id, the `request_id` extension of problems
+    pub request_id: Option<String>,
+}
+
+impl ErrorContext {
+    /// Capture the context of a request
+    pub fn from_request(request: &ElifRequest) -> Self {
+        let header = |name: &str| {
+            request
+                .header(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        Self {
+            format: ErrorFormat::negotiate_or(header("accept").as_deref(), request.error_format()),
+            instance: Some(request.path().to_string()),
+            request_id: header(HEADER_REQUEST_ID),
+        }
+    }
+
+    /// Render an error in the negotiated format
+    pub fn render(&self, error: HttpError) -> ElifResponse {
+        match self.format {
+            ErrorFormat::Json => error.to_json_response(),
+            ErrorFormat::Problem => self.render_problem(error.to_problem()),
+        }
+    }
+
+    /// Render problem details, adding the request path and id unless already set
+    pub fn render_problem(&self, mut problem: ProblemDetails) -> ElifResponse {
+        if problem.instance.is_none() {
+            problem.instance = self.instance.clone();
+        }
+        if let Some(request_id) = &self.request_id {
+            if !problem.extensions.contains_key("request_id") {
+                problem = problem.with_request_id(request_id.clone());
+            }
+        }
+        problem.into_response()
+    }
+}
+
+impl ElifRequest {
+    /// Get the error format the server renders this request's errors in by default
+    pub fn error_format(&self) -> ErrorFormat {
+        self.get_extension::<ErrorFormat>()
+            .copied()
+            .unwrap_or_else(ErrorFormat::current)
+    }
+
+    /// Carry the server's error format from Axum request extensions
+    pub(crate) fn with_error_format_from(mut self, extensions: &axum::http::Extensions) -> Self {
+        if let Some(format) = extensions.get::<ErrorFormat>().copied() {
+            self.insert_extension(format);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::ElifHeaderMap;
+    use serde_json::json;
+
+    #[test]
+    fn test_negotiates_problem_details() {
+        assert_eq!(
+            ErrorFormat::negotiate(Some("application/problem+json")),
+            ErrorFormat::Problem
+        );
+        assert_eq!(
+            ErrorFormat::negotiate(Some("application/json, application/problem+json;q=0.5")),
+            ErrorFormat::Problem
+        );
+        assert_eq!(
+            ErrorFormat::negotiate(Some("application/problem+json;q=0")),
+            ErrorFormat::global()
+        );
+        assert_eq!(
+            ErrorFormat::negotiate(Some("application/json")),
+            ErrorFormat::global()
+        );
+        assert_eq!(ErrorFormat::parse("problem"), Some(ErrorFormat::Problem));
+        assert_eq!(ErrorFormat::parse("xml"), None);
+    }
+
+    #[test]
+    fn test_serializes_problem_details() {
+        let problem = ProblemDetails::validation(&json!({"email": ["Email is required"]}))
+            .with_instance("/users")
+            .with_request_id("req-1");
+
+        assert_eq!(
+            serde_json::to_value(&problem).unwrap(),
+            json!({
+                "type": "about:blank",
+                "title": "Validation failed",
+                "status": 422,
+                "instance": "/users",
+                "errors": {"email": ["Email is required"]},
+                "request_id": "req-1"
+            })
+        );
+
+        let parsed: ProblemDetails =
+            serde_json::from_value(json!({"title": "Not Found", "status": 404})).unwrap();
+        assert_eq!(parsed.problem_type, ABOUT_BLANK);
+    }
+
+    #[tokio::test]
+    async fn test_renders_errors_in_negotiated_format() {
+        let mut headers = ElifHeaderMap::new();
+        headers.insert(
+            "accept".parse().unwrap(),
+            "application/problem+json".parse().unwrap(),
+        );
+        headers.insert(
+            HEADER_REQUEST_ID.parse().unwrap(),
+            "req-42".parse().unwrap(),
+        );
+        let request = ElifRequest::new(ElifMethod::GET, "/users/7".parse().unwrap(), headers);
+
+        let context = ErrorContext::from_request(&request);
+        let response = context
+            .render(HttpError::not_found("User"))
+            .into_axum_response();
+        assert_eq!(response.status().as_u16(), 404);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "type": "about:blank",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "Resource not found: User",
+                "instance": "/users/7",
+                "code": "RESOURCE_NOT_FOUND",
+                "request_id": "req-42"
+            })
+        );
+
+        let context = ErrorContext::default();
+        let response = context
+            .render(HttpError::not_found("User"))
+            .into_axum_response();
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handler_errors_negotiate_problem_details() {
+        use crate::routing::ElifRouter;
+        use tower::ServiceExt;
+
+        async fn missing(_req: ElifRequest) -> crate::errors::HttpResult<ElifResponse> {
+            Err(HttpError::not_found("Post"))
+        }
+
+        let app = ElifRouter::<()>::new()
+            .get("/posts/:id", missing)
+            .into_axum_router();
+
+        let mut headers = ElifHeaderMap::new();
+        headers.insert(
+            "accept".parse().unwrap(),
+            "application/problem+json".parse().unwrap(),
+        );
+        let request = ElifRequest::new(ElifMethod::GET, "/posts/3".parse().unwrap(), headers)
+            .into_axum_request();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 404);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["instance"], "/posts/3");
+        assert_eq!(body["detail"], "Resource not found: Post");
+    }
+
+    #[tokio::test]
+    async fn test_server_error_format_applies_to_its_requests() {
+        use crate::routing::ElifRouter;
+        use tower::ServiceExt;
+
+        async fn missing(_req: ElifRequest) -> crate::errors::HttpResult<ElifResponse> {
+            Err(HttpError::not_found("Post"))
+        }
+        async fn invalid(_req: ElifRequest) -> crate::errors::HttpResult<ElifResponse> {
+            Ok(crate::response::validation_error(
+                &json!({"title": ["Title is required"]}),
+            ))
+        }
+
+        let app = ElifRouter::<()>::new()
+            .get("/posts/:id", missing)
+            .post("/posts", invalid)
+            .into_axum_router()
+            .layer(axum::Extension(ErrorFormat::Problem));
+
+        for (method, path) in [(ElifMethod::GET, "/posts/3"), (ElifMethod::POST, "/posts")] {
+            let request = ElifRequest::new(method, path.parse().unwrap(), ElifHeaderMap::new())
+                .into_axum_request();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(
+                response.headers().get("content-type").unwrap(),
+                PROBLEM_JSON_CONTENT_TYPE
+            );
+        }
+        assert_eq!(ErrorFormat::global(), ErrorFormat::Json);
+    }
+}
@@ -1,9 +1,8 @@
 //! HTTP error response formatting
 
-use super::HttpError;
+use super::{ErrorContext, ErrorFormat, HttpError};
 use crate::response::{ElifResponse, ElifStatusCode, IntoElifResponse};
 use axum::response::{IntoResponse, Response};
-use axum::Json;
 use serde_json::json;
 
 impl HttpError {
@@ -42,9 +41,9 @@ impl HttpError {
     }
 }
 
-// Implement IntoElifResponse for HttpError
-impl IntoElifResponse for HttpError {
-    fn into_response(self) -> ElifResponse {
+impl HttpError {
+    /// Render this error in the framework's JSON shape
+    pub fn to_json_response(&self) -> ElifResponse {
         let body = json!({
             "error": {
                 "code": self.error_code(),
@@ -57,19 +56,21 @@ impl IntoElifResponse for HttpError {
     }
 }
 
+// Implement IntoElifResponse for HttpError, in the current error format
+impl IntoElifResponse for HttpError {
+    fn into_response(self) -> ElifResponse {
+        ErrorContext {
+            format: ErrorFormat::current(),
+            ..ErrorContext::default()
+        }
+        .render(self)
+    }
+}
+
 // Implement IntoResponse for automatic HTTP error responses (Axum compatibility)
 impl IntoResponse for HttpError {
     fn into_response(self) -> Response {
-        let status = self.status_code().to_axum(); // Convert to Axum StatusCode
-        let body = json!({
-            "error": {
-                "code": self.error_code(),
-                "message": self.to_string(),
-                "hint": self.error_hint()
-            }
-        });
-
-        (status, Json(body)).into_response()
+        IntoElifResponse::into_response(self).into_axum_response()
     }
 }
 
@@ -2,7 +2,7 @@
 //!
 //! This module provides a bridge between elif types and Axum's handler system.
 
-use crate::errors::HttpResult;
+use crate::errors::{ErrorContext, HttpResult};
 use crate::request::ElifRequest;
 use crate::response::{ElifResponse, IntoElifResponse};
 use axum::{
@@ -72,9 +72,6 @@ where
             // Convert Axum request to ElifRequest
             let (parts, body) = req.into_parts();
 
-            // Extract body bytes
-            let body_bytes = (axum::body::to_bytes(body, usize::MAX).await).ok();
-
             // Extract query parameters from URI
             let query_params = if let Some(query) = parts.uri.query() {
                 serde_urlencoded::from_str::<HashMap<String, String>>(query).unwrap_or_default()
@@ -86,20 +83,41 @@ where
                 crate::request::ElifMethod::from_axum(parts.method),
                 parts.uri,
                 crate::response::ElifHeaderMap::from_axum(parts.headers),
-                body_bytes,
+                None,
             )
-            .with_query_params(query_params);
-
-            match (self.handler)(elif_request).await {
-                Ok(response) => {
-                    let elif_response = response.into_response();
-                    convert_elif_to_axum_response(elif_response)
-                }
-                Err(error) => {
-                    let error_response = crate::response::IntoElifResponse::into_response(error);
-                    convert_elif_to_axum_response(error_response)
-                }
-            }
+            .with_route_limits_from(&parts.extensions)
+            .with_axum_body(body)
+            .await;
+            let elif_request = match elif_request {
+                Ok(request) => request,
+                Err(error) => return convert_elif_to_axum_response(error.into_response()),
+            };
+
+            let elif_request = elif_request
+                .with_query_params(query_params)
+                .with_connection_from(&parts.extensions)
+                .with_forwarded_from(&parts.extensions)
+                .with_host_params_from(&parts.extensions)
+                .with_error_format_from(&parts.extensions);
+
+            #[cfg(feature = "orm")]
+            let elif_request = elif_request.with_model_binding_from(&parts.extensions);
+            #[cfg(feature = "auth")]
+            let elif_request = elif_request.with_user_from(&parts.extensions);
+
+            let error_context = ErrorContext::from_request(&elif_request);
+            let error_format = elif_request.error_format();
+            error_format
+                .scope(async move {
+                    match (self.handler)(elif_request).await {
+                        Ok(response) => {
+                            let elif_response = response.into_response();
+                            convert_elif_to_axum_response(elif_response)
+                        }
+                        Err(error) => convert_elif_to_axum_response(error_context.render(error)),
+                    }
+                })
+                .await
         })
     }
 }
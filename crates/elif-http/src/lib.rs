@@ -18,6 +18,7 @@ pub mod errors;
 pub mod foundation;
 pub mod handlers;
 pub mod logging;
+pub mod metrics;
 pub mod middleware;
 pub mod request;
 pub mod response;
@@ -33,8 +34,11 @@ pub mod auth;
 // Main server API - NestJS-like experience
 pub use bootstrap::{AppBootstrap, AppBootstrapper, BootstrapError, BootstrapResult, create_bootstrapper};
 pub use config::HttpConfig;
-pub use errors::{HttpError, HttpResult, VersionedError, VersionedErrorBuilder, VersionedErrorExt};
-pub use server::Server;
+pub use errors::{
+    ErrorContext, ErrorFormat, HttpError, HttpResult, ProblemDetails, VersionedError,
+    VersionedErrorBuilder, VersionedErrorExt,
+};
+pub use server::{HealthCheck, HealthRegistry, Server, ShutdownCoordinator};
 
 // Re-export foundation types
 pub use foundation::{BoxFuture, GenericHandler, IntoElifResponse, RequestExtractor};
@@ -53,6 +57,7 @@ pub use routing::{
     RouteBuilder,
     RouteGroup,
     RouteInfo,
+    RouteLimits,
     RouteParam as RoutingRouteParam,
     RouteRegistry,
     VersionedRouteBuilder,
@@ -62,6 +67,8 @@ pub use routing::{
 
 // Re-export request/response types
 pub use request::{ElifMethod, ElifPath, ElifQuery, ElifRequest, ElifState};
+#[cfg(feature = "orm")]
+pub use request::{ElifModel, ModelBinding, ModelBindingMiddleware};
 pub use response::{
     ElifHeaderMap, ElifHeaderName, ElifHeaderValue, ElifResponse, ElifStatusCode, ResponseBody,
 };
@@ -80,6 +87,7 @@ pub use middleware::{
         error_handler, error_handler_with_config, ErrorHandlerConfig, ErrorHandlerMiddleware,
     },
     logging::LoggingMiddleware as LegacyLoggingMiddleware,
+    route_limits::RouteLimitsMiddleware,
     timeout::{apply_timeout, TimeoutConfig, TimeoutInfo, TimeoutMiddleware},
     timing::{format_duration, RequestStartTime, TimingMiddleware},
     tracing::{RequestMetadata, TracingConfig, TracingMiddleware},
@@ -101,10 +109,16 @@ pub use middleware::{
 pub use auth::{AuthMiddleware, RequestAuthExt};
 
 // Re-export logging types
-pub use logging::{init_logging, log_shutdown_info, log_startup_info, structured, LoggingConfig};
+pub use logging::{
+    init_logging, log_shutdown_info, log_startup_info, structured, LoggingConfig, OtlpConfig,
+    OtlpProtocol,
+};
 // Re-export specific LoggingContext from context module
 pub use logging::context::LoggingContext;
 
+// Re-export metrics types
+pub use metrics::{MetricsCollector, MetricsRegistry};
+
 // Re-export controller types
 pub use controller::{
     BaseController, Controller, ControllerRoute, ElifController, RouteParam as ControllerRouteParam,
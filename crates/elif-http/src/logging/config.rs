@@ -3,9 +3,12 @@
 //! Complete structured logging system for the elif.rs framework with
 //! JSON output, tracing integration, and production-ready configuration.
 
+use super::telemetry::OtlpConfig;
 use serde_json::{json, Value};
 use std::io;
-use tracing_subscriber::{fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing_subscriber::{
+    fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer as _,
+};
 
 /// Logging configuration for the elif.rs framework
 #[derive(Debug, Clone)]
@@ -28,6 +31,8 @@ pub struct LoggingConfig {
     pub service_name: Option<String>,
     /// Service version to include in all logs
     pub service_version: Option<String>,
+    /// Export spans to an OpenTelemetry collector (requires the `otel` feature)
+    pub otlp: Option<OtlpConfig>,
 }
 
 impl Default for LoggingConfig {
@@ -42,6 +47,7 @@ impl Default for LoggingConfig {
             env_filter: None,
             service_name: None,
             service_version: None,
+            otlp: None,
         }
     }
 }
@@ -63,6 +69,7 @@ impl LoggingConfig {
             env_filter: Some("elif=info,tower=warn,axum=warn".to_string()),
             service_name: None,
             service_version: None,
+            otlp: None,
         }
     }
 
@@ -82,6 +89,7 @@ impl LoggingConfig {
             env_filter: Some("elif=debug,tower=debug,axum=debug".to_string()),
             service_name: None,
             service_version: None,
+            otlp: None,
         }
     }
 
@@ -101,6 +109,7 @@ impl LoggingConfig {
             env_filter: Some("elif=error".to_string()),
             service_name: None,
             service_version: None,
+            otlp: None,
         }
     }
 
@@ -126,6 +135,12 @@ impl LoggingConfig {
         self.env_filter = Some(filter.into());
         self
     }
+
+    /// Export spans to an OpenTelemetry collector
+    pub fn with_otlp(mut self, otlp: OtlpConfig) -> Self {
+        self.otlp = Some(otlp);
+        self
+    }
 }
 
 /// Initialize structured logging for the application
@@ -134,24 +149,39 @@ pub fn init_logging(config: LoggingConfig) -> Result<(), Box<dyn std::error::Err
 
     let filter = EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new(env_filter))?;
 
-    if config.json_format {
+    let output = if config.json_format {
         // JSON structured logging
-        tracing_subscriber::registry()
-            .with(filter)
-            .with(Layer::new().with_writer(io::stdout).json())
-            .init();
+        Layer::new().with_writer(io::stdout).json().boxed()
     } else if config.pretty_print {
         // Pretty text logging
-        tracing_subscriber::registry()
-            .with(filter)
-            .with(Layer::new().with_writer(io::stdout).pretty())
-            .init();
+        Layer::new().with_writer(io::stdout).pretty().boxed()
     } else {
         // Plain text logging
-        tracing_subscriber::registry()
-            .with(filter)
-            .with(Layer::new().with_writer(io::stdout))
-            .init();
+        Layer::new().with_writer(io::stdout).boxed()
+    };
+    let subscriber = tracing_subscriber::registry().with(filter).with(output);
+
+    #[cfg(feature = "otel")]
+    let subscriber = {
+        let tracer = match &config.otlp {
+            Some(otlp) => Some(super::telemetry::install_tracer_provider(
+                otlp,
+                config.service_name.as_deref(),
+                config.service_version.as_deref(),
+            )?),
+            None => None,
+        };
+        subscriber.with(tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)))
+    };
+
+    subscriber.init();
+
+    #[cfg(not(feature = "otel"))]
+    if config.otlp.is_some() {
+        tracing::warn!(
+            target: "elif::logging",
+            "OTLP export is configured but elif-http was built without the `otel` feature"
+        );
     }
 
     // Log initialization message with global fields
@@ -1,5 +1,7 @@
 pub mod config;
 pub mod context;
 pub mod structured;
+pub mod telemetry;
 
 pub use config::{init_logging, log_shutdown_info, log_startup_info, LoggingConfig};
+pub use telemetry::{OtlpConfig, OtlpProtocol};
@@ -0,0 +1,299 @@
+//! OpenTelemetry export and W3C trace context propagation
+//!
+//! With the `otel` feature, [`init_logging`](super::init_logging) exports spans to an OTLP
+//! collector when [`LoggingConfig::otlp`](super::LoggingConfig::otlp) is set, and
+//! `TracingMiddleware` continues the trace of incoming `traceparent`/`tracestate` headers.
+//! The propagator is installed globally, so `elif-queue`, `elif-email` and the ORM (each
+//! with their `otel` feature) carry the active trace into jobs, provider calls and queries.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Encoding of the spans sent to the collector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OtlpProtocol {
+    /// Protobuf over HTTP
+    #[default]
+    HttpBinary,
+    /// JSON over HTTP
+    HttpJson,
+}
+
+/// OTLP/HTTP exporter configuration
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Base URL of the collector; spans are posted to `{endpoint}/v1/traces`
+    pub endpoint: String,
+    pub protocol: OtlpProtocol,
+    /// Timeout of each export request
+    pub timeout: Duration,
+    /// Headers sent with each export request, e.g. the API key of a hosted collector
+    pub headers: HashMap<String, String>,
+    /// Fraction of new traces to record; traces continued from a caller follow its decision
+    pub sampling_ratio: f64,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self::new("http://localhost:4318")
+    }
+}
+
+impl OtlpConfig {
+    /// Export to the collector at the given base URL
+    pub fn new<E: Into<String>>(endpoint: E) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            protocol: OtlpProtocol::default(),
+            timeout: Duration::from_secs(10),
+            headers: HashMap::new(),
+            sampling_ratio: 1.0,
+        }
+    }
+
+    /// Set the encoding of exported spans
+    pub fn with_protocol(mut self, protocol: OtlpProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Set the timeout of export requests
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Add a header to export requests
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Record only a fraction of new traces, between 0.0 and 1.0
+    pub fn with_sampling_ratio(mut self, ratio: f64) -> Self {
+        self.sampling_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Get the URL spans are posted to
+    pub fn traces_endpoint(&self) -> String {
+        format!("{}/v1/traces", self.endpoint.trim_end_matches('/'))
+    }
+}
+
+#[cfg(feature = "otel")]
+pub use otel::*;
+
+#[cfg(feature = "otel")]
+mod otel {
+    use super::{OtlpConfig, OtlpProtocol};
+    use crate::response::ElifHeaderMap;
+    use opentelemetry::propagation::{Extractor, Injector};
+    use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+    use opentelemetry::{global, Context};
+    use opentelemetry_otlp::{Protocol, WithExportConfig, WithHttpConfig};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider, Tracer};
+    use opentelemetry_sdk::Resource;
+    use std::sync::Mutex;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    static TRACER_PROVIDER: Mutex<Option<SdkTracerProvider>> = Mutex::new(None);
+
+    /// Create the OTLP exporter and install it as the global tracer provider
+    ///
+    /// Also installs the W3C trace context propagator. Returns the tracer the
+    /// `tracing-opentelemetry` layer reports spans to.
+    pub fn install_tracer_provider(
+        config: &OtlpConfig,
+        service_name: Option<&str>,
+        service_version: Option<&str>,
+    ) -> Result<Tracer, Box<dyn std::error::Error + Send + Sync>> {
+        let protocol = match config.protocol {
+            OtlpProtocol::HttpBinary => Protocol::HttpBinary,
+            OtlpProtocol::HttpJson => Protocol::HttpJson,
+        };
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(config.traces_endpoint())
+            .with_protocol(protocol)
+            .with_timeout(config.timeout)
+            .with_headers(config.headers.clone())
+            .build()?;
+
+        let mut resource =
+            Resource::builder().with_service_name(service_name.unwrap_or("elif").to_string());
+        if let Some(version) = service_version {
+            resource = resource.with_attribute(opentelemetry::KeyValue::new(
+                "service.version",
+                version.to_string(),
+            ));
+        }
+
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                config.sampling_ratio,
+            ))))
+            .with_resource(resource.build())
+            .build();
+        let tracer = provider.tracer("elif");
+
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(provider.clone());
+        *TRACER_PROVIDER.lock().unwrap_or_else(|e| e.into_inner()) = Some(provider);
+
+        Ok(tracer)
+    }
+
+    /// Export the remaining spans and stop the tracer provider installed by `init_logging`
+    pub fn shutdown_telemetry() {
+        let provider = TRACER_PROVIDER
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(provider) = provider {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!(target: "elif::logging", "Failed to shut down tracing: {}", e);
+            }
+        }
+    }
+
+    /// Read the trace context of `traceparent`/`tracestate` headers
+    pub fn extract_trace_context(headers: &ElifHeaderMap) -> Context {
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+    }
+
+    /// Write the trace context of the current span as `traceparent`/`tracestate` headers
+    ///
+    /// Use it for outgoing requests to continue the trace in the called service.
+    pub fn inject_trace_context(headers: &mut ElifHeaderMap) {
+        let context = tracing::Span::current().context();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut HeaderInjector(headers))
+        });
+    }
+
+    /// Get the hex trace id of the current span, if it is part of a trace
+    pub fn current_trace_id() -> Option<String> {
+        let context = tracing::Span::current().context();
+        let span_context = context.span().span_context().clone();
+        span_context
+            .is_valid()
+            .then(|| span_context.trace_id().to_string())
+    }
+
+    struct HeaderExtractor<'a>(&'a ElifHeaderMap);
+
+    impl Extractor for HeaderExtractor<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get_str(key).and_then(|value| value.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|name| name.as_str()).collect()
+        }
+    }
+
+    struct HeaderInjector<'a>(&'a mut ElifHeaderMap);
+
+    impl Injector for HeaderInjector<'_> {
+        fn set(&mut self, key: &str, value: String) {
+            let _ = self.0.add_header(key, &value);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use super::*;
+    use crate::middleware::core::tracing::TracingMiddleware;
+    use crate::middleware::v2::MiddlewarePipelineV2;
+    use crate::request::{ElifMethod, ElifRequest};
+    use crate::response::{ElifHeaderMap, ElifResponse};
+    use axum::{body::Bytes, routing::post, Router};
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+    /// Collector stand-in recording the bodies posted to `/v1/traces`
+    async fn start_collector() -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/v1/traces",
+            post(move |body: Bytes| {
+                let sink = sink.clone();
+                async move {
+                    sink.lock()
+                        .unwrap()
+                        .push(serde_json::from_slice(&body).unwrap());
+                    "{}"
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}", addr), received)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_span_continues_incoming_trace() {
+        let (endpoint, received) = start_collector().await;
+        let config = OtlpConfig::new(endpoint).with_protocol(OtlpProtocol::HttpJson);
+        let tracer = install_tracer_provider(&config, Some("test-service"), None).unwrap();
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut headers = ElifHeaderMap::new();
+        headers
+            .add_header(
+                "traceparent",
+                &format!("00-{}-{}-01", TRACE_ID, PARENT_SPAN_ID),
+            )
+            .unwrap();
+        let request = ElifRequest::new(ElifMethod::GET, "/orders".parse().unwrap(), headers);
+
+        // Outgoing calls made by the handler carry the incoming trace
+        let outgoing = Arc::new(Mutex::new(None));
+        let seen = outgoing.clone();
+        let pipeline = MiddlewarePipelineV2::new().add(TracingMiddleware::new());
+        pipeline
+            .execute(request, move |_req| {
+                let mut headers = ElifHeaderMap::new();
+                inject_trace_context(&mut headers);
+                *seen.lock().unwrap() = Some((headers, current_trace_id()));
+                Box::pin(async move { ElifResponse::ok().text("ok") })
+            })
+            .await;
+
+        let (headers, trace_id) = outgoing.lock().unwrap().take().unwrap();
+        let traceparent = headers.get_str("traceparent").unwrap().to_str().unwrap();
+        assert!(traceparent.starts_with(&format!("00-{}-", TRACE_ID)));
+        assert!(!traceparent.contains(PARENT_SPAN_ID));
+        assert_eq!(trace_id.as_deref(), Some(TRACE_ID));
+
+        tokio::task::spawn_blocking(shutdown_telemetry)
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        let spans: Vec<&serde_json::Value> = received
+            .iter()
+            .flat_map(|body| body["resourceSpans"].as_array().unwrap())
+            .flat_map(|resource| resource["scopeSpans"].as_array().unwrap())
+            .flat_map(|scope| scope["spans"].as_array().unwrap())
+            .collect();
+        let request_span = spans
+            .iter()
+            .find(|span| span["name"] == "http_request")
+            .expect("request span exported");
+        assert_eq!(request_span["traceId"], TRACE_ID);
+        assert_eq!(request_span["parentSpanId"], PARENT_SPAN_ID);
+    }
+}
@@ -0,0 +1,327 @@
+//! Collectors exporting the statistics framework components already keep
+//!
+//! - [`MiddlewareCollector`] - `DebugPipeline` execution statistics
+//! - [`WebSocketCollector`] - `ConnectionRegistry` connection and traffic statistics
+//! - [`PoolCollector`] - database pool statistics (`orm` feature)
+//! - [`QueueCollector`] - `elif-queue` job metrics (`queue` feature)
+//! - [`CacheCollector`] - `elif-cache` backend statistics (`cache` feature)
+
+use super::{MetricsCollector, MetricsRegistry};
+use crate::middleware::v2::introspection::DebugPipeline;
+use crate::websocket::ConnectionRegistry;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[cfg(feature = "cache")]
+pub use self::cache::CacheCollector;
+#[cfg(feature = "orm")]
+pub use self::pool::PoolCollector;
+#[cfg(feature = "queue")]
+pub use self::queue::QueueCollector;
+
+/// Exports the execution count and time of each middleware of a debug pipeline
+pub struct MiddlewareCollector {
+    pipeline: Arc<DebugPipeline>,
+}
+
+impl MiddlewareCollector {
+    pub fn new(pipeline: Arc<DebugPipeline>) -> Self {
+        Self { pipeline }
+    }
+}
+
+#[async_trait]
+impl MetricsCollector for MiddlewareCollector {
+    async fn collect(&self, registry: &MetricsRegistry) {
+        for (name, stats) in self.pipeline.stats() {
+            let labels = [("middleware", name.as_str())];
+            registry.set_counter(
+                "middleware_executions_total",
+                "Middleware executions",
+                &labels,
+                stats.executions as f64,
+            );
+            registry.set_counter(
+                "middleware_duration_seconds_total",
+                "Time spent in middleware",
+                &labels,
+                stats.total_time.as_secs_f64(),
+            );
+        }
+    }
+}
+
+/// Exports WebSocket connections by state and the messages and bytes exchanged
+pub struct WebSocketCollector {
+    registry: Arc<ConnectionRegistry>,
+}
+
+impl WebSocketCollector {
+    pub fn new(registry: Arc<ConnectionRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl MetricsCollector for WebSocketCollector {
+    async fn collect(&self, registry: &MetricsRegistry) {
+        let stats = self.registry.stats().await;
+
+        for (state, count) in [
+            ("connected", stats.active_connections),
+            ("connecting", stats.connecting_connections),
+            ("closing", stats.closing_connections),
+            ("closed", stats.closed_connections),
+            ("failed", stats.failed_connections),
+        ] {
+            registry.set_gauge(
+                "websocket_connections",
+                "WebSocket connections by state",
+                &[("state", state)],
+                count as f64,
+            );
+        }
+
+        for (direction, messages, bytes) in [
+            ("sent", stats.total_messages_sent, stats.total_bytes_sent),
+            (
+                "received",
+                stats.total_messages_received,
+                stats.total_bytes_received,
+            ),
+        ] {
+            let labels = [("direction", direction)];
+            registry.set_gauge(
+                "websocket_messages",
+                "Messages exchanged over open WebSocket connections",
+                &labels,
+                messages as f64,
+            );
+            registry.set_gauge(
+                "websocket_bytes",
+                "Bytes exchanged over open WebSocket connections",
+                &labels,
+                bytes as f64,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "orm")]
+mod pool {
+    use super::{MetricsCollector, MetricsRegistry};
+    use async_trait::async_trait;
+    use orm::connection::ManagedPool;
+    use std::sync::Arc;
+
+    /// Exports the connections and acquisitions of a database pool
+    pub struct PoolCollector {
+        name: String,
+        pool: Arc<ManagedPool>,
+    }
+
+    impl PoolCollector {
+        /// Export a pool under the given `pool` label
+        pub fn new<N: Into<String>>(name: N, pool: Arc<ManagedPool>) -> Self {
+            Self {
+                name: name.into(),
+                pool,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MetricsCollector for PoolCollector {
+        async fn collect(&self, registry: &MetricsRegistry) {
+            let stats = self.pool.extended_stats();
+            let pool = self.name.as_str();
+
+            for (state, count) in [
+                ("active", stats.pool_stats.active_connections),
+                ("idle", stats.pool_stats.idle_connections),
+            ] {
+                registry.set_gauge(
+                    "db_pool_connections",
+                    "Database pool connections by state",
+                    &[("pool", pool), ("state", state)],
+                    count as f64,
+                );
+            }
+            registry.set_counter(
+                "db_pool_acquires_total",
+                "Database connections acquired",
+                &[("pool", pool)],
+                stats.acquire_count as f64,
+            );
+            registry.set_counter(
+                "db_pool_acquire_errors_total",
+                "Failed database connection acquisitions",
+                &[("pool", pool)],
+                stats.acquire_errors as f64,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "queue")]
+mod queue {
+    use super::{MetricsCollector, MetricsRegistry};
+    use async_trait::async_trait;
+    use elif_queue::JobMetricsCollector;
+    use std::sync::Arc;
+
+    /// Exports the job counts and execution times of a queue
+    pub struct QueueCollector {
+        name: String,
+        metrics: Arc<JobMetricsCollector>,
+    }
+
+    impl QueueCollector {
+        /// Export the metrics of a queue under the given `queue` label
+        pub fn new<N: Into<String>>(name: N, metrics: Arc<JobMetricsCollector>) -> Self {
+            Self {
+                name: name.into(),
+                metrics,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MetricsCollector for QueueCollector {
+        async fn collect(&self, registry: &MetricsRegistry) {
+            let metrics = self.metrics.get_metrics();
+            let queue = self.name.as_str();
+
+            for (status, count) in [
+                ("scheduled", metrics.total_scheduled),
+                ("executed", metrics.total_executed),
+                ("succeeded", metrics.successful_jobs),
+                ("failed", metrics.failed_jobs),
+                ("retried", metrics.retried_jobs),
+                ("timed_out", metrics.timeout_jobs),
+                ("cancelled", metrics.cancelled_jobs),
+            ] {
+                registry.set_counter(
+                    "queue_jobs_total",
+                    "Jobs by outcome",
+                    &[("queue", queue), ("status", status)],
+                    count as f64,
+                );
+            }
+            registry.set_gauge(
+                "queue_jobs_running",
+                "Jobs being executed",
+                &[("queue", queue)],
+                self.metrics.active_executions_count() as f64,
+            );
+            registry.set_gauge(
+                "queue_job_duration_seconds_avg",
+                "Average job execution time",
+                &[("queue", queue)],
+                metrics.avg_execution_time_ms / 1000.0,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+mod cache {
+    use super::{MetricsCollector, MetricsRegistry};
+    use async_trait::async_trait;
+    use elif_cache::CacheBackend;
+    use std::sync::Arc;
+
+    /// Exports the hits, misses and size of a cache backend
+    pub struct CacheCollector {
+        name: String,
+        backend: Arc<dyn CacheBackend>,
+    }
+
+    impl CacheCollector {
+        /// Export a cache under the given `cache` label
+        pub fn new<N: Into<String>>(name: N, backend: Arc<dyn CacheBackend>) -> Self {
+            Self {
+                name: name.into(),
+                backend,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MetricsCollector for CacheCollector {
+        async fn collect(&self, registry: &MetricsRegistry) {
+            let stats = match self.backend.stats().await {
+                Ok(stats) => stats,
+                Err(e) => {
+                    tracing::warn!("Failed to read statistics of cache '{}': {}", self.name, e);
+                    return;
+                }
+            };
+            let labels = [("cache", self.name.as_str())];
+
+            registry.set_counter("cache_hits_total", "Cache hits", &labels, stats.hits as f64);
+            registry.set_counter(
+                "cache_misses_total",
+                "Cache misses",
+                &labels,
+                stats.misses as f64,
+            );
+            registry.set_gauge(
+                "cache_keys",
+                "Keys stored",
+                &labels,
+                stats.total_keys as f64,
+            );
+            registry.set_gauge(
+                "cache_memory_bytes",
+                "Memory used by stored values",
+                &labels,
+                stats.memory_usage as f64,
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use elif_cache::{CacheConfig, MemoryBackend};
+
+        #[tokio::test]
+        async fn test_exports_cache_stats() {
+            let backend = Arc::new(MemoryBackend::new(CacheConfig::default()));
+            backend.put("user:1", b"ada".to_vec(), None).await.unwrap();
+            backend.get("user:1").await.unwrap();
+            backend.get("user:2").await.unwrap();
+
+            let registry = MetricsRegistry::new();
+            registry.register_collector(CacheCollector::new("default", backend));
+            let output = registry.render().await;
+
+            assert!(output.contains("cache_hits_total{cache=\"default\"} 1"));
+            assert!(output.contains("cache_misses_total{cache=\"default\"} 1"));
+            assert!(output.contains("cache_keys{cache=\"default\"} 1"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::v2::MiddlewarePipelineV2;
+
+    #[tokio::test]
+    async fn test_exports_websocket_and_middleware_stats() {
+        let registry = MetricsRegistry::new();
+        registry.register_collector(WebSocketCollector::new(Arc::new(ConnectionRegistry::new())));
+        registry.register_collector(MiddlewareCollector::new(Arc::new(
+            MiddlewarePipelineV2::new()
+                .add(crate::middleware::v2::LoggingMiddleware)
+                .with_debug(),
+        )));
+
+        let output = registry.render().await;
+        assert!(output.contains("websocket_connections{state=\"connected\"} 0"));
+        assert!(output.contains("websocket_messages{direction=\"sent\"} 0"));
+        assert!(output.contains("# TYPE middleware_executions_total counter"));
+    }
+}
@@ -0,0 +1,263 @@
+//! RED metrics of HTTP requests per route and the `/metrics` endpoint
+
+use super::MetricsRegistry;
+use crate::request::ElifRequest;
+use crate::response::ElifResponse;
+use crate::routing::RouteRegistry;
+use axum::extract::MatchedPath;
+use axum::middleware::Next;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Label of requests that matched no route
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Records request count, errors, latency and in-flight requests per route
+///
+/// The `route` label is the route name when the route has one, otherwise its path
+/// pattern (e.g. `/users/:id`), so that it stays bounded whatever the requested URLs.
+#[derive(Clone)]
+pub struct HttpMetrics {
+    registry: Arc<MetricsRegistry>,
+    route_names: Arc<HashMap<(String, String), String>>,
+}
+
+impl HttpMetrics {
+    /// Record into a registry, labelling named routes of the route registry by name
+    pub fn new(registry: Arc<MetricsRegistry>, routes: &RouteRegistry) -> Self {
+        let route_names = routes
+            .all_routes()
+            .values()
+            .filter_map(|route| {
+                let name = route.name.clone()?;
+                Some((
+                    (route.method.as_str().to_string(), route.path.clone()),
+                    name,
+                ))
+            })
+            .collect();
+
+        Self {
+            registry,
+            route_names: Arc::new(route_names),
+        }
+    }
+
+    fn route_label(&self, method: &str, matched_path: Option<&str>) -> String {
+        match matched_path {
+            Some(path) => self
+                .route_names
+                .get(&(method.to_string(), path.to_string()))
+                .cloned()
+                .unwrap_or_else(|| path.to_string()),
+            None => UNMATCHED_ROUTE.to_string(),
+        }
+    }
+
+    /// Axum middleware function recording the requests it runs around
+    ///
+    /// Must be added with `Router::layer` so the matched route is known. It runs below the
+    /// framework request types, hence the raw Axum ones.
+    pub(crate) fn layer_fn(
+        self,
+    ) -> impl Fn(
+        axum::extract::Request,
+        Next,
+    ) -> Pin<Box<dyn Future<Output = axum::response::Response> + Send>>
+           + Clone
+           + Send
+           + Sync
+           + 'static {
+        move |request, next| {
+            let metrics = self.clone();
+            Box::pin(async move {
+                let method = request.method().as_str().to_string();
+                let route = metrics.route_label(
+                    &method,
+                    request
+                        .extensions()
+                        .get::<MatchedPath>()
+                        .map(MatchedPath::as_str),
+                );
+                let labels = [("method", method.as_str()), ("route", route.as_str())];
+
+                let registry = &metrics.registry;
+                let in_flight = InFlight::start(registry, &labels);
+                let start = Instant::now();
+                let response = next.run(request).await;
+                let elapsed = start.elapsed().as_secs_f64();
+                drop(in_flight);
+
+                let status = response.status();
+                let status_label = status.as_u16().to_string();
+                registry.increment_counter(
+                    "http_requests_total",
+                    "HTTP requests handled",
+                    &[
+                        ("method", method.as_str()),
+                        ("route", route.as_str()),
+                        ("status", status_label.as_str()),
+                    ],
+                    1.0,
+                );
+                if status.is_server_error() {
+                    registry.increment_counter(
+                        "http_request_errors_total",
+                        "HTTP requests answered with a 5xx status",
+                        &labels,
+                        1.0,
+                    );
+                }
+                registry.observe(
+                    "http_request_duration_seconds",
+                    "HTTP request latency",
+                    &labels,
+                    elapsed,
+                );
+
+                response
+            })
+        }
+    }
+}
+
+/// Counts a request as in flight until dropped, also when the client goes away
+struct InFlight<'a> {
+    registry: &'a MetricsRegistry,
+    method: String,
+    route: String,
+}
+
+impl<'a> InFlight<'a> {
+    const NAME: &'static str = "http_requests_in_flight";
+    const HELP: &'static str = "HTTP requests being processed";
+
+    fn start(registry: &'a MetricsRegistry, labels: &[(&str, &str); 2]) -> Self {
+        registry.add_gauge(Self::NAME, Self::HELP, labels, 1.0);
+        Self {
+            registry,
+            method: labels[0].1.to_string(),
+            route: labels[1].1.to_string(),
+        }
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        let labels = [
+            ("method", self.method.as_str()),
+            ("route", self.route.as_str()),
+        ];
+        self.registry
+            .add_gauge(Self::NAME, Self::HELP, &labels, -1.0);
+    }
+}
+
+/// Handler serving the metrics of a registry in the Prometheus text format
+pub fn metrics_handler(
+    registry: Arc<MetricsRegistry>,
+) -> impl Fn(
+    ElifRequest,
+) -> Pin<Box<dyn Future<Output = crate::errors::HttpResult<ElifResponse>> + Send>>
+       + Clone
+       + Send
+       + 'static {
+    move |_request| {
+        let registry = Arc::clone(&registry);
+        Box::pin(async move {
+            let body = registry.render().await;
+            ElifResponse::text_with_type(&body, "text/plain; version=0.0.4; charset=utf-8")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::HttpResult;
+    use crate::request::ElifMethod;
+    use crate::response::{ElifHeaderMap, ElifStatusCode};
+    use crate::routing::ElifRouter;
+    use tower::ServiceExt;
+
+    async fn show_user(_req: ElifRequest) -> HttpResult<ElifResponse> {
+        Ok(ElifResponse::ok().text("user"))
+    }
+
+    async fn fail(_req: ElifRequest) -> HttpResult<ElifResponse> {
+        Ok(ElifResponse::with_status(
+            ElifStatusCode::INTERNAL_SERVER_ERROR,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_records_red_metrics_per_route() {
+        let registry = Arc::new(MetricsRegistry::new());
+        let app = ElifRouter::<()>::new()
+            .route("/users/:id")
+            .name("users.show")
+            .get(show_user)
+            .get("/fail", fail)
+            .get("/metrics", metrics_handler(Arc::clone(&registry)))
+            .with_metrics(Arc::clone(&registry))
+            .into_axum_router();
+
+        let call = |path: &'static str| {
+            let app = app.clone();
+            async move {
+                let request =
+                    ElifRequest::new(ElifMethod::GET, path.parse().unwrap(), ElifHeaderMap::new())
+                        .into_axum_request();
+                let response = app.oneshot(request).await.unwrap();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                String::from_utf8(body.to_vec()).unwrap()
+            }
+        };
+
+        call("/users/1").await;
+        call("/users/2").await;
+        call("/fail").await;
+        call("/missing").await;
+
+        let show = [("method", "GET"), ("route", "users.show")];
+        assert_eq!(
+            registry.value(
+                "http_requests_total",
+                &[show[0], show[1], ("status", "200")]
+            ),
+            Some(2.0)
+        );
+        assert_eq!(
+            registry.value("http_request_duration_seconds", &show),
+            Some(2.0)
+        );
+        assert_eq!(registry.value("http_requests_in_flight", &show), Some(0.0));
+
+        let failed = [("method", "GET"), ("route", "/fail")];
+        assert_eq!(
+            registry.value("http_request_errors_total", &failed),
+            Some(1.0)
+        );
+        assert_eq!(
+            registry.value(
+                "http_requests_total",
+                &[
+                    ("method", "GET"),
+                    ("route", UNMATCHED_ROUTE),
+                    ("status", "404")
+                ]
+            ),
+            Some(1.0)
+        );
+
+        let output = call("/metrics").await;
+        assert!(output
+            .contains("http_requests_total{method=\"GET\",route=\"users.show\",status=\"200\"} 2"));
+        assert!(output.contains("# TYPE http_request_duration_seconds histogram"));
+    }
+}
@@ -0,0 +1,24 @@
+//! Application metrics in the Prometheus text format
+//!
+//! A [`MetricsRegistry`] holds counters, gauges and histograms. When
+//! [`HttpConfig::metrics_path`](crate::config::HttpConfig::metrics_path) is set, the server
+//! records RED metrics (requests, errors and latency) for every route and serves the
+//! registry at that path. [`MetricsCollector`]s copy the statistics of other components
+//! into the registry at scrape time, see [`collectors`].
+//!
+//! The server uses the registry registered in the IoC container, or
+//! [`MetricsRegistry::global`] when there is none.
+
+pub mod collectors;
+pub mod http;
+pub mod registry;
+
+#[cfg(feature = "cache")]
+pub use collectors::CacheCollector;
+#[cfg(feature = "orm")]
+pub use collectors::PoolCollector;
+#[cfg(feature = "queue")]
+pub use collectors::QueueCollector;
+pub use collectors::{MiddlewareCollector, WebSocketCollector};
+pub use http::{metrics_handler, HttpMetrics, UNMATCHED_ROUTE};
+pub use registry::{MetricsCollector, MetricsRegistry, DEFAULT_BUCKETS};
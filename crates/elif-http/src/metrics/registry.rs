@@ -0,0 +1,371 @@
+//! Metrics registry rendering the Prometheus text format
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Default histogram buckets in seconds, suited to request latencies
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Source of metrics read when the registry is rendered
+///
+/// Collectors copy the statistics other components keep, such as pool or cache
+/// statistics, into gauges and counters right before each scrape.
+#[async_trait]
+pub trait MetricsCollector: Send + Sync {
+    /// Record the current values into the registry
+    async fn collect(&self, registry: &MetricsRegistry);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+type Labels = Vec<(String, String)>;
+
+#[derive(Debug, Clone)]
+enum Series {
+    Value(f64),
+    Histogram {
+        buckets: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+#[derive(Debug)]
+struct Family {
+    kind: MetricKind,
+    help: String,
+    series: BTreeMap<Labels, Series>,
+}
+
+static GLOBAL_REGISTRY: Lazy<Arc<MetricsRegistry>> = Lazy::new(|| Arc::new(MetricsRegistry::new()));
+
+/// Counters, gauges and histograms labelled by name/value pairs
+///
+/// Metric names should follow the Prometheus conventions, e.g. `http_requests_total`;
+/// the first update of a name fixes its type and help text.
+pub struct MetricsRegistry {
+    families: Mutex<BTreeMap<String, Family>>,
+    collectors: RwLock<Vec<Arc<dyn MetricsCollector>>>,
+    buckets: Vec<f64>,
+}
+
+impl std::fmt::Debug for MetricsRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsRegistry")
+            .field("buckets", &self.buckets)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry with the default histogram buckets
+    pub fn new() -> Self {
+        Self {
+            families: Mutex::new(BTreeMap::new()),
+            collectors: RwLock::new(Vec::new()),
+            buckets: DEFAULT_BUCKETS.to_vec(),
+        }
+    }
+
+    /// Use different histogram bucket bounds
+    pub fn with_buckets(mut self, mut buckets: Vec<f64>) -> Self {
+        buckets.sort_by(|a, b| a.total_cmp(b));
+        self.buckets = buckets;
+        self
+    }
+
+    /// Get the registry the server uses unless one is registered in the container
+    pub fn global() -> Arc<MetricsRegistry> {
+        Arc::clone(&GLOBAL_REGISTRY)
+    }
+
+    /// Add a collector run before every render
+    pub fn register_collector<C: MetricsCollector + 'static>(&self, collector: C) {
+        self.collectors
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::new(collector));
+    }
+
+    /// Increase a counter
+    pub fn increment_counter(&self, name: &str, help: &str, labels: &[(&str, &str)], by: f64) {
+        self.update(name, help, MetricKind::Counter, labels, |series| {
+            if let Series::Value(value) = series {
+                *value += by;
+            }
+        });
+    }
+
+    /// Set a counter to a total kept elsewhere, e.g. in a statistics struct
+    pub fn set_counter(&self, name: &str, help: &str, labels: &[(&str, &str)], total: f64) {
+        self.update(name, help, MetricKind::Counter, labels, |series| {
+            *series = Series::Value(total);
+        });
+    }
+
+    /// Set a gauge
+    pub fn set_gauge(&self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        self.update(name, help, MetricKind::Gauge, labels, |series| {
+            *series = Series::Value(value);
+        });
+    }
+
+    /// Increase a gauge, or decrease it with a negative amount
+    pub fn add_gauge(&self, name: &str, help: &str, labels: &[(&str, &str)], by: f64) {
+        self.update(name, help, MetricKind::Gauge, labels, |series| {
+            if let Series::Value(value) = series {
+                *value += by;
+            }
+        });
+    }
+
+    /// Record an observation in a histogram
+    pub fn observe(&self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        let bounds = &self.buckets;
+        self.update(name, help, MetricKind::Histogram, labels, |series| {
+            if let Series::Histogram {
+                buckets,
+                sum,
+                count,
+            } = series
+            {
+                for (bucket, bound) in buckets.iter_mut().zip(bounds) {
+                    if value <= *bound {
+                        *bucket += 1;
+                    }
+                }
+                *sum += value;
+                *count += 1;
+            }
+        });
+    }
+
+    /// Get the value of a counter or gauge, or the observation count of a histogram
+    pub fn value(&self, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+        let families = self.families.lock().unwrap_or_else(|e| e.into_inner());
+        match families.get(name)?.series.get(&owned_labels(labels))? {
+            Series::Value(value) => Some(*value),
+            Series::Histogram { count, .. } => Some(*count as f64),
+        }
+    }
+
+    /// Run the collectors and render all metrics in the Prometheus text format
+    pub async fn render(&self) -> String {
+        let collectors = self
+            .collectors
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for collector in collectors {
+            collector.collect(self).await;
+        }
+        self.encode()
+    }
+
+    /// Render the current metrics in the Prometheus text format without running collectors
+    pub fn encode(&self) -> String {
+        let families = self.families.lock().unwrap_or_else(|e| e.into_inner());
+        let mut output = String::new();
+
+        for (name, family) in families.iter() {
+            let _ = writeln!(output, "# HELP {} {}", name, escape_help(&family.help));
+            let _ = writeln!(output, "# TYPE {} {}", name, family.kind.as_str());
+
+            for (labels, series) in &family.series {
+                match series {
+                    Series::Value(value) => {
+                        let _ = writeln!(
+                            output,
+                            "{}{} {}",
+                            name,
+                            format_labels(labels, None),
+                            format_value(*value)
+                        );
+                    }
+                    Series::Histogram {
+                        buckets,
+                        sum,
+                        count,
+                    } => {
+                        for (bound, bucket) in self.buckets.iter().zip(buckets) {
+                            let _ = writeln!(
+                                output,
+                                "{}_bucket{} {}",
+                                name,
+                                format_labels(labels, Some(&format_value(*bound))),
+                                bucket
+                            );
+                        }
+                        let _ = writeln!(
+                            output,
+                            "{}_bucket{} {}",
+                            name,
+                            format_labels(labels, Some("+Inf")),
+                            count
+                        );
+                        let _ = writeln!(
+                            output,
+                            "{}_sum{} {}",
+                            name,
+                            format_labels(labels, None),
+                            format_value(*sum)
+                        );
+                        let _ = writeln!(
+                            output,
+                            "{}_count{} {}",
+                            name,
+                            format_labels(labels, None),
+                            count
+                        );
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    fn update<F>(&self, name: &str, help: &str, kind: MetricKind, labels: &[(&str, &str)], f: F)
+    where
+        F: FnOnce(&mut Series),
+    {
+        let mut families = self.families.lock().unwrap_or_else(|e| e.into_inner());
+        let family = families.entry(name.to_string()).or_insert_with(|| Family {
+            kind,
+            help: help.to_string(),
+            series: BTreeMap::new(),
+        });
+        if family.kind != kind {
+            tracing::warn!(
+                "Metric '{}' is a {}, ignoring {} update",
+                name,
+                family.kind.as_str(),
+                kind.as_str()
+            );
+            return;
+        }
+
+        let series = family
+            .series
+            .entry(owned_labels(labels))
+            .or_insert_with(|| match kind {
+                MetricKind::Histogram => Series::Histogram {
+                    buckets: vec![0; self.buckets.len()],
+                    sum: 0.0,
+                    count: 0,
+                },
+                _ => Series::Value(0.0),
+            });
+        f(series);
+    }
+}
+
+fn owned_labels(labels: &[(&str, &str)]) -> Labels {
+    labels
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ConstantCollector;
+
+    #[async_trait]
+    impl MetricsCollector for ConstantCollector {
+        async fn collect(&self, registry: &MetricsRegistry) {
+            registry.set_gauge("queue_depth", "Jobs waiting", &[("queue", "mail")], 3.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_renders_prometheus_text() {
+        let registry = MetricsRegistry::new().with_buckets(vec![1.0, 0.1]);
+        registry.register_collector(ConstantCollector);
+
+        let labels = [("route", "users.show"), ("status", "200")];
+        registry.increment_counter("http_requests_total", "Requests", &labels, 1.0);
+        registry.increment_counter("http_requests_total", "Requests", &labels, 1.0);
+        registry.observe("latency_seconds", "Latency", &[], 0.05);
+        registry.observe("latency_seconds", "Latency", &[], 0.5);
+        registry.set_gauge("label_escaping", "Escaping", &[("path", "a\"b")], 1.0);
+
+        let output = registry.render().await;
+        assert!(output.contains("# TYPE http_requests_total counter"));
+        assert!(output.contains("http_requests_total{route=\"users.show\",status=\"200\"} 2"));
+        assert!(output.contains("latency_seconds_bucket{le=\"0.1\"} 1"));
+        assert!(output.contains("latency_seconds_bucket{le=\"1\"} 2"));
+        assert!(output.contains("latency_seconds_bucket{le=\"+Inf\"} 2"));
+        assert!(output.contains("latency_seconds_sum 0.55"));
+        assert!(output.contains("latency_seconds_count 2"));
+        assert!(output.contains("queue_depth{queue=\"mail\"} 3"));
+        assert!(output.contains("label_escaping{path=\"a\\\"b\"} 1"));
+
+        // The first update fixes the type of a metric
+        registry.set_gauge("http_requests_total", "Requests", &labels, 10.0);
+        assert_eq!(registry.value("http_requests_total", &labels), Some(2.0));
+    }
+}
@@ -174,12 +174,7 @@ impl Middleware for EnhancedLoggingMiddleware {
                     .and_then(|h| h.to_str().ok())
                     .map(String::from);
 
-                let remote_addr = request
-                    .headers
-                    .get_str("x-forwarded-for")
-                    .or_else(|| request.headers.get_str("x-real-ip"))
-                    .and_then(|h| h.to_str().ok())
-                    .map(String::from);
+                let remote_addr = request.client_ip();
 
                 RequestContext {
                     correlation_id,
@@ -3,10 +3,9 @@
 //! Provides comprehensive error handling including panic recovery and error response formatting.
 
 use crate::{
-    errors::HttpError,
+    errors::{ErrorContext, HttpError},
     middleware::v2::{Middleware, Next, NextFuture},
     request::ElifRequest,
-    response::IntoElifResponse,
 };
 use futures_util::future::FutureExt;
 
@@ -70,6 +69,7 @@ impl Default for ErrorHandlerMiddleware {
 impl Middleware for ErrorHandlerMiddleware {
     fn handle(&self, request: ElifRequest, next: Next) -> NextFuture<'static> {
         let config = self.config.clone();
+        let error_context = ErrorContext::from_request(&request);
         Box::pin(async move {
             // The future from `next.run()` might panic, so we catch it.
             // `AssertUnwindSafe` is used because the handler might not be `UnwindSafe`.
@@ -100,8 +100,7 @@ impl Middleware for ErrorHandlerMiddleware {
                         "Internal server error occurred".to_string()
                     };
 
-                    let http_error = HttpError::internal(error_message);
-                    http_error.into_response()
+                    error_context.render(HttpError::internal(error_message))
                 }
             }
         })
@@ -128,7 +127,7 @@ mod tests {
     use crate::{
         middleware::v2::MiddlewarePipelineV2,
         request::ElifMethod,
-        response::{headers::ElifHeaderMap, ElifResponse},
+        response::{headers::ElifHeaderMap, ElifResponse, IntoElifResponse},
     };
 
     #[tokio::test]
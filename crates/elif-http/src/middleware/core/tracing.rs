@@ -4,12 +4,13 @@
 //! Replaces tower-http TraceLayer with framework-native implementation.
 
 use std::time::Instant;
-use tracing::{error, info, warn, Level, Span};
+use tracing::{error, info, warn, Instrument, Level, Span};
 use uuid::Uuid;
 
 use crate::{
     middleware::v2::{Middleware, Next, NextFuture},
     request::ElifRequest,
+    response::ElifResponse,
 };
 
 /// Configuration for tracing middleware
@@ -124,6 +125,184 @@ impl TracingMiddleware {
             .iter()
             .any(|h| h == &header_lower)
     }
+
+    /// Log the request, run it and log the response
+    async fn trace(
+        config: TracingConfig,
+        request: ElifRequest,
+        next: Next,
+        request_id: Uuid,
+        start_time: Instant,
+    ) -> ElifResponse {
+        // Log request details based on level
+        match config.level {
+            Level::ERROR => error!(
+                "HTTP Request: {} {} (ID: {})",
+                request.method, request.uri, request_id
+            ),
+            Level::WARN => warn!(
+                "HTTP Request: {} {} (ID: {})",
+                request.method, request.uri, request_id
+            ),
+            Level::INFO => info!(
+                "HTTP Request: {} {} (ID: {})",
+                request.method, request.uri, request_id
+            ),
+            Level::DEBUG => {
+                let headers = {
+                    let mut header_strings = Vec::new();
+
+                    for name in request.headers.keys() {
+                        let name_str = name.as_str();
+                        if let Some(value) = request.headers.get_str(name_str) {
+                            let value_str = if config.include_sensitive_headers {
+                                value.to_str().unwrap_or("[INVALID_UTF8]")
+                            } else {
+                                let name_lower = name_str.to_lowercase();
+                                if config.sensitive_headers.iter().any(|h| h == &name_lower) {
+                                    "[REDACTED]"
+                                } else {
+                                    value.to_str().unwrap_or("[INVALID_UTF8]")
+                                }
+                            };
+                            header_strings.push(format!("{}={}", name_str, value_str));
+                        }
+                    }
+
+                    header_strings.join(", ")
+                };
+                tracing::debug!(
+                    "HTTP Request: {} {} (ID: {}) - Headers: {}",
+                    request.method,
+                    request.uri,
+                    request_id,
+                    headers
+                );
+            }
+            Level::TRACE => {
+                let headers = {
+                    let mut header_strings = Vec::new();
+
+                    for name in request.headers.keys() {
+                        let name_str = name.as_str();
+                        if let Some(value) = request.headers.get_str(name_str) {
+                            let value_str = if config.include_sensitive_headers {
+                                value.to_str().unwrap_or("[INVALID_UTF8]")
+                            } else {
+                                let name_lower = name_str.to_lowercase();
+                                if config.sensitive_headers.iter().any(|h| h == &name_lower) {
+                                    "[REDACTED]"
+                                } else {
+                                    value.to_str().unwrap_or("[INVALID_UTF8]")
+                                }
+                            };
+                            header_strings.push(format!("{}={}", name_str, value_str));
+                        }
+                    }
+
+                    header_strings.join(", ")
+                };
+                tracing::trace!(
+                    "HTTP Request: {} {} (ID: {}) - Headers: {} - Body tracing: {}",
+                    request.method,
+                    request.uri,
+                    request_id,
+                    headers,
+                    config.trace_bodies
+                );
+            }
+        }
+
+        // Continue to next middleware/handler
+        let response = next.run(request).await;
+
+        // Calculate duration and log response
+        let duration = start_time.elapsed();
+        let status = response.status_code();
+
+        match config.level {
+            Level::ERROR if status.is_server_error() => {
+                error!(
+                    "HTTP Response: {:?} (Server Error) - Duration: {:?} (ID: {})",
+                    status, duration, request_id
+                );
+            }
+            Level::WARN if status.is_client_error() => {
+                warn!(
+                    "HTTP Response: {:?} (Client Error) - Duration: {:?} (ID: {})",
+                    status, duration, request_id
+                );
+            }
+            Level::INFO => {
+                info!(
+                    "HTTP Response: {:?} - Duration: {:?} (ID: {})",
+                    status, duration, request_id
+                );
+            }
+            Level::DEBUG => {
+                let headers = {
+                    let mut header_strings = Vec::new();
+
+                    for (name, value) in response.headers().iter() {
+                        let name_str = name.as_str();
+                        let value_str = if config.include_sensitive_headers {
+                            value.to_str().unwrap_or("[INVALID_UTF8]")
+                        } else {
+                            let name_lower = name_str.to_lowercase();
+                            if config.sensitive_headers.iter().any(|h| h == &name_lower) {
+                                "[REDACTED]"
+                            } else {
+                                value.to_str().unwrap_or("[INVALID_UTF8]")
+                            }
+                        };
+                        header_strings.push(format!("{}={}", name_str, value_str));
+                    }
+
+                    header_strings.join(", ")
+                };
+                tracing::debug!(
+                    "HTTP Response: {:?} - Duration: {:?} - Headers: {} (ID: {})",
+                    status,
+                    duration,
+                    headers,
+                    request_id
+                );
+            }
+            Level::TRACE => {
+                let headers = {
+                    let mut header_strings = Vec::new();
+
+                    for (name, value) in response.headers().iter() {
+                        let name_str = name.as_str();
+                        let value_str = if config.include_sensitive_headers {
+                            value.to_str().unwrap_or("[INVALID_UTF8]")
+                        } else {
+                            let name_lower = name_str.to_lowercase();
+                            if config.sensitive_headers.iter().any(|h| h == &name_lower) {
+                                "[REDACTED]"
+                            } else {
+                                value.to_str().unwrap_or("[INVALID_UTF8]")
+                            }
+                        };
+                        header_strings.push(format!("{}={}", name_str, value_str));
+                    }
+
+                    header_strings.join(", ")
+                };
+                tracing::trace!(
+                    "HTTP Response: {:?} - Duration: {:?} - Headers: {} - Body tracing: {} (ID: {})",
+                    status,
+                    duration,
+                    headers,
+                    config.trace_response_bodies,
+                    request_id
+                );
+            }
+            _ => {} // Skip logging for other combinations
+        }
+
+        response
+    }
 }
 
 impl Default for TracingMiddleware {
@@ -178,177 +357,22 @@ impl Middleware for TracingMiddleware {
                 ),
             };
 
-            // Enter the span for this request
-            let _enter = span.enter();
-
-            // Log request details based on level
-            match config.level {
-                Level::ERROR => error!(
-                    "HTTP Request: {} {} (ID: {})",
-                    request.method, request.uri, request_id
-                ),
-                Level::WARN => warn!(
-                    "HTTP Request: {} {} (ID: {})",
-                    request.method, request.uri, request_id
-                ),
-                Level::INFO => info!(
-                    "HTTP Request: {} {} (ID: {})",
-                    request.method, request.uri, request_id
-                ),
-                Level::DEBUG => {
-                    let headers = {
-                        let mut header_strings = Vec::new();
-
-                        for name in request.headers.keys() {
-                            let name_str = name.as_str();
-                            if let Some(value) = request.headers.get_str(name_str) {
-                                let value_str = if config.include_sensitive_headers {
-                                    value.to_str().unwrap_or("[INVALID_UTF8]")
-                                } else {
-                                    let name_lower = name_str.to_lowercase();
-                                    if config.sensitive_headers.iter().any(|h| h == &name_lower) {
-                                        "[REDACTED]"
-                                    } else {
-                                        value.to_str().unwrap_or("[INVALID_UTF8]")
-                                    }
-                                };
-                                header_strings.push(format!("{}={}", name_str, value_str));
-                            }
-                        }
-
-                        header_strings.join(", ")
-                    };
-                    tracing::debug!(
-                        "HTTP Request: {} {} (ID: {}) - Headers: {}",
-                        request.method,
-                        request.uri,
-                        request_id,
-                        headers
-                    );
-                }
-                Level::TRACE => {
-                    let headers = {
-                        let mut header_strings = Vec::new();
-
-                        for name in request.headers.keys() {
-                            let name_str = name.as_str();
-                            if let Some(value) = request.headers.get_str(name_str) {
-                                let value_str = if config.include_sensitive_headers {
-                                    value.to_str().unwrap_or("[INVALID_UTF8]")
-                                } else {
-                                    let name_lower = name_str.to_lowercase();
-                                    if config.sensitive_headers.iter().any(|h| h == &name_lower) {
-                                        "[REDACTED]"
-                                    } else {
-                                        value.to_str().unwrap_or("[INVALID_UTF8]")
-                                    }
-                                };
-                                header_strings.push(format!("{}={}", name_str, value_str));
-                            }
-                        }
-
-                        header_strings.join(", ")
-                    };
-                    tracing::trace!(
-                        "HTTP Request: {} {} (ID: {}) - Headers: {} - Body tracing: {}",
-                        request.method,
-                        request.uri,
-                        request_id,
-                        headers,
-                        config.trace_bodies
-                    );
-                }
-            }
-
-            // Continue to next middleware/handler
-            let response = next.run(request).await;
-
-            // Calculate duration and log response
-            let duration = start_time.elapsed();
-            let status = response.status_code();
-
-            match config.level {
-                Level::ERROR if status.is_server_error() => {
-                    error!(
-                        "HTTP Response: {:?} (Server Error) - Duration: {:?} (ID: {})",
-                        status, duration, request_id
-                    );
-                }
-                Level::WARN if status.is_client_error() => {
-                    warn!(
-                        "HTTP Response: {:?} (Client Error) - Duration: {:?} (ID: {})",
-                        status, duration, request_id
-                    );
-                }
-                Level::INFO => {
-                    info!(
-                        "HTTP Response: {:?} - Duration: {:?} (ID: {})",
-                        status, duration, request_id
-                    );
-                }
-                Level::DEBUG => {
-                    let headers = {
-                        let mut header_strings = Vec::new();
-
-                        for (name, value) in response.headers().iter() {
-                            let name_str = name.as_str();
-                            let value_str = if config.include_sensitive_headers {
-                                value.to_str().unwrap_or("[INVALID_UTF8]")
-                            } else {
-                                let name_lower = name_str.to_lowercase();
-                                if config.sensitive_headers.iter().any(|h| h == &name_lower) {
-                                    "[REDACTED]"
-                                } else {
-                                    value.to_str().unwrap_or("[INVALID_UTF8]")
-                                }
-                            };
-                            header_strings.push(format!("{}={}", name_str, value_str));
-                        }
-
-                        header_strings.join(", ")
-                    };
-                    tracing::debug!(
-                        "HTTP Response: {:?} - Duration: {:?} - Headers: {} (ID: {})",
-                        status,
-                        duration,
-                        headers,
-                        request_id
-                    );
-                }
-                Level::TRACE => {
-                    let headers = {
-                        let mut header_strings = Vec::new();
-
-                        for (name, value) in response.headers().iter() {
-                            let name_str = name.as_str();
-                            let value_str = if config.include_sensitive_headers {
-                                value.to_str().unwrap_or("[INVALID_UTF8]")
-                            } else {
-                                let name_lower = name_str.to_lowercase();
-                                if config.sensitive_headers.iter().any(|h| h == &name_lower) {
-                                    "[REDACTED]"
-                                } else {
-                                    value.to_str().unwrap_or("[INVALID_UTF8]")
-                                }
-                            };
-                            header_strings.push(format!("{}={}", name_str, value_str));
-                        }
+            // Continue the trace of the caller, if it sent a W3C trace context
+            #[cfg(feature = "otel")]
+            {
+                use opentelemetry::trace::TraceContextExt;
+                use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-                        header_strings.join(", ")
-                    };
-                    tracing::trace!(
-                        "HTTP Response: {:?} - Duration: {:?} - Headers: {} - Body tracing: {} (ID: {})",
-                        status,
-                        duration,
-                        headers,
-                        config.trace_response_bodies,
-                        request_id
-                    );
+                let parent = crate::logging::telemetry::extract_trace_context(&request.headers);
+                if parent.span().span_context().is_remote() {
+                    let _ = span.set_parent(parent);
                 }
-                _ => {} // Skip logging for other combinations
             }
 
-            response
+            // Run the rest of the request inside its span
+            Self::trace(config, request, next, request_id, start_time)
+                .instrument(span)
+                .await
         })
     }
 
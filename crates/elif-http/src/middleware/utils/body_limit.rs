@@ -129,6 +129,14 @@ impl Middleware for BodyLimitMiddleware {
     fn handle(&self, request: ElifRequest, next: Next) -> NextFuture<'static> {
         let config = self.config.clone();
         Box::pin(async move {
+            // Routes declaring their own body size limit enforce it themselves
+            if request
+                .route_limits()
+                .is_some_and(|limits| limits.max_body_size.is_some())
+            {
+                return next.run(request).await;
+            }
+
             // First, check Content-Length header if present
             let _content_length = {
                 if let Some(content_length) = request.headers.get_str("content-length") {
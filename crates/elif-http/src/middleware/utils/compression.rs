@@ -158,8 +158,8 @@ impl Middleware for CompressionMiddleware {
             // First get the response from the next handler
             let response = next.run(request).await;
 
-            if !wants_compression {
-                // Client doesn't want compression, return as-is
+            if !wants_compression || response.is_streaming() {
+                // Client doesn't want compression or the body is streamed, return as-is
                 return response;
             }
 
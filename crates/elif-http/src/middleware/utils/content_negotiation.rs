@@ -353,6 +353,11 @@ impl ContentNegotiationMiddleware {
         response: ElifResponse,
         target_type: ContentType,
     ) -> ElifResponse {
+        // Streamed bodies can't be buffered for conversion
+        if response.is_streaming() {
+            return response;
+        }
+
         let axum_response = response.into_axum_response();
         let (parts, body) = axum_response.into_parts();
 
@@ -55,6 +55,20 @@ impl ETagType {
         }
     }
 
+    /// Parse a comma-separated list of ETags from an If-Match/If-None-Match header
+    ///
+    /// `*` yields an empty list and must be handled by the caller.
+    pub fn parse_list(header_value: &str) -> Vec<Self> {
+        if header_value.trim() == "*" {
+            return Vec::new();
+        }
+
+        header_value
+            .split(',')
+            .filter_map(Self::from_header_value)
+            .collect()
+    }
+
     /// Check if this ETag matches another for conditional requests
     /// For If-None-Match, both strong and weak comparison allowed
     pub fn matches_for_if_none_match(&self, other: &Self) -> bool {
@@ -226,40 +240,12 @@ impl ETagMiddleware {
 
     /// Parse If-None-Match header
     fn parse_if_none_match(&self, header_value: &str) -> Vec<ETagType> {
-        let mut etags = Vec::new();
-
-        // Handle "*" case
-        if header_value.trim() == "*" {
-            return etags; // Return empty vec, will be handled specially
-        }
-
-        // Parse comma-separated ETags
-        for etag_str in header_value.split(',') {
-            if let Some(etag) = ETagType::from_header_value(etag_str) {
-                etags.push(etag);
-            }
-        }
-
-        etags
+        ETagType::parse_list(header_value)
     }
 
     /// Parse If-Match header
     fn parse_if_match(&self, header_value: &str) -> Vec<ETagType> {
-        let mut etags = Vec::new();
-
-        // Handle "*" case
-        if header_value.trim() == "*" {
-            return etags; // Return empty vec, will be handled specially
-        }
-
-        // Parse comma-separated ETags
-        for etag_str in header_value.split(',') {
-            if let Some(etag) = ETagType::from_header_value(etag_str) {
-                etags.push(etag);
-            }
-        }
-
-        etags
+        ETagType::parse_list(header_value)
     }
 
     /// Check If-None-Match condition
@@ -294,6 +280,11 @@ impl ETagMiddleware {
         if_match: Option<ElifHeaderValue>,
         request_method: ElifMethod,
     ) -> ElifResponse {
+        // Streamed bodies can't be hashed without buffering them
+        if response.is_streaming() {
+            return response;
+        }
+
         // Convert elif types to axum types for internal processing
         let axum_if_none_match = if_none_match.as_ref().map(|v| v.to_axum());
         let axum_if_match = if_match.as_ref().map(|v| v.to_axum());
@@ -336,16 +336,9 @@ impl MaintenanceModeMiddleware {
             }
         }
 
-        // Check allowed IPs (simplified - would need real IP extraction in production)
-        // For now, we'll check X-Forwarded-For or X-Real-IP headers
-        let client_ip = request
-            .header("x-forwarded-for")
-            .or_else(|| request.header("x-real-ip"))
-            .and_then(|h| h.to_str().ok())
-            .map(|s| s.split(',').next().unwrap_or(s).trim());
-
-        if let Some(ip) = client_ip {
-            if self.config.allowed_ips.contains(ip) {
+        // Check allowed IPs against the resolved client address
+        if let Some(ip) = request.client_ip() {
+            if self.config.allowed_ips.contains(&ip) {
                 return true;
             }
         }
@@ -627,17 +620,36 @@ mod tests {
             .allow_ip("192.168.1.100");
 
         // Request from allowed IP
+        let mut request = ElifRequest::new(
+            ElifMethod::GET,
+            "/api/data".parse().unwrap(),
+            ElifHeaderMap::new(),
+        );
+        request.insert_extension(crate::request::ConnectionInfo::plain(
+            "192.168.1.100:40000".parse().unwrap(),
+        ));
+
+        let next = Next::new(|_req| Box::pin(async move { ElifResponse::ok().text("Allowed IP") }));
+
+        let response = middleware.handle(request, next).await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+
+        // A spoofed forwarding header from another address is ignored
         let mut headers = ElifHeaderMap::new();
         headers.insert(
             crate::response::headers::ElifHeaderName::from_str("x-forwarded-for").unwrap(),
             "192.168.1.100".parse().unwrap(),
         );
-        let request = ElifRequest::new(ElifMethod::GET, "/api/data".parse().unwrap(), headers);
+        let mut request = ElifRequest::new(ElifMethod::GET, "/api/data".parse().unwrap(), headers);
+        request.insert_extension(crate::request::ConnectionInfo::plain(
+            "203.0.113.50:40000".parse().unwrap(),
+        ));
 
-        let next = Next::new(|_req| Box::pin(async move { ElifResponse::ok().text("Allowed IP") }));
+        let next =
+            Next::new(|_req| Box::pin(async move { ElifResponse::ok().text("Should be blocked") }));
 
         let response = middleware.handle(request, next).await;
-        assert_eq!(response.status_code(), ElifStatusCode::OK);
+        assert_eq!(response.status_code(), ElifStatusCode::SERVICE_UNAVAILABLE);
     }
 
     #[tokio::test]
@@ -3,13 +3,23 @@ pub mod compression;
 pub mod content_negotiation;
 pub mod etag;
 pub mod maintenance_mode;
+#[cfg(feature = "orm")]
+pub mod query_recorder;
 pub mod request_id;
+pub mod route_limits;
 pub mod timeout;
+pub mod trusted_proxy;
+pub mod validate_signature;
 
 pub use body_limit::*;
 pub use compression::*;
 pub use content_negotiation::*;
 pub use etag::*;
 pub use maintenance_mode::*;
+#[cfg(feature = "orm")]
+pub use query_recorder::*;
 pub use request_id::*;
+pub use route_limits::*;
 pub use timeout::*;
+pub use trusted_proxy::*;
+pub use validate_signature::*;
@@ -0,0 +1,147 @@
+//! # Query Recorder Middleware
+//!
+//! Installs an ORM `QueryRecorder` for every request so repeated lazy relationship
+//! queries (N+1 patterns) are detected and logged with the request ID.
+//! Place it after `RequestIdMiddleware` so the generated ID is available.
+
+use std::sync::Arc;
+
+use orm::loading::{QueryRecorder, QueryRecorderConfig};
+
+use crate::middleware::utils::request_id::RequestIdExt;
+use crate::middleware::v2::{Middleware, Next, NextFuture};
+use crate::request::ElifRequest;
+
+/// Middleware that records ORM queries per request and reports N+1 patterns
+#[derive(Debug, Clone, Default)]
+pub struct QueryRecorderMiddleware {
+    config: QueryRecorderConfig,
+}
+
+impl QueryRecorderMiddleware {
+    /// Create new query recorder middleware (enabled in debug builds only)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create query recorder middleware with custom configuration
+    pub fn with_config(config: QueryRecorderConfig) -> Self {
+        Self { config }
+    }
+
+    /// Enable or disable query recording
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.config.enabled = enabled;
+        self
+    }
+
+    /// Set the number of identical relationship queries that counts as N+1
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.config.threshold = threshold;
+        self
+    }
+}
+
+impl Middleware for QueryRecorderMiddleware {
+    fn handle(&self, request: ElifRequest, next: Next) -> NextFuture<'static> {
+        if !self.config.enabled {
+            return Box::pin(async move { next.run(request).await });
+        }
+
+        // Detections are reported once per request below instead of by the recorder
+        let log_warnings = self.config.log_warnings;
+        let mut recorder = QueryRecorder::with_config(QueryRecorderConfig {
+            log_warnings: false,
+            ..self.config.clone()
+        });
+        if let Some(request_id) = request.request_id_with_fallbacks() {
+            recorder = recorder.for_request(request_id);
+        }
+        let recorder = Arc::new(recorder);
+        let path = request.path().to_string();
+
+        Box::pin(async move {
+            let response = recorder.clone().scope(next.run(request)).await;
+
+            let detections = recorder.detections();
+            if log_warnings && !detections.is_empty() {
+                tracing::warn!(
+                    request_id = recorder.request_id().unwrap_or("-"),
+                    path = %path,
+                    queries = recorder.query_count(),
+                    relations = ?detections.iter().map(|d| d.relation.as_str()).collect::<Vec<_>>(),
+                    "Request issued N+1 queries"
+                );
+            }
+
+            response
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "QueryRecorderMiddleware"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::headers::{ElifHeaderMap, ElifHeaderName};
+    use crate::response::ElifResponse;
+
+    #[tokio::test]
+    async fn test_recorder_is_scoped_to_request() {
+        let middleware = QueryRecorderMiddleware::new().enabled(true);
+
+        let mut headers = ElifHeaderMap::new();
+        headers.insert(
+            ElifHeaderName::from_str("x-request-id").unwrap(),
+            "req-42".parse().unwrap(),
+        );
+        let request = ElifRequest::new(ElifMethod::GET, "/users".parse().unwrap(), headers);
+
+        let next = Next::new(|_req| {
+            Box::pin(async move {
+                let recorder = QueryRecorder::current().expect("recorder should be installed");
+                assert_eq!(recorder.request_id(), Some("req-42"));
+                assert!(!recorder.config().log_warnings);
+
+                for _ in 0..3 {
+                    orm::loading::record_relationship_query(
+                        "users.posts",
+                        "SELECT * FROM \"posts\" WHERE \"user_id\" = $1",
+                    );
+                }
+                assert!(recorder.has_n_plus_one());
+
+                ElifResponse::ok().text("Success")
+            })
+        });
+
+        let response = middleware.handle(request, next).await;
+        assert_eq!(
+            response.status_code(),
+            crate::response::status::ElifStatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_middleware_skips_recording() {
+        let middleware = QueryRecorderMiddleware::new().enabled(false);
+        let request = ElifRequest::new(
+            ElifMethod::GET,
+            "/users".parse().unwrap(),
+            ElifHeaderMap::new(),
+        );
+
+        let next = Next::new(|_req| {
+            Box::pin(async move {
+                assert!(QueryRecorder::current().is_none());
+                ElifResponse::ok().text("Success")
+            })
+        });
+
+        middleware.handle(request, next).await;
+    }
+}
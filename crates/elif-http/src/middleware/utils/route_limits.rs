@@ -0,0 +1,279 @@
+//! # Route Limits Middleware
+//!
+//! Enforces the [`RouteLimits`] declared on a single route: requests with a larger body
+//! are rejected with `413`, requests beyond the concurrency cap with `503`, and slow
+//! handlers are cut off with `408`. Routers install it automatically for routes that
+//! declare limits, in front of the global middleware; the request then carries its
+//! [`RouteLimits`] so that a global [`TimeoutMiddleware`](super::TimeoutMiddleware) or
+//! [`BodyLimitMiddleware`](super::BodyLimitMiddleware) defers to them.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_body_util::{LengthLimitError, Limited};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
+use tracing::warn;
+
+use crate::middleware::v2::{Middleware, Next, NextFuture};
+use crate::request::ElifRequest;
+use crate::response::{ElifResponse, ElifStatusCode};
+use crate::routing::RouteLimits;
+
+/// Middleware enforcing the limits of one route
+///
+/// Clones share the in-flight counter, so a single instance should be used per route.
+#[derive(Debug, Clone)]
+pub struct RouteLimitsMiddleware {
+    limits: RouteLimits,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+impl RouteLimitsMiddleware {
+    /// Create middleware enforcing the given limits
+    pub fn new(limits: RouteLimits) -> Self {
+        let in_flight = limits
+            .max_concurrency
+            .map(|requests| Arc::new(Semaphore::new(requests)));
+        Self { limits, in_flight }
+    }
+
+    /// Get the enforced limits
+    pub fn limits(&self) -> &RouteLimits {
+        &self.limits
+    }
+
+    /// Size of the request body, from the buffered body or the `Content-Length` header
+    fn body_size(request: &ElifRequest) -> Option<usize> {
+        request.body_bytes().map(|body| body.len()).or_else(|| {
+            request
+                .header("content-length")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+        })
+    }
+
+    /// Take an in-flight slot, `None` when the route has no concurrency limit
+    fn try_acquire(&self) -> Result<Option<OwnedSemaphorePermit>, TryAcquireError> {
+        self.in_flight
+            .as_ref()
+            .map(|semaphore| Arc::clone(semaphore).try_acquire_owned())
+            .transpose()
+    }
+
+    fn concurrency_exceeded(&self, path: &str) -> ElifResponse {
+        warn!("Route concurrency limit reached: {}", path);
+        ElifResponse::with_status(ElifStatusCode::SERVICE_UNAVAILABLE)
+            .with_header("retry-after", "1")
+            .json_value(serde_json::json!({
+                "error": {
+                    "code": "CONCURRENCY_LIMIT_EXCEEDED",
+                    "message": "Too many concurrent requests for this route",
+                    "max_concurrency": self.limits.max_concurrency
+                }
+            }))
+    }
+
+    /// Run `response` within the route timeout, mapping a timeout with `timed_out`
+    async fn within_timeout<T>(
+        &self,
+        response: impl Future<Output = T>,
+        timed_out: impl FnOnce(ElifResponse) -> T,
+    ) -> T {
+        match self.limits.timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, response).await {
+                Ok(response) => response,
+                Err(_) => {
+                    warn!("Request timed out after {:?}", timeout);
+                    timed_out(Self::request_timeout(timeout))
+                }
+            },
+            None => response.await,
+        }
+    }
+
+    fn payload_too_large(size: Option<usize>, max_size: usize, path: &str) -> ElifResponse {
+        match size {
+            Some(size) => warn!(
+                "Request body size {} bytes exceeds route limit of {} bytes: {}",
+                size, max_size, path
+            ),
+            None => warn!(
+                "Request body exceeds route limit of {} bytes: {}",
+                max_size, path
+            ),
+        }
+
+        ElifResponse::with_status(ElifStatusCode::PAYLOAD_TOO_LARGE).json_value(serde_json::json!({
+            "error": {
+                "code": "PAYLOAD_TOO_LARGE",
+                "message": format!("Request body exceeds the limit of {} bytes", max_size),
+                "max_body_size": max_size
+            }
+        }))
+    }
+
+    fn request_timeout(timeout: Duration) -> ElifResponse {
+        ElifResponse::with_status(ElifStatusCode::REQUEST_TIMEOUT).json_value(serde_json::json!({
+            "error": {
+                "code": "REQUEST_TIMEOUT",
+                "message": "Request timed out",
+                "timeout_duration_ms": timeout.as_millis() as u64
+            }
+        }))
+    }
+
+    /// Enforce the limits on an Axum request before any framework middleware runs
+    ///
+    /// Bodies declaring a larger `Content-Length` are rejected right away. Other bodies are
+    /// passed on unbuffered and fail with a [`LengthLimitError`] once they are read past the
+    /// limit, which the body and multipart readers turn into a `413`. Runs below the
+    /// framework request types, hence the raw Axum ones.
+    pub(crate) async fn handle_axum(
+        &self,
+        request: axum::extract::Request,
+        next: axum::middleware::Next,
+    ) -> axum::response::Response {
+        let path = request.uri().path().to_string();
+
+        // Held until the response is produced
+        let _permit = match self.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => return self.concurrency_exceeded(&path).into_axum_response(),
+        };
+
+        let (mut parts, body) = request.into_parts();
+        let body = match self.limits.max_body_size {
+            Some(max_size) => {
+                let declared = parts
+                    .headers
+                    .get(axum::http::header::CONTENT_LENGTH)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<usize>().ok());
+                if let Some(size) = declared.filter(|size| *size > max_size) {
+                    return Self::payload_too_large(Some(size), max_size, &path)
+                        .into_axum_response();
+                }
+
+                axum::body::Body::new(Limited::new(body, max_size))
+            }
+            None => body,
+        };
+        parts.extensions.insert(self.limits.clone());
+
+        let request = axum::extract::Request::from_parts(parts, body);
+        self.within_timeout(next.run(request), ElifResponse::into_axum_response)
+            .await
+    }
+}
+
+/// Check whether reading a body failed because it exceeded a route's size limit
+pub(crate) fn is_length_limit_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut error = Some(error);
+    while let Some(current) = error {
+        if current.is::<LengthLimitError>() {
+            return true;
+        }
+        error = current.source();
+    }
+    false
+}
+
+impl Middleware for RouteLimitsMiddleware {
+    fn handle(&self, mut request: ElifRequest, next: Next) -> NextFuture<'static> {
+        let middleware = self.clone();
+
+        Box::pin(async move {
+            if let Some(max_size) = middleware.limits.max_body_size {
+                if let Some(size) = Self::body_size(&request).filter(|size| *size > max_size) {
+                    return Self::payload_too_large(Some(size), max_size, request.path());
+                }
+            }
+
+            // Held until the response is produced
+            let _permit = match middleware.try_acquire() {
+                Ok(permit) => permit,
+                Err(_) => return middleware.concurrency_exceeded(request.path()),
+            };
+
+            request.insert_extension(middleware.limits.clone());
+            middleware
+                .within_timeout(next.run(request), |response| response)
+                .await
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "RouteLimitsMiddleware"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::ElifHeaderMap;
+    use std::time::Duration;
+
+    fn request(body: &'static [u8]) -> ElifRequest {
+        ElifRequest::new(
+            ElifMethod::POST,
+            "/upload".parse().unwrap(),
+            ElifHeaderMap::new(),
+        )
+        .with_body(body.into())
+    }
+
+    fn slow_next(delay: Duration) -> Next {
+        Next::new(move |_request: ElifRequest| {
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                ElifResponse::ok()
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_body_limit_and_timeout() {
+        let middleware = RouteLimitsMiddleware::new(
+            RouteLimits::new()
+                .max_body_size(4)
+                .timeout(Duration::from_millis(20)),
+        );
+
+        let response = middleware
+            .handle(request(b"1234"), slow_next(Duration::ZERO))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+
+        let response = middleware
+            .handle(request(b"12345"), slow_next(Duration::ZERO))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::PAYLOAD_TOO_LARGE);
+
+        let response = middleware
+            .handle(request(b""), slow_next(Duration::from_secs(5)))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::REQUEST_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let middleware = RouteLimitsMiddleware::new(RouteLimits::new().max_concurrency(1));
+
+        let first = middleware.handle(request(b""), slow_next(Duration::from_millis(50)));
+        let first = tokio::spawn(first);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let rejected = middleware
+            .handle(request(b""), slow_next(Duration::ZERO))
+            .await;
+        assert_eq!(rejected.status_code(), ElifStatusCode::SERVICE_UNAVAILABLE);
+
+        assert_eq!(first.await.unwrap().status_code(), ElifStatusCode::OK);
+        let response = middleware
+            .handle(request(b""), slow_next(Duration::ZERO))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+    }
+}
@@ -125,6 +125,14 @@ impl Middleware for TimeoutMiddleware {
         let timeout_message = self.config.timeout_message.clone();
 
         Box::pin(async move {
+            // Routes declaring their own timeout enforce it themselves
+            if request
+                .route_limits()
+                .is_some_and(|limits| limits.timeout.is_some())
+            {
+                return next.run(request).await;
+            }
+
             // Apply timeout to the entire middleware chain
             match timeout(timeout_duration, next.run(request)).await {
                 Ok(response) => {
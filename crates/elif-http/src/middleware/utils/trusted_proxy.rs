@@ -0,0 +1,323 @@
+//! # Trusted Proxy Middleware
+//!
+//! Resolves the original client address, scheme and host from the RFC 7239 `Forwarded`
+//! header or the `X-Forwarded-For`/`X-Forwarded-Proto`/`X-Forwarded-Host` headers, but only
+//! when the connected peer is a trusted proxy. The result is exposed through
+//! [`ElifRequest::client_ip`], [`ElifRequest::is_secure`] and [`ElifRequest::host`].
+
+use crate::config::{HttpConfig, TrustedProxies};
+use crate::errors::{HttpError, HttpResult};
+use crate::middleware::v2::{Middleware, Next, NextFuture};
+use crate::request::{ElifRequest, ForwardedInfo};
+use crate::response::ElifHeaderMap;
+
+use std::net::{IpAddr, SocketAddr};
+
+/// One proxy hop recorded in the forwarding headers
+#[derive(Debug, Default)]
+struct Hop {
+    /// `None` for obfuscated or unknown addresses
+    ip: Option<IpAddr>,
+    proto: Option<String>,
+    host: Option<String>,
+}
+
+/// Middleware resolving client details from forwarding headers set by trusted proxies
+#[derive(Debug, Clone)]
+pub struct TrustedProxyMiddleware {
+    proxies: TrustedProxies,
+}
+
+impl TrustedProxyMiddleware {
+    /// Create middleware trusting the given proxy networks
+    pub fn new(proxies: TrustedProxies) -> Self {
+        Self { proxies }
+    }
+
+    /// Create middleware from the `trusted_proxies` setting of an HTTP config
+    pub fn from_config(config: &HttpConfig) -> HttpResult<Self> {
+        let proxies = config
+            .trusted_proxies()
+            .map_err(|e| HttpError::config(format!("Invalid trusted proxies: {}", e)))?;
+        Ok(Self::new(proxies))
+    }
+
+    /// Trusted proxy networks
+    pub fn proxies(&self) -> &TrustedProxies {
+        &self.proxies
+    }
+
+    /// Resolve client details for a request, `None` unless the peer is a trusted proxy
+    pub fn resolve(&self, request: &ElifRequest) -> Option<ForwardedInfo> {
+        let peer = request.connection()?.remote_addr?.ip();
+        self.resolve_headers(peer, &request.headers)
+    }
+
+    /// Resolve client details from the headers of a request sent by `peer`
+    pub(crate) fn resolve_headers(
+        &self,
+        peer: IpAddr,
+        headers: &ElifHeaderMap,
+    ) -> Option<ForwardedInfo> {
+        if !self.proxies.contains(&peer) {
+            return None;
+        }
+
+        let hops = match header_values(headers, "forwarded") {
+            Some(forwarded) => parse_forwarded(&forwarded),
+            None => parse_x_forwarded(headers),
+        };
+        if hops.is_empty() {
+            return None;
+        }
+
+        // Walk from the nearest hop towards the client, stopping at the first address
+        // that isn't a trusted proxy: anything before it may have been forged.
+        let mut client_ip = peer;
+        let mut chosen = None;
+        for hop in hops.iter().rev() {
+            if !self.proxies.contains(&client_ip) {
+                break;
+            }
+            chosen = Some(hop);
+            match hop.ip {
+                Some(ip) => client_ip = ip,
+                None => break,
+            }
+        }
+
+        let hop = chosen?;
+        Some(ForwardedInfo {
+            client_ip,
+            scheme: hop.proto.clone(),
+            host: hop.host.clone(),
+        })
+    }
+}
+
+impl Middleware for TrustedProxyMiddleware {
+    fn handle(&self, mut request: ElifRequest, next: Next) -> NextFuture<'static> {
+        if let Some(forwarded) = self.resolve(&request) {
+            request.insert_extension(forwarded);
+        }
+
+        Box::pin(async move { next.run(request).await })
+    }
+
+    fn name(&self) -> &'static str {
+        "TrustedProxyMiddleware"
+    }
+}
+
+/// All values of a header joined into one comma-separated list
+fn header_values(headers: &ElifHeaderMap, name: &str) -> Option<String> {
+    let values: Vec<&str> = headers
+        .iter()
+        .filter(|(header, _)| header.as_str().eq_ignore_ascii_case(name))
+        .filter_map(|(_, value)| value.to_str().ok())
+        .collect();
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(","))
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Parse an RFC 7239 `Forwarded` header into hops, client first
+fn parse_forwarded(value: &str) -> Vec<Hop> {
+    value
+        .split(',')
+        .map(|element| {
+            let mut hop = Hop::default();
+            for pair in element.split(';') {
+                let Some((key, value)) = pair.split_once('=') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"');
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "for" => hop.ip = parse_node(value),
+                    "proto" => hop.proto = Some(value.to_ascii_lowercase()),
+                    "host" => hop.host = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+            hop
+        })
+        .collect()
+}
+
+/// Build hops from `X-Forwarded-For`, pairing `X-Forwarded-Proto`/`X-Forwarded-Host`
+/// entries by position, or using their last entry when the lists differ in length
+fn parse_x_forwarded(headers: &ElifHeaderMap) -> Vec<Hop> {
+    let protos = header_values(headers, "x-forwarded-proto")
+        .map(|value| split_list(&value))
+        .unwrap_or_default();
+    let hosts = header_values(headers, "x-forwarded-host")
+        .map(|value| split_list(&value))
+        .unwrap_or_default();
+    let addresses = header_values(headers, "x-forwarded-for")
+        .map(|value| split_list(&value))
+        .unwrap_or_default();
+
+    let entry = |list: &[String], index: usize, len: usize| {
+        if list.len() == len {
+            list.get(index).cloned()
+        } else {
+            list.last().cloned()
+        }
+    };
+
+    if addresses.is_empty() {
+        if protos.is_empty() && hosts.is_empty() {
+            return Vec::new();
+        }
+        // Scheme or host forwarded without addresses: the proxy itself is the client hop
+        return vec![Hop {
+            ip: None,
+            proto: entry(&protos, 0, 1).map(|proto| proto.to_ascii_lowercase()),
+            host: entry(&hosts, 0, 1),
+        }];
+    }
+
+    let len = addresses.len();
+    addresses
+        .iter()
+        .enumerate()
+        .map(|(index, address)| Hop {
+            ip: parse_node(address),
+            proto: entry(&protos, index, len).map(|proto| proto.to_ascii_lowercase()),
+            host: entry(&hosts, index, len),
+        })
+        .collect()
+}
+
+/// Parse a node address: `192.0.2.1`, `192.0.2.1:8080`, `[2001:db8::1]:4711` or `2001:db8::1`
+fn parse_node(value: &str) -> Option<IpAddr> {
+    let value = value.trim();
+    if let Ok(ip) = value.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Ok(addr) = value.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    value
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(ip, _)| ip.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::{ConnectionInfo, ElifMethod};
+    use crate::response::{ElifHeaderMap, ElifResponse};
+
+    fn request_from(peer: &str, headers: &[(&str, &str)]) -> ElifRequest {
+        let mut header_map = ElifHeaderMap::new();
+        for (name, value) in headers {
+            header_map.add_header(name, value).unwrap();
+        }
+        let mut request = ElifRequest::new(ElifMethod::GET, "/".parse().unwrap(), header_map);
+        request.insert_extension(ConnectionInfo::plain(peer.parse().unwrap()));
+        request
+    }
+
+    fn middleware() -> TrustedProxyMiddleware {
+        TrustedProxyMiddleware::new(TrustedProxies::parse(["10.0.0.0/8"]).unwrap())
+    }
+
+    #[test]
+    fn test_untrusted_peer_is_ignored() {
+        let request = request_from(
+            "203.0.113.7:5000",
+            &[
+                ("x-forwarded-for", "1.2.3.4"),
+                ("x-forwarded-proto", "https"),
+            ],
+        );
+
+        assert!(middleware().resolve(&request).is_none());
+        assert_eq!(request.client_ip().as_deref(), Some("203.0.113.7"));
+        assert!(!request.is_secure());
+    }
+
+    #[test]
+    fn test_x_forwarded_headers_skip_trusted_hops() {
+        let request = request_from(
+            "10.0.0.2:5000",
+            &[
+                ("x-forwarded-for", "6.6.6.6, 198.51.100.4, 10.0.0.9"),
+                ("x-forwarded-proto", "https"),
+                ("x-forwarded-host", "shop.example.com:8443"),
+            ],
+        );
+
+        let forwarded = middleware().resolve(&request).unwrap();
+        assert_eq!(
+            forwarded.client_ip,
+            "198.51.100.4".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(forwarded.scheme.as_deref(), Some("https"));
+        assert_eq!(forwarded.host.as_deref(), Some("shop.example.com:8443"));
+    }
+
+    #[test]
+    fn test_forwarded_header() {
+        let request = request_from(
+            "10.0.0.2:5000",
+            &[
+                (
+                    "forwarded",
+                    "for=\"[2001:db8::17]:4711\";proto=https;host=api.example.com",
+                ),
+                ("x-forwarded-for", "6.6.6.6"),
+            ],
+        );
+
+        let forwarded = middleware().resolve(&request).unwrap();
+        assert_eq!(
+            forwarded.client_ip,
+            "2001:db8::17".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(forwarded.scheme.as_deref(), Some("https"));
+
+        let request = request_from("10.0.0.2:5000", &[("forwarded", "for=unknown;proto=http")]);
+        let forwarded = middleware().resolve(&request).unwrap();
+        assert_eq!(forwarded.client_ip, "10.0.0.2".parse::<IpAddr>().unwrap());
+        assert_eq!(forwarded.scheme.as_deref(), Some("http"));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_exposes_client_details() {
+        let request = request_from(
+            "10.1.1.1:5000",
+            &[
+                ("host", "internal:8080"),
+                ("x-forwarded-for", "192.0.2.44"),
+                ("x-forwarded-proto", "https"),
+                ("x-forwarded-host", "www.example.com"),
+            ],
+        );
+
+        let next = Next::new(|request: ElifRequest| {
+            Box::pin(async move {
+                assert_eq!(request.client_ip().as_deref(), Some("192.0.2.44"));
+                assert!(request.is_secure());
+                assert_eq!(request.host(), Some("www.example.com"));
+                ElifResponse::ok()
+            })
+        });
+
+        let response = middleware().handle(request, next).await;
+        assert_eq!(response.status_code(), crate::response::ElifStatusCode::OK);
+    }
+}
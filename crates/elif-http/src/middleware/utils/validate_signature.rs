@@ -0,0 +1,104 @@
+//! # Validate Signature Middleware
+//!
+//! Rejects requests whose URL was not produced by
+//! [`Router::signed_url_for`](crate::routing::Router::signed_url_for), was modified after
+//! signing, or has expired. Apply it to routes behind email verification or unsubscribe
+//! links.
+
+use crate::errors::{HttpError, HttpResult};
+use crate::middleware::v2::{Middleware, Next, NextFuture};
+use crate::request::ElifRequest;
+use crate::response::ElifResponse;
+use crate::routing::{SignatureError, UrlSigner};
+
+/// Middleware accepting only requests with a valid, unexpired URL signature
+#[derive(Debug, Clone)]
+pub struct ValidateSignature {
+    signer: UrlSigner,
+}
+
+impl ValidateSignature {
+    /// Validate signatures made with the given signer
+    pub fn new(signer: UrlSigner) -> Self {
+        Self { signer }
+    }
+
+    /// Validate signatures made with the application key from `APP_KEY`
+    pub fn from_env() -> HttpResult<Self> {
+        let signer = UrlSigner::from_env()
+            .map_err(|e| HttpError::config(format!("Cannot validate signatures: {}", e)))?;
+        Ok(Self::new(signer))
+    }
+
+    /// Check the signature of a request
+    pub fn validate(&self, request: &ElifRequest) -> Result<(), SignatureError> {
+        let path_and_query = request
+            .uri
+            .path_and_query()
+            .map_or_else(|| request.path(), |target| target.as_str());
+        self.signer.verify(path_and_query)
+    }
+}
+
+impl Middleware for ValidateSignature {
+    fn handle(&self, request: ElifRequest, next: Next) -> NextFuture<'static> {
+        let result = self.validate(&request);
+
+        Box::pin(async move {
+            match result {
+                Ok(()) => next.run(request).await,
+                Err(error) => {
+                    let code = match error {
+                        SignatureError::Expired => "expired_signature",
+                        _ => "invalid_signature",
+                    };
+                    ElifResponse::forbidden().with_json(&serde_json::json!({
+                        "error": {
+                            "code": code,
+                            "message": error.to_string()
+                        }
+                    }))
+                }
+            }
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "ValidateSignature"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::{ElifHeaderMap, ElifStatusCode};
+
+    async fn run(middleware: &ValidateSignature, url: &str) -> ElifStatusCode {
+        let request = ElifRequest::new(ElifMethod::GET, url.parse().unwrap(), ElifHeaderMap::new());
+        let next = Next::new(|_request: ElifRequest| Box::pin(async { ElifResponse::ok() }));
+        middleware.handle(request, next).await.status_code()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_modified_and_expired_links() {
+        let signer = UrlSigner::new("secret").unwrap();
+        let middleware = ValidateSignature::new(signer.clone());
+
+        let url = signer.sign("/email/verify/5?hash=abc", None);
+        assert_eq!(run(&middleware, &url).await, ElifStatusCode::OK);
+
+        let modified = url.replace("verify/5", "verify/6");
+        assert_eq!(run(&middleware, &modified).await, ElifStatusCode::FORBIDDEN);
+        assert_eq!(
+            run(&middleware, "/email/verify/5?hash=abc").await,
+            ElifStatusCode::FORBIDDEN
+        );
+
+        let expired = signer.sign(
+            "/email/verify/5",
+            Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+        );
+        assert_eq!(run(&middleware, &expired).await, ElifStatusCode::FORBIDDEN);
+    }
+}
@@ -661,11 +661,7 @@ impl RateLimitMiddleware {
 
     fn get_client_id(&self, request: &ElifRequest) -> String {
         // Simple IP-based rate limiting - in production you might use user ID, API key, etc.
-        request
-            .header("x-forwarded-for")
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("unknown")
-            .to_string()
+        request.client_ip().unwrap_or_else(|| "unknown".to_string())
     }
 
     fn is_rate_limited(&self, client_id: &str) -> bool {
@@ -844,6 +840,14 @@ impl Middleware for TimeoutMiddleware {
     fn handle(&self, request: ElifRequest, next: Next) -> NextFuture<'static> {
         let timeout = self.timeout;
         Box::pin(async move {
+            // Routes declaring their own timeout enforce it themselves
+            if request
+                .route_limits()
+                .is_some_and(|limits| limits.timeout.is_some())
+            {
+                return next.run(request).await;
+            }
+
             match tokio::time::timeout(timeout, next.run(request)).await {
                 Ok(response) => response,
                 Err(_) => ElifResponse::with_status(
@@ -880,6 +884,14 @@ impl Middleware for BodyLimitMiddleware {
     fn handle(&self, request: ElifRequest, next: Next) -> NextFuture<'static> {
         let max_bytes = self.max_bytes;
         Box::pin(async move {
+            // Routes declaring their own body size limit enforce it themselves
+            if request
+                .route_limits()
+                .is_some_and(|limits| limits.max_body_size.is_some())
+            {
+                return next.run(request).await;
+            }
+
             // Check if request has body and if it exceeds limit
             if let Some(body) = request.body_bytes() {
                 if body.len() as u64 > max_bytes {
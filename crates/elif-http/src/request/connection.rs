@@ -0,0 +1,151 @@
+//! Connection-level request information
+//!
+//! The server attaches a [`ConnectionInfo`] to every request it accepts: the peer
+//! address, whether the connection uses TLS and, with mutual TLS, the certificate the
+//! client presented. Behind trusted proxies, [`ForwardedInfo`] records the original
+//! client address, scheme and host taken from the forwarding headers.
+
+use super::ElifRequest;
+use axum::body::Bytes;
+use std::net::{IpAddr, SocketAddr};
+
+/// Certificate chain presented by a client over mutual TLS
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificate {
+    chain: Vec<Bytes>,
+}
+
+impl ClientCertificate {
+    /// Create from DER-encoded certificates, end-entity certificate first
+    pub fn new(chain: Vec<Bytes>) -> Self {
+        Self { chain }
+    }
+
+    /// DER encoding of the client's own certificate
+    pub fn der(&self) -> &[u8] {
+        self.chain
+            .first()
+            .map(|cert| cert.as_ref())
+            .unwrap_or_default()
+    }
+
+    /// DER encodings of the full chain, end-entity certificate first
+    pub fn chain(&self) -> &[Bytes] {
+        &self.chain
+    }
+}
+
+/// Information about the connection a request arrived on
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// Address of the connected peer
+    pub remote_addr: Option<SocketAddr>,
+    /// Whether the connection uses TLS
+    pub secure: bool,
+    /// Certificate presented by the client, with mutual TLS
+    pub client_certificate: Option<ClientCertificate>,
+}
+
+impl ConnectionInfo {
+    /// Connection info for a plain TCP connection
+    pub fn plain(remote_addr: SocketAddr) -> Self {
+        Self {
+            remote_addr: Some(remote_addr),
+            ..Default::default()
+        }
+    }
+
+    /// Connection info for a TLS connection
+    pub fn tls(remote_addr: SocketAddr, client_certificate: Option<ClientCertificate>) -> Self {
+        Self {
+            remote_addr: Some(remote_addr),
+            secure: true,
+            client_certificate,
+        }
+    }
+
+    /// Find connection info in Axum request extensions
+    pub(crate) fn from_extensions(extensions: &axum::http::Extensions) -> Option<Self> {
+        extensions.get::<ConnectionInfo>().cloned().or_else(|| {
+            extensions
+                .get::<axum::extract::ConnectInfo<SocketAddr>>()
+                .map(|info| Self::plain(info.0))
+        })
+    }
+}
+
+/// Client details reported by trusted proxies through forwarding headers
+///
+/// Inserted by [`TrustedProxyMiddleware`](crate::middleware::utils::TrustedProxyMiddleware)
+/// only when the connected peer is a trusted proxy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardedInfo {
+    /// Address of the original client
+    pub client_ip: IpAddr,
+    /// Scheme the client used, e.g. `https`
+    pub scheme: Option<String>,
+    /// Host the client requested
+    pub host: Option<String>,
+}
+
+impl ElifRequest {
+    /// Get information about the connection the request arrived on
+    pub fn connection(&self) -> Option<&ConnectionInfo> {
+        self.get_extension::<ConnectionInfo>()
+    }
+
+    /// Get the certificate presented by the client over mutual TLS
+    pub fn client_certificate(&self) -> Option<&ClientCertificate> {
+        self.connection()?.client_certificate.as_ref()
+    }
+
+    /// Get the client details resolved from trusted forwarding headers
+    pub fn forwarded(&self) -> Option<&ForwardedInfo> {
+        self.get_extension::<ForwardedInfo>()
+    }
+
+    /// Carry connection info from Axum request extensions
+    pub(crate) fn with_connection_from(mut self, extensions: &axum::http::Extensions) -> Self {
+        if let Some(connection) = ConnectionInfo::from_extensions(extensions) {
+            self.insert_extension(connection);
+        }
+        self
+    }
+
+    /// Carry client details resolved from trusted proxies out of Axum request extensions
+    pub(crate) fn with_forwarded_from(mut self, extensions: &axum::http::Extensions) -> Self {
+        if let Some(forwarded) = extensions.get::<ForwardedInfo>() {
+            self.insert_extension(forwarded.clone());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::ElifHeaderMap;
+
+    #[test]
+    fn test_connection_info_from_extensions() {
+        let addr: SocketAddr = "10.0.0.7:51234".parse().unwrap();
+        let certificate = ClientCertificate::new(vec![Bytes::from_static(b"leaf")]);
+
+        let mut extensions = axum::http::Extensions::new();
+        extensions.insert(ConnectionInfo::tls(addr, Some(certificate.clone())));
+        let request = ElifRequest::new(ElifMethod::GET, "/".parse().unwrap(), ElifHeaderMap::new())
+            .with_connection_from(&extensions);
+
+        assert!(request.is_secure());
+        assert_eq!(request.connection().unwrap().remote_addr, Some(addr));
+        assert_eq!(request.client_certificate().unwrap().der(), b"leaf");
+
+        let mut extensions = axum::http::Extensions::new();
+        extensions.insert(axum::extract::ConnectInfo(addr));
+        let request = ElifRequest::new(ElifMethod::GET, "/".parse().unwrap(), ElifHeaderMap::new())
+            .with_connection_from(&extensions);
+        assert!(!request.is_secure());
+        assert!(request.client_certificate().is_none());
+    }
+}
@@ -1,11 +1,19 @@
+pub mod connection;
 pub mod extractors;
 pub mod method;
+#[cfg(feature = "orm")]
+pub mod model_binding;
+pub mod multipart;
 pub mod pipeline;
 pub mod request;
 pub mod validation;
 
+pub use connection::*;
 pub use extractors::*;
 pub use method::*;
+#[cfg(feature = "orm")]
+pub use model_binding::*;
+pub use multipart::*;
 pub use pipeline::*;
 pub use request::*;
 pub use validation::*;
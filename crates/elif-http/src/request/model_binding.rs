@@ -0,0 +1,485 @@
+//! Route model binding
+//!
+//! Resolves route parameters such as `{user}` into ORM models, looked up by
+//! [`Model::route_key_name`] (the primary key unless overridden). Missing models, including
+//! soft-deleted ones, produce a 404 response. Install [`ModelBindingMiddleware`] to make the
+//! database pool available to handlers.
+
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use orm::backends::{DatabasePool, DatabaseValue, SqlDialect};
+use orm::query::QueryBuilder;
+use orm::Model;
+
+use crate::errors::{HttpError, HttpResult};
+use crate::middleware::v2::{Middleware, Next, NextFuture};
+use crate::request::ElifRequest;
+
+/// Database pool used to resolve route-bound models, carried in request extensions
+#[derive(Clone)]
+pub struct ModelBinding {
+    pool: Arc<dyn DatabasePool>,
+    dialect: SqlDialect,
+}
+
+impl ModelBinding {
+    /// Create a binding that resolves models from the given PostgreSQL pool
+    pub fn new(pool: Arc<dyn DatabasePool>) -> Self {
+        Self {
+            pool,
+            dialect: SqlDialect::PostgreSQL,
+        }
+    }
+
+    /// Set the SQL dialect of the pool's database
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Look up a model by its route key, returning `None` when no row matches
+    pub async fn find<M>(&self, value: &str, with_trashed: bool) -> HttpResult<Option<M>>
+    where
+        M: Model,
+        M::PrimaryKey: FromStr + Into<DatabaseValue>,
+    {
+        let route_key = M::route_key_name();
+        // A value that isn't a valid primary key can't match any row
+        let Some(param) = route_key_value::<M>(route_key, value) else {
+            return Ok(None);
+        };
+
+        let mut query = QueryBuilder::<M>::new()
+            .with_dialect(self.dialect.clone())
+            .from(M::table_name())
+            .where_eq(route_key, value);
+        if M::uses_soft_deletes() && !with_trashed {
+            query = query.where_null(M::deleted_at_column());
+        }
+        let (sql, _) = query.limit(1).to_sql_with_params();
+
+        match self.pool.fetch_optional(&sql, &[param]).await? {
+            Some(row) => Ok(Some(M::from_database_row(row.as_ref())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Carry the binding from Axum request extensions
+    pub(crate) fn from_extensions(extensions: &axum::http::Extensions) -> Option<Self> {
+        extensions.get::<Self>().cloned()
+    }
+}
+
+impl std::fmt::Debug for ModelBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelBinding").finish_non_exhaustive()
+    }
+}
+
+/// Bind a route key to the column type
+///
+/// Primary keys are parsed as the model's declared key type, other route keys are bound as
+/// strings. Returns `None` when the value doesn't parse as the primary key type.
+fn route_key_value<M>(route_key: &str, value: &str) -> Option<DatabaseValue>
+where
+    M: Model,
+    M::PrimaryKey: FromStr + Into<DatabaseValue>,
+{
+    if route_key == M::primary_key_name() {
+        value.parse::<M::PrimaryKey>().ok().map(Into::into)
+    } else {
+        Some(DatabaseValue::String(value.to_string()))
+    }
+}
+
+impl ElifRequest {
+    /// Get the model binding installed by [`ModelBindingMiddleware`]
+    pub fn model_binding(&self) -> Option<&ModelBinding> {
+        self.get_extension::<ModelBinding>()
+    }
+
+    /// Resolve a route parameter into a model, failing with 404 when it does not exist
+    pub async fn route_model<M>(&self, param: &str) -> HttpResult<M>
+    where
+        M: Model,
+        M::PrimaryKey: FromStr + Into<DatabaseValue>,
+    {
+        ElifModel::from_request(self, param)
+            .await
+            .map(ElifModel::into_inner)
+    }
+
+    /// Carry the model binding from Axum request extensions
+    pub(crate) fn with_model_binding_from(mut self, extensions: &axum::http::Extensions) -> Self {
+        if let Some(binding) = ModelBinding::from_extensions(extensions) {
+            self.insert_extension(binding);
+        }
+        self
+    }
+}
+
+/// A model resolved from a route parameter
+#[derive(Debug, Clone)]
+pub struct ElifModel<M>(pub M);
+
+impl<M> ElifModel<M>
+where
+    M: Model,
+    M::PrimaryKey: FromStr + Into<DatabaseValue>,
+{
+    /// Resolve the model named by a route parameter, excluding soft-deleted models
+    pub async fn from_request(request: &ElifRequest, param: &str) -> HttpResult<Self> {
+        Self::resolve(request, param, false).await
+    }
+
+    /// Resolve the model named by a route parameter, including soft-deleted models
+    pub async fn from_request_with_trashed(request: &ElifRequest, param: &str) -> HttpResult<Self> {
+        Self::resolve(request, param, true).await
+    }
+
+    async fn resolve(request: &ElifRequest, param: &str, with_trashed: bool) -> HttpResult<Self> {
+        let value = request
+            .path_param(param)
+            .ok_or_else(|| HttpError::bad_request(format!("Missing path parameter: {}", param)))?;
+        let binding = request.model_binding().ok_or_else(|| {
+            HttpError::internal("Route model binding requires ModelBindingMiddleware")
+        })?;
+
+        binding
+            .find::<M>(value, with_trashed)
+            .await?
+            .map(ElifModel)
+            .ok_or_else(|| HttpError::not_found(format!("{} '{}'", M::table_name(), value)))
+    }
+
+    /// Get the resolved model
+    pub fn into_inner(self) -> M {
+        self.0
+    }
+}
+
+impl<M> Deref for ElifModel<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M> DerefMut for ElifModel<M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Middleware making a database pool available for route model binding
+#[derive(Debug, Clone)]
+pub struct ModelBindingMiddleware {
+    binding: ModelBinding,
+}
+
+impl ModelBindingMiddleware {
+    /// Resolve route-bound models from the given PostgreSQL pool
+    pub fn new(pool: Arc<dyn DatabasePool>) -> Self {
+        Self {
+            binding: ModelBinding::new(pool),
+        }
+    }
+
+    /// Set the SQL dialect of the pool's database
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.binding = self.binding.with_dialect(dialect);
+        self
+    }
+}
+
+impl Middleware for ModelBindingMiddleware {
+    fn handle(&self, mut request: ElifRequest, next: Next) -> NextFuture<'static> {
+        request.insert_extension(self.binding.clone());
+        Box::pin(async move { next.run(request).await })
+    }
+
+    fn name(&self) -> &'static str {
+        "ModelBindingMiddleware"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::ElifHeaderMap;
+    use async_trait::async_trait;
+    use orm::backends::{DatabaseConnection, DatabasePoolStats, DatabaseRow, DatabaseTransaction};
+    use orm::{ModelError, ModelResult, OrmResult};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Post {
+        id: i64,
+        slug: String,
+    }
+
+    impl Model for Post {
+        type PrimaryKey = i64;
+
+        fn table_name() -> &'static str {
+            "posts"
+        }
+
+        fn route_key_name() -> &'static str {
+            "slug"
+        }
+
+        fn uses_soft_deletes() -> bool {
+            true
+        }
+
+        fn primary_key(&self) -> Option<i64> {
+            Some(self.id)
+        }
+
+        fn set_primary_key(&mut self, key: i64) {
+            self.id = key;
+        }
+
+        fn from_row(_row: &sqlx::postgres::PgRow) -> ModelResult<Self> {
+            Err(ModelError::Serialization("unused".to_string()))
+        }
+
+        fn from_database_row(row: &dyn DatabaseRow) -> ModelResult<Self> {
+            let id = match row.get_by_name("id")? {
+                DatabaseValue::Int64(id) => id,
+                _ => return Err(ModelError::Serialization("id".to_string())),
+            };
+            let slug = match row.get_by_name("slug")? {
+                DatabaseValue::String(slug) => slug,
+                _ => return Err(ModelError::Serialization("slug".to_string())),
+            };
+            Ok(Post { id, slug })
+        }
+
+        fn to_fields(&self) -> HashMap<String, serde_json::Value> {
+            HashMap::new()
+        }
+    }
+
+    /// Model keyed by its name
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Tag {
+        name: String,
+    }
+
+    impl Model for Tag {
+        type PrimaryKey = String;
+
+        fn table_name() -> &'static str {
+            "tags"
+        }
+
+        fn primary_key_name() -> &'static str {
+            "name"
+        }
+
+        fn primary_key(&self) -> Option<String> {
+            Some(self.name.clone())
+        }
+
+        fn set_primary_key(&mut self, key: String) {
+            self.name = key;
+        }
+
+        fn from_row(_row: &sqlx::postgres::PgRow) -> ModelResult<Self> {
+            Err(ModelError::Serialization("unused".to_string()))
+        }
+
+        fn to_fields(&self) -> HashMap<String, serde_json::Value> {
+            HashMap::new()
+        }
+    }
+
+    struct PostRow(Post);
+
+    impl DatabaseRow for PostRow {
+        fn get_by_index(&self, _index: usize) -> OrmResult<DatabaseValue> {
+            Err(ModelError::ColumnNotFound("index".to_string()))
+        }
+
+        fn get_by_name(&self, name: &str) -> OrmResult<DatabaseValue> {
+            match name {
+                "id" => Ok(DatabaseValue::Int64(self.0.id)),
+                "slug" => Ok(DatabaseValue::String(self.0.slug.clone())),
+                _ => Err(ModelError::ColumnNotFound(name.to_string())),
+            }
+        }
+
+        fn column_count(&self) -> usize {
+            2
+        }
+
+        fn column_names(&self) -> Vec<String> {
+            vec!["id".to_string(), "slug".to_string()]
+        }
+
+        fn to_json(&self) -> OrmResult<serde_json::Value> {
+            Ok(serde_json::to_value(&self.0).unwrap())
+        }
+
+        fn to_map(&self) -> OrmResult<HashMap<String, DatabaseValue>> {
+            Ok(HashMap::new())
+        }
+    }
+
+    /// Pool returning the `hello-world` post and recording the queries it receives
+    #[derive(Default)]
+    struct PostPool {
+        queries: Mutex<Vec<(String, Vec<DatabaseValue>)>>,
+    }
+
+    #[async_trait]
+    impl DatabasePool for PostPool {
+        async fn acquire(&self) -> OrmResult<Box<dyn DatabaseConnection>> {
+            Err(ModelError::Connection("unsupported".to_string()))
+        }
+
+        async fn begin_transaction(&self) -> OrmResult<Box<dyn DatabaseTransaction>> {
+            Err(ModelError::Transaction("unsupported".to_string()))
+        }
+
+        async fn execute(&self, _sql: &str, _params: &[DatabaseValue]) -> OrmResult<u64> {
+            Ok(0)
+        }
+
+        async fn fetch_all(
+            &self,
+            _sql: &str,
+            _params: &[DatabaseValue],
+        ) -> OrmResult<Vec<Box<dyn DatabaseRow>>> {
+            Ok(Vec::new())
+        }
+
+        async fn fetch_optional(
+            &self,
+            sql: &str,
+            params: &[DatabaseValue],
+        ) -> OrmResult<Option<Box<dyn DatabaseRow>>> {
+            self.queries
+                .lock()
+                .unwrap()
+                .push((sql.to_string(), params.to_vec()));
+            match params.first() {
+                Some(DatabaseValue::String(slug)) if slug == "hello-world" => {
+                    Ok(Some(Box::new(PostRow(Post {
+                        id: 1,
+                        slug: slug.clone(),
+                    }))))
+                }
+                _ => Ok(None),
+            }
+        }
+
+        async fn close(&self) -> OrmResult<()> {
+            Ok(())
+        }
+
+        fn stats(&self) -> DatabasePoolStats {
+            DatabasePoolStats {
+                total_connections: 0,
+                idle_connections: 0,
+                active_connections: 0,
+            }
+        }
+
+        async fn health_check(&self) -> OrmResult<std::time::Duration> {
+            Ok(std::time::Duration::ZERO)
+        }
+    }
+
+    fn request(slug: &str, pool: Arc<PostPool>) -> ElifRequest {
+        let mut request = ElifRequest::new(
+            ElifMethod::GET,
+            format!("/posts/{}", slug).parse().unwrap(),
+            ElifHeaderMap::new(),
+        );
+        request.add_path_param("post", slug);
+        request.insert_extension(ModelBinding::new(pool));
+        request
+    }
+
+    #[tokio::test]
+    async fn test_resolves_model_by_route_key() {
+        let pool = Arc::new(PostPool::default());
+
+        let post: Post = request("hello-world", pool.clone())
+            .route_model("post")
+            .await
+            .unwrap();
+        assert_eq!(post.id, 1);
+
+        let (sql, params) = pool.queries.lock().unwrap()[0].clone();
+        assert_eq!(
+            sql,
+            "SELECT * FROM \"posts\" WHERE \"slug\" = $1 AND \"deleted_at\" IS NULL LIMIT 1"
+        );
+        assert!(matches!(&params[..], [DatabaseValue::String(slug)] if slug == "hello-world"));
+
+        ElifModel::<Post>::from_request_with_trashed(&request("hello-world", pool.clone()), "post")
+            .await
+            .unwrap();
+        let (sql, _) = pool.queries.lock().unwrap()[1].clone();
+        assert!(!sql.contains("deleted_at"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_is_not_found() {
+        let pool = Arc::new(PostPool::default());
+
+        let error = ElifModel::<Post>::from_request(&request("missing", pool), "post")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, HttpError::NotFound { .. }));
+
+        let unbound = ElifRequest::new(
+            ElifMethod::GET,
+            "/posts/x".parse().unwrap(),
+            ElifHeaderMap::new(),
+        );
+        let error = ElifModel::<Post>::from_request(&unbound, "post")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, HttpError::BadRequest { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_query_follows_dialect() {
+        let pool = Arc::new(PostPool::default());
+        let binding = ModelBinding::new(pool.clone()).with_dialect(SqlDialect::MySQL);
+
+        binding.find::<Post>("hello-world", false).await.unwrap();
+        let (sql, _) = pool.queries.lock().unwrap()[0].clone();
+        assert!(sql.contains("\"slug\" = ? AND"));
+    }
+
+    #[test]
+    fn test_primary_key_values_are_typed() {
+        assert!(matches!(
+            route_key_value::<Post>("id", "42"),
+            Some(DatabaseValue::Int64(42))
+        ));
+        assert!(route_key_value::<Post>("id", "first").is_none());
+        assert!(matches!(
+            route_key_value::<Post>("slug", "42"),
+            Some(DatabaseValue::String(_))
+        ));
+
+        // String keys stay strings even when they look numeric
+        assert!(matches!(
+            route_key_value::<Tag>("name", "2024"),
+            Some(DatabaseValue::String(name)) if name == "2024"
+        ));
+    }
+}
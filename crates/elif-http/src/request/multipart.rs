@@ -0,0 +1,617 @@
+//! Multipart/form-data request parsing
+//!
+//! Parts are read one at a time and file parts can be consumed chunk by chunk, so uploads
+//! never have to be held in memory as a whole. Per-part and total size limits are enforced
+//! while parsing. With the `storage` feature, file parts can be streamed straight into an
+//! `elif-storage` backend with its file validation applied.
+
+use std::io;
+
+use axum::body::Bytes;
+use futures_util::{Stream, StreamExt};
+
+use crate::errors::{HttpError, HttpResult};
+use crate::request::ElifRequest;
+
+/// Limits applied while parsing a multipart body
+#[derive(Debug, Clone)]
+pub struct MultipartConfig {
+    /// Maximum size of a single part in bytes
+    pub max_part_size: u64,
+    /// Maximum size of the whole multipart body in bytes
+    pub max_total_size: u64,
+    /// Maximum number of parts
+    pub max_parts: usize,
+}
+
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        Self {
+            max_part_size: 10 * 1024 * 1024,  // 10MB
+            max_total_size: 50 * 1024 * 1024, // 50MB
+            max_parts: 100,
+        }
+    }
+}
+
+impl MultipartConfig {
+    /// Create config with default limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum size of a single part
+    pub fn max_part_size(mut self, size: u64) -> Self {
+        self.max_part_size = size;
+        self
+    }
+
+    /// Set the maximum size of the whole body
+    pub fn max_total_size(mut self, size: u64) -> Self {
+        self.max_total_size = size;
+        self
+    }
+
+    /// Set the maximum number of parts
+    pub fn max_parts(mut self, count: usize) -> Self {
+        self.max_parts = count;
+        self
+    }
+
+    fn constraints(&self) -> multer::Constraints {
+        multer::Constraints::new().size_limit(
+            multer::SizeLimit::new()
+                .whole_stream(self.max_total_size)
+                .per_field(self.max_part_size),
+        )
+    }
+}
+
+/// Streaming multipart/form-data reader
+pub struct ElifMultipart {
+    inner: multer::Multipart<'static>,
+    body_limit: Option<usize>,
+    max_parts: usize,
+    parts_read: usize,
+}
+
+impl ElifMultipart {
+    /// Read the multipart body of a request with default limits
+    pub fn from_request(request: &ElifRequest) -> HttpResult<Self> {
+        Self::from_request_with_config(request, MultipartConfig::default())
+    }
+
+    /// Read the multipart body of a request with custom limits
+    ///
+    /// The body of a request received by the server is streamed from the connection, so
+    /// size limits stop the upload as soon as they are exceeded. It can only be read once.
+    pub fn from_request_with_config(
+        request: &ElifRequest,
+        config: MultipartConfig,
+    ) -> HttpResult<Self> {
+        let content_type = request
+            .content_type()?
+            .ok_or_else(|| HttpError::bad_request("Missing Content-Type header"))?;
+
+        let mut multipart = match request.take_body_stream() {
+            Some(body) => Self::from_stream(&content_type, body.into_data_stream(), config),
+            None => {
+                let body = request.body_bytes().cloned().unwrap_or_default();
+                let stream = futures_util::stream::once(async move { Ok::<_, io::Error>(body) });
+                Self::from_stream(&content_type, stream, config)
+            }
+        }?;
+        multipart.body_limit = request.body_limit();
+        Ok(multipart)
+    }
+
+    /// Read a multipart body from a byte stream, using the boundary of a Content-Type value
+    pub fn from_stream<S, E>(
+        content_type: &str,
+        stream: S,
+        config: MultipartConfig,
+    ) -> HttpResult<Self>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        let boundary = multer::parse_boundary(content_type).map_err(|e| {
+            HttpError::bad_request(format!("Invalid multipart Content-Type: {}", e))
+        })?;
+
+        Ok(Self {
+            inner: multer::Multipart::with_constraints(stream, boundary, config.constraints()),
+            body_limit: None,
+            max_parts: config.max_parts,
+            parts_read: 0,
+        })
+    }
+
+    /// Get the next part, or `None` once the body is exhausted
+    ///
+    /// The previous part must be fully consumed or dropped before the next one is read.
+    pub async fn next_part(&mut self) -> HttpResult<Option<MultipartPart>> {
+        let body_limit = self.body_limit;
+        let field = self
+            .inner
+            .next_field()
+            .await
+            .map_err(|e| multipart_error(e, body_limit))?;
+        let Some(field) = field else {
+            return Ok(None);
+        };
+
+        self.parts_read += 1;
+        if self.parts_read > self.max_parts {
+            return Err(HttpError::bad_request(format!(
+                "Too many multipart parts (limit {})",
+                self.max_parts
+            )));
+        }
+
+        Ok(Some(MultipartPart { field, body_limit }))
+    }
+}
+
+impl std::fmt::Debug for ElifMultipart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElifMultipart")
+            .field("max_parts", &self.max_parts)
+            .field("parts_read", &self.parts_read)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A single part of a multipart body
+pub struct MultipartPart {
+    field: multer::Field<'static>,
+    body_limit: Option<usize>,
+}
+
+impl MultipartPart {
+    /// Form field name of the part
+    pub fn name(&self) -> Option<&str> {
+        self.field.name()
+    }
+
+    /// Original filename, present for file parts
+    pub fn file_name(&self) -> Option<&str> {
+        self.field.file_name()
+    }
+
+    /// Declared content type of the part
+    pub fn content_type(&self) -> Option<String> {
+        self.field.content_type().map(|mime| mime.to_string())
+    }
+
+    /// Check whether the part is a file upload
+    pub fn is_file(&self) -> bool {
+        self.field.file_name().is_some()
+    }
+
+    /// Read the next chunk of the part's content
+    pub async fn chunk(&mut self) -> HttpResult<Option<Bytes>> {
+        let body_limit = self.body_limit;
+        self.field
+            .chunk()
+            .await
+            .map_err(|e| multipart_error(e, body_limit))
+    }
+
+    /// Read the full content of the part
+    pub async fn bytes(self) -> HttpResult<Bytes> {
+        let body_limit = self.body_limit;
+        self.field
+            .bytes()
+            .await
+            .map_err(|e| multipart_error(e, body_limit))
+    }
+
+    /// Read the full content of the part as text
+    pub async fn text(self) -> HttpResult<String> {
+        let body_limit = self.body_limit;
+        self.field
+            .text()
+            .await
+            .map_err(|e| multipart_error(e, body_limit))
+    }
+
+    /// Convert the part into a stream of content chunks
+    ///
+    /// Limit violations are yielded as `io::Error`s of kind `InvalidData`.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Bytes, io::Error>> + Send + Unpin {
+        self.field
+            .map(|chunk| chunk.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+
+    /// Stream a file part into a storage backend, applying file validation
+    ///
+    /// The filename, extension and content type are validated before anything is written;
+    /// size and content are validated as the part streams through. A partially written file
+    /// is deleted if the upload fails.
+    #[cfg(feature = "storage")]
+    pub async fn store<B>(
+        self,
+        backend: &B,
+        path: &str,
+        validator: Option<&elif_storage::FileValidator>,
+        options: Option<elif_storage::UploadOptions>,
+    ) -> HttpResult<elif_storage::FileMetadata>
+    where
+        B: elif_storage::StorageBackend,
+    {
+        use elif_storage::ValidatedStream;
+
+        let file_name = self.file_name().unwrap_or(path).to_string();
+        let mime_type = options
+            .as_ref()
+            .and_then(|options| options.content_type.clone())
+            .or_else(|| self.content_type())
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+        let mut options = options.unwrap_or_default();
+        options.content_type = Some(mime_type.clone());
+
+        let result = match validator {
+            Some(validator) => {
+                validator
+                    .validate_metadata(&file_name, &mime_type)
+                    .map_err(storage_error)?;
+                let stream = validator.validate_stream(&file_name, &mime_type, self.into_stream());
+                backend.put_stream(path, stream, Some(options)).await
+            }
+            None => {
+                backend
+                    .put_stream(path, self.into_stream(), Some(options))
+                    .await
+            }
+        };
+
+        match result {
+            Ok(metadata) => Ok(metadata),
+            Err(error) => {
+                let _ = backend.delete(path).await;
+                Err(storage_error(ValidatedStream::<()>::into_storage_error(
+                    error,
+                )))
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for MultipartPart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultipartPart")
+            .field("name", &self.name())
+            .field("file_name", &self.file_name())
+            .field("content_type", &self.content_type())
+            .finish()
+    }
+}
+
+/// Map a parsing error, `body_limit` being the size limit of the request's route
+fn multipart_error(error: multer::Error, body_limit: Option<usize>) -> HttpError {
+    match error {
+        multer::Error::FieldSizeExceeded { limit, .. }
+        | multer::Error::StreamSizeExceeded { limit } => {
+            let limit = limit as usize;
+            HttpError::payload_too_large(limit.saturating_add(1), limit)
+        }
+        multer::Error::StreamReadFailed(e)
+            if crate::middleware::utils::route_limits::is_length_limit_error(e.as_ref()) =>
+        {
+            let limit = body_limit.unwrap_or_default();
+            HttpError::payload_too_large(limit.saturating_add(1), limit)
+        }
+        other => HttpError::bad_request(format!("Invalid multipart body: {}", other)),
+    }
+}
+
+#[cfg(feature = "storage")]
+fn storage_error(error: elif_storage::StorageError) -> HttpError {
+    use elif_storage::StorageError;
+
+    match error {
+        StorageError::FileTooLarge(size, limit) => {
+            HttpError::payload_too_large(size as usize, limit as usize)
+        }
+        StorageError::Validation(message) => HttpError::validation_error(message),
+        StorageError::UnsupportedFileType(mime_type) => {
+            HttpError::validation_error(format!("Unsupported file type: {}", mime_type))
+        }
+        other => HttpError::internal(format!("Failed to store upload: {}", other)),
+    }
+}
+
+impl ElifRequest {
+    /// Read the request body as multipart/form-data with default limits
+    pub fn multipart(&self) -> HttpResult<ElifMultipart> {
+        ElifMultipart::from_request(self)
+    }
+
+    /// Read the request body as multipart/form-data with custom limits
+    pub fn multipart_with_config(&self, config: MultipartConfig) -> HttpResult<ElifMultipart> {
+        ElifMultipart::from_request_with_config(self, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::ElifHeaderMap;
+
+    const BOUNDARY: &str = "X-ELIF-BOUNDARY";
+
+    fn multipart_request(body: &str) -> ElifRequest {
+        let mut request = ElifRequest::new(
+            ElifMethod::POST,
+            "/upload".parse().unwrap(),
+            ElifHeaderMap::new(),
+        )
+        .with_body(Bytes::from(body.replace('\n', "\r\n")));
+        request
+            .add_header(
+                "content-type",
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            )
+            .unwrap();
+        request
+    }
+
+    fn sample_body() -> String {
+        format!(
+            "--{b}\nContent-Disposition: form-data; name=\"title\"\n\nHoliday\n\
+             --{b}\nContent-Disposition: form-data; name=\"photo\"; filename=\"beach.txt\"\n\
+             Content-Type: text/plain\n\nsand and sea\n--{b}--\n",
+            b = BOUNDARY
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reads_fields_and_files() {
+        let request = multipart_request(&sample_body());
+        let mut multipart = request.multipart().unwrap();
+
+        let field = multipart.next_part().await.unwrap().unwrap();
+        assert_eq!(field.name(), Some("title"));
+        assert!(!field.is_file());
+        assert_eq!(field.text().await.unwrap(), "Holiday");
+
+        let mut file = multipart.next_part().await.unwrap().unwrap();
+        assert_eq!(file.name(), Some("photo"));
+        assert_eq!(file.file_name(), Some("beach.txt"));
+        assert_eq!(file.content_type().as_deref(), Some("text/plain"));
+        let mut content = Vec::new();
+        while let Some(chunk) = file.chunk().await.unwrap() {
+            content.extend_from_slice(&chunk);
+        }
+        assert_eq!(content, b"sand and sea");
+        drop(file);
+
+        assert!(multipart.next_part().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enforces_limits() {
+        let request = multipart_request(&sample_body());
+        let mut multipart = request
+            .multipart_with_config(MultipartConfig::new().max_part_size(8))
+            .unwrap();
+        multipart.next_part().await.unwrap().unwrap(); // title
+        let file = multipart.next_part().await.unwrap().unwrap();
+        assert!(matches!(
+            file.bytes().await,
+            Err(HttpError::RequestTooLarge { limit: 8, .. })
+        ));
+
+        let request = multipart_request(&sample_body());
+        let mut multipart = request
+            .multipart_with_config(MultipartConfig::new().max_parts(1))
+            .unwrap();
+        let title = multipart.next_part().await.unwrap().unwrap();
+        drop(title);
+        assert!(multipart.next_part().await.is_err());
+
+        let request = multipart_request(&sample_body());
+        let mut multipart = request
+            .multipart_with_config(MultipartConfig::new().max_total_size(32))
+            .unwrap();
+        let error = loop {
+            match multipart.next_part().await {
+                Ok(Some(part)) => {
+                    if let Err(e) = part.bytes().await {
+                        break e;
+                    }
+                }
+                Ok(None) => panic!("body should exceed the total size limit"),
+                Err(e) => break e,
+            }
+        };
+        assert!(matches!(
+            error,
+            HttpError::RequestTooLarge { limit: 32, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_upload_rejected_before_body_is_read() {
+        use crate::response::ElifResponse;
+        use crate::routing::ElifRouter;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        async fn upload(request: ElifRequest) -> HttpResult<ElifResponse> {
+            let mut multipart =
+                request.multipart_with_config(MultipartConfig::new().max_part_size(1024))?;
+            while let Some(part) = multipart.next_part().await? {
+                part.bytes().await?;
+            }
+            Ok(ElifResponse::ok())
+        }
+
+        const CHUNKS: usize = 1000;
+        let chunks_read = Arc::new(AtomicUsize::new(0));
+        let header = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"video\"; filename=\"big.bin\"\r\n\r\n",
+            BOUNDARY
+        );
+        let counter = Arc::clone(&chunks_read);
+        let body = futures_util::stream::iter(
+            std::iter::once(Bytes::from(header))
+                .chain(std::iter::repeat_n(Bytes::from(vec![b'a'; 1024]), CHUNKS)),
+        )
+        .then(move |chunk| {
+            let counter = Arc::clone(&counter);
+            async move {
+                // Chunks arrive over time, as from a connection
+                tokio::task::yield_now().await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, io::Error>(chunk)
+            }
+        });
+
+        let request = axum::extract::Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            )
+            .body(axum::body::Body::from_stream(body))
+            .unwrap();
+        let response = ElifRouter::<()>::new()
+            .post("/upload", upload)
+            .into_axum_router()
+            .oneshot(request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 413);
+        assert!(chunks_read.load(Ordering::SeqCst) < 10);
+    }
+
+    #[tokio::test]
+    async fn test_route_body_limit_keeps_uploads_streaming() {
+        use crate::response::ElifResponse;
+        use crate::routing::ElifRouter;
+        use std::sync::{Arc, Mutex};
+        use tower::ServiceExt;
+
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel::<()>();
+        let started_tx = Arc::new(Mutex::new(Some(started_tx)));
+        let upload = move |request: ElifRequest| {
+            let started_tx = Arc::clone(&started_tx);
+            async move {
+                // The handler runs before the client has sent the whole body
+                if let Some(started) = started_tx.lock().unwrap().take() {
+                    let _ = started.send(());
+                }
+                let mut multipart = request.multipart()?;
+                let mut size = 0;
+                while let Some(part) = multipart.next_part().await? {
+                    size += part.bytes().await?.len();
+                }
+                Ok::<_, HttpError>(ElifResponse::ok().text(size.to_string()))
+            }
+        };
+        let app = ElifRouter::<()>::new()
+            .route("/upload")
+            .max_body_size(4096)
+            .post(upload)
+            .into_axum_router();
+
+        let post = |chunks: tokio::sync::mpsc::Receiver<Bytes>| {
+            let body = futures_util::stream::unfold(chunks, |mut chunks| async move {
+                chunks
+                    .recv()
+                    .await
+                    .map(|chunk| (Ok::<_, io::Error>(chunk), chunks))
+            });
+            let request = axum::extract::Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={}", BOUNDARY),
+                )
+                .body(axum::body::Body::from_stream(body))
+                .unwrap();
+            tokio::spawn(app.clone().oneshot(request))
+        };
+        let header = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n\r\n",
+            BOUNDARY
+        );
+        let footer = format!("\r\n--{}--\r\n", BOUNDARY);
+
+        let (chunks, rx) = tokio::sync::mpsc::channel(4);
+        let response = post(rx);
+        chunks.send(Bytes::from(header.clone())).await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), started_rx)
+            .await
+            .expect("the body was buffered before the handler ran")
+            .unwrap();
+        chunks.send(Bytes::from(vec![b'a'; 1024])).await.unwrap();
+        chunks.send(Bytes::from(footer.clone())).await.unwrap();
+        drop(chunks);
+        let response = response.await.unwrap().unwrap();
+        assert_eq!(response.status(), 200);
+
+        let (chunks, rx) = tokio::sync::mpsc::channel(16);
+        let response = post(rx);
+        chunks.send(Bytes::from(header)).await.unwrap();
+        for _ in 0..8 {
+            if chunks.send(Bytes::from(vec![b'a'; 1024])).await.is_err() {
+                break;
+            }
+        }
+        let _ = chunks.send(Bytes::from(footer)).await;
+        drop(chunks);
+        assert_eq!(response.await.unwrap().unwrap().status(), 413);
+    }
+
+    #[cfg(feature = "storage")]
+    #[tokio::test]
+    async fn test_store_file_part_with_validation() {
+        use elif_storage::{
+            FileValidator, LocalBackend, LocalStorageConfig, StorageBackend, ValidationConfig,
+        };
+
+        let root = std::env::temp_dir().join(format!("elif-multipart-{}", uuid::Uuid::new_v4()));
+        let backend = LocalBackend::new(LocalStorageConfig::new().with_root_path(root.clone()));
+
+        let request = multipart_request(&sample_body());
+        let mut multipart = request.multipart().unwrap();
+        multipart.next_part().await.unwrap().unwrap(); // title
+        let file = multipart.next_part().await.unwrap().unwrap();
+        let validator = FileValidator::new(ValidationConfig::new().max_size(1024));
+        let metadata = file
+            .store(&backend, "photos/beach.txt", Some(&validator), None)
+            .await
+            .unwrap();
+        assert_eq!(metadata.size, 12);
+
+        let request = multipart_request(&sample_body());
+        let mut multipart = request.multipart().unwrap();
+        multipart.next_part().await.unwrap().unwrap(); // title
+        let file = multipart.next_part().await.unwrap().unwrap();
+        let validator = FileValidator::new(ValidationConfig::new().max_size(4));
+        let result = file
+            .store(&backend, "photos/too-big.txt", Some(&validator), None)
+            .await;
+        assert!(matches!(result, Err(HttpError::RequestTooLarge { .. })));
+        assert!(!backend.exists("photos/too-big.txt").await.unwrap());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_requires_multipart_content_type() {
+        let mut request = multipart_request(&sample_body());
+        request
+            .add_header("content-type", "application/json")
+            .unwrap();
+        assert!(request.multipart().is_err());
+    }
+}
@@ -7,10 +7,11 @@
 //! - Provides clean error handling throughout the pipeline
 //! - Supports route-specific middleware execution
 
+use crate::middleware::utils::RouteLimitsMiddleware;
 use crate::middleware::v2::{MiddlewarePipelineV2, NextFuture};
 use crate::request::ElifRequest;
 use crate::response::{ElifResponse, ElifStatusCode};
-use crate::routing::{HttpMethod, RouteMatch, RouteMatcher};
+use crate::routing::{HttpMethod, RouteLimits, RouteMatch, RouteMatcher};
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
@@ -22,7 +23,11 @@ pub enum PipelineError {
     RouteNotFound { method: HttpMethod, path: String },
 
     #[error("Method not allowed: {method}")]
-    MethodNotAllowed { method: String },
+    MethodNotAllowed {
+        method: String,
+        /// Methods the requested path does support, empty for unsupported HTTP methods
+        allowed: Vec<HttpMethod>,
+    },
 
     #[error("Parameter error: {0}")]
     Parameter(#[from] ParamError),
@@ -69,6 +74,8 @@ pub struct RequestPipeline {
     global_middleware: MiddlewarePipelineV2,
     /// Named middleware groups for route-specific execution
     middleware_groups: HashMap<String, MiddlewarePipelineV2>,
+    /// Limits enforced per route ID
+    route_limits: HashMap<String, RouteLimitsMiddleware>,
     /// Route handlers mapped by route ID (wrapped in Arc for efficient sharing)
     handlers: Arc<HashMap<String, Arc<HandlerFn>>>,
 }
@@ -80,6 +87,7 @@ impl RequestPipeline {
             matcher: Arc::new(matcher),
             global_middleware: MiddlewarePipelineV2::new(),
             middleware_groups: HashMap::new(),
+            route_limits: HashMap::new(),
             handlers: Arc::new(HashMap::new()),
         }
     }
@@ -103,6 +111,13 @@ impl RequestPipeline {
         self
     }
 
+    /// Enforce timeout, body size and concurrency limits for a specific route ID
+    pub fn add_route_limits<S: Into<String>>(mut self, route_id: S, limits: RouteLimits) -> Self {
+        self.route_limits
+            .insert(route_id.into(), RouteLimitsMiddleware::new(limits));
+        self
+    }
+
     /// Register a handler for a specific route ID
     pub fn add_handler<S: Into<String>, F>(mut self, route_id: S, handler: F) -> Self
     where
@@ -175,16 +190,40 @@ impl RequestPipeline {
             unsupported => {
                 return Err(PipelineError::MethodNotAllowed {
                     method: unsupported.to_string(),
+                    allowed: Vec::new(),
                 });
             }
         };
 
-        self.matcher
-            .resolve(&http_method, request.path())
-            .ok_or_else(|| PipelineError::RouteNotFound {
+        if let Some(route_match) =
+            self.matcher
+                .resolve_host(&http_method, request.host(), request.path())
+        {
+            return Ok(route_match);
+        }
+
+        // HEAD requests are answered by GET routes unless a HEAD route is defined
+        if http_method == HttpMethod::HEAD {
+            if let Some(route_match) =
+                self.matcher
+                    .resolve_host(&HttpMethod::GET, request.host(), request.path())
+            {
+                return Ok(route_match);
+            }
+        }
+
+        let allowed = self.matcher.allowed_methods(request.host(), request.path());
+        if allowed.is_empty() {
+            Err(PipelineError::RouteNotFound {
                 method: http_method,
                 path: request.path().to_string(),
             })
+        } else {
+            Err(PipelineError::MethodNotAllowed {
+                method: http_method.to_string(),
+                allowed,
+            })
+        }
     }
 
     /// Inject route parameters into the request
@@ -198,9 +237,16 @@ impl RequestPipeline {
     /// Build the complete middleware pipeline for a specific route
     fn build_route_pipeline(
         &self,
-        _route_match: &RouteMatch,
+        route_match: &RouteMatch,
     ) -> Result<MiddlewarePipelineV2, PipelineError> {
-        let pipeline = self.global_middleware.clone();
+        // Route limits run first so that they override global timeouts and body limits;
+        // clones share the in-flight counter
+        let pipeline = match self.route_limits.get(&route_match.route_id) {
+            Some(limits) => MiddlewarePipelineV2::new()
+                .add(limits.clone())
+                .extend(self.global_middleware.clone()),
+            None => self.global_middleware.clone(),
+        };
 
         // Add route-specific middleware groups
         // For now, we'll look for middleware group names in route metadata
@@ -220,16 +266,35 @@ impl RequestPipeline {
                     }
                 }))
             }
-            PipelineError::MethodNotAllowed { method } => ElifResponse::with_status(
-                ElifStatusCode::METHOD_NOT_ALLOWED,
-            )
-            .with_json(&serde_json::json!({
-                "error": {
-                    "code": "method_not_allowed",
-                    "message": format!("HTTP method '{}' is not supported", method),
-                    "hint": "Supported methods: GET, POST, PUT, DELETE, PATCH, HEAD, OPTIONS, TRACE"
+            PipelineError::MethodNotAllowed { method, allowed } if allowed.is_empty() => {
+                ElifResponse::with_status(ElifStatusCode::METHOD_NOT_ALLOWED).with_json(
+                    &serde_json::json!({
+                        "error": {
+                            "code": "method_not_allowed",
+                            "message": format!("HTTP method '{}' is not supported", method),
+                            "hint": "Supported methods: GET, POST, PUT, DELETE, PATCH, HEAD, OPTIONS, TRACE"
+                        }
+                    }),
+                )
+            }
+            PipelineError::MethodNotAllowed { method, allowed } => {
+                let allow = allow_header_value(&allowed);
+
+                // Automatic OPTIONS response for paths without an explicit OPTIONS route
+                if method == HttpMethod::OPTIONS.as_str() {
+                    return ElifResponse::no_content().with_header("allow", &allow);
                 }
-            })),
+
+                ElifResponse::with_status(ElifStatusCode::METHOD_NOT_ALLOWED)
+                    .with_header("allow", &allow)
+                    .with_json(&serde_json::json!({
+                        "error": {
+                            "code": "method_not_allowed",
+                            "message": format!("HTTP method '{}' is not allowed for this resource", method),
+                            "allowed": allow
+                        }
+                    }))
+            }
             PipelineError::Parameter(param_error) => {
                 ElifResponse::bad_request().with_json(&serde_json::json!({
                     "error": {
@@ -283,11 +348,21 @@ impl std::fmt::Debug for RequestPipeline {
             .field("matcher", &self.matcher)
             .field("global_middleware", &self.global_middleware)
             .field("middleware_groups", &self.middleware_groups)
+            .field("route_limits", &self.route_limits)
             .field("handlers", &self.handlers.len())
             .finish()
     }
 }
 
+/// `Allow` header value for a set of methods, always including `OPTIONS`
+pub(crate) fn allow_header_value(allowed: &[HttpMethod]) -> String {
+    let mut methods: Vec<&str> = allowed.iter().map(HttpMethod::as_str).collect();
+    if !methods.contains(&HttpMethod::OPTIONS.as_str()) {
+        methods.push(HttpMethod::OPTIONS.as_str());
+    }
+    methods.join(", ")
+}
+
 /// Statistics about the request pipeline
 #[derive(Debug, Clone)]
 pub struct PipelineStats {
@@ -302,6 +377,7 @@ pub struct RequestPipelineBuilder {
     matcher: Option<RouteMatcher>,
     global_middleware: MiddlewarePipelineV2,
     middleware_groups: HashMap<String, MiddlewarePipelineV2>,
+    route_limits: HashMap<String, RouteLimits>,
     handlers: HashMap<String, Arc<HandlerFn>>,
 }
 
@@ -312,6 +388,7 @@ impl RequestPipelineBuilder {
             matcher: None,
             global_middleware: MiddlewarePipelineV2::new(),
             middleware_groups: HashMap::new(),
+            route_limits: HashMap::new(),
             handlers: HashMap::new(),
         }
     }
@@ -341,6 +418,12 @@ impl RequestPipelineBuilder {
         self
     }
 
+    /// Add limits for a route
+    pub fn route_limits<S: Into<String>>(mut self, route_id: S, limits: RouteLimits) -> Self {
+        self.route_limits.insert(route_id.into(), limits);
+        self
+    }
+
     /// Add a route handler
     pub fn handler<S: Into<String>, F>(mut self, route_id: S, handler: F) -> Self
     where
@@ -360,6 +443,11 @@ impl RequestPipelineBuilder {
             matcher: Arc::new(matcher),
             global_middleware: self.global_middleware,
             middleware_groups: self.middleware_groups,
+            route_limits: self
+                .route_limits
+                .into_iter()
+                .map(|(route_id, limits)| (route_id, RouteLimitsMiddleware::new(limits)))
+                .collect(),
             handlers: Arc::new(self.handlers),
         })
     }
@@ -746,4 +834,150 @@ mod tests {
             assert_eq!(response.status_code(), ElifStatusCode::OK);
         }
     }
+
+    #[tokio::test]
+    async fn test_host_routes() {
+        let matcher = RouteMatcherBuilder::new()
+            .get("site".to_string(), "/dashboard".to_string())
+            .host_route(
+                "tenant".to_string(),
+                HttpMethod::GET,
+                "{tenant}.example.com".to_string(),
+                "/dashboard".to_string(),
+            )
+            .build()
+            .unwrap();
+
+        let pipeline = RequestPipelineBuilder::new()
+            .matcher(matcher)
+            .handler("site", |_req| {
+                Box::pin(async move { ElifResponse::ok().with_text("site") })
+            })
+            .handler("tenant", |req| {
+                Box::pin(async move {
+                    match req.path_param("tenant") {
+                        Some(tenant) if tenant == "acme" => ElifResponse::ok(),
+                        _ => ElifResponse::bad_request(),
+                    }
+                })
+            })
+            .build()
+            .unwrap();
+
+        let request_for = |host: &str| {
+            let mut headers = crate::response::ElifHeaderMap::new();
+            headers.add_header("host", host).unwrap();
+            ElifRequest::new(
+                crate::request::ElifMethod::GET,
+                "/dashboard".parse().unwrap(),
+                headers,
+            )
+        };
+
+        let response = pipeline.process(request_for("acme.example.com")).await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+
+        let response = pipeline.process(request_for("example.org")).await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed_and_automatic_options() {
+        let matcher = RouteMatcherBuilder::new()
+            .get("users_index".to_string(), "/users".to_string())
+            .post("users_store".to_string(), "/users".to_string())
+            .build()
+            .unwrap();
+
+        let pipeline = RequestPipelineBuilder::new()
+            .matcher(matcher)
+            .handler("users_index", |_req| {
+                Box::pin(async move { ElifResponse::ok().with_text("index") })
+            })
+            .handler("users_store", |_req| {
+                Box::pin(async move { ElifResponse::created() })
+            })
+            .build()
+            .unwrap();
+
+        let request = |method| {
+            ElifRequest::new(
+                method,
+                "/users".parse().unwrap(),
+                crate::response::ElifHeaderMap::new(),
+            )
+        };
+        let allow = |response: &ElifResponse| {
+            response
+                .headers()
+                .get_str("allow")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        let response = pipeline
+            .process(request(crate::request::ElifMethod::DELETE))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(
+            allow(&response).as_deref(),
+            Some("GET, POST, HEAD, OPTIONS")
+        );
+
+        let response = pipeline
+            .process(request(crate::request::ElifMethod::OPTIONS))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::NO_CONTENT);
+        assert_eq!(
+            allow(&response).as_deref(),
+            Some("GET, POST, HEAD, OPTIONS")
+        );
+
+        let response = pipeline
+            .process(request(crate::request::ElifMethod::HEAD))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_route_limits() {
+        let matcher = RouteMatcherBuilder::new()
+            .post("upload".to_string(), "/upload".to_string())
+            .post("comment".to_string(), "/comments".to_string())
+            .build()
+            .unwrap();
+
+        let pipeline = RequestPipelineBuilder::new()
+            .matcher(matcher)
+            .route_limits("upload", RouteLimits::new().max_body_size(8))
+            .handler("upload", |_req| Box::pin(async move { ElifResponse::ok() }))
+            .handler("comment", |_req| {
+                Box::pin(async move { ElifResponse::ok() })
+            })
+            .build()
+            .unwrap();
+
+        let request = |path: &str, body: &'static [u8]| {
+            ElifRequest::new(
+                crate::request::ElifMethod::POST,
+                path.parse().unwrap(),
+                crate::response::ElifHeaderMap::new(),
+            )
+            .with_body(body.into())
+        };
+
+        let response = pipeline.process(request("/upload", b"tiny")).await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+
+        let response = pipeline
+            .process(request("/upload", b"much too large"))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::PAYLOAD_TOO_LARGE);
+
+        // Limits only apply to the route they were declared for
+        let response = pipeline
+            .process(request("/comments", b"much too large"))
+            .await;
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+    }
 }
@@ -9,6 +9,7 @@ use axum::{body::Bytes, http::Uri};
 use serde::de::DeserializeOwned;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 /// Request abstraction that wraps Axum's request types
 /// with additional parsing and extraction capabilities
@@ -21,6 +22,8 @@ pub struct ElifRequest {
     pub query_params: HashMap<String, String>,
     pub extensions: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
     body_bytes: Option<Bytes>,
+    /// Unread body of multipart requests, consumed as a stream by the multipart reader
+    body_stream: Mutex<Option<axum::body::Body>>,
 }
 
 impl ElifRequest {
@@ -34,6 +37,7 @@ impl ElifRequest {
             query_params: HashMap::new(),
             extensions: HashMap::new(),
             body_bytes: None,
+            body_stream: Mutex::new(None),
         }
     }
 
@@ -192,11 +196,54 @@ impl ElifRequest {
         }
     }
 
+    /// Check if request has a multipart/form-data content type
+    pub fn is_multipart(&self) -> bool {
+        matches!(
+            self.content_type(),
+            Ok(Some(content_type)) if content_type.to_ascii_lowercase().starts_with("multipart/form-data")
+        )
+    }
+
     /// Get request body as bytes
+    ///
+    /// Multipart bodies are not buffered and are read with [`ElifRequest::multipart`] instead.
     pub fn body_bytes(&self) -> Option<&Bytes> {
         self.body_bytes.as_ref()
     }
 
+    /// Set the body from an Axum body, buffering it unless it is a multipart upload
+    ///
+    /// Fails with `413` when the body exceeds the size limit of the request's route.
+    pub(crate) async fn with_axum_body(mut self, body: axum::body::Body) -> HttpResult<Self> {
+        if self.is_multipart() {
+            self.body_stream = Mutex::new(Some(body));
+            return Ok(self);
+        }
+
+        match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(bytes) => self.body_bytes = Some(bytes),
+            Err(e) if crate::middleware::utils::route_limits::is_length_limit_error(&e) => {
+                let limit = self.body_limit().unwrap_or_default();
+                return Err(HttpError::payload_too_large(limit.saturating_add(1), limit));
+            }
+            Err(_) => self.body_bytes = None,
+        }
+        Ok(self)
+    }
+
+    /// Body size limit of the route handling this request, if it declares one
+    pub(crate) fn body_limit(&self) -> Option<usize> {
+        self.route_limits().and_then(|limits| limits.max_body_size)
+    }
+
+    /// Take the unread body of a multipart request, which can only be read once
+    pub(crate) fn take_body_stream(&self) -> Option<axum::body::Body> {
+        self.body_stream
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
     /// Parse JSON body to specified type
     pub fn json<T: DeserializeOwned>(&self) -> HttpResult<T> {
         let bytes = self
@@ -284,35 +331,55 @@ impl ElifRequest {
         }
     }
 
-    /// Get request IP address from headers or connection
-    pub fn client_ip(&self) -> Option<String> {
-        // Try common forwarded headers first
-        if let Ok(Some(forwarded)) = self.header_string("x-forwarded-for") {
-            // Take first IP if multiple
-            if let Some(ip) = forwarded.split(',').next() {
-                return Some(ip.trim().to_string());
-            }
-        }
+    /// Get the `Last-Event-ID` header sent by reconnecting Server-Sent Events clients
+    pub fn last_event_id(&self) -> Option<String> {
+        self.header_string("last-event-id").unwrap_or(None)
+    }
 
-        if let Ok(Some(real_ip)) = self.header_string("x-real-ip") {
-            return Some(real_ip);
+    /// Get the client IP address
+    ///
+    /// Uses the address resolved by
+    /// [`TrustedProxyMiddleware`](crate::middleware::utils::TrustedProxyMiddleware) when the
+    /// request came through a trusted proxy, and the connected peer's address otherwise.
+    /// Forwarding headers are never read directly since any client can set them.
+    pub fn client_ip(&self) -> Option<String> {
+        if let Some(forwarded) = self.forwarded() {
+            return Some(forwarded.client_ip.to_string());
         }
 
-        // Could extend with connection info if available
-        None
+        self.connection()
+            .and_then(|connection| connection.remote_addr)
+            .map(|addr| addr.ip().to_string())
     }
 
-    /// Check if request is HTTPS
+    /// Check if request is HTTPS, as seen by the client
     pub fn is_secure(&self) -> bool {
-        self.uri
-            .scheme()
-            .map(|s| s == &axum::http::uri::Scheme::HTTPS)
-            .unwrap_or(false)
+        if let Some(scheme) = self.forwarded().and_then(|f| f.scheme.as_deref()) {
+            return scheme.eq_ignore_ascii_case("https");
+        }
+
+        self.connection()
+            .is_some_and(|connection| connection.secure)
+            || self
+                .uri
+                .scheme()
+                .map(|s| s == &axum::http::uri::Scheme::HTTPS)
+                .unwrap_or(false)
     }
 
-    /// Get request host
+    /// Get request host without port, as requested by the client
     pub fn host(&self) -> Option<&str> {
-        self.uri.host()
+        let host = self
+            .forwarded()
+            .and_then(|forwarded| forwarded.host.as_deref())
+            .or_else(|| self.uri.host())
+            .or_else(|| {
+                self.headers
+                    .get_str("host")
+                    .and_then(|value| value.to_str().ok())
+            })?;
+
+        Some(strip_port(host))
     }
 
     /// Get request path
@@ -329,9 +396,20 @@ impl ElifRequest {
     pub(crate) fn into_axum_request(self) -> axum::extract::Request {
         use axum::body::Body;
 
-        let body = match self.body_bytes {
-            Some(bytes) => Body::from(bytes),
-            None => Body::empty(),
+        let connection = self.connection().cloned();
+        let forwarded = self.forwarded().cloned();
+        let host_params = self.get_extension::<crate::routing::HostParams>().cloned();
+        let route_limits = self.route_limits().cloned();
+        let error_format = self.get_extension::<crate::errors::ErrorFormat>().copied();
+        #[cfg(feature = "orm")]
+        let model_binding = self.model_binding().cloned();
+        #[cfg(feature = "auth")]
+        let user = self.get_extension::<crate::auth::UserContext>().cloned();
+        let body_stream = self.take_body_stream();
+        let body = match (body_stream, self.body_bytes) {
+            (Some(stream), _) => stream,
+            (None, Some(bytes)) => Body::from(bytes),
+            (None, None) => Body::empty(),
         };
 
         let mut builder = axum::extract::Request::builder()
@@ -343,24 +421,86 @@ impl ElifRequest {
             builder = builder.header(key.to_axum(), value.to_axum());
         }
 
+        if let Some(connection) = connection {
+            builder = builder.extension(connection);
+        }
+        if let Some(forwarded) = forwarded {
+            builder = builder.extension(forwarded);
+        }
+        if let Some(host_params) = host_params {
+            builder = builder.extension(host_params);
+        }
+        if let Some(route_limits) = route_limits {
+            builder = builder.extension(route_limits);
+        }
+        if let Some(error_format) = error_format {
+            builder = builder.extension(error_format);
+        }
+        #[cfg(feature = "orm")]
+        if let Some(model_binding) = model_binding {
+            builder = builder.extension(model_binding);
+        }
+        #[cfg(feature = "auth")]
+        if let Some(user) = user {
+            builder = builder.extension(user);
+        }
+
         builder
             .body(body)
             .expect("Failed to construct Axum request")
     }
 
     /// Convert Axum Request to ElifRequest for backward compatibility
-    pub(crate) async fn from_axum_request(request: axum::extract::Request) -> Self {
+    ///
+    /// Fails when the body exceeds the size limit of the request's route.
+    pub(crate) async fn from_axum_request(request: axum::extract::Request) -> HttpResult<Self> {
         let (parts, body) = request.into_parts();
 
-        // Extract body bytes
-        let body_bytes = (axum::body::to_bytes(body, usize::MAX).await).ok();
-
-        Self::extract_elif_request(
+        let request = Self::extract_elif_request(
+            ElifMethod::from_axum(parts.method),
+            parts.uri,
+            ElifHeaderMap::from_axum(parts.headers),
+            None,
+        )
+        .with_route_limits_from(&parts.extensions)
+        .with_axum_body(body)
+        .await?
+        .with_connection_from(&parts.extensions)
+        .with_forwarded_from(&parts.extensions)
+        .with_host_params_from(&parts.extensions)
+        .with_error_format_from(&parts.extensions);
+
+        #[cfg(feature = "orm")]
+        let request = request.with_model_binding_from(&parts.extensions);
+        #[cfg(feature = "auth")]
+        let request = request.with_user_from(&parts.extensions);
+
+        Ok(request)
+    }
+
+    /// Convert the parts of an Axum request whose body is not read, e.g. a WebSocket upgrade
+    pub(crate) fn from_axum_parts(parts: axum::http::request::Parts) -> Self {
+        let query_params = parts
+            .uri
+            .query()
+            .and_then(|query| serde_urlencoded::from_str(query).ok())
+            .unwrap_or_default();
+
+        let request = Self::new(
             ElifMethod::from_axum(parts.method),
             parts.uri,
             ElifHeaderMap::from_axum(parts.headers),
-            body_bytes,
         )
+        .with_query_params(query_params)
+        .with_connection_from(&parts.extensions)
+        .with_forwarded_from(&parts.extensions)
+        .with_host_params_from(&parts.extensions)
+        .with_error_format_from(&parts.extensions);
+
+        #[cfg(feature = "auth")]
+        let request = request.with_user_from(&parts.extensions);
+
+        request
     }
 
     /// Get a reference to the extensions map for reading middleware-added data
@@ -574,6 +714,20 @@ impl ElifRequest {
     }
 }
 
+/// Strip a trailing port from a host, keeping IPv6 literals bracketed
+pub(crate) fn strip_port(host: &str) -> &str {
+    if host.starts_with('[') {
+        return host.find(']').map_or(host, |end| &host[..=end]);
+    }
+
+    match host.rsplit_once(':') {
+        Some((name, port)) if !name.contains(':') && port.bytes().all(|b| b.is_ascii_digit()) => {
+            name
+        }
+        _ => host,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
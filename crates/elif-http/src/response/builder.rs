@@ -33,7 +33,7 @@
 //! }
 //! ```
 
-use crate::errors::HttpResult;
+use crate::errors::{ErrorFormat, HttpResult, ProblemDetails, PROBLEM_JSON_CONTENT_TYPE};
 use crate::response::{ElifResponse, ElifStatusCode, ResponseBody};
 use axum::body::Bytes;
 use serde::Serialize;
@@ -258,8 +258,19 @@ impl ResponseBuilder {
         self
     }
 
-    /// Create validation error response
+    /// Create validation error response, as problem details in the problem error format
     pub fn validation_error<T: Serialize>(mut self, errors: T) -> Self {
+        if ErrorFormat::current() == ErrorFormat::Problem {
+            let problem = ProblemDetails::validation(&errors);
+            self.status = Some(problem.status_code());
+            self.body = serde_json::to_value(problem).ok().map(ResponseBody::Json);
+            self.headers.push((
+                "content-type".to_string(),
+                PROBLEM_JSON_CONTENT_TYPE.to_string(),
+            ));
+            return self;
+        }
+
         let error_data = serde_json::json!({
             "error": {
                 "type": "validation",
@@ -379,6 +390,9 @@ impl ResponseBuilder {
                 ResponseBody::Json(value) => {
                     response = response.json_value(value);
                 }
+                ResponseBody::Stream(stream) => {
+                    response = response.stream_body(stream);
+                }
             }
         }
 
@@ -21,7 +21,8 @@
 //! }
 //! ```
 
-use crate::response::{ElifResponse, ElifStatusCode};
+use crate::errors::{ErrorFormat, ProblemDetails};
+use crate::response::{ElifResponse, ElifStatusCode, IntoElifResponse};
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -148,7 +149,13 @@ pub fn server_error<S: AsRef<str>>(message: S) -> ElifResponse {
 /// Create a validation error response (422) with field errors
 ///
 /// Simple equivalent: `return response()->json(['errors' => $errors], 422)`
+///
+/// In the problem error format, the field errors are the `errors` member of a problem
+/// details document.
 pub fn validation_error<T: Serialize>(errors: &T) -> ElifResponse {
+    if ErrorFormat::current() == ErrorFormat::Problem {
+        return ProblemDetails::validation(errors).into_response();
+    }
     let response_body = HashMap::from([("errors", errors)]);
     json_status(&response_body, ElifStatusCode::UNPROCESSABLE_ENTITY)
 }
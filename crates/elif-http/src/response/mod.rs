@@ -3,6 +3,8 @@ pub mod headers;
 pub mod helpers;
 pub mod json;
 pub mod response;
+pub mod sse;
+pub mod static_files;
 pub mod status;
 
 pub use builder::*;
@@ -10,4 +12,6 @@ pub use headers::*;
 pub use helpers::*;
 pub use json::*;
 pub use response::*;
+pub use sse::*;
+pub use static_files::StaticFiles;
 pub use status::*;
@@ -3,12 +3,15 @@
 //! Provides fluent response building with status codes, headers, and JSON serialization.
 
 use super::{ElifHeaderMap, ElifHeaderName, ElifHeaderValue, ElifStatusCode};
-use crate::errors::{HttpError, HttpResult};
+use crate::errors::{ErrorFormat, HttpError, HttpResult, ProblemDetails};
 use axum::{
-    body::{Body, Bytes},
+    body::{Body, Bytes, HttpBody},
     response::{IntoResponse, Response},
+    BoxError,
 };
+use futures_util::{Stream, TryStreamExt};
 use serde::Serialize;
+use std::pin::Pin;
 
 /// Response builder for creating HTTP responses with fluent API
 #[derive(Debug)]
@@ -19,12 +22,28 @@ pub struct ElifResponse {
 }
 
 /// Response body types
-#[derive(Debug)]
 pub enum ResponseBody {
     Empty,
     Text(String),
     Bytes(Bytes),
     Json(serde_json::Value),
+    /// Body streamed to the client chunk by chunk as it is produced
+    Stream(BodyStream),
+}
+
+/// Stream of body chunks for streaming responses
+pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send + 'static>>;
+
+impl std::fmt::Debug for ResponseBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResponseBody::Empty => write!(f, "Empty"),
+            ResponseBody::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            ResponseBody::Bytes(bytes) => f.debug_tuple("Bytes").field(bytes).finish(),
+            ResponseBody::Json(value) => f.debug_tuple("Json").field(value).finish(),
+            ResponseBody::Stream(_) => write!(f, "Stream(..)"),
+        }
+    }
 }
 
 impl ElifResponse {
@@ -220,6 +239,21 @@ impl ElifResponse {
         self.body = ResponseBody::Json(value);
     }
 
+    /// Set a streaming body, sent with chunked transfer encoding
+    pub fn stream_body<S, E>(mut self, stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        self.body = ResponseBody::Stream(Box::pin(stream.map_err(Into::into)));
+        self
+    }
+
+    /// Check if the response body is streamed
+    pub fn is_streaming(&self) -> bool {
+        matches!(self.body, ResponseBody::Stream(_))
+    }
+
     /// Build the response
     pub fn build(mut self) -> HttpResult<Response<Body>> {
         // Set default content type based on body type
@@ -245,6 +279,7 @@ impl ElifResponse {
                 })?;
                 Body::from(json_string)
             }
+            ResponseBody::Stream(stream) => Body::from_stream(stream),
         };
 
         let mut response = Response::builder().status(self.status.to_axum());
@@ -268,6 +303,13 @@ impl ElifResponse {
     pub(crate) async fn from_axum_response(response: Response<Body>) -> Self {
         let (parts, body) = response.into_parts();
 
+        // Keep bodies of unknown length streaming instead of buffering them
+        if body.size_hint().exact().is_none() {
+            let mut elif_response = Self::with_status(ElifStatusCode::from_axum(parts.status));
+            elif_response.headers = ElifHeaderMap::from_axum(parts.headers);
+            return elif_response.stream_body(body.into_data_stream());
+        }
+
         // Extract body bytes
         let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
             Ok(bytes) => bytes,
@@ -281,7 +323,9 @@ impl ElifResponse {
         // Try to determine body type based on content-type header
         if let Some(content_type) = headers.get("content-type") {
             if let Ok(content_type_str) = content_type.to_str() {
-                if content_type_str.contains("application/json") {
+                if content_type_str.contains("application/json")
+                    || content_type_str.contains("+json")
+                {
                     // Try to parse as JSON
                     if let Ok(json_value) = serde_json::from_slice::<serde_json::Value>(&body_bytes)
                     {
@@ -381,8 +425,12 @@ impl ElifResponse {
         Self::with_status(status).json_value(error_data).build()
     }
 
-    /// Create validation error response
+    /// Create validation error response, as problem details in the problem error format
     pub fn validation_error<T: Serialize>(errors: &T) -> HttpResult<Response<Body>> {
+        if ErrorFormat::current() == ErrorFormat::Problem {
+            return ProblemDetails::validation(errors).into_response().build();
+        }
+
         let error_data = serde_json::json!({
             "error": {
                 "code": 422,
@@ -487,41 +535,12 @@ impl ElifResponse {
         let content = std::fs::read(path)
             .map_err(|e| HttpError::internal(format!("Failed to read file: {}", e)))?;
 
-        let mime_type = Self::guess_mime_type(path);
+        let mime_type = super::static_files::mime_type_for(path);
 
         Ok(Self::ok()
             .header("content-type", mime_type)?
             .bytes(Bytes::from(content)))
     }
-
-    /// Guess MIME type from file extension
-    fn guess_mime_type(path: &std::path::Path) -> &'static str {
-        match path.extension().and_then(|ext| ext.to_str()) {
-            Some("html") | Some("htm") => "text/html; charset=utf-8",
-            Some("css") => "text/css",
-            Some("js") => "application/javascript",
-            Some("json") => "application/json",
-            Some("xml") => "application/xml",
-            Some("pdf") => "application/pdf",
-            Some("txt") => "text/plain; charset=utf-8",
-            Some("png") => "image/png",
-            Some("jpg") | Some("jpeg") => "image/jpeg",
-            Some("gif") => "image/gif",
-            Some("svg") => "image/svg+xml",
-            Some("ico") => "image/x-icon",
-            Some("woff") => "font/woff",
-            Some("woff2") => "font/woff2",
-            Some("ttf") => "font/ttf",
-            Some("mp4") => "video/mp4",
-            Some("webm") => "video/webm",
-            Some("mp3") => "audio/mpeg",
-            Some("wav") => "audio/wav",
-            Some("zip") => "application/zip",
-            Some("tar") => "application/x-tar",
-            Some("gz") => "application/gzip",
-            _ => "application/octet-stream",
-        }
-    }
 }
 
 /// Enhanced response helper methods for common patterns
@@ -569,11 +588,19 @@ impl ElifResponse {
     }
 
     /// Create streaming response with chunked transfer encoding
+    #[deprecated(
+        since = "0.8.9",
+        note = "the body is still buffered; use `ElifResponse::ok().stream_body(stream)` instead"
+    )]
     pub fn stream() -> HttpResult<Self> {
         Self::ok().header("transfer-encoding", "chunked")
     }
 
     /// Create Server-Sent Events (SSE) response
+    #[deprecated(
+        since = "0.8.9",
+        note = "the body is still buffered; use `ElifResponse::sse_stream(events)` instead"
+    )]
     pub fn sse() -> HttpResult<Self> {
         Self::ok()
             .header("content-type", "text/event-stream")?
@@ -699,6 +726,7 @@ impl ElifResponse {
                     "invalid_json".hash(&mut hasher);
                 }
             }
+            ResponseBody::Stream(_) => "stream".hash(&mut hasher),
         }
 
         format!("{:x}", hasher.finish())
@@ -706,6 +734,11 @@ impl ElifResponse {
 
     /// Create conditional response based on If-None-Match header
     pub fn conditional(self, request_etag: Option<&str>) -> Self {
+        // Streamed content is unknown up front, so it can't be validated
+        if self.is_streaming() {
+            return self;
+        }
+
         if let Some(request_etag) = request_etag {
             let response_etag = self.generate_etag();
             let response_etag_quoted = format!("\"{}\"", response_etag);
@@ -811,6 +844,7 @@ impl ElifResponse {
                 // Estimate JSON serialization size
                 serde_json::to_string(value).map(|s| s.len()).unwrap_or(0)
             }
+            ResponseBody::Stream(_) => 0,
         }
     }
 }
@@ -1121,4 +1155,23 @@ mod tests {
             ElifStatusCode::NOT_MODIFIED
         );
     }
+
+    #[tokio::test]
+    async fn test_stream_body_survives_axum_round_trip() {
+        let chunks = futures_util::stream::iter(vec![
+            Ok::<_, std::io::Error>(Bytes::from("chunk 1, ")),
+            Ok(Bytes::from("chunk 2")),
+        ]);
+        let response = ElifResponse::ok().stream_body(chunks);
+        assert!(response.is_streaming());
+        assert_eq!(response.body_size_estimate(), 0);
+
+        let response = ElifResponse::from_axum_response(response.into_axum_response()).await;
+        assert!(response.is_streaming());
+
+        let body = axum::body::to_bytes(response.build().unwrap().into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "chunk 1, chunk 2");
+    }
 }
@@ -0,0 +1,379 @@
+//! Server-Sent Events (SSE) responses
+//!
+//! Builds `text/event-stream` responses from a stream of [`SseEvent`]s. The body is
+//! streamed to the client as events are produced, with periodic keep-alive comments so
+//! proxies do not close idle connections. Reconnecting clients send the last event ID
+//! they received, available through [`ElifRequest::last_event_id`](crate::request::ElifRequest::last_event_id).
+
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use axum::body::Bytes;
+use futures_util::Stream;
+use serde::Serialize;
+
+use super::{ElifResponse, IntoElifResponse};
+use crate::errors::{HttpError, HttpResult};
+
+/// A single Server-Sent Event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    id: Option<String>,
+    event: Option<String>,
+    data: Option<String>,
+    retry: Option<Duration>,
+    comment: Option<String>,
+}
+
+impl SseEvent {
+    /// Create an unnamed event carrying text data
+    pub fn new<S: Into<String>>(data: S) -> Self {
+        Self {
+            data: Some(data.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create an event carrying JSON-serialized data
+    pub fn json<T: Serialize>(data: &T) -> HttpResult<Self> {
+        let data = serde_json::to_string(data)
+            .map_err(|e| HttpError::internal(format!("JSON serialization failed: {}", e)))?;
+        Ok(Self::new(data))
+    }
+
+    /// Create a comment line, ignored by clients
+    pub fn comment<S: Into<String>>(comment: S) -> Self {
+        Self {
+            comment: Some(comment.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the event ID, sent back by reconnecting clients as `Last-Event-ID`
+    ///
+    /// Fails when the ID contains a line break, which would end the field early.
+    pub fn id<S: Into<String>>(mut self, id: S) -> HttpResult<Self> {
+        self.id = Some(single_line("id", id.into())?);
+        Ok(self)
+    }
+
+    /// Set the event name (dispatched to `addEventListener(name, ...)` on the client)
+    ///
+    /// Fails when the name contains a line break, which would end the field early.
+    pub fn event<S: Into<String>>(mut self, event: S) -> HttpResult<Self> {
+        self.event = Some(single_line("event", event.into())?);
+        Ok(self)
+    }
+
+    /// Set the data of the event
+    pub fn data<S: Into<String>>(mut self, data: S) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Set the client reconnection delay
+    pub fn retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Encode the event in the `text/event-stream` wire format
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buffer = String::new();
+
+        if let Some(comment) = &self.comment {
+            for line in split_lines(comment) {
+                let _ = writeln!(buffer, ":{}", line);
+            }
+        }
+        if let Some(event) = &self.event {
+            let _ = writeln!(buffer, "event: {}", event);
+        }
+        if let Some(id) = &self.id {
+            let _ = writeln!(buffer, "id: {}", id);
+        }
+        if let Some(retry) = self.retry {
+            let _ = writeln!(buffer, "retry: {}", retry.as_millis());
+        }
+        if let Some(data) = &self.data {
+            for line in split_lines(data) {
+                let _ = writeln!(buffer, "data: {}", line);
+            }
+        }
+        buffer.push('\n');
+
+        Bytes::from(buffer)
+    }
+}
+
+/// Field values other than data and comments may not span lines
+fn single_line(field: &str, value: String) -> HttpResult<String> {
+    if value.contains(['\r', '\n']) {
+        return Err(HttpError::internal(format!(
+            "SSE {} may not contain line breaks",
+            field
+        )));
+    }
+    Ok(value)
+}
+
+/// Split a value on every line ending of the event stream format: CRLF, LF and a lone CR
+fn split_lines(value: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(value);
+    std::iter::from_fn(move || {
+        let current = rest?;
+        let Some(end) = current.find(['\r', '\n']) else {
+            rest = None;
+            return Some(current);
+        };
+        let next = if current[end..].starts_with("\r\n") {
+            end + 2
+        } else {
+            end + 1
+        };
+        rest = Some(&current[next..]);
+        Some(&current[..end])
+    })
+}
+
+/// Keep-alive comments sent while no event is produced
+#[derive(Debug, Clone)]
+pub struct SseKeepAlive {
+    interval: Duration,
+    text: String,
+}
+
+impl SseKeepAlive {
+    /// Send keep-alive comments every 15 seconds
+    pub fn new() -> Self {
+        Self {
+            interval: Duration::from_secs(15),
+            text: String::new(),
+        }
+    }
+
+    /// Set the keep-alive interval
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the text of the keep-alive comment
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.text = text.into();
+        self
+    }
+}
+
+impl Default for SseKeepAlive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Server-Sent Events response built from a stream of events
+pub struct Sse<S> {
+    events: S,
+    keep_alive: Option<SseKeepAlive>,
+}
+
+impl<S> Sse<S>
+where
+    S: Stream<Item = SseEvent> + Send + 'static,
+{
+    /// Create an SSE response with default keep-alive comments
+    pub fn new(events: S) -> Self {
+        Self {
+            events,
+            keep_alive: Some(SseKeepAlive::default()),
+        }
+    }
+
+    /// Configure keep-alive comments
+    pub fn keep_alive(mut self, keep_alive: SseKeepAlive) -> Self {
+        self.keep_alive = Some(keep_alive);
+        self
+    }
+
+    /// Disable keep-alive comments
+    pub fn without_keep_alive(mut self) -> Self {
+        self.keep_alive = None;
+        self
+    }
+}
+
+impl<S> std::fmt::Debug for Sse<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sse")
+            .field("keep_alive", &self.keep_alive)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> IntoElifResponse for Sse<S>
+where
+    S: Stream<Item = SseEvent> + Send + 'static,
+{
+    fn into_response(self) -> ElifResponse {
+        let body = SseBody {
+            events: Box::pin(self.events),
+            keep_alive: self.keep_alive.map(|keep_alive| {
+                let sleep = Box::pin(tokio::time::sleep(keep_alive.interval));
+                (
+                    SseEvent::comment(keep_alive.text).to_bytes(),
+                    keep_alive.interval,
+                    sleep,
+                )
+            }),
+        };
+
+        let mut response = ElifResponse::ok().stream_body(body);
+        let headers = [
+            ("content-type", "text/event-stream"),
+            ("cache-control", "no-cache"),
+            ("x-accel-buffering", "no"),
+        ];
+        for (name, value) in headers {
+            // Static header names and values are always valid
+            let _ = response.add_header(name, value);
+        }
+        response
+    }
+}
+
+type KeepAliveState = (Bytes, Duration, Pin<Box<tokio::time::Sleep>>);
+
+/// Body stream encoding events and interleaving keep-alive comments
+struct SseBody {
+    events: Pin<Box<dyn Stream<Item = SseEvent> + Send>>,
+    keep_alive: Option<KeepAliveState>,
+}
+
+impl Stream for SseBody {
+    type Item = Result<Bytes, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        match this.events.as_mut().poll_next(cx) {
+            Poll::Ready(Some(event)) => {
+                if let Some((_, interval, sleep)) = &mut this.keep_alive {
+                    sleep
+                        .as_mut()
+                        .reset(tokio::time::Instant::now() + *interval);
+                }
+                return Poll::Ready(Some(Ok(event.to_bytes())));
+            }
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => {}
+        }
+
+        if let Some((comment, interval, sleep)) = &mut this.keep_alive {
+            if sleep.as_mut().poll(cx).is_ready() {
+                sleep
+                    .as_mut()
+                    .reset(tokio::time::Instant::now() + *interval);
+                return Poll::Ready(Some(Ok(comment.clone())));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl ElifResponse {
+    /// Create a Server-Sent Events response streaming the given events
+    pub fn sse_stream<S>(events: S) -> Self
+    where
+        S: Stream<Item = SseEvent> + Send + 'static,
+    {
+        Sse::new(events).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[test]
+    fn test_event_encoding() {
+        let event = SseEvent::new("line one\nline two")
+            .id("42")
+            .unwrap()
+            .event("progress")
+            .unwrap()
+            .retry(Duration::from_secs(3));
+
+        assert_eq!(
+            event.to_bytes(),
+            "event: progress\nid: 42\nretry: 3000\ndata: line one\ndata: line two\n\n"
+        );
+        assert_eq!(SseEvent::comment("ping").to_bytes(), ":ping\n\n");
+        assert_eq!(
+            SseEvent::json(&serde_json::json!({"done": 3}))
+                .unwrap()
+                .to_bytes(),
+            "data: {\"done\":3}\n\n"
+        );
+    }
+
+    #[test]
+    fn test_every_line_ending_splits_fields() {
+        assert_eq!(
+            SseEvent::new("one\rtwo\r\nthree\nfour").to_bytes(),
+            "data: one\ndata: two\ndata: three\ndata: four\n\n"
+        );
+        assert_eq!(SseEvent::comment("a\rb").to_bytes(), ":a\n:b\n\n");
+        assert_eq!(SseEvent::new("").to_bytes(), "data: \n\n");
+
+        for value in ["a\nb", "a\rb", "a\r\nb"] {
+            assert!(SseEvent::new("x").id(value).is_err());
+            assert!(SseEvent::new("x").event(value).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_response_streams_events() {
+        let events = futures_util::stream::iter(vec![
+            SseEvent::new("first").id("1").unwrap(),
+            SseEvent::new("second").id("2").unwrap(),
+        ]);
+        let response = ElifResponse::sse_stream(events);
+
+        assert!(response.is_streaming());
+        let response = response.build().unwrap();
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "text/event-stream"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "id: 1\ndata: first\n\nid: 2\ndata: second\n\n");
+    }
+
+    #[tokio::test]
+    async fn test_keep_alive_comments() {
+        let events = futures_util::stream::pending::<SseEvent>();
+        let body = Sse::new(events)
+            .keep_alive(
+                SseKeepAlive::new()
+                    .interval(Duration::from_millis(20))
+                    .text("keep-alive"),
+            )
+            .into_response()
+            .build()
+            .unwrap()
+            .into_body();
+
+        let mut stream = body.into_data_stream();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk, ":keep-alive\n\n");
+    }
+}
@@ -0,0 +1,665 @@
+//! Static file serving
+//!
+//! [`StaticFiles`] serves files from a directory with async streaming, byte ranges
+//! (`Range`/`If-Range`), conditional requests (`ETag`/`Last-Modified`, following the
+//! semantics of the ETag middleware) and precompressed `.br`/`.gz` siblings. Request
+//! paths are resolved strictly inside the root directory. Mount it on a router with
+//! [`ElifRouter::static_dir`](crate::routing::ElifRouter::static_dir).
+
+use std::io::SeekFrom;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+use super::{ElifResponse, ElifStatusCode};
+use crate::errors::{HttpError, HttpResult};
+use crate::middleware::utils::etag::ETagType;
+use crate::request::{ElifMethod, ElifRequest};
+
+/// Precompressed encodings in order of preference, with their file suffix
+const PRECOMPRESSED: [(&str, &str); 2] = [("br", "br"), ("gzip", "gz")];
+
+/// Size of the chunks files are streamed in
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Service serving files from a directory
+#[derive(Debug, Clone)]
+pub struct StaticFiles {
+    root: PathBuf,
+    index_file: Option<String>,
+    precompressed: bool,
+    cache_control: Option<String>,
+    allow_hidden: bool,
+}
+
+impl StaticFiles {
+    /// Serve files from a root directory
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            index_file: Some("index.html".to_string()),
+            precompressed: true,
+            cache_control: None,
+            allow_hidden: false,
+        }
+    }
+
+    /// Set the file served for directory requests (`index.html` by default)
+    pub fn index_file<S: Into<String>>(mut self, index_file: S) -> Self {
+        self.index_file = Some(index_file.into());
+        self
+    }
+
+    /// Don't serve anything for directory requests
+    pub fn without_index_file(mut self) -> Self {
+        self.index_file = None;
+        self
+    }
+
+    /// Enable or disable serving `.br`/`.gz` siblings to clients accepting them
+    pub fn precompressed(mut self, enabled: bool) -> Self {
+        self.precompressed = enabled;
+        self
+    }
+
+    /// Set the Cache-Control header sent with files
+    pub fn cache_control<S: Into<String>>(mut self, value: S) -> Self {
+        self.cache_control = Some(value.into());
+        self
+    }
+
+    /// Allow serving files and directories whose name starts with a dot
+    pub fn allow_hidden(mut self, allow: bool) -> Self {
+        self.allow_hidden = allow;
+        self
+    }
+
+    /// Get the root directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Serve the file at a percent-encoded path relative to the root
+    pub async fn serve(
+        &self,
+        request: &ElifRequest,
+        relative_path: &str,
+    ) -> HttpResult<ElifResponse> {
+        if request.method != ElifMethod::GET && request.method != ElifMethod::HEAD {
+            return ElifResponse::with_status(ElifStatusCode::METHOD_NOT_ALLOWED)
+                .header("allow", "GET, HEAD");
+        }
+
+        let path = self
+            .resolve(relative_path)
+            .await
+            .ok_or_else(|| HttpError::not_found("File"))?;
+        let file = self.select_encoding(request, path).await;
+
+        let metadata = tokio::fs::metadata(&file.path)
+            .await
+            .map_err(|_| HttpError::not_found("File"))?;
+        let len = metadata.len();
+        let modified = metadata.modified().ok();
+        let validators = Validators::new(len, modified, file.encoding);
+
+        let mut response = match self.check_preconditions(request, &validators) {
+            Some(response) => response,
+            None => {
+                let range = match request.header_string("range")? {
+                    Some(range) if self.if_range_matches(request, &validators)? => {
+                        parse_range(&range, len)
+                    }
+                    _ => RangeRequest::Full,
+                };
+
+                match range {
+                    RangeRequest::Full => {
+                        let body = open_range(&file.path, 0, len).await?;
+                        ElifResponse::ok()
+                            .header("content-length", len.to_string())?
+                            .stream_body(body)
+                    }
+                    RangeRequest::Partial(start, end) => {
+                        let body = open_range(&file.path, start, end - start + 1).await?;
+                        ElifResponse::with_status(ElifStatusCode::PARTIAL_CONTENT)
+                            .header("content-length", (end - start + 1).to_string())?
+                            .header("content-range", format!("bytes {}-{}/{}", start, end, len))?
+                            .stream_body(body)
+                    }
+                    RangeRequest::Unsatisfiable => {
+                        ElifResponse::with_status(ElifStatusCode::RANGE_NOT_SATISFIABLE)
+                            .header("content-range", format!("bytes */{}", len))?
+                    }
+                }
+            }
+        };
+
+        response.add_header("content-type", mime_type_for(&file.original))?;
+        response.add_header("accept-ranges", "bytes")?;
+        self.add_validator_headers(&mut response, &validators)?;
+        if let Some(encoding) = file.encoding {
+            response.add_header("content-encoding", encoding)?;
+        }
+        Ok(response)
+    }
+
+    /// Resolve a request path to a file inside the root, rejecting traversal attempts
+    async fn resolve(&self, relative_path: &str) -> Option<PathBuf> {
+        let decoded = percent_encoding::percent_decode_str(relative_path)
+            .decode_utf8()
+            .ok()?;
+
+        let mut path = self.root.clone();
+        for segment in decoded.split('/') {
+            if segment.is_empty() || segment == "." {
+                continue;
+            }
+            if segment == ".."
+                || segment.contains(['\\', '\0'])
+                || (segment.starts_with('.') && !self.allow_hidden)
+            {
+                return None;
+            }
+            // Reject anything that isn't a plain file name on this platform (e.g. `C:`)
+            let mut components = Path::new(segment).components();
+            if !matches!(
+                (components.next(), components.next()),
+                (Some(Component::Normal(_)), None)
+            ) {
+                return None;
+            }
+            path.push(segment);
+        }
+
+        let mut metadata = tokio::fs::metadata(&path).await.ok()?;
+        if metadata.is_dir() {
+            path.push(self.index_file.as_ref()?);
+            metadata = tokio::fs::metadata(&path).await.ok()?;
+        }
+        if !metadata.is_file() {
+            return None;
+        }
+
+        self.is_inside_root(&path).await.then_some(path)
+    }
+
+    /// Check that a file is inside the root once symlinks are followed
+    async fn is_inside_root(&self, path: &Path) -> bool {
+        let (Ok(root), Ok(canonical)) = (
+            tokio::fs::canonicalize(&self.root).await,
+            tokio::fs::canonicalize(path).await,
+        ) else {
+            return false;
+        };
+        canonical.starts_with(&root)
+    }
+
+    /// Pick a precompressed sibling of the file if the client accepts it
+    async fn select_encoding(&self, request: &ElifRequest, path: PathBuf) -> SelectedFile {
+        let identity = SelectedFile {
+            path: path.clone(),
+            original: path.clone(),
+            encoding: None,
+        };
+
+        // Ranges always apply to the identity representation
+        if !self.precompressed || request.header("range").is_some() {
+            return identity;
+        }
+        let Ok(Some(accept_encoding)) = request.header_string("accept-encoding") else {
+            return identity;
+        };
+
+        for (encoding, suffix) in PRECOMPRESSED {
+            if !accepts_encoding(&accept_encoding, encoding) {
+                continue;
+            }
+            let mut sibling = path.clone().into_os_string();
+            sibling.push(".");
+            sibling.push(suffix);
+            let sibling = PathBuf::from(sibling);
+            if tokio::fs::metadata(&sibling)
+                .await
+                .is_ok_and(|metadata| metadata.is_file())
+                && self.is_inside_root(&sibling).await
+            {
+                return SelectedFile {
+                    path: sibling,
+                    original: path,
+                    encoding: Some(encoding),
+                };
+            }
+        }
+
+        identity
+    }
+
+    /// Evaluate conditional request headers, returning the 304/412 response if one applies
+    fn check_preconditions(
+        &self,
+        request: &ElifRequest,
+        validators: &Validators,
+    ) -> Option<ElifResponse> {
+        if let Ok(Some(if_match)) = request.header_string("if-match") {
+            let matches = if_match.trim() == "*"
+                || ETagType::parse_list(&if_match)
+                    .iter()
+                    .any(|etag| validators.etag.matches_for_if_match(etag));
+            if !matches {
+                return Some(ElifResponse::with_status(
+                    ElifStatusCode::PRECONDITION_FAILED,
+                ));
+            }
+        } else if let Some(since) = header_date(request, "if-unmodified-since") {
+            if validators
+                .modified_secs
+                .is_some_and(|modified| modified > since)
+            {
+                return Some(ElifResponse::with_status(
+                    ElifStatusCode::PRECONDITION_FAILED,
+                ));
+            }
+        }
+
+        if let Ok(Some(if_none_match)) = request.header_string("if-none-match") {
+            let matches = if_none_match.trim() == "*"
+                || ETagType::parse_list(&if_none_match)
+                    .iter()
+                    .any(|etag| validators.etag.matches_for_if_none_match(etag));
+            if matches {
+                return Some(ElifResponse::with_status(ElifStatusCode::NOT_MODIFIED));
+            }
+        } else if let Some(since) = header_date(request, "if-modified-since") {
+            if validators
+                .modified_secs
+                .is_some_and(|modified| modified <= since)
+            {
+                return Some(ElifResponse::with_status(ElifStatusCode::NOT_MODIFIED));
+            }
+        }
+
+        None
+    }
+
+    /// Check whether an `If-Range` condition (if any) allows serving a partial response
+    fn if_range_matches(&self, request: &ElifRequest, validators: &Validators) -> HttpResult<bool> {
+        let Some(if_range) = request.header_string("if-range")? else {
+            return Ok(true);
+        };
+
+        Ok(match ETagType::from_header_value(&if_range) {
+            Some(etag) => validators.etag.matches_for_if_match(&etag),
+            None => match (
+                httpdate::parse_http_date(&if_range),
+                validators.modified_secs,
+            ) {
+                (Ok(date), Some(modified)) => unix_secs(date) == Some(modified),
+                _ => false,
+            },
+        })
+    }
+
+    fn add_validator_headers(
+        &self,
+        response: &mut ElifResponse,
+        validators: &Validators,
+    ) -> HttpResult<()> {
+        response.add_header("etag", validators.etag.to_header_value())?;
+        if let Some(last_modified) = &validators.last_modified {
+            response.add_header("last-modified", last_modified)?;
+        }
+        if let Some(cache_control) = &self.cache_control {
+            response.add_header("cache-control", cache_control)?;
+        }
+        if self.precompressed {
+            response.add_header("vary", "accept-encoding")?;
+        }
+        Ok(())
+    }
+}
+
+/// The file chosen to answer a request
+struct SelectedFile {
+    path: PathBuf,
+    original: PathBuf,
+    encoding: Option<&'static str>,
+}
+
+/// Validators of the selected file
+struct Validators {
+    etag: ETagType,
+    last_modified: Option<String>,
+    modified_secs: Option<u64>,
+}
+
+impl Validators {
+    fn new(len: u64, modified: Option<SystemTime>, encoding: Option<&str>) -> Self {
+        let modified_secs = modified.and_then(unix_secs);
+        let mut etag = format!("{:x}-{:x}", len, modified_secs.unwrap_or_default());
+        if let Some(encoding) = encoding {
+            etag.push('-');
+            etag.push_str(encoding);
+        }
+
+        Self {
+            etag: ETagType::Strong(etag),
+            last_modified: modified.map(httpdate::fmt_http_date),
+            modified_secs,
+        }
+    }
+}
+
+/// Outcome of evaluating a `Range` header
+#[derive(Debug, PartialEq, Eq)]
+enum RangeRequest {
+    /// Serve the whole file (no usable single range)
+    Full,
+    /// Serve the inclusive byte range
+    Partial(u64, u64),
+    /// The range lies outside the file
+    Unsatisfiable,
+}
+
+/// Parse a single `bytes=` range; multiple ranges are answered with the full file
+fn parse_range(header: &str, len: u64) -> RangeRequest {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.split_once('-') else {
+        return RangeRequest::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    if start.is_empty() {
+        return match end.parse::<u64>() {
+            Ok(0) => RangeRequest::Unsatisfiable,
+            Ok(_) if len == 0 => RangeRequest::Unsatisfiable,
+            Ok(suffix) => RangeRequest::Partial(len.saturating_sub(suffix), len - 1),
+            Err(_) => RangeRequest::Full,
+        };
+    }
+
+    let Ok(start) = start.parse::<u64>() else {
+        return RangeRequest::Full;
+    };
+    let end = if end.is_empty() {
+        None
+    } else {
+        match end.parse::<u64>() {
+            Ok(end) if end >= start => Some(end),
+            _ => return RangeRequest::Full,
+        }
+    };
+
+    if start >= len {
+        return RangeRequest::Unsatisfiable;
+    }
+    RangeRequest::Partial(start, end.map_or(len - 1, |end| end.min(len - 1)))
+}
+
+/// Check whether an `Accept-Encoding` header accepts an encoding
+fn accepts_encoding(header: &str, encoding: &str) -> bool {
+    header.split(',').any(|item| {
+        let mut params = item.split(';');
+        let name = params.next().unwrap_or("").trim();
+        (name.eq_ignore_ascii_case(encoding) || name == "*")
+            && !params.any(|param| {
+                param
+                    .trim()
+                    .strip_prefix("q=")
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            })
+    })
+}
+
+fn header_date(request: &ElifRequest, name: &str) -> Option<u64> {
+    let value = request.header_string(name).ok()??;
+    httpdate::parse_http_date(&value).ok().and_then(unix_secs)
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+/// Open a file and stream `len` bytes starting at `start`
+async fn open_range(
+    path: &Path,
+    start: u64,
+    len: u64,
+) -> HttpResult<ReaderStream<tokio::io::Take<tokio::fs::File>>> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| HttpError::internal(format!("Failed to open file: {}", e)))?;
+    if start > 0 {
+        file.seek(SeekFrom::Start(start))
+            .await
+            .map_err(|e| HttpError::internal(format!("Failed to read file: {}", e)))?;
+    }
+    Ok(ReaderStream::with_capacity(file.take(len), CHUNK_SIZE))
+}
+
+/// Guess the content type of a file from its extension
+pub(crate) fn mime_type_for(path: &Path) -> String {
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    if mime.type_() == mime_guess::mime::TEXT {
+        format!("{}; charset=utf-8", mime.essence_str())
+    } else {
+        mime.essence_str().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::ElifHeaderMap;
+
+    fn fixture() -> (tempfile::TempDir, StaticFiles) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.js"), "console.log('hello world');").unwrap();
+        std::fs::write(dir.path().join("app.js.gz"), "gzipped").unwrap();
+        std::fs::create_dir(dir.path().join("docs")).unwrap();
+        std::fs::write(dir.path().join("docs/index.html"), "<h1>Docs</h1>").unwrap();
+        std::fs::write(dir.path().join(".env"), "SECRET=1").unwrap();
+        let files = StaticFiles::new(dir.path().join(""));
+        (dir, files)
+    }
+
+    fn request(headers: &[(&str, &str)]) -> ElifRequest {
+        let mut request = ElifRequest::new(
+            ElifMethod::GET,
+            "/assets/app.js".parse().unwrap(),
+            ElifHeaderMap::new(),
+        );
+        for (name, value) in headers {
+            request.add_header(name, value).unwrap();
+        }
+        request
+    }
+
+    async fn body(response: ElifResponse) -> String {
+        let response = response.build().unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    fn header(response: &ElifResponse, name: &str) -> Option<String> {
+        response
+            .headers()
+            .get_str(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+
+    #[tokio::test]
+    async fn test_serves_files_with_validators() {
+        let (_dir, files) = fixture();
+        let response = files.serve(&request(&[]), "app.js").await.unwrap();
+
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+        assert!(response.is_streaming());
+        assert_eq!(
+            header(&response, "content-type").unwrap(),
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(header(&response, "content-length").unwrap(), "27");
+        assert!(header(&response, "etag").unwrap().starts_with("\"1b-"));
+        assert!(header(&response, "last-modified").is_some());
+        assert_eq!(body(response).await, "console.log('hello world');");
+
+        let response = files.serve(&request(&[]), "docs/").await.unwrap();
+        assert_eq!(body(response).await, "<h1>Docs</h1>");
+    }
+
+    #[tokio::test]
+    async fn test_conditional_requests() {
+        let (_dir, files) = fixture();
+        let response = files.serve(&request(&[]), "app.js").await.unwrap();
+        let etag = header(&response, "etag").unwrap();
+        let last_modified = header(&response, "last-modified").unwrap();
+
+        let response = files
+            .serve(&request(&[("if-none-match", &etag)]), "app.js")
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), ElifStatusCode::NOT_MODIFIED);
+        assert_eq!(header(&response, "etag").unwrap(), etag);
+
+        let response = files
+            .serve(&request(&[("if-modified-since", &last_modified)]), "app.js")
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), ElifStatusCode::NOT_MODIFIED);
+
+        let response = files
+            .serve(&request(&[("if-match", "\"other\"")]), "app.js")
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), ElifStatusCode::PRECONDITION_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_range_requests() {
+        let (_dir, files) = fixture();
+
+        let response = files
+            .serve(&request(&[("range", "bytes=0-6")]), "app.js")
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), ElifStatusCode::PARTIAL_CONTENT);
+        assert_eq!(header(&response, "content-range").unwrap(), "bytes 0-6/27");
+        assert_eq!(body(response).await, "console");
+
+        let response = files
+            .serve(&request(&[("range", "bytes=-3")]), "app.js")
+            .await
+            .unwrap();
+        assert_eq!(body(response).await, "');");
+
+        let response = files
+            .serve(&request(&[("range", "bytes=100-")]), "app.js")
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status_code(),
+            ElifStatusCode::RANGE_NOT_SATISFIABLE
+        );
+        assert_eq!(header(&response, "content-range").unwrap(), "bytes */27");
+
+        // A stale If-Range validator gets the whole file
+        let response = files
+            .serve(
+                &request(&[("range", "bytes=0-6"), ("if-range", "\"stale\"")]),
+                "app.js",
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status_code(), ElifStatusCode::OK);
+
+        assert_eq!(parse_range("bytes=0-1,4-5", 10), RangeRequest::Full);
+        assert_eq!(parse_range("bytes=5-2", 10), RangeRequest::Full);
+        assert_eq!(parse_range("bytes=2-", 10), RangeRequest::Partial(2, 9));
+    }
+
+    #[tokio::test]
+    async fn test_precompressed_siblings() {
+        let (_dir, files) = fixture();
+
+        let response = files
+            .serve(&request(&[("accept-encoding", "br;q=0, gzip")]), "app.js")
+            .await
+            .unwrap();
+        assert_eq!(header(&response, "content-encoding").unwrap(), "gzip");
+        assert_eq!(
+            header(&response, "content-type").unwrap(),
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(header(&response, "vary").unwrap(), "accept-encoding");
+        assert!(header(&response, "etag").unwrap().ends_with("-gzip\""));
+        assert_eq!(body(response).await, "gzipped");
+
+        let response = files
+            .serve(&request(&[("accept-encoding", "br")]), "app.js")
+            .await
+            .unwrap();
+        assert!(header(&response, "content-encoding").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_path_traversal_is_rejected() {
+        let (dir, _) = fixture();
+        std::fs::create_dir(dir.path().join("public")).unwrap();
+        std::fs::write(dir.path().join("secret.txt"), "secret").unwrap();
+        let files = StaticFiles::new(dir.path().join("public"));
+
+        for path in [
+            "../secret.txt",
+            "%2e%2e/secret.txt",
+            "..%2fsecret.txt",
+            "..%5csecret.txt",
+            "%00",
+        ] {
+            assert!(
+                matches!(
+                    files.serve(&request(&[]), path).await,
+                    Err(HttpError::NotFound { .. })
+                ),
+                "{} should not be served",
+                path
+            );
+        }
+
+        let (_dir, files) = fixture();
+        assert!(files.serve(&request(&[]), ".env").await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_precompressed_siblings_outside_root_are_ignored() {
+        let (dir, _) = fixture();
+        std::fs::create_dir(dir.path().join("public")).unwrap();
+        std::fs::write(dir.path().join("public/app.js"), "console.log(1);").unwrap();
+        std::fs::write(dir.path().join("secret.gz"), "secret").unwrap();
+        std::os::unix::fs::symlink(
+            dir.path().join("secret.gz"),
+            dir.path().join("public/app.js.gz"),
+        )
+        .unwrap();
+        let files = StaticFiles::new(dir.path().join("public"));
+
+        let response = files
+            .serve(&request(&[("accept-encoding", "gzip")]), "app.js")
+            .await
+            .unwrap();
+        assert!(header(&response, "content-encoding").is_none());
+        assert_eq!(body(response).await, "console.log(1);");
+    }
+}
@@ -13,6 +13,7 @@ impl ElifStatusCode {
     pub const CREATED: Self = Self(axum::http::StatusCode::CREATED);
     pub const ACCEPTED: Self = Self(axum::http::StatusCode::ACCEPTED);
     pub const NO_CONTENT: Self = Self(axum::http::StatusCode::NO_CONTENT);
+    pub const PARTIAL_CONTENT: Self = Self(axum::http::StatusCode::PARTIAL_CONTENT);
     pub const MOVED_PERMANENTLY: Self = Self(axum::http::StatusCode::MOVED_PERMANENTLY);
     pub const FOUND: Self = Self(axum::http::StatusCode::FOUND);
     pub const SEE_OTHER: Self = Self(axum::http::StatusCode::SEE_OTHER);
@@ -28,6 +29,7 @@ impl ElifStatusCode {
     pub const UNPROCESSABLE_ENTITY: Self = Self(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
     pub const REQUEST_TIMEOUT: Self = Self(axum::http::StatusCode::REQUEST_TIMEOUT);
     pub const PAYLOAD_TOO_LARGE: Self = Self(axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+    pub const RANGE_NOT_SATISFIABLE: Self = Self(axum::http::StatusCode::RANGE_NOT_SATISFIABLE);
     pub const TOO_MANY_REQUESTS: Self = Self(axum::http::StatusCode::TOO_MANY_REQUESTS);
     pub const INTERNAL_SERVER_ERROR: Self = Self(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
     pub const NOT_IMPLEMENTED: Self = Self(axum::http::StatusCode::NOT_IMPLEMENTED);
@@ -46,6 +48,11 @@ impl ElifStatusCode {
         self.0.as_u16()
     }
 
+    /// Get the standard reason phrase, e.g. `Not Found`
+    pub fn canonical_reason(&self) -> Option<&'static str> {
+        self.0.canonical_reason()
+    }
+
     /// Check if status code is informational (1xx)
     pub fn is_informational(&self) -> bool {
         self.0.is_informational()
@@ -5,8 +5,8 @@
 
 use super::extraction::ParameterExtractor;
 use super::matcher::{RouteDefinition, RouteMatchError, RouteMatcher};
-use super::pattern::{RoutePattern, RoutePatternError};
-use super::{HttpMethod, RouteInfo};
+use super::pattern::{HostPattern, RoutePattern, RoutePatternError};
+use super::{HttpMethod, RouteInfo, RouteLimits};
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
@@ -59,6 +59,7 @@ pub struct CompilationStats {
     pub dynamic_routes: usize,
     pub parameter_routes: usize,
     pub catch_all_routes: usize,
+    pub host_routes: usize,
     pub conflicts_detected: usize,
     pub optimizations_applied: usize,
     pub compilation_time_ms: u128,
@@ -70,6 +71,7 @@ pub struct CompilableRoute {
     pub id: String,
     pub method: HttpMethod,
     pub path: String,
+    pub host: Option<String>,
     pub name: Option<String>,
     pub metadata: HashMap<String, String>,
 }
@@ -80,11 +82,18 @@ impl CompilableRoute {
             id,
             method,
             path,
+            host: None,
             name: None,
             metadata: HashMap::new(),
         }
     }
 
+    /// Restrict the route to hosts matching a pattern, e.g. `{tenant}.example.com`
+    pub fn with_host(mut self, host: String) -> Self {
+        self.host = Some(host);
+        self
+    }
+
     pub fn with_name(mut self, name: String) -> Self {
         self.name = Some(name);
         self
@@ -172,10 +181,16 @@ impl RouteCompiler {
         let mut dynamic_count = 0;
         let mut parameter_count = 0;
         let mut catch_all_count = 0;
+        let mut host_count = 0;
 
         for route in self.routes {
             let pattern = RoutePattern::parse(&route.path)?;
 
+            if let Some(host) = &route.host {
+                HostPattern::parse(host)?;
+                host_count += 1;
+            }
+
             // Collect statistics
             if pattern.is_static() {
                 static_count += 1;
@@ -209,6 +224,7 @@ impl RouteCompiler {
             let route_id = route.id;
             let route_method = route.method;
             let route_path = route.path;
+            let route_host = route.host;
             let route_name = route.name;
             let route_group = route.metadata.get("group").cloned();
             let is_pattern_static = pattern.is_static();
@@ -219,6 +235,7 @@ impl RouteCompiler {
                 id: route_id.clone(),
                 method: route_method.clone(),
                 path: route_path.clone(),
+                host: route_host.clone(),
             };
 
             // Try to add route, handle conflicts
@@ -234,9 +251,11 @@ impl RouteCompiler {
                     let route_info = RouteInfo {
                         name: route_name,
                         path: route_path,
+                        host: route_host,
                         method: route_method,
                         params: pattern_param_names,
                         group: route_group,
+                        limits: RouteLimits::default(),
                     };
                     route_registry.insert(route_id, route_info);
                 }
@@ -271,6 +290,7 @@ impl RouteCompiler {
             dynamic_routes: dynamic_count,
             parameter_routes: parameter_count,
             catch_all_routes: catch_all_count,
+            host_routes: host_count,
             conflicts_detected,
             optimizations_applied,
             compilation_time_ms: compilation_time,
@@ -544,6 +564,42 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_host_conflict_detection() {
+        let tenant_route = |id: &str, host: &str| {
+            CompilableRoute::new(id.to_string(), HttpMethod::GET, "/dashboard".to_string())
+                .with_host(host.to_string())
+        };
+
+        let result = RouteCompilerBuilder::new()
+            .get("dashboard".to_string(), "/dashboard".to_string())
+            .route(tenant_route("tenant_dashboard", "{tenant}.example.com"))
+            .route(tenant_route("admin_dashboard", "admin.example.com"))
+            .build()
+            .unwrap();
+
+        assert_eq!(result.stats.host_routes, 2);
+        let route_info = result.route_registry.get("tenant_dashboard").unwrap();
+        assert_eq!(route_info.host.as_deref(), Some("{tenant}.example.com"));
+
+        let result = RouteCompilerBuilder::new()
+            .route(tenant_route("tenant_dashboard", "{tenant}.example.com"))
+            .route(tenant_route("account_dashboard", "{account}.example.com"))
+            .build();
+        assert!(matches!(
+            result.unwrap_err(),
+            CompilationError::RouteConflict(_, _)
+        ));
+
+        let result = RouteCompilerBuilder::new()
+            .route(tenant_route("invalid", "*.example.com"))
+            .build();
+        assert!(matches!(
+            result.unwrap_err(),
+            CompilationError::PatternError(_)
+        ));
+    }
+
     #[test]
     fn test_conflict_warnings() {
         let result = RouteCompilerBuilder::new()
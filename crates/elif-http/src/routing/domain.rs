@@ -0,0 +1,213 @@
+//! Host and subdomain based routing
+//!
+//! Routes registered with [`Router::domain`](super::Router::domain) only answer requests
+//! whose `Host` matches the domain's [`HostPattern`]. Parameters captured from the host,
+//! such as `{tenant}` in `{tenant}.example.com`, are exposed like path parameters.
+
+use super::pattern::HostPattern;
+use super::table::RouteTable;
+use crate::middleware::utils::TrustedProxyMiddleware;
+use crate::request::{strip_port, ConnectionInfo, ElifRequest, ForwardedInfo};
+use crate::response::ElifHeaderMap;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Router as AxumRouter;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, Weak};
+use tower::ServiceExt;
+
+/// Parameters captured from the request host by a domain route group
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostParams(HashMap<String, String>);
+
+impl HostParams {
+    /// Get a host parameter by name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Iterate over all host parameters
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter()
+    }
+}
+
+impl ElifRequest {
+    /// Get a parameter captured from the host by a domain route group
+    pub fn host_param(&self, name: &str) -> Option<&str> {
+        self.get_extension::<HostParams>()?.get(name)
+    }
+
+    /// Carry host parameters from Axum request extensions, also exposing them as path params
+    pub(crate) fn with_host_params_from(mut self, extensions: &axum::http::Extensions) -> Self {
+        if let Some(params) = extensions.get::<HostParams>().cloned() {
+            for (name, value) in params.iter() {
+                self.add_path_param(name, value);
+            }
+            self.insert_extension(params);
+        }
+        self
+    }
+}
+
+/// Routes registered for one host pattern
+#[derive(Debug)]
+pub(crate) struct DomainRoutes<S> {
+    pub(crate) host: HostPattern,
+    pub(crate) router: AxumRouter<S>,
+    pub(crate) routes: RouteTable,
+}
+
+/// Domain routers and the host-independent router, bound to the application state
+struct ResolvedDomains {
+    domains: Vec<(HostPattern, AxumRouter)>,
+    default: AxumRouter,
+    proxies: Option<TrustedProxyMiddleware>,
+}
+
+/// Build an Axum router that dispatches on the request host before routing on the path
+///
+/// Domains are tried from the most to the least specific host pattern; when a matching
+/// domain has no route for the path, the next matching domain and finally the
+/// host-independent routes are tried. Routers are bound to the state of the first
+/// request. Behind `proxies`, the host comes from their forwarding headers.
+pub(crate) fn host_dispatch_router<S>(
+    default: AxumRouter<S>,
+    mut domains: Vec<DomainRoutes<S>>,
+    proxies: Option<TrustedProxyMiddleware>,
+) -> AxumRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    domains.sort_by_key(|domain| domain.host.priority());
+
+    let routes = Arc::new((default, domains, proxies));
+    let resolved: Arc<OnceLock<ResolvedDomains>> = Arc::default();
+
+    AxumRouter::new().fallback(
+        move |State(state): State<S>, request: axum::extract::Request| {
+            let routes = Arc::clone(&routes);
+            let resolved = Arc::clone(&resolved);
+            async move {
+                let shared = Arc::downgrade(&resolved);
+                let domains = resolved.get_or_init(|| {
+                    let (default, domains, proxies) = &*routes;
+                    resolve(default, domains, proxies.clone(), state, shared)
+                });
+                dispatch(domains, 0, request).await
+            }
+        },
+    )
+}
+
+/// Bind routers to the application state, chaining each domain to the ones after it
+fn resolve<S>(
+    default: &AxumRouter<S>,
+    domains: &[DomainRoutes<S>],
+    proxies: Option<TrustedProxyMiddleware>,
+    state: S,
+    shared: Weak<OnceLock<ResolvedDomains>>,
+) -> ResolvedDomains
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let domains = domains
+        .iter()
+        .enumerate()
+        .map(|(index, domain)| {
+            let shared = shared.clone();
+            let router: AxumRouter = domain.router.clone().with_state(state.clone()).fallback(
+                move |request: axum::extract::Request| {
+                    let shared = shared.clone();
+                    async move {
+                        match shared.upgrade() {
+                            Some(resolved) => match resolved.get() {
+                                Some(domains) => dispatch(domains, index + 1, request).await,
+                                None => crate::response::ElifStatusCode::NOT_FOUND
+                                    .to_axum()
+                                    .into_response(),
+                            },
+                            None => crate::response::ElifStatusCode::NOT_FOUND
+                                .to_axum()
+                                .into_response(),
+                        }
+                    }
+                },
+            );
+            (domain.host.clone(), router)
+        })
+        .collect();
+
+    ResolvedDomains {
+        domains,
+        default: default.clone().with_state(state),
+        proxies,
+    }
+}
+
+/// Route a request to the first matching domain at or after `start`
+async fn dispatch(
+    resolved: &ResolvedDomains,
+    start: usize,
+    mut request: axum::extract::Request,
+) -> axum::response::Response {
+    request.extensions_mut().remove::<HostParams>();
+
+    if let Some(host) = request_host(&request, resolved.proxies.as_ref()) {
+        let domain = resolved.domains[start.min(resolved.domains.len())..]
+            .iter()
+            .find(|(pattern, _)| pattern.matches(&host));
+
+        if let Some((pattern, router)) = domain {
+            let params = HostParams(pattern.extract_params(&host));
+            request.extensions_mut().insert(params);
+            return router
+                .clone()
+                .oneshot(request)
+                .await
+                .unwrap_or_else(|never| match never {});
+        }
+    }
+
+    resolved
+        .default
+        .clone()
+        .oneshot(request)
+        .await
+        .unwrap_or_else(|never| match never {})
+}
+
+/// Host the request was sent to
+///
+/// Like [`ElifRequest::host`], the host forwarded by a trusted proxy wins over the URI
+/// authority and the `Host` header.
+fn request_host(
+    request: &axum::extract::Request,
+    proxies: Option<&TrustedProxyMiddleware>,
+) -> Option<String> {
+    let forwarded = request
+        .extensions()
+        .get::<ForwardedInfo>()
+        .cloned()
+        .or_else(|| {
+            let proxies = proxies?;
+            let peer = ConnectionInfo::from_extensions(request.extensions())?.remote_addr?;
+            let headers = ElifHeaderMap::from_axum(request.headers().clone());
+            proxies.resolve_headers(peer.ip(), &headers)
+        });
+
+    let host = match forwarded.and_then(|forwarded| forwarded.host) {
+        Some(host) => host,
+        None => request
+            .uri()
+            .host()
+            .or_else(|| {
+                request
+                    .headers()
+                    .get(axum::http::header::HOST)
+                    .and_then(|value| value.to_str().ok())
+            })?
+            .to_string(),
+    };
+    Some(strip_port(&host).to_string())
+}
@@ -1,6 +1,6 @@
 //! Route groups for organizing related routes
 
-use super::{HttpMethod, RouteInfo, RouteRegistry};
+use super::{HttpMethod, RouteInfo, RouteLimits, RouteRegistry};
 use axum::{
     handler::Handler,
     routing::{delete, get, patch, post, put},
@@ -80,9 +80,11 @@ where
         let route_info = RouteInfo {
             name: final_name,
             path: full_path,
+            host: None,
             method,
             params,
             group: Some(self.name.clone()),
+            limits: RouteLimits::default(),
         };
 
         let route_id = format!("{}_{}", self.name, uuid::Uuid::new_v4());
@@ -0,0 +1,217 @@
+//! Per-route limits
+//!
+//! Routes can override the global request timeout and body size limit and cap the number
+//! of requests they handle concurrently. Limits are declared on
+//! [`RouteBuilder`](super::RouteBuilder) or with the `#[limits(...)]` controller attribute
+//! and enforced by [`RouteLimitsMiddleware`](crate::middleware::utils::RouteLimitsMiddleware).
+//!
+//! Route limits are enforced before the global middleware runs and take precedence over
+//! a global [`TimeoutMiddleware`](crate::middleware::utils::TimeoutMiddleware) or
+//! [`BodyLimitMiddleware`](crate::middleware::utils::BodyLimitMiddleware), so a route can
+//! allow larger bodies or longer requests than the rest of the application.
+
+use crate::request::ElifRequest;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Timeout, body size and concurrency limits of a single route
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteLimits {
+    /// Maximum time to produce a response
+    pub timeout: Option<Duration>,
+    /// Maximum request body size in bytes
+    pub max_body_size: Option<usize>,
+    /// Maximum number of requests handled at the same time
+    pub max_concurrency: Option<usize>,
+}
+
+impl RouteLimits {
+    /// Create route limits without any limit set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the request timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum request body size in bytes
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = Some(bytes);
+        self
+    }
+
+    /// Set the maximum number of in-flight requests
+    pub fn max_concurrency(mut self, requests: usize) -> Self {
+        self.max_concurrency = Some(requests);
+        self
+    }
+
+    /// Check whether no limit is set
+    pub fn is_empty(&self) -> bool {
+        self.timeout.is_none() && self.max_body_size.is_none() && self.max_concurrency.is_none()
+    }
+
+    /// Parse a duration such as `500ms`, `30s`, `2m` or `1h`
+    ///
+    /// Usable in constants; `#[limits(timeout = "...")]` is checked with it at compile time.
+    pub const fn parse_duration(value: &str) -> Option<Duration> {
+        let Some((amount, unit)) = split_amount(value.as_bytes()) else {
+            return None;
+        };
+        let factor = match unit {
+            b"ms" => 1,
+            b"s" => 1000,
+            b"m" => 60 * 1000,
+            b"h" => 60 * 60 * 1000,
+            _ => return None,
+        };
+        match amount.checked_mul(factor) {
+            Some(millis) => Some(Duration::from_millis(millis)),
+            None => None,
+        }
+    }
+
+    /// Parse a size such as `1024`, `512KB`, `10MB` or `1GB` into bytes
+    ///
+    /// Units are case-insensitive. Usable in constants; `#[limits(max_body_size = "...")]`
+    /// is checked with it at compile time.
+    pub const fn parse_size(value: &str) -> Option<usize> {
+        let Some((amount, unit)) = split_amount(value.as_bytes()) else {
+            return None;
+        };
+        let factor: u64 = match unit {
+            [] | [b'b' | b'B'] => 1,
+            [b'k' | b'K', b'b' | b'B'] => 1 << 10,
+            [b'm' | b'M', b'b' | b'B'] => 1 << 20,
+            [b'g' | b'G', b'b' | b'B'] => 1 << 30,
+            _ => return None,
+        };
+        match amount.checked_mul(factor) {
+            Some(bytes) if bytes <= usize::MAX as u64 => Some(bytes as usize),
+            _ => None,
+        }
+    }
+}
+
+/// Split surrounding whitespace off `value` and its leading digits from the unit after them
+const fn split_amount(value: &[u8]) -> Option<(u64, &[u8])> {
+    let value = value.trim_ascii();
+    let mut amount: u64 = 0;
+    let mut digits = 0;
+    while digits < value.len() && value[digits].is_ascii_digit() {
+        amount = match amount.checked_mul(10) {
+            Some(amount) => match amount.checked_add((value[digits] - b'0') as u64) {
+                Some(amount) => amount,
+                None => return None,
+            },
+            None => return None,
+        };
+        digits += 1;
+    }
+
+    if digits == 0 {
+        return None;
+    }
+    let (_, unit) = value.split_at(digits);
+    Some((amount, unit.trim_ascii()))
+}
+
+impl fmt::Display for RouteLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut limits = Vec::new();
+        if let Some(timeout) = self.timeout {
+            limits.push(format!("timeout={:?}", timeout));
+        }
+        if let Some(bytes) = self.max_body_size {
+            limits.push(format!("body={}", format_bytes(bytes)));
+        }
+        if let Some(requests) = self.max_concurrency {
+            limits.push(format!("concurrency={}", requests));
+        }
+
+        if limits.is_empty() {
+            write!(f, "-")
+        } else {
+            write!(f, "{}", limits.join(" "))
+        }
+    }
+}
+
+impl ElifRequest {
+    /// Get the limits declared by the route handling this request
+    pub fn route_limits(&self) -> Option<&RouteLimits> {
+        self.get_extension::<RouteLimits>()
+    }
+
+    /// Carry route limits from Axum request extensions
+    pub(crate) fn with_route_limits_from(mut self, extensions: &axum::http::Extensions) -> Self {
+        if let Some(limits) = extensions.get::<RouteLimits>().cloned() {
+            self.insert_extension(limits);
+        }
+        self
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [(usize, &str); 3] = [(1 << 30, "GB"), (1 << 20, "MB"), (1 << 10, "KB")];
+
+    UNITS
+        .iter()
+        .find(|(size, _)| bytes >= *size && bytes.is_multiple_of(*size))
+        .map(|(size, unit)| format!("{}{}", bytes / size, unit))
+        .unwrap_or_else(|| format!("{}B", bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_limits_display() {
+        assert_eq!(RouteLimits::new().to_string(), "-");
+        assert!(RouteLimits::new().is_empty());
+
+        let limits = RouteLimits::new()
+            .timeout(Duration::from_secs(120))
+            .max_body_size(50 * 1024 * 1024)
+            .max_concurrency(4);
+        assert!(!limits.is_empty());
+        assert_eq!(limits.to_string(), "timeout=120s body=50MB concurrency=4");
+        assert_eq!(
+            RouteLimits::new().max_body_size(1500).to_string(),
+            "body=1500B"
+        );
+    }
+
+    #[test]
+    fn test_parse_limit_values() {
+        assert_eq!(
+            RouteLimits::parse_duration("500ms"),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            RouteLimits::parse_duration(" 2m "),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            RouteLimits::parse_duration("1h"),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(RouteLimits::parse_duration("30"), None);
+        assert_eq!(RouteLimits::parse_duration("soon"), None);
+
+        assert_eq!(RouteLimits::parse_size("1024"), Some(1024));
+        assert_eq!(RouteLimits::parse_size("512KB"), Some(512 * 1024));
+        assert_eq!(RouteLimits::parse_size("10 mb"), Some(10 * 1024 * 1024));
+        assert_eq!(RouteLimits::parse_size("1GB"), Some(1 << 30));
+        assert_eq!(RouteLimits::parse_size("10TB"), None);
+        assert_eq!(RouteLimits::parse_size("MB"), None);
+
+        const UPLOAD_TIMEOUT: Option<Duration> = RouteLimits::parse_duration("90s");
+        assert_eq!(UPLOAD_TIMEOUT, Some(Duration::from_secs(90)));
+    }
+}
@@ -3,7 +3,10 @@
 //! This module provides the core route matching functionality that efficiently
 //! resolves incoming requests to the appropriate route handlers.
 
-use super::pattern::{CompiledRoute, RouteId, RouteMatch, RoutePattern, RoutePatternError};
+use super::pattern::{
+    segments_conflict, CompiledRoute, HostPattern, RouteId, RouteMatch, RoutePattern,
+    RoutePatternError,
+};
 use super::HttpMethod;
 use std::collections::HashMap;
 use thiserror::Error;
@@ -25,6 +28,8 @@ pub struct RouteDefinition {
     pub id: RouteId,
     pub method: HttpMethod,
     pub path: String,
+    /// Host pattern the route is restricted to, e.g. `{tenant}.example.com`
+    pub host: Option<String>,
 }
 
 /// High-performance route matcher
@@ -35,6 +40,8 @@ pub struct RouteMatcher {
     static_routes: HashMap<HttpMethod, HashMap<String, RouteId>>,
     /// Dynamic routes sorted by priority
     dynamic_routes: Vec<CompiledRoute>,
+    /// Host-restricted routes sorted by host then path priority, checked before all others
+    host_routes: Vec<CompiledRoute>,
     /// All route definitions for introspection
     route_definitions: HashMap<RouteId, RouteDefinition>,
 }
@@ -45,6 +52,7 @@ impl RouteMatcher {
         Self {
             static_routes: HashMap::new(),
             dynamic_routes: Vec::new(),
+            host_routes: Vec::new(),
             route_definitions: HashMap::new(),
         }
     }
@@ -53,6 +61,11 @@ impl RouteMatcher {
     pub fn add_route(&mut self, definition: RouteDefinition) -> Result<(), RouteMatchError> {
         let pattern = RoutePattern::parse(&definition.path)?;
 
+        if let Some(host) = &definition.host {
+            let host = HostPattern::parse(host)?;
+            return self.add_host_route(definition, pattern, host);
+        }
+
         // Check for route conflicts
         self.check_conflicts(&definition, &pattern)?;
 
@@ -82,7 +95,60 @@ impl RouteMatcher {
         Ok(())
     }
 
+    /// Add a route restricted to hosts matching a pattern
+    fn add_host_route(
+        &mut self,
+        definition: RouteDefinition,
+        pattern: RoutePattern,
+        host: HostPattern,
+    ) -> Result<(), RouteMatchError> {
+        // Host and path parameters share one namespace
+        if let Some(name) = host
+            .param_names
+            .iter()
+            .find(|name| pattern.param_names.contains(name))
+        {
+            return Err(RoutePatternError::DuplicateParameter(name.clone()).into());
+        }
+
+        for existing_route in &self.host_routes {
+            let same_host = existing_route
+                .host
+                .as_ref()
+                .is_some_and(|existing_host| existing_host.conflicts_with(&host));
+
+            if existing_route.method == definition.method
+                && same_host
+                && self.patterns_conflict(&pattern, &existing_route.pattern)
+            {
+                return Err(RouteMatchError::RouteConflict(
+                    definition.id.clone(),
+                    existing_route.id.clone(),
+                ));
+            }
+        }
+
+        self.route_definitions
+            .insert(definition.id.clone(), definition.clone());
+
+        let sort_key = (host.priority(), pattern.priority());
+        let compiled_route =
+            CompiledRoute::new(definition.id, definition.method, pattern).with_host(host);
+
+        let insert_pos = self
+            .host_routes
+            .binary_search_by_key(&sort_key, |r| {
+                (r.host.as_ref().map_or(0, |h| h.priority()), r.priority)
+            })
+            .unwrap_or_else(|pos| pos);
+
+        self.host_routes.insert(insert_pos, compiled_route);
+        Ok(())
+    }
+
     /// Resolve an incoming request to a matching route
+    ///
+    /// Only routes without a host restriction are considered, see [`Self::resolve_host`].
     pub fn resolve(&self, method: &HttpMethod, path: &str) -> Option<RouteMatch> {
         // Fast path: check static routes first (no allocation!)
         if let Some(method_routes) = self.static_routes.get(method) {
@@ -108,6 +174,46 @@ impl RouteMatcher {
         None
     }
 
+    /// Resolve an incoming request to a matching route, taking the request host into account
+    ///
+    /// Routes restricted to a matching host take precedence over unrestricted routes. Host
+    /// parameters are returned alongside path parameters.
+    pub fn resolve_host(
+        &self,
+        method: &HttpMethod,
+        host: Option<&str>,
+        path: &str,
+    ) -> Option<RouteMatch> {
+        if host.is_some() {
+            for compiled_route in &self.host_routes {
+                if compiled_route.matches_host(method, host, path) {
+                    return Some(RouteMatch {
+                        route_id: compiled_route.id.clone(),
+                        params: compiled_route.extract_host_params(host, path),
+                    });
+                }
+            }
+        }
+
+        self.resolve(method, path)
+    }
+
+    /// Methods with a route matching the host and path, used for `Allow` headers
+    ///
+    /// `HEAD` is included whenever `GET` is, since `HEAD` requests are answered by `GET` routes.
+    pub fn allowed_methods(&self, host: Option<&str>, path: &str) -> Vec<HttpMethod> {
+        let mut allowed: Vec<HttpMethod> = HttpMethod::ALL
+            .iter()
+            .filter(|method| self.resolve_host(method, host, path).is_some())
+            .cloned()
+            .collect();
+
+        if allowed.contains(&HttpMethod::GET) && !allowed.contains(&HttpMethod::HEAD) {
+            allowed.push(HttpMethod::HEAD);
+        }
+        allowed
+    }
+
     /// Check for route conflicts before adding a new route
     fn check_conflicts(
         &self,
@@ -146,29 +252,7 @@ impl RouteMatcher {
         // Two patterns conflict if they are structurally identical.
         // This means they have the same number of segments, and each corresponding
         // segment is of the same type with the same static value or constraint.
-        if pattern1.segments.len() != pattern2.segments.len() {
-            return false;
-        }
-
-        for (seg1, seg2) in pattern1.segments.iter().zip(pattern2.segments.iter()) {
-            match (seg1, seg2) {
-                (
-                    super::pattern::PathSegment::Static(s1),
-                    super::pattern::PathSegment::Static(s2),
-                ) if s1 == s2 => continue,
-                (
-                    super::pattern::PathSegment::Parameter { constraint: c1, .. },
-                    super::pattern::PathSegment::Parameter { constraint: c2, .. },
-                ) if c1 == c2 => continue,
-                (
-                    super::pattern::PathSegment::CatchAll { .. },
-                    super::pattern::PathSegment::CatchAll { .. },
-                ) => continue,
-                _ => return false, // Segments are not structurally identical
-            }
-        }
-
-        true // All segments are structurally identical, so the patterns conflict.
+        segments_conflict(&pattern1.segments, &pattern2.segments)
     }
 
     /// Get all route definitions for introspection
@@ -192,6 +276,7 @@ impl RouteMatcher {
         MatcherStats {
             static_routes: static_routes_count,
             dynamic_routes: self.dynamic_routes.len(),
+            host_routes: self.host_routes.len(),
             total_routes: self.route_definitions.len(),
         }
     }
@@ -200,6 +285,7 @@ impl RouteMatcher {
     pub fn clear(&mut self) {
         self.static_routes.clear();
         self.dynamic_routes.clear();
+        self.host_routes.clear();
         self.route_definitions.clear();
     }
 }
@@ -215,6 +301,7 @@ impl Default for RouteMatcher {
 pub struct MatcherStats {
     pub static_routes: usize,
     pub dynamic_routes: usize,
+    pub host_routes: usize,
     pub total_routes: usize,
 }
 
@@ -231,7 +318,29 @@ impl RouteMatcherBuilder {
 
     /// Add a route to the builder
     pub fn route(mut self, id: String, method: HttpMethod, path: String) -> Self {
-        self.routes.push(RouteDefinition { id, method, path });
+        self.routes.push(RouteDefinition {
+            id,
+            method,
+            path,
+            host: None,
+        });
+        self
+    }
+
+    /// Add a route restricted to hosts matching a pattern
+    pub fn host_route(
+        mut self,
+        id: String,
+        method: HttpMethod,
+        host: String,
+        path: String,
+    ) -> Self {
+        self.routes.push(RouteDefinition {
+            id,
+            method,
+            path,
+            host: Some(host),
+        });
         self
     }
 
@@ -290,6 +399,7 @@ mod tests {
             id: "home".to_string(),
             method: HttpMethod::GET,
             path: "/".to_string(),
+            host: None,
         };
 
         matcher.add_route(route_def).unwrap();
@@ -316,6 +426,7 @@ mod tests {
             id: "user_show".to_string(),
             method: HttpMethod::GET,
             path: "/users/{id}".to_string(),
+            host: None,
         };
 
         matcher.add_route(route_def).unwrap();
@@ -338,6 +449,7 @@ mod tests {
                 id: "catch_all".to_string(),
                 method: HttpMethod::GET,
                 path: "/files/*path".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -346,6 +458,7 @@ mod tests {
                 id: "specific".to_string(),
                 method: HttpMethod::GET,
                 path: "/files/config.json".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -354,6 +467,7 @@ mod tests {
                 id: "param".to_string(),
                 method: HttpMethod::GET,
                 path: "/files/{name}".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -380,6 +494,7 @@ mod tests {
                 id: "route1".to_string(),
                 method: HttpMethod::GET,
                 path: "/users".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -388,6 +503,7 @@ mod tests {
             id: "route2".to_string(),
             method: HttpMethod::GET,
             path: "/users".to_string(),
+            host: None,
         });
 
         assert!(result.is_err());
@@ -404,6 +520,7 @@ mod tests {
                 id: "users_by_id".to_string(),
                 method: HttpMethod::GET,
                 path: "/users/{id}".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -411,6 +528,7 @@ mod tests {
             id: "users_by_name".to_string(),
             method: HttpMethod::GET,
             path: "/users/{name}".to_string(),
+            host: None,
         });
         assert!(
             result.is_err(),
@@ -422,6 +540,7 @@ mod tests {
             id: "users_post".to_string(),
             method: HttpMethod::POST,
             path: "/users/{id}".to_string(),
+            host: None,
         });
         assert!(result.is_ok(), "Different methods should not conflict");
 
@@ -430,6 +549,7 @@ mod tests {
             id: "posts_by_id".to_string(),
             method: HttpMethod::GET,
             path: "/posts/{id}".to_string(),
+            host: None,
         });
         assert!(
             result.is_ok(),
@@ -442,6 +562,7 @@ mod tests {
                 id: "files_serve".to_string(),
                 method: HttpMethod::GET,
                 path: "/files/*path".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -449,6 +570,7 @@ mod tests {
             id: "files_download".to_string(),
             method: HttpMethod::GET,
             path: "/files/*file_path".to_string(),
+            host: None,
         });
         assert!(
             result.is_err(),
@@ -460,6 +582,7 @@ mod tests {
             id: "admin_static".to_string(),
             method: HttpMethod::GET,
             path: "/admin/dashboard".to_string(),
+            host: None,
         });
         assert!(
             result.is_ok(),
@@ -477,6 +600,7 @@ mod tests {
                 id: "user_by_int_id".to_string(),
                 method: HttpMethod::GET,
                 path: "/users/{id:int}".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -484,6 +608,7 @@ mod tests {
             id: "user_by_int_uid".to_string(),
             method: HttpMethod::GET,
             path: "/users/{uid:int}".to_string(),
+            host: None,
         });
         assert!(result.is_err(), "Same constraints should conflict");
 
@@ -492,6 +617,7 @@ mod tests {
             id: "user_by_uuid".to_string(),
             method: HttpMethod::GET,
             path: "/users/{id:uuid}".to_string(),
+            host: None,
         });
         assert!(result.is_ok(), "Different constraints should not conflict");
 
@@ -500,6 +626,7 @@ mod tests {
             id: "user_by_string".to_string(),
             method: HttpMethod::GET,
             path: "/users/{name}".to_string(),
+            host: None,
         });
         assert!(
             result.is_ok(),
@@ -517,6 +644,7 @@ mod tests {
                 id: "api_user_posts".to_string(),
                 method: HttpMethod::GET,
                 path: "/api/v1/users/{user_id}/posts/{post_id}".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -525,6 +653,7 @@ mod tests {
             id: "api_member_articles".to_string(),
             method: HttpMethod::GET,
             path: "/api/v1/users/{member_id}/posts/{article_id}".to_string(),
+            host: None,
         });
         assert!(
             result.is_err(),
@@ -536,6 +665,7 @@ mod tests {
             id: "api_user_comments".to_string(),
             method: HttpMethod::GET,
             path: "/api/v1/users/{user_id}/comments/{comment_id}".to_string(),
+            host: None,
         });
         assert!(
             result.is_ok(),
@@ -547,6 +677,7 @@ mod tests {
             id: "api_user_profile".to_string(),
             method: HttpMethod::GET,
             path: "/api/v1/users/{user_id}/profile".to_string(),
+            host: None,
         });
         assert!(
             result.is_ok(),
@@ -554,6 +685,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_host_route_matching() {
+        let matcher = RouteMatcherBuilder::new()
+            .get("home".to_string(), "/".to_string())
+            .host_route(
+                "tenant_home".to_string(),
+                HttpMethod::GET,
+                "{tenant}.example.com".to_string(),
+                "/".to_string(),
+            )
+            .host_route(
+                "admin_home".to_string(),
+                HttpMethod::GET,
+                "admin.example.com".to_string(),
+                "/".to_string(),
+            )
+            .host_route(
+                "tenant_invoice".to_string(),
+                HttpMethod::GET,
+                "{tenant}.example.com".to_string(),
+                "/invoices/{id:int}".to_string(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(matcher.stats().host_routes, 3);
+
+        // Static host is preferred over a parameterized one
+        let result = matcher.resolve_host(&HttpMethod::GET, Some("admin.example.com"), "/");
+        assert_eq!(result.unwrap().route_id, "admin_home");
+
+        let result = matcher
+            .resolve_host(
+                &HttpMethod::GET,
+                Some("acme.example.com:443"),
+                "/invoices/7",
+            )
+            .unwrap();
+        assert_eq!(result.route_id, "tenant_invoice");
+        assert_eq!(result.params.get("tenant"), Some(&"acme".to_string()));
+        assert_eq!(result.params.get("id"), Some(&"7".to_string()));
+
+        // Other hosts fall back to unrestricted routes
+        let result = matcher.resolve_host(&HttpMethod::GET, Some("example.org"), "/");
+        assert_eq!(result.unwrap().route_id, "home");
+        assert_eq!(
+            matcher.resolve(&HttpMethod::GET, "/").unwrap().route_id,
+            "home"
+        );
+        assert!(matcher
+            .resolve_host(&HttpMethod::GET, Some("example.org"), "/invoices/7")
+            .is_none());
+    }
+
+    #[test]
+    fn test_host_route_conflicts() {
+        let mut matcher = RouteMatcher::new();
+        let route = |id: &str, host: Option<&str>, path: &str| RouteDefinition {
+            id: id.to_string(),
+            method: HttpMethod::GET,
+            path: path.to_string(),
+            host: host.map(String::from),
+        };
+
+        matcher
+            .add_route(route("tenant", Some("{tenant}.example.com"), "/users/{id}"))
+            .unwrap();
+
+        // Same host structure and path structure conflict
+        let result = matcher.add_route(route(
+            "account",
+            Some("{account}.example.com"),
+            "/users/{uid}",
+        ));
+        assert!(matches!(result, Err(RouteMatchError::RouteConflict(_, _))));
+
+        // Different hosts, or no host at all, don't conflict
+        assert!(matcher
+            .add_route(route("admin", Some("admin.example.com"), "/users/{id}"))
+            .is_ok());
+        assert!(matcher.add_route(route("any", None, "/users/{id}")).is_ok());
+
+        // Host and path parameters must not share a name
+        let result = matcher.add_route(route("dup", Some("{id}.example.com"), "/posts/{id}"));
+        assert!(matches!(result, Err(RouteMatchError::PatternError(_))));
+    }
+
     #[test]
     fn test_matcher_builder() {
         let matcher = RouteMatcherBuilder::new()
@@ -583,6 +801,7 @@ mod tests {
                 id: "user_by_id".to_string(),
                 method: HttpMethod::GET,
                 path: "/users/{id:int}".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -606,6 +825,7 @@ mod tests {
                 id: "api_status".to_string(),
                 method: HttpMethod::GET,
                 path: "/api/status".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -614,6 +834,7 @@ mod tests {
                 id: "api_user".to_string(),
                 method: HttpMethod::GET,
                 path: "/api/users/{id}".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -622,6 +843,7 @@ mod tests {
                 id: "root".to_string(),
                 method: HttpMethod::GET,
                 path: "/".to_string(),
+                host: None,
             })
             .unwrap();
 
@@ -640,6 +862,21 @@ mod tests {
         assert_eq!(route_match.params.get("id"), Some(&"456".to_string()));
     }
 
+    #[test]
+    fn test_allowed_methods() {
+        let matcher = RouteMatcherBuilder::new()
+            .get("show".to_string(), "/users/{id}".to_string())
+            .delete("destroy".to_string(), "/users/{id}".to_string())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            matcher.allowed_methods(None, "/users/5"),
+            vec![HttpMethod::GET, HttpMethod::DELETE, HttpMethod::HEAD]
+        );
+        assert!(matcher.allowed_methods(None, "/posts/5").is_empty());
+    }
+
     #[test]
     fn test_no_match() {
         let matcher = RouteMatcherBuilder::new()
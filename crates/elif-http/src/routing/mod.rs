@@ -18,9 +18,13 @@ pub mod versioned;
 // New framework-independent routing engine
 pub mod compiler;
 pub mod diagnostics;
+pub mod domain;
 pub mod extraction;
+pub mod limits;
 pub mod matcher;
 pub mod pattern;
+pub mod signed;
+mod table;
 
 // Legacy exports (for backward compatibility)
 pub use group::{GroupBuilder, RouteGroup};
@@ -36,9 +40,12 @@ pub use compiler::{
     CompilableRoute, CompilationResult, CompilationStats, RouteCompiler, RouteCompilerBuilder,
 };
 pub use diagnostics::{CliDiagnosticsFormatter, RouteDiagnostics};
+pub use domain::HostParams;
 pub use extraction::{ExtractedParams, ExtractionError, ParameterExtractor, TypedExtractorBuilder};
+pub use limits::RouteLimits;
 pub use matcher::{MatcherStats, RouteDefinition, RouteMatcher, RouteMatcherBuilder, RouteMatchError};
-pub use pattern::{CompiledRoute, ParamConstraint, PathSegment, RouteMatch, RoutePattern};
+pub use pattern::{CompiledRoute, HostPattern, ParamConstraint, PathSegment, RouteMatch, RoutePattern};
+pub use signed::{SignatureError, UrlSigner};
 
 use axum::http::Method;
 use serde::{Deserialize, Serialize};
@@ -58,6 +65,18 @@ pub enum HttpMethod {
 }
 
 impl HttpMethod {
+    /// All supported methods
+    pub const ALL: [HttpMethod; 8] = [
+        HttpMethod::GET,
+        HttpMethod::POST,
+        HttpMethod::PUT,
+        HttpMethod::DELETE,
+        HttpMethod::PATCH,
+        HttpMethod::HEAD,
+        HttpMethod::OPTIONS,
+        HttpMethod::TRACE,
+    ];
+
     /// Get the string representation of the HTTP method
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -115,9 +134,15 @@ impl From<HttpMethod> for Method {
 pub struct RouteInfo {
     pub name: Option<String>,
     pub path: String,
+    /// Host pattern the route is restricted to, e.g. `{tenant}.example.com`
+    #[serde(default)]
+    pub host: Option<String>,
     pub method: HttpMethod,
     pub params: Vec<String>,
     pub group: Option<String>,
+    /// Timeout, body size and concurrency limits declared for the route
+    #[serde(default)]
+    pub limits: RouteLimits,
 }
 
 /// Route registry for managing all registered routes
@@ -344,6 +344,147 @@ impl RoutePattern {
     }
 }
 
+/// Parsed host pattern such as `{tenant}.example.com`
+///
+/// Hosts are matched label by label, case-insensitively and ignoring any port. Labels
+/// use the same `{name}` / `{name:constraint}` syntax as path segments; catch-all labels
+/// are not supported.
+#[derive(Debug, Clone)]
+pub struct HostPattern {
+    /// The original host string
+    pub original_host: String,
+    /// Parsed host labels, left to right
+    pub segments: Vec<PathSegment>,
+    /// Parameter names in order
+    pub param_names: Vec<String>,
+}
+
+impl HostPattern {
+    /// Parse a host pattern from a host string
+    pub fn parse(host: &str) -> Result<Self, RoutePatternError> {
+        let host = host.trim().trim_end_matches('.');
+        if host.is_empty() {
+            return Err(RoutePatternError::InvalidSyntax(
+                "Host pattern cannot be empty".to_string(),
+            ));
+        }
+
+        let mut segments = Vec::new();
+        let mut param_names = Vec::new();
+
+        for label in host.split('.') {
+            if label.starts_with('{') && label.ends_with('}') {
+                let (name, constraint) =
+                    RoutePattern::parse_parameter_definition(&label[1..label.len() - 1])?;
+
+                if param_names.contains(&name) {
+                    return Err(RoutePatternError::DuplicateParameter(name));
+                }
+
+                param_names.push(name.clone());
+                segments.push(PathSegment::Parameter { name, constraint });
+            } else if label.is_empty() || label.contains(['{', '}', '*', '/', ':']) {
+                return Err(RoutePatternError::InvalidSyntax(format!(
+                    "Invalid host label '{}' in '{}'",
+                    label, host
+                )));
+            } else {
+                segments.push(PathSegment::Static(label.to_ascii_lowercase()));
+            }
+        }
+
+        Ok(HostPattern {
+            original_host: host.to_string(),
+            segments,
+            param_names,
+        })
+    }
+
+    /// Split a request host into lowercase labels, dropping any port
+    fn labels(host: &str) -> Vec<String> {
+        crate::request::strip_port(host.trim())
+            .trim_end_matches('.')
+            .split('.')
+            .map(|label| label.to_ascii_lowercase())
+            .collect()
+    }
+
+    /// Check if this pattern matches a given host
+    pub fn matches(&self, host: &str) -> bool {
+        let labels = Self::labels(host);
+        labels.len() == self.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(&labels)
+                .all(|(segment, label)| match segment {
+                    PathSegment::Static(expected) => expected == label,
+                    PathSegment::Parameter { constraint, .. } => constraint.validate(label),
+                    PathSegment::CatchAll { .. } => false,
+                })
+    }
+
+    /// Extract parameter values from a host that matches this pattern
+    pub fn extract_params(&self, host: &str) -> HashMap<String, String> {
+        self.segments
+            .iter()
+            .zip(Self::labels(host))
+            .filter_map(|(segment, label)| match segment {
+                PathSegment::Parameter { name, .. } => Some((name.clone(), label)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Calculate priority for host matching (lower = higher priority)
+    ///
+    /// Uses the same weights as [`RoutePattern::priority`], so `admin.example.com`
+    /// is preferred over `{tenant}.example.com`.
+    pub fn priority(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Static(_) => 1,
+                PathSegment::Parameter { constraint, .. } => match constraint {
+                    ParamConstraint::Int | ParamConstraint::Uuid => 5,
+                    ParamConstraint::Custom(_) => 6,
+                    ParamConstraint::Alpha | ParamConstraint::Slug => 8,
+                    ParamConstraint::None => 10,
+                },
+                PathSegment::CatchAll { .. } => 100,
+            })
+            .sum()
+    }
+
+    /// Check if this host pattern has no parameters
+    pub fn is_static(&self) -> bool {
+        self.param_names.is_empty()
+    }
+
+    /// Check if two host patterns are structurally identical and would match the same hosts
+    pub fn conflicts_with(&self, other: &HostPattern) -> bool {
+        segments_conflict(&self.segments, &other.segments)
+    }
+}
+
+/// Check if two segment lists are structurally identical: same length, and each pair is
+/// the same static value, parameters with the same constraint, or both catch-all
+pub(crate) fn segments_conflict(segments1: &[PathSegment], segments2: &[PathSegment]) -> bool {
+    segments1.len() == segments2.len()
+        && segments1
+            .iter()
+            .zip(segments2)
+            .all(|(seg1, seg2)| match (seg1, seg2) {
+                (PathSegment::Static(s1), PathSegment::Static(s2)) => s1 == s2,
+                (
+                    PathSegment::Parameter { constraint: c1, .. },
+                    PathSegment::Parameter { constraint: c2, .. },
+                ) => c1 == c2,
+                (PathSegment::CatchAll { .. }, PathSegment::CatchAll { .. }) => true,
+                _ => false,
+            })
+}
+
 /// Unique identifier for a route
 pub type RouteId = String;
 
@@ -361,6 +502,8 @@ pub struct CompiledRoute {
     pub method: HttpMethod,
     pub pattern: RoutePattern,
     pub priority: usize,
+    /// Host the route is restricted to, matches any host when absent
+    pub host: Option<HostPattern>,
 }
 
 impl CompiledRoute {
@@ -371,18 +514,45 @@ impl CompiledRoute {
             method,
             pattern,
             priority,
+            host: None,
         }
     }
 
+    /// Restrict this route to hosts matching a pattern
+    pub fn with_host(mut self, host: HostPattern) -> Self {
+        self.host = Some(host);
+        self
+    }
+
     /// Check if this route matches the given method and path
     pub fn matches(&self, method: &HttpMethod, path: &str) -> bool {
         self.method == *method && self.pattern.matches(path)
     }
 
+    /// Check if this route matches the given method, host and path
+    pub fn matches_host(&self, method: &HttpMethod, host: Option<&str>, path: &str) -> bool {
+        let host_matches = match (&self.host, host) {
+            (None, _) => true,
+            (Some(pattern), Some(host)) => pattern.matches(host),
+            (Some(_), None) => false,
+        };
+        host_matches && self.matches(method, path)
+    }
+
     /// Extract parameters from a matching path
     pub fn extract_params(&self, path: &str) -> HashMap<String, String> {
         self.pattern.extract_params(path)
     }
+
+    /// Extract host and path parameters from a matching request
+    pub fn extract_host_params(&self, host: Option<&str>, path: &str) -> HashMap<String, String> {
+        let mut params = match (&self.host, host) {
+            (Some(pattern), Some(host)) => pattern.extract_params(host),
+            _ => HashMap::new(),
+        };
+        params.extend(self.extract_params(path));
+        params
+    }
 }
 
 #[cfg(test)]
@@ -569,4 +739,54 @@ mod tests {
         assert!(!route.matches(&HttpMethod::POST, "/users/123")); // Wrong method
         assert!(!route.matches(&HttpMethod::GET, "/users/abc")); // Constraint violation
     }
+
+    #[test]
+    fn test_host_pattern_matching() {
+        let pattern = HostPattern::parse("{tenant:slug}.Example.com").unwrap();
+        assert_eq!(pattern.param_names, vec!["tenant"]);
+        assert!(!pattern.is_static());
+
+        assert!(pattern.matches("acme.example.com"));
+        assert!(pattern.matches("ACME.example.com:8080"));
+        assert!(!pattern.matches("example.com"));
+        assert!(!pattern.matches("a.b.example.com"));
+        assert!(!pattern.matches("acme.example.org"));
+
+        let params = pattern.extract_params("Acme.example.com:8080");
+        assert_eq!(params.get("tenant"), Some(&"acme".to_string()));
+
+        let admin = HostPattern::parse("admin.example.com").unwrap();
+        assert!(admin.is_static());
+        assert!(admin.priority() < pattern.priority());
+
+        assert!(HostPattern::parse("{a}.{a}.example.com").is_err());
+        assert!(HostPattern::parse("*.example.com").is_err());
+        assert!(HostPattern::parse("example..com").is_err());
+        assert!(HostPattern::parse("").is_err());
+    }
+
+    #[test]
+    fn test_host_pattern_conflicts() {
+        let tenant = HostPattern::parse("{tenant}.example.com").unwrap();
+        let account = HostPattern::parse("{account}.example.com").unwrap();
+        let admin = HostPattern::parse("admin.example.com").unwrap();
+
+        assert!(tenant.conflicts_with(&account));
+        assert!(!tenant.conflicts_with(&admin));
+    }
+
+    #[test]
+    fn test_compiled_route_host_matching() {
+        let pattern = RoutePattern::parse("/dashboard/{id:int}").unwrap();
+        let route = CompiledRoute::new("test".to_string(), HttpMethod::GET, pattern)
+            .with_host(HostPattern::parse("{tenant}.example.com").unwrap());
+
+        assert!(route.matches_host(&HttpMethod::GET, Some("acme.example.com"), "/dashboard/1"));
+        assert!(!route.matches_host(&HttpMethod::GET, Some("example.com"), "/dashboard/1"));
+        assert!(!route.matches_host(&HttpMethod::GET, None, "/dashboard/1"));
+
+        let params = route.extract_host_params(Some("acme.example.com"), "/dashboard/1");
+        assert_eq!(params.get("tenant"), Some(&"acme".to_string()));
+        assert_eq!(params.get("id"), Some(&"1".to_string()));
+    }
 }
@@ -1,21 +1,29 @@
 //! Core routing functionality
 
-use super::{HttpMethod, RouteInfo, RouteRegistry};
+use super::domain::{host_dispatch_router, DomainRoutes};
+use super::pattern::HostPattern;
+use super::signed::UrlSigner;
+use super::table::RouteTable;
+use super::{HttpMethod, RouteInfo, RouteLimits, RouteRegistry};
 use crate::controller::{factory::IocControllable, ElifController};
-use crate::errors::HttpResult;
+use crate::errors::{HttpError, HttpResult};
 use crate::handlers::elif_handler;
+use crate::metrics::{HttpMetrics, MetricsRegistry};
+use crate::middleware::utils::TrustedProxyMiddleware;
 use crate::middleware::v2::{Middleware, MiddlewarePipelineV2};
 use crate::request::ElifRequest;
-use crate::response::{ElifResponse, IntoElifResponse};
+use crate::response::{ElifResponse, ElifStatusCode, IntoElifResponse, StaticFiles};
 use axum::{
-    routing::{delete, get, patch, post, put},
+    routing::{any, delete, get, head, on, options, patch, post, put, MethodFilter},
     Router as AxumRouter,
 };
 use elif_core::container::IocContainer;
 use std::collections::HashMap;
 use std::future::Future;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Main router for the elif.rs framework
 #[derive(Debug)]
@@ -31,6 +39,10 @@ where
     route_middleware: HashMap<String, Vec<String>>, // route_id -> middleware group names
     controller_registry: Arc<Mutex<ControllerRegistry>>,
     ioc_container: Option<Arc<IocContainer>>,
+    domains: Vec<DomainRoutes<S>>,
+    routes: RouteTable,
+    url_signer: Option<UrlSigner>,
+    trusted_proxies: Option<TrustedProxyMiddleware>,
 }
 
 impl<S> Router<S>
@@ -48,6 +60,10 @@ where
             route_middleware: HashMap::new(),
             controller_registry: Arc::new(Mutex::new(ControllerRegistry::new())),
             ioc_container: None,
+            domains: Vec::new(),
+            routes: RouteTable::default(),
+            url_signer: None,
+            trusted_proxies: None,
         }
     }
 
@@ -62,6 +78,10 @@ where
             route_middleware: HashMap::new(),
             controller_registry: Arc::new(Mutex::new(ControllerRegistry::new())),
             ioc_container: None,
+            domains: Vec::new(),
+            routes: RouteTable::default(),
+            url_signer: None,
+            trusted_proxies: None,
         }
     }
 
@@ -73,7 +93,13 @@ where
     }
 
     /// Register a route with the registry
-    fn register_route(&self, method: HttpMethod, path: &str, name: Option<String>) -> String {
+    fn register_route(
+        &self,
+        method: HttpMethod,
+        path: &str,
+        name: Option<String>,
+        limits: &RouteLimits,
+    ) -> String {
         let route_id = self.next_route_id();
         let params = self.extract_param_names(path);
 
@@ -83,6 +109,8 @@ where
             method,
             params,
             group: None, // TODO: Support groups
+            host: None,
+            limits: limits.clone(),
         };
 
         self.registry
@@ -132,9 +160,35 @@ where
 
     /// Private helper method to add routes with less duplication
     fn add_route<F, Fut, R, M>(
+        self,
+        methods: &[HttpMethod],
+        path: &str,
+        handler: F,
+        method_router_fn: M,
+    ) -> Self
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+        M: FnOnce(
+            crate::handlers::handler::ElifHandlerWrapper<F, Fut, R>,
+        ) -> axum::routing::MethodRouter<S>,
+    {
+        self.add_limited_route(
+            methods,
+            path,
+            &RouteLimits::default(),
+            handler,
+            method_router_fn,
+        )
+    }
+
+    /// Add a route enforcing the given limits
+    fn add_limited_route<F, Fut, R, M>(
         mut self,
-        method: HttpMethod,
+        methods: &[HttpMethod],
         path: &str,
+        limits: &RouteLimits,
         handler: F,
         method_router_fn: M,
     ) -> Self
@@ -146,9 +200,13 @@ where
             crate::handlers::handler::ElifHandlerWrapper<F, Fut, R>,
         ) -> axum::routing::MethodRouter<S>,
     {
-        self.register_route(method, path, None);
-        let method_router = method_router_fn(elif_handler(handler));
-        self.axum_router = self.axum_router.route(path, method_router);
+        for method in methods {
+            self.register_route(method.clone(), path, None, limits);
+        }
+        self.routes.insert(methods, path, limits);
+        self.axum_router = self
+            .axum_router
+            .route(path, method_router_fn(elif_handler(handler)));
         self
     }
 
@@ -159,7 +217,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_route(HttpMethod::GET, path, handler, get)
+        self.add_route(&[HttpMethod::GET], path, handler, get)
     }
 
     /// Add a POST route with elif handler
@@ -169,7 +227,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_route(HttpMethod::POST, path, handler, post)
+        self.add_route(&[HttpMethod::POST], path, handler, post)
     }
 
     /// Add a PUT route with elif handler
@@ -179,7 +237,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_route(HttpMethod::PUT, path, handler, put)
+        self.add_route(&[HttpMethod::PUT], path, handler, put)
     }
 
     /// Add a DELETE route with elif handler
@@ -189,7 +247,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_route(HttpMethod::DELETE, path, handler, delete)
+        self.add_route(&[HttpMethod::DELETE], path, handler, delete)
     }
 
     /// Add a PATCH route with elif handler
@@ -199,7 +257,111 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_route(HttpMethod::PATCH, path, handler, patch)
+        self.add_route(&[HttpMethod::PATCH], path, handler, patch)
+    }
+
+    /// Add a HEAD route with elif handler
+    ///
+    /// `HEAD` requests are answered by `GET` routes automatically; use this to handle them
+    /// separately.
+    pub fn head<F, Fut, R>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        self.add_route(&[HttpMethod::HEAD], path, handler, head)
+    }
+
+    /// Add an OPTIONS route with elif handler, replacing the automatic `OPTIONS` response
+    pub fn options<F, Fut, R>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        self.add_route(&[HttpMethod::OPTIONS], path, handler, options)
+    }
+
+    /// Add a route answering every HTTP method
+    pub fn any<F, Fut, R>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        self.add_route(&HttpMethod::ALL, path, handler, any)
+    }
+
+    /// Add a route answering the given HTTP methods
+    ///
+    /// Fails if `methods` is empty.
+    pub fn match_methods<F, Fut, R>(
+        self,
+        methods: &[HttpMethod],
+        path: &str,
+        handler: F,
+    ) -> HttpResult<Self>
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        let filter = method_filter(methods)?;
+        Ok(self.add_route(methods, path, handler, |handler| on(filter, handler)))
+    }
+
+    /// Handle requests that match no route, e.g. to render a custom 404 page
+    ///
+    /// Requests whose path matches a route registered for other methods still receive
+    /// `405 Method Not Allowed`. Merging two routers that both define a fallback panics.
+    pub fn fallback<F, Fut, R>(mut self, handler: F) -> Self
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        self.axum_router = self.axum_router.fallback(elif_handler(handler));
+        self
+    }
+
+    /// Serve files from a directory under a path prefix
+    ///
+    /// ```rust,no_run
+    /// # use elif_http::routing::ElifRouter;
+    /// let router = ElifRouter::<()>::new().static_dir("/assets", "public");
+    /// ```
+    pub fn static_dir<P: Into<PathBuf>>(self, prefix: &str, root: P) -> Self {
+        self.static_files(prefix, StaticFiles::new(root))
+    }
+
+    /// Serve files under a path prefix with a configured static file service
+    pub fn static_files(self, prefix: &str, files: StaticFiles) -> Self {
+        let prefix = prefix.trim_end_matches('/').to_string();
+        let files = Arc::new(files);
+        let handler = {
+            let prefix = prefix.clone();
+            move |request: ElifRequest| {
+                let files = Arc::clone(&files);
+                let prefix = prefix.clone();
+                async move {
+                    let path = request.path().strip_prefix(prefix.as_str()).unwrap_or("");
+                    files.serve(&request, path).await
+                }
+            }
+        };
+
+        let router = if prefix.is_empty() {
+            self
+        } else {
+            self.add_route(&[HttpMethod::GET], &prefix, handler.clone(), get)
+        };
+        router.add_route(
+            &[HttpMethod::GET],
+            &format!("{}/*path", prefix),
+            handler,
+            get,
+        )
     }
 
     /// Register a controller with automatic route registration
@@ -217,12 +379,24 @@ where
             let handler =
                 controller_handler(Arc::clone(&controller_arc), route.handler_name.clone());
 
+            let methods = [route.method.clone()];
+            let limits = &route.limits;
             self = match route.method {
-                HttpMethod::GET => self.get(&full_path, handler),
-                HttpMethod::POST => self.post(&full_path, handler),
-                HttpMethod::PUT => self.put(&full_path, handler),
-                HttpMethod::DELETE => self.delete(&full_path, handler),
-                HttpMethod::PATCH => self.patch(&full_path, handler),
+                HttpMethod::GET => {
+                    self.add_limited_route(&methods, &full_path, limits, handler, get)
+                }
+                HttpMethod::POST => {
+                    self.add_limited_route(&methods, &full_path, limits, handler, post)
+                }
+                HttpMethod::PUT => {
+                    self.add_limited_route(&methods, &full_path, limits, handler, put)
+                }
+                HttpMethod::DELETE => {
+                    self.add_limited_route(&methods, &full_path, limits, handler, delete)
+                }
+                HttpMethod::PATCH => {
+                    self.add_limited_route(&methods, &full_path, limits, handler, patch)
+                }
                 _ => {
                     // For unsupported HTTP methods, we'll skip for now
                     // This can be extended to support more methods
@@ -428,6 +602,14 @@ where
 
         // Merge the underlying Axum routers
         self.axum_router = self.axum_router.merge(other.axum_router);
+        self.domains.extend(other.domains);
+        self.routes.extend(other.routes);
+        if self.trusted_proxies.is_none() {
+            self.trusted_proxies = other.trusted_proxies;
+        }
+        if self.url_signer.is_none() {
+            self.url_signer = other.url_signer;
+        }
         self
     }
 
@@ -462,51 +644,151 @@ where
 
         // Apply nested router's global middleware as a layer before nesting
         // This ensures the middleware only applies to the nested routes
-        let has_nested_middleware = !router.middleware_stack.is_empty();
-        let nested_middleware = router.middleware_stack.clone();
-        let nested_axum_router = router.axum_router;
-
-        let nested_axum_router = if has_nested_middleware {
-            use axum::extract::Request;
-            use axum::middleware::from_fn;
-            use axum::middleware::Next;
-
-            // Create a layer from the nested router's middleware pipeline
-            nested_axum_router.layer(from_fn(move |req: Request, next: Next| {
-                let pipeline = nested_middleware.clone();
-                async move {
-                    // Convert axum request to ElifRequest
-                    let elif_req = crate::request::ElifRequest::from_axum_request(req).await;
-
-                    // Execute middleware pipeline
-                    let response = pipeline
-                        .execute(elif_req, |req| {
-                            Box::pin(async move {
-                                // Convert back to axum request for next handler
-                                let axum_req = req.into_axum_request();
-                                let axum_response = next.run(axum_req).await;
-                                // Convert axum response to ElifResponse
-                                crate::response::ElifResponse::from_axum_response(axum_response)
-                                    .await
-                            })
-                        })
-                        .await;
-
-                    // Convert ElifResponse back to axum response
-                    response.into_axum_response()
+        let nested_middleware = router.middleware_stack;
+        let nested_axum_router = with_pipeline_layer(router.axum_router, &nested_middleware);
+
+        // Domain routes of the nested router keep their host and gain the path prefix
+        self.domains
+            .extend(router.domains.into_iter().map(|domain| {
+                let mut routes = RouteTable::default();
+                routes.extend_nested(path, domain.routes);
+                DomainRoutes {
+                    host: domain.host,
+                    router: AxumRouter::new()
+                        .nest(path, with_pipeline_layer(domain.router, &nested_middleware)),
+                    routes,
                 }
-            }))
-        } else {
-            nested_axum_router
-        };
+            }));
 
+        self.routes.extend_nested(path, router.routes);
         self.axum_router = self.axum_router.nest(path, nested_axum_router);
         self
     }
 
+    /// Register routes that only answer requests for a host pattern
+    ///
+    /// Host labels may be parameters, e.g. `{tenant}.example.com`; their values are
+    /// available as path parameters and through [`ElifRequest::host_param`]. Requests
+    /// for other hosts, or paths the domain doesn't define, fall through to the
+    /// host-independent routes. Fails if the host pattern is invalid.
+    pub fn domain<F>(mut self, host: &str, routes: F) -> HttpResult<Self>
+    where
+        F: FnOnce(Router<S>) -> Router<S>,
+    {
+        let pattern = HostPattern::parse(host)
+            .map_err(|e| HttpError::config(format!("Invalid domain pattern '{}': {}", host, e)))?;
+
+        let mut child = Router::new();
+        child.ioc_container = self.ioc_container.clone();
+        let child = routes(child);
+
+        // Register the domain's routes with their host and host parameters
+        if let (Ok(mut self_registry), Ok(child_registry)) =
+            (self.registry.lock(), child.registry.lock())
+        {
+            for route_info in child_registry.all_routes().values() {
+                let mut route_info = route_info.clone();
+                let mut params = pattern.param_names.clone();
+                params.append(&mut route_info.params);
+                route_info.params = params;
+                route_info.host = Some(pattern.original_host.clone());
+
+                let new_id = self.next_route_id();
+                self_registry.register(new_id, route_info);
+            }
+        }
+
+        self.middleware_groups.extend(child.middleware_groups);
+        self.route_middleware.extend(child.route_middleware);
+
+        if let Ok(child_controller_registry) = child.controller_registry.lock() {
+            let controllers_to_merge: Vec<_> = child_controller_registry
+                .all_controllers()
+                .map(|(name, controller)| (name.clone(), Arc::clone(controller)))
+                .collect();
+            drop(child_controller_registry);
+
+            if let Ok(mut self_controller_registry) = self.controller_registry.lock() {
+                for (name, controller) in controllers_to_merge {
+                    self_controller_registry.register(name, controller);
+                }
+            }
+        }
+
+        // Domains declared inside a domain are ignored in favour of the enclosing host
+        self.domains.push(DomainRoutes {
+            host: pattern,
+            router: with_pipeline_layer(child.axum_router, &child.middleware_stack),
+            routes: child.routes,
+        });
+        Ok(self)
+    }
+
+    /// Match domain routes against the host forwarded by these trusted proxies
+    ///
+    /// Domains are chosen before the middleware runs, so a [`TrustedProxyMiddleware`] in
+    /// the middleware stack doesn't affect them.
+    pub fn with_trusted_proxies(mut self, proxies: TrustedProxyMiddleware) -> Self {
+        self.trusted_proxies = Some(proxies);
+        self
+    }
+
     /// Get the underlying Axum router
+    ///
+    /// The global middleware runs around every route. Route limits are enforced before
+    /// it, so they take precedence over a global timeout or body size limit.
+    ///
+    /// `OPTIONS` requests to paths without an explicit `OPTIONS` route are answered with
+    /// `204 No Content` and an `Allow` header listing the methods the path supports.
     pub fn into_axum_router(self) -> AxumRouter<S> {
-        self.axum_router
+        let middleware = self.middleware_stack;
+        let routes = Arc::new(self.routes);
+        let default = with_route_limits(
+            with_pipeline_layer(self.axum_router, &middleware),
+            Arc::clone(&routes),
+        );
+        if self.domains.is_empty() {
+            return with_automatic_options(default, Some(routes));
+        }
+
+        let domains = self
+            .domains
+            .into_iter()
+            .map(|domain| DomainRoutes {
+                host: domain.host,
+                router: with_route_limits(
+                    with_pipeline_layer(domain.router, &middleware),
+                    Arc::new(domain.routes),
+                ),
+                routes: RouteTable::default(),
+            })
+            .collect();
+
+        // Dispatching through an outer router lets the automatic OPTIONS layer see final
+        // responses, including the `Allow` header Axum adds outside of route layers
+        with_automatic_options(
+            host_dispatch_router(default, domains, self.trusted_proxies),
+            None,
+        )
+    }
+
+    /// Record request count, errors and latency of the routes added so far
+    ///
+    /// Routes are labelled by name when they have one, otherwise by path pattern; see
+    /// [`HttpMetrics`]. Call it after adding routes and nesting routers.
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        let metrics = HttpMetrics::new(metrics, &self.registry.lock().unwrap());
+        let layer = axum::middleware::from_fn(metrics.layer_fn());
+        self.axum_router = self.axum_router.layer(layer.clone());
+        self.domains = self
+            .domains
+            .into_iter()
+            .map(|domain| DomainRoutes {
+                router: domain.router.layer(layer.clone()),
+                ..domain
+            })
+            .collect();
+        self
     }
 
     /// Get route registry for introspection
@@ -517,17 +799,50 @@ where
     /// Generate URL for a named route
     pub fn url_for(&self, name: &str, params: &HashMap<String, String>) -> Option<String> {
         let registry = self.registry.lock().unwrap();
-        if let Some(route) = registry.get_by_name(name) {
-            let mut url = route.path.clone();
+        let route = registry.get_by_name(name)?;
+        let substitute = |template: &str| {
+            let mut url = template.to_string();
             for (key, value) in params {
                 url = url.replace(&format!("{{{}}}", key), value);
             }
-            Some(url)
-        } else {
-            None
+            url
+        };
+
+        match &route.host {
+            // Scheme-relative so the URL works behind both HTTP and HTTPS
+            Some(host) => Some(format!("//{}{}", substitute(host), substitute(&route.path))),
+            None => Some(substitute(&route.path)),
         }
     }
 
+    /// Sign URLs with the given signer instead of one built from `APP_KEY`
+    pub fn with_url_signer(mut self, signer: UrlSigner) -> Self {
+        self.url_signer = Some(signer);
+        self
+    }
+
+    /// Generate a tamper-proof URL for a named route, optionally expiring at a given time
+    ///
+    /// The URL is signed with the application key (`APP_KEY`) unless a signer was set with
+    /// [`Self::with_url_signer`]; requests are checked by the `ValidateSignature` middleware.
+    pub fn signed_url_for(
+        &self,
+        name: &str,
+        params: &HashMap<String, String>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> HttpResult<String> {
+        let url = self
+            .url_for(name, params)
+            .ok_or_else(|| HttpError::not_found(format!("Route '{}'", name)))?;
+
+        let signer = match &self.url_signer {
+            Some(signer) => signer.clone(),
+            None => UrlSigner::from_env()
+                .map_err(|e| HttpError::config(format!("Cannot sign URLs: {}", e)))?,
+        };
+        Ok(signer.sign(&url, expires_at))
+    }
+
     /// Get the global middleware pipeline
     pub fn middleware_pipeline(&self) -> &MiddlewarePipelineV2 {
         &self.middleware_stack
@@ -573,6 +888,185 @@ where
     }
 }
 
+/// Axum method filter matching any of the given methods
+fn method_filter(methods: &[HttpMethod]) -> HttpResult<MethodFilter> {
+    let filter = |method: &HttpMethod| match method {
+        HttpMethod::GET => MethodFilter::GET,
+        HttpMethod::POST => MethodFilter::POST,
+        HttpMethod::PUT => MethodFilter::PUT,
+        HttpMethod::DELETE => MethodFilter::DELETE,
+        HttpMethod::PATCH => MethodFilter::PATCH,
+        HttpMethod::HEAD => MethodFilter::HEAD,
+        HttpMethod::OPTIONS => MethodFilter::OPTIONS,
+        HttpMethod::TRACE => MethodFilter::TRACE,
+    };
+
+    let (first, rest) = methods
+        .split_first()
+        .ok_or_else(|| HttpError::config("match_methods requires at least one HTTP method"))?;
+    Ok(rest.iter().fold(filter(first), |combined, method| {
+        combined.or(filter(method))
+    }))
+}
+
+/// Answer `OPTIONS` requests that no route handles with the methods the path allows
+///
+/// Axum responds `405 Method Not Allowed` when a path exists but the method doesn't; for
+/// `OPTIONS` that becomes `204 No Content`. The allowed methods come from the response's
+/// `Allow` header, or from `routes` when the layer wraps the routes themselves and runs
+/// before Axum adds that header.
+fn with_automatic_options<S>(
+    router: AxumRouter<S>,
+    routes: Option<Arc<RouteTable>>,
+) -> AxumRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    use axum::middleware::{from_fn, Next};
+
+    let layer = move |request: axum::extract::Request, next: Next| {
+        let routes = routes.clone();
+        async move {
+            let is_options = request.method().as_str() == HttpMethod::OPTIONS.as_str();
+            let matched_path = request
+                .extensions()
+                .get::<axum::extract::MatchedPath>()
+                .map(|path| path.as_str().to_string());
+            let mut response = next.run(request).await;
+
+            if is_options && response.status() == ElifStatusCode::METHOD_NOT_ALLOWED.to_axum() {
+                let header = response
+                    .headers()
+                    .get(axum::http::header::ALLOW)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| {
+                        value
+                            .split(',')
+                            .filter_map(|method| {
+                                HttpMethod::ALL
+                                    .iter()
+                                    .find(|known| known.as_str() == method.trim())
+                                    .cloned()
+                            })
+                            .collect::<Vec<_>>()
+                    });
+                let allowed = header.or_else(|| {
+                    let routes = routes.as_ref()?;
+                    routes
+                        .allowed_methods(matched_path.as_deref()?)
+                        .map(<[HttpMethod]>::to_vec)
+                });
+
+                *response.status_mut() = ElifStatusCode::NO_CONTENT.to_axum();
+                *response.body_mut() = Default::default();
+                if let Some(allowed) = allowed {
+                    let allow = crate::request::pipeline::allow_header_value(&allowed);
+                    if let Ok(value) = axum::http::HeaderValue::from_str(&allow) {
+                        response
+                            .headers_mut()
+                            .insert(axum::http::header::ALLOW, value);
+                    }
+                }
+                response
+                    .headers_mut()
+                    .remove(axum::http::header::CONTENT_TYPE);
+                response
+                    .headers_mut()
+                    .remove(axum::http::header::CONTENT_LENGTH);
+            }
+
+            response
+        }
+    };
+
+    router.layer(from_fn(layer))
+}
+
+/// Apply a middleware pipeline to every route of an Axum router as a layer
+fn with_pipeline_layer<S>(router: AxumRouter<S>, pipeline: &MiddlewarePipelineV2) -> AxumRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if pipeline.is_empty() {
+        return router;
+    }
+
+    router.layer(axum::middleware::from_fn(pipeline_middleware(
+        pipeline.clone(),
+    )))
+}
+
+/// Enforce the limits of the routes of an Axum router, outside of its other layers
+///
+/// Must be added with `Router::layer` so the matched route is known.
+fn with_route_limits<S>(router: AxumRouter<S>, routes: Arc<RouteTable>) -> AxumRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if !routes.has_limits() {
+        return router;
+    }
+
+    let layer = move |request: axum::extract::Request, next: axum::middleware::Next| {
+        let routes = Arc::clone(&routes);
+        async move {
+            let limits = request
+                .extensions()
+                .get::<axum::extract::MatchedPath>()
+                .and_then(|path| routes.limits(request.method().as_str(), path.as_str()))
+                .cloned();
+            match limits {
+                Some(limits) => limits.handle_axum(request, next).await,
+                None => next.run(request).await,
+            }
+        }
+    };
+    router.layer(axum::middleware::from_fn(layer))
+}
+
+/// Axum middleware function running a middleware pipeline around the inner service
+fn pipeline_middleware(
+    pipeline: MiddlewarePipelineV2,
+) -> impl Fn(
+    axum::extract::Request,
+    axum::middleware::Next,
+) -> Pin<Box<dyn Future<Output = axum::response::Response> + Send>>
+       + Clone
+       + Send
+       + Sync
+       + 'static {
+    move |req, next| {
+        let pipeline = pipeline.clone();
+        Box::pin(async move {
+            // Convert axum request to ElifRequest
+            let elif_req = match crate::request::ElifRequest::from_axum_request(req).await {
+                Ok(request) => request,
+                Err(error) => {
+                    return crate::response::IntoElifResponse::into_response(error)
+                        .into_axum_response()
+                }
+            };
+            let error_format = elif_req.error_format();
+
+            // Execute middleware pipeline
+            let response = error_format
+                .scope(pipeline.execute(elif_req, |req| {
+                    Box::pin(async move {
+                        // Convert back to axum request for next handler
+                        let axum_req = req.into_axum_request();
+                        let axum_response = next.run(axum_req).await;
+                        // Convert axum response to ElifResponse
+                        crate::response::ElifResponse::from_axum_response(axum_response).await
+                    })
+                }))
+                .await;
+
+            // Convert ElifResponse back to axum response
+            response.into_axum_response()
+        })
+    }
+}
+
 /// Builder for creating routes with middleware groups and additional metadata
 pub struct RouteBuilder<S = ()>
 where
@@ -582,6 +1076,7 @@ where
     path: String,
     middleware_groups: Vec<String>,
     name: Option<String>,
+    limits: RouteLimits,
 }
 
 impl<S> RouteBuilder<S>
@@ -594,6 +1089,7 @@ where
             path,
             middleware_groups: Vec::new(),
             name: None,
+            limits: RouteLimits::default(),
         }
     }
 
@@ -609,10 +1105,34 @@ where
         self
     }
 
+    /// Respond with `408 Request Timeout` when the handler takes longer than `timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.limits = self.limits.timeout(timeout);
+        self
+    }
+
+    /// Reject request bodies larger than `bytes` with `413 Payload Too Large`
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.limits = self.limits.max_body_size(bytes);
+        self
+    }
+
+    /// Reject requests beyond `requests` in flight with `503 Service Unavailable`
+    pub fn max_concurrency(mut self, requests: usize) -> Self {
+        self.limits = self.limits.max_concurrency(requests);
+        self
+    }
+
+    /// Set all route limits at once
+    pub fn limits(mut self, limits: RouteLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Private helper method for RouteBuilder route registration
     fn add_method_route<F, Fut, R, M>(
         mut self,
-        method: HttpMethod,
+        methods: &[HttpMethod],
         handler: F,
         method_router_fn: M,
     ) -> Router<S>
@@ -624,14 +1144,22 @@ where
             crate::handlers::handler::ElifHandlerWrapper<F, Fut, R>,
         ) -> axum::routing::MethodRouter<S>,
     {
-        let route_id = self
+        for method in methods {
+            let route_id = self.router.register_route(
+                method.clone(),
+                &self.path,
+                self.name.clone(),
+                &self.limits,
+            );
+            self.router
+                .route_middleware
+                .insert(route_id, self.middleware_groups.clone());
+        }
+        self.router.routes.insert(methods, &self.path, &self.limits);
+        self.router.axum_router = self
             .router
-            .register_route(method, &self.path, self.name.clone());
-        self.router
-            .route_middleware
-            .insert(route_id, self.middleware_groups);
-        let method_router = method_router_fn(elif_handler(handler));
-        self.router.axum_router = self.router.axum_router.route(&self.path, method_router);
+            .axum_router
+            .route(&self.path, method_router_fn(elif_handler(handler)));
         self.router
     }
 
@@ -642,7 +1170,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_method_route(HttpMethod::GET, handler, get)
+        self.add_method_route(&[HttpMethod::GET], handler, get)
     }
 
     /// Add a POST route with elif handler
@@ -652,7 +1180,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_method_route(HttpMethod::POST, handler, post)
+        self.add_method_route(&[HttpMethod::POST], handler, post)
     }
 
     /// Add a PUT route with elif handler
@@ -662,7 +1190,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_method_route(HttpMethod::PUT, handler, put)
+        self.add_method_route(&[HttpMethod::PUT], handler, put)
     }
 
     /// Add a DELETE route with elif handler
@@ -672,7 +1200,7 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_method_route(HttpMethod::DELETE, handler, delete)
+        self.add_method_route(&[HttpMethod::DELETE], handler, delete)
     }
 
     /// Add a PATCH route with elif handler
@@ -682,7 +1210,54 @@ where
         Fut: Future<Output = HttpResult<R>> + Send + 'static,
         R: IntoElifResponse + Send + 'static,
     {
-        self.add_method_route(HttpMethod::PATCH, handler, patch)
+        self.add_method_route(&[HttpMethod::PATCH], handler, patch)
+    }
+
+    /// Add a HEAD route with elif handler
+    pub fn head<F, Fut, R>(self, handler: F) -> Router<S>
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        self.add_method_route(&[HttpMethod::HEAD], handler, head)
+    }
+
+    /// Add an OPTIONS route with elif handler
+    pub fn options<F, Fut, R>(self, handler: F) -> Router<S>
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        self.add_method_route(&[HttpMethod::OPTIONS], handler, options)
+    }
+
+    /// Add a route answering every HTTP method
+    pub fn any<F, Fut, R>(self, handler: F) -> Router<S>
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        self.add_method_route(&HttpMethod::ALL, handler, any)
+    }
+
+    /// Add a route answering the given HTTP methods
+    ///
+    /// Fails if `methods` is empty.
+    pub fn match_methods<F, Fut, R>(
+        self,
+        methods: &[HttpMethod],
+        handler: F,
+    ) -> HttpResult<Router<S>>
+    where
+        F: Fn(ElifRequest) -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = HttpResult<R>> + Send + 'static,
+        R: IntoElifResponse + Send + 'static,
+    {
+        let filter = method_filter(methods)?;
+        Ok(self.add_method_route(methods, handler, |handler| on(filter, handler)))
     }
 }
 
@@ -709,6 +1284,17 @@ mod tests {
         assert_eq!(reg.all_routes().len(), 3);
     }
 
+    #[test]
+    fn test_static_dir_registers_routes() {
+        let router = Router::<()>::new().static_dir("/assets/", "public");
+
+        let registry = router.registry();
+        let reg = registry.lock().unwrap();
+        let mut paths: Vec<_> = reg.all_routes().values().map(|r| r.path.clone()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/assets", "/assets/*path"]);
+    }
+
     #[test]
     fn test_param_extraction() {
         let router = Router::<()>::new();
@@ -729,6 +1315,8 @@ mod tests {
                 method: HttpMethod::GET,
                 params: vec!["id".to_string(), "slug".to_string()],
                 group: None,
+                host: None,
+                limits: RouteLimits::default(),
             };
             registry.register("test_route".to_string(), route_info);
         }
@@ -741,6 +1329,389 @@ mod tests {
         assert_eq!(url, Some("/users/123/posts/hello-world".to_string()));
     }
 
+    #[test]
+    fn test_domain_routes_registered_with_host() {
+        let router = Router::<()>::new()
+            .get("/dashboard", elif_handler)
+            .domain("{tenant}.example.com", |r| {
+                r.route("/dashboard")
+                    .name("tenant.dashboard")
+                    .get(elif_handler)
+            })
+            .unwrap();
+
+        let registry = router.registry.lock().unwrap();
+        let route = registry.get_by_name("tenant.dashboard").unwrap();
+        assert_eq!(route.host.as_deref(), Some("{tenant}.example.com"));
+        assert_eq!(route.params, vec!["tenant"]);
+        assert_eq!(registry.all_routes().len(), 2);
+        drop(registry);
+
+        let mut params = HashMap::new();
+        params.insert("tenant".to_string(), "acme".to_string());
+        assert_eq!(
+            router.url_for("tenant.dashboard", &params),
+            Some("//acme.example.com/dashboard".to_string())
+        );
+    }
+
+    #[test]
+    fn test_domain_rejects_invalid_pattern() {
+        assert!(Router::<()>::new()
+            .domain("{tenant}..example.com", |r| r)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_domain_dispatch() {
+        use tower::ServiceExt;
+
+        async fn tenant(req: ElifRequest) -> HttpResult<ElifResponse> {
+            Ok(ElifResponse::ok().text(format!(
+                "tenant:{}:{}",
+                req.host_param("tenant").unwrap_or_default(),
+                req.path_param("tenant")
+                    .map(String::as_str)
+                    .unwrap_or_default()
+            )))
+        }
+        async fn admin(_req: ElifRequest) -> HttpResult<ElifResponse> {
+            Ok(ElifResponse::ok().text("admin"))
+        }
+        async fn site(req: ElifRequest) -> HttpResult<ElifResponse> {
+            assert!(req.host_param("tenant").is_none());
+            Ok(ElifResponse::ok().text("site"))
+        }
+
+        let proxies = crate::config::TrustedProxies::parse(["10.0.0.0/8"]).unwrap();
+        let app = Router::<()>::new()
+            .get("/dashboard", site)
+            .get("/about", site)
+            .domain("{tenant}.example.com", |r| r.get("/dashboard", tenant))
+            .unwrap()
+            .domain("admin.example.com", |r| r.get("/dashboard", admin))
+            .unwrap()
+            .with_trusted_proxies(TrustedProxyMiddleware::new(proxies))
+            .into_axum_router();
+
+        let call_from = |peer: &'static str,
+                         headers: &[(&'static str, &'static str)],
+                         path: &'static str| {
+            let app = app.clone();
+            let mut header_map = crate::response::ElifHeaderMap::new();
+            for (name, value) in headers {
+                header_map.add_header(name, value).unwrap();
+            }
+            async move {
+                let mut request = ElifRequest::new(
+                    crate::request::ElifMethod::GET,
+                    path.parse().unwrap(),
+                    header_map,
+                );
+                request
+                    .insert_extension(crate::request::ConnectionInfo::plain(peer.parse().unwrap()));
+                let request = request.into_axum_request();
+                let response = app.oneshot(request).await.unwrap();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                String::from_utf8(body.to_vec()).unwrap()
+            }
+        };
+
+        let call = |host: &'static str, path: &'static str| {
+            call_from("203.0.113.7:5000", &[("host", host)], path)
+        };
+
+        assert_eq!(
+            call("acme.example.com", "/dashboard").await,
+            "tenant:acme:acme"
+        );
+        assert_eq!(call("admin.example.com:8080", "/dashboard").await, "admin");
+        assert_eq!(call("example.com", "/dashboard").await, "site");
+        // Paths a domain doesn't define fall through to host-independent routes
+        assert_eq!(call("acme.example.com", "/about").await, "site");
+
+        // Trusted proxies decide the host, anyone else's forwarding headers are ignored
+        let forwarded = [
+            ("host", "internal.local"),
+            ("x-forwarded-for", "198.51.100.4"),
+            ("x-forwarded-host", "admin.example.com"),
+        ];
+        assert_eq!(
+            call_from("10.0.0.2:5000", &forwarded, "/dashboard").await,
+            "admin"
+        );
+        assert_eq!(
+            call_from("203.0.113.7:5000", &forwarded, "/dashboard").await,
+            "site"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handlers_see_client_details_from_trusted_proxies() {
+        use crate::middleware::utils::TrustedProxyMiddleware;
+        use tower::ServiceExt;
+
+        async fn client(req: ElifRequest) -> HttpResult<ElifResponse> {
+            Ok(ElifResponse::ok().text(format!(
+                "{} {} {}",
+                req.client_ip().unwrap_or_default(),
+                req.is_secure(),
+                req.host().unwrap_or_default()
+            )))
+        }
+
+        let proxies = crate::config::TrustedProxies::parse(["10.0.0.0/8"]).unwrap();
+        let app = Router::<()>::new()
+            .get("/client", client)
+            .use_middleware(TrustedProxyMiddleware::new(proxies))
+            .into_axum_router();
+
+        let call_from = |peer: &'static str| {
+            let app = app.clone();
+            async move {
+                let mut headers = crate::response::ElifHeaderMap::new();
+                headers.add_header("host", "internal.local").unwrap();
+                headers
+                    .add_header("x-forwarded-for", "198.51.100.4")
+                    .unwrap();
+                headers.add_header("x-forwarded-proto", "https").unwrap();
+                headers
+                    .add_header("x-forwarded-host", "shop.example.com")
+                    .unwrap();
+                let mut request = ElifRequest::new(
+                    crate::request::ElifMethod::GET,
+                    "/client".parse().unwrap(),
+                    headers,
+                );
+                request
+                    .insert_extension(crate::request::ConnectionInfo::plain(peer.parse().unwrap()));
+                let response = app.oneshot(request.into_axum_request()).await.unwrap();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                String::from_utf8(body.to_vec()).unwrap()
+            }
+        };
+
+        assert_eq!(
+            call_from("10.0.0.2:5000").await,
+            "198.51.100.4 true shop.example.com"
+        );
+        assert_eq!(
+            call_from("203.0.113.7:5000").await,
+            "203.0.113.7 false internal.local"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_limits() {
+        use tower::ServiceExt;
+
+        let router = Router::<()>::new()
+            .route("/upload")
+            .name("upload")
+            .max_body_size(8)
+            .timeout(Duration::from_secs(30))
+            .post(elif_handler)
+            .post("/comments", elif_handler);
+
+        {
+            let registry = router.registry.lock().unwrap();
+            let upload = registry.get_by_name("upload").unwrap();
+            assert_eq!(upload.limits.max_body_size, Some(8));
+            assert_eq!(upload.limits.timeout, Some(Duration::from_secs(30)));
+            assert!(registry
+                .all_routes()
+                .values()
+                .any(|route| route.path == "/comments" && route.limits.is_empty()));
+        }
+
+        let app = router.into_axum_router();
+        let status = |path: &'static str, body: &'static [u8]| {
+            let app = app.clone();
+            async move {
+                let request = ElifRequest::new(
+                    crate::request::ElifMethod::POST,
+                    path.parse().unwrap(),
+                    crate::response::ElifHeaderMap::new(),
+                )
+                .with_body(body.into())
+                .into_axum_request();
+                app.oneshot(request).await.unwrap().status().as_u16()
+            }
+        };
+
+        assert_eq!(status("/upload", b"tiny").await, 200);
+        assert_eq!(status("/upload", b"much too large").await, 413);
+        assert_eq!(status("/comments", b"much too large").await, 200);
+    }
+
+    #[tokio::test]
+    async fn test_route_limits_override_global_limits() {
+        use crate::middleware::utils::{BodyLimitMiddleware, TimeoutMiddleware};
+        use tower::ServiceExt;
+
+        async fn slow(_req: ElifRequest) -> HttpResult<ElifResponse> {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(ElifResponse::ok())
+        }
+
+        let app = Router::<()>::new()
+            .use_middleware(BodyLimitMiddleware::with_limit(1024))
+            .use_middleware(TimeoutMiddleware::with_duration(Duration::from_millis(20)))
+            .route("/upload")
+            .max_body_size(10 * 1024 * 1024)
+            .post(elif_handler)
+            .route("/reports")
+            .timeout(Duration::from_secs(5))
+            .post(slow)
+            .post("/comments", elif_handler)
+            .post("/slow", slow)
+            .into_axum_router();
+
+        let status = |path: &'static str, body: Vec<u8>| {
+            let app = app.clone();
+            async move {
+                let mut headers = crate::response::ElifHeaderMap::new();
+                headers
+                    .add_header("content-length", &body.len().to_string())
+                    .unwrap();
+                let request = ElifRequest::new(
+                    crate::request::ElifMethod::POST,
+                    path.parse().unwrap(),
+                    headers,
+                )
+                .with_body(body.into())
+                .into_axum_request();
+                app.oneshot(request).await.unwrap().status().as_u16()
+            }
+        };
+
+        // The route allows bodies the global limit rejects, up to its own limit
+        assert_eq!(status("/upload", vec![b'x'; 64 * 1024]).await, 200);
+        assert_eq!(status("/upload", vec![b'x'; 11 * 1024 * 1024]).await, 413);
+        assert_eq!(status("/comments", vec![b'x'; 64 * 1024]).await, 413);
+
+        // The route allows more time than the global timeout
+        assert_eq!(status("/reports", Vec::new()).await, 200);
+        assert_eq!(status("/slow", Vec::new()).await, 408);
+    }
+
+    #[test]
+    fn test_signed_url_generation() {
+        let signer = UrlSigner::new("secret").unwrap();
+        let router = Router::<()>::new()
+            .with_url_signer(signer.clone())
+            .route("/unsubscribe/{id}")
+            .name("unsubscribe")
+            .get(elif_handler);
+
+        let mut params = HashMap::new();
+        params.insert("id".to_string(), "42".to_string());
+
+        let url = router.signed_url_for("unsubscribe", &params, None).unwrap();
+        assert!(url.starts_with("/unsubscribe/42?signature="));
+        assert!(signer.verify(&url).is_ok());
+
+        let expires = chrono::Utc::now() + chrono::Duration::hours(1);
+        let url = router
+            .signed_url_for("unsubscribe", &params, Some(expires))
+            .unwrap();
+        assert!(url.contains(&format!("expires={}", expires.timestamp())));
+        assert!(signer.verify(&url).is_ok());
+
+        assert!(router.signed_url_for("missing", &params, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_method_routes_options_and_fallback() {
+        use tower::ServiceExt;
+
+        async fn not_found(req: ElifRequest) -> HttpResult<ElifResponse> {
+            Ok(ElifResponse::not_found().text(format!("missing {}", req.path())))
+        }
+
+        let router = Router::<()>::new()
+            .get("/users", elif_handler)
+            .post("/users", elif_handler)
+            .any("/webhook", elif_handler)
+            .match_methods(
+                &[HttpMethod::PUT, HttpMethod::PATCH],
+                "/profile",
+                elif_handler,
+            )
+            .unwrap()
+            .route("/status")
+            .head(elif_handler)
+            .options("/status", elif_handler)
+            .fallback(not_found);
+
+        {
+            let registry = router.registry.lock().unwrap();
+            let methods = |path: &str| {
+                let mut methods: Vec<_> = registry
+                    .all_routes()
+                    .values()
+                    .filter(|route| route.path == path)
+                    .map(|route| route.method.as_str())
+                    .collect();
+                methods.sort();
+                methods
+            };
+            assert_eq!(methods("/profile"), vec!["PATCH", "PUT"]);
+            assert_eq!(methods("/webhook").len(), HttpMethod::ALL.len());
+            assert_eq!(methods("/status"), vec!["HEAD", "OPTIONS"]);
+        }
+        assert!(Router::<()>::new()
+            .match_methods(&[], "/empty", elif_handler)
+            .is_err());
+
+        let app = router.into_axum_router();
+        let call = |method: crate::request::ElifMethod, path: &'static str| {
+            let app = app.clone();
+            async move {
+                let request = ElifRequest::new(
+                    method,
+                    path.parse().unwrap(),
+                    crate::response::ElifHeaderMap::new(),
+                )
+                .into_axum_request();
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status().as_u16();
+                let allow = response
+                    .headers()
+                    .get("allow")
+                    .map(|value| value.to_str().unwrap().to_string());
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (status, allow, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        use crate::request::ElifMethod;
+        let (status, allow, body) = call(ElifMethod::OPTIONS, "/users").await;
+        assert_eq!(status, 204);
+        assert_eq!(allow.as_deref(), Some("GET, HEAD, POST, OPTIONS"));
+        assert!(body.is_empty());
+
+        let (status, allow, _) = call(ElifMethod::DELETE, "/users").await;
+        assert_eq!(status, 405);
+        assert!(allow.unwrap().contains("POST"));
+
+        assert_eq!(call(ElifMethod::HEAD, "/users").await.0, 200);
+        assert_eq!(call(ElifMethod::DELETE, "/webhook").await.0, 200);
+        assert_eq!(call(ElifMethod::PATCH, "/profile").await.0, 200);
+        assert_eq!(call(ElifMethod::GET, "/profile").await.0, 405);
+        assert_eq!(call(ElifMethod::OPTIONS, "/status").await.0, 200);
+
+        let (status, _, body) = call(ElifMethod::GET, "/nowhere").await;
+        assert_eq!(status, 404);
+        assert_eq!(body, "missing /nowhere");
+    }
+
     #[test]
     fn test_middleware_integration() {
         use crate::middleware::v2::LoggingMiddleware;
@@ -0,0 +1,270 @@
+//! Signed URLs
+//!
+//! A signed URL carries an HMAC-SHA256 signature of its path and query, computed with the
+//! application key, so links such as email verification or unsubscribe links can't be
+//! altered. An optional `expires` timestamp is covered by the signature as well.
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+use thiserror::Error;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Query parameter holding the signature
+pub const SIGNATURE_PARAM: &str = "signature";
+
+/// Query parameter holding the expiry as a Unix timestamp
+pub const EXPIRES_PARAM: &str = "expires";
+
+/// Errors from signing or validating URLs
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("Application key is not configured")]
+    MissingKey,
+
+    #[error("Invalid application key: {0}")]
+    InvalidKey(String),
+
+    #[error("URL is not signed")]
+    Missing,
+
+    #[error("Invalid signature")]
+    Invalid,
+
+    #[error("Signed URL has expired")]
+    Expired,
+}
+
+/// Signs and validates URLs with the application key
+#[derive(Clone)]
+pub struct UrlSigner {
+    key: Vec<u8>,
+}
+
+impl UrlSigner {
+    /// Create a signer from raw key bytes
+    pub fn new(key: impl Into<Vec<u8>>) -> Result<Self, SignatureError> {
+        let key = key.into();
+        if key.is_empty() {
+            return Err(SignatureError::MissingKey);
+        }
+        Ok(Self { key })
+    }
+
+    /// Create a signer from an application key, accepting the `base64:` prefixed form
+    pub fn from_app_key(app_key: &str) -> Result<Self, SignatureError> {
+        match app_key.trim().strip_prefix("base64:") {
+            Some(encoded) => {
+                let key = base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|e| SignatureError::InvalidKey(e.to_string()))?;
+                Self::new(key)
+            }
+            None => Self::new(app_key.trim()),
+        }
+    }
+
+    /// Create a signer from the `APP_KEY` environment variable
+    pub fn from_env() -> Result<Self, SignatureError> {
+        let app_key = std::env::var("APP_KEY").map_err(|_| SignatureError::MissingKey)?;
+        Self::from_app_key(&app_key)
+    }
+
+    /// Sign a URL, optionally expiring at the given time
+    ///
+    /// Only the path and query are signed, so the URL may be absolute, scheme-relative
+    /// (`//host/path`) or a bare path, and a base URL can be prepended afterwards.
+    pub fn sign(&self, url: &str, expires_at: Option<DateTime<Utc>>) -> String {
+        let (origin, target) = split_origin(url);
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut pairs: Vec<&str> = query_pairs(query)
+            .filter(|pair| !is_param(pair, SIGNATURE_PARAM) && !is_param(pair, EXPIRES_PARAM))
+            .collect();
+        let expires = expires_at.map(|at| format!("{}={}", EXPIRES_PARAM, at.timestamp()));
+        if let Some(expires) = &expires {
+            pairs.push(expires);
+        }
+
+        let unsigned = join_target(path, &pairs);
+        let signature = hex(&self.mac(&unsigned).finalize().into_bytes());
+        let separator = if pairs.is_empty() { '?' } else { '&' };
+        format!(
+            "{}{}{}{}={}",
+            origin, unsigned, separator, SIGNATURE_PARAM, signature
+        )
+    }
+
+    /// Validate the signature and expiry of a request path and query
+    pub fn verify(&self, path_and_query: &str) -> Result<(), SignatureError> {
+        self.verify_at(path_and_query, Utc::now())
+    }
+
+    /// Validate a signed path and query as of the given time
+    pub fn verify_at(
+        &self,
+        path_and_query: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), SignatureError> {
+        let (_, target) = split_origin(path_and_query);
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut signature = None;
+        let mut pairs = Vec::new();
+        for pair in query_pairs(query) {
+            match pair
+                .strip_prefix(SIGNATURE_PARAM)
+                .and_then(|rest| rest.strip_prefix('='))
+            {
+                Some(value) => signature = Some(value),
+                None => pairs.push(pair),
+            }
+        }
+
+        let signature = signature.ok_or(SignatureError::Missing)?;
+        let signature = unhex(signature).ok_or(SignatureError::Invalid)?;
+        self.mac(&join_target(path, &pairs))
+            .verify_slice(&signature)
+            .map_err(|_| SignatureError::Invalid)?;
+
+        let expires = pairs
+            .iter()
+            .find_map(|pair| pair.strip_prefix(EXPIRES_PARAM)?.strip_prefix('='));
+        if let Some(expires) = expires {
+            let expires: i64 = expires.parse().map_err(|_| SignatureError::Invalid)?;
+            if now.timestamp() > expires {
+                return Err(SignatureError::Expired);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn mac(&self, message: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(message.as_bytes());
+        mac
+    }
+}
+
+impl fmt::Debug for UrlSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UrlSigner").finish_non_exhaustive()
+    }
+}
+
+/// Split `scheme://host` or `//host` from the path and query
+fn split_origin(url: &str) -> (&str, &str) {
+    let authority_start = if let Some(index) = url.find("://") {
+        index + 3
+    } else if url.starts_with("//") {
+        2
+    } else {
+        return ("", url);
+    };
+
+    match url[authority_start..].find(['/', '?']) {
+        Some(index) => url.split_at(authority_start + index),
+        None => (url, "/"),
+    }
+}
+
+fn query_pairs(query: &str) -> impl Iterator<Item = &str> {
+    query.split('&').filter(|pair| !pair.is_empty())
+}
+
+fn is_param(pair: &str, name: &str) -> bool {
+    pair.split_once('=').map_or(pair, |(key, _)| key) == name
+}
+
+fn join_target(path: &str, pairs: &[&str]) -> String {
+    if pairs.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}", path, pairs.join("&"))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn signer() -> UrlSigner {
+        UrlSigner::new("test-app-key").unwrap()
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let url = signer().sign("/unsubscribe/42?list=news", None);
+        assert!(url.starts_with("/unsubscribe/42?list=news&signature="));
+        assert_eq!(signer().verify(&url), Ok(()));
+
+        let tampered = url.replace("/42", "/43");
+        assert_eq!(signer().verify(&tampered), Err(SignatureError::Invalid));
+        assert_eq!(
+            signer().verify("/unsubscribe/42?list=news"),
+            Err(SignatureError::Missing)
+        );
+        assert_eq!(
+            UrlSigner::new("other-key").unwrap().verify(&url),
+            Err(SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_expiring_urls() {
+        let now = Utc::now();
+        let url = signer().sign("/email/verify/7", Some(now + Duration::minutes(60)));
+        assert!(url.contains("expires="));
+        assert_eq!(signer().verify_at(&url, now), Ok(()));
+        assert_eq!(
+            signer().verify_at(&url, now + Duration::minutes(61)),
+            Err(SignatureError::Expired)
+        );
+
+        // Extending the expiry invalidates the signature
+        let extended = url.replace(
+            &format!("expires={}", (now + Duration::minutes(60)).timestamp()),
+            &format!("expires={}", (now + Duration::days(30)).timestamp()),
+        );
+        assert_eq!(
+            signer().verify_at(&extended, now),
+            Err(SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_absolute_urls_and_app_keys() {
+        let url = signer().sign("https://example.com/verify?id=1", None);
+        assert!(url.starts_with("https://example.com/verify?id=1&signature="));
+        let path = url.trim_start_matches("https://example.com");
+        assert_eq!(signer().verify(path), Ok(()));
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode("test-app-key");
+        let from_base64 = UrlSigner::from_app_key(&format!("base64:{}", encoded)).unwrap();
+        assert_eq!(from_base64.verify(path), Ok(()));
+        assert_eq!(
+            UrlSigner::from_app_key("").unwrap_err(),
+            SignatureError::MissingKey
+        );
+    }
+}
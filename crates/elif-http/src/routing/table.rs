@@ -0,0 +1,132 @@
+//! Methods and limits of the routes of a router, by Axum path pattern
+//!
+//! Axum only reveals the route a request matched, not what the route allows. The table
+//! is built alongside the Axum router, follows it through merging and nesting, and is
+//! looked up with the [`MatchedPath`](axum::extract::MatchedPath) of a request.
+
+use super::limits::RouteLimits;
+use super::HttpMethod;
+use crate::middleware::utils::RouteLimitsMiddleware;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RouteTable {
+    methods: HashMap<String, Vec<HttpMethod>>,
+    limits: HashMap<(String, String), RouteLimitsMiddleware>,
+}
+
+impl RouteTable {
+    /// Record a route answering `methods` on `path`
+    ///
+    /// Methods sharing limits share one in-flight counter.
+    pub(crate) fn insert(&mut self, methods: &[HttpMethod], path: &str, limits: &RouteLimits) {
+        let allowed = self.methods.entry(path.to_string()).or_default();
+        for method in methods {
+            // Axum answers HEAD requests with the GET handler
+            let implied = (*method == HttpMethod::GET).then_some(HttpMethod::HEAD);
+            for method in std::iter::once(method.clone()).chain(implied) {
+                if !allowed.contains(&method) {
+                    allowed.push(method);
+                }
+            }
+        }
+
+        if limits.is_empty() {
+            return;
+        }
+        let middleware = RouteLimitsMiddleware::new(limits.clone());
+        for method in methods {
+            self.limits.insert(
+                (method.as_str().to_string(), path.to_string()),
+                middleware.clone(),
+            );
+        }
+    }
+
+    /// Add the routes of another router
+    pub(crate) fn extend(&mut self, other: RouteTable) {
+        for (path, methods) in other.methods {
+            self.insert(&methods, &path, &RouteLimits::default());
+        }
+        self.limits.extend(other.limits);
+    }
+
+    /// Add the routes of a router nested under `prefix`
+    pub(crate) fn extend_nested(&mut self, prefix: &str, other: RouteTable) {
+        for (path, methods) in other.methods {
+            self.insert(
+                &methods,
+                &nested_path(prefix, &path),
+                &RouteLimits::default(),
+            );
+        }
+        self.limits.extend(
+            other
+                .limits
+                .into_iter()
+                .map(|((method, path), limits)| ((method, nested_path(prefix, &path)), limits)),
+        );
+    }
+
+    /// Get the limits of the route matching a method and Axum path pattern
+    pub(crate) fn limits(&self, method: &str, path: &str) -> Option<&RouteLimitsMiddleware> {
+        self.limits.get(&(method.to_string(), path.to_string()))
+    }
+
+    /// Get the methods answered on an Axum path pattern, in the order Axum lists them
+    pub(crate) fn allowed_methods(&self, path: &str) -> Option<&[HttpMethod]> {
+        self.methods.get(path).map(Vec::as_slice)
+    }
+
+    /// Check whether any route declares limits
+    pub(crate) fn has_limits(&self) -> bool {
+        !self.limits.is_empty()
+    }
+}
+
+/// Path pattern Axum matches a nested route by
+fn nested_path(prefix: &str, path: &str) -> String {
+    if prefix.ends_with('/') {
+        format!("{}{}", prefix, path.trim_start_matches('/'))
+    } else if path == "/" {
+        prefix.to_string()
+    } else {
+        format!("{}{}", prefix, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_table_nesting() {
+        let mut nested = RouteTable::default();
+        nested.insert(
+            &[HttpMethod::POST],
+            "/upload",
+            &RouteLimits::new().max_body_size(8),
+        );
+        nested.insert(&[HttpMethod::GET], "/", &RouteLimits::new());
+        nested.insert(&[HttpMethod::PUT], "/upload", &RouteLimits::new());
+        assert!(nested.has_limits());
+
+        let mut table = RouteTable::default();
+        table.extend_nested("/api", nested);
+        assert_eq!(
+            table.limits("POST", "/api/upload").unwrap().limits(),
+            &RouteLimits::new().max_body_size(8)
+        );
+        assert!(table.limits("POST", "/upload").is_none());
+        // Routes without limits only record their methods
+        assert!(table.limits("GET", "/api").is_none());
+        assert_eq!(
+            table.allowed_methods("/api"),
+            Some(&[HttpMethod::GET, HttpMethod::HEAD][..])
+        );
+        assert_eq!(
+            table.allowed_methods("/api/upload"),
+            Some(&[HttpMethod::POST, HttpMethod::PUT][..])
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Health checks of the components framework crates provide
+//!
+//! - [`PoolCheck`] - database pool connectivity and statistics (`orm` feature)
+//! - [`CacheCheck`] - cache backend reachability, e.g. Redis (`cache` feature)
+//! - [`QueueCheck`] - queue backend reachability and backlog (`queue` feature)
+//! - [`StorageCheck`] - storage backend reachability (`storage` feature)
+//! - [`EmailCheck`] - email provider connectivity, e.g. SMTP (`email` feature)
+
+#[cfg(feature = "cache")]
+pub use self::cache::CacheCheck;
+#[cfg(feature = "email")]
+pub use self::email::EmailCheck;
+#[cfg(feature = "orm")]
+pub use self::pool::PoolCheck;
+#[cfg(feature = "queue")]
+pub use self::queue::QueueCheck;
+#[cfg(feature = "storage")]
+pub use self::storage::StorageCheck;
+
+/// Key or path probed by checks that read from a backend
+#[cfg(any(feature = "cache", feature = "storage"))]
+const PROBE_KEY: &str = "elif-health-probe";
+
+#[cfg(feature = "orm")]
+mod pool {
+    use super::super::{CheckResult, HealthCheck};
+    use async_trait::async_trait;
+    use orm::connection::ManagedPool;
+    use std::sync::Arc;
+
+    /// Checks a database pool with `ManagedPool::detailed_health_check`
+    ///
+    /// A pool that answers but is slow, has a high error rate or no available
+    /// connections is degraded.
+    pub struct PoolCheck {
+        name: String,
+        pool: Arc<ManagedPool>,
+    }
+
+    impl PoolCheck {
+        /// Check a pool, reported under the given name
+        pub fn new<N: Into<String>>(name: N, pool: Arc<ManagedPool>) -> Self {
+            Self {
+                name: name.into(),
+                pool,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for PoolCheck {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn check(&self) -> CheckResult {
+            let report = match self.pool.detailed_health_check().await {
+                Ok(report) => report,
+                Err(e) => return CheckResult::unhealthy(e.to_string()),
+            };
+
+            let result = if report.is_healthy() {
+                CheckResult::healthy()
+            } else if !report.is_responsive() {
+                CheckResult::degraded("Database answers slowly")
+            } else if !report.has_acceptable_error_rate() {
+                CheckResult::degraded("Connection acquisition error rate is high")
+            } else {
+                CheckResult::degraded("No database connection available")
+            };
+
+            result
+                .with_detail("pool_size", report.pool_size)
+                .with_detail("active_connections", report.active_connections)
+                .with_detail("idle_connections", report.idle_connections)
+                .with_detail("error_rate", report.error_rate)
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+mod cache {
+    use super::super::{CheckResult, HealthCheck};
+    use super::PROBE_KEY;
+    use async_trait::async_trait;
+    use elif_cache::CacheBackend;
+    use std::sync::Arc;
+
+    /// Checks that a cache backend answers lookups
+    pub struct CacheCheck {
+        name: String,
+        backend: Arc<dyn CacheBackend>,
+    }
+
+    impl CacheCheck {
+        /// Check a cache, reported under the given name
+        pub fn new<N: Into<String>>(name: N, backend: Arc<dyn CacheBackend>) -> Self {
+            Self {
+                name: name.into(),
+                backend,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for CacheCheck {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn check(&self) -> CheckResult {
+            match self.backend.exists(PROBE_KEY).await {
+                Ok(_) => CheckResult::healthy(),
+                Err(e) => CheckResult::unhealthy(e.to_string()),
+            }
+        }
+
+        // Applications usually keep serving, slower, without their cache
+        fn critical(&self) -> bool {
+            false
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::server::health::HealthState;
+        use elif_cache::{CacheConfig, MemoryBackend};
+
+        #[tokio::test]
+        async fn test_checks_cache_backend() {
+            let check = CacheCheck::new(
+                "cache",
+                Arc::new(MemoryBackend::new(CacheConfig::default())),
+            );
+            assert_eq!(check.check().await.status, HealthState::Healthy);
+            assert!(!check.critical());
+        }
+    }
+}
+
+#[cfg(feature = "queue")]
+mod queue {
+    use super::super::{CheckResult, HealthCheck};
+    use async_trait::async_trait;
+    use elif_queue::QueueBackend;
+    use std::sync::Arc;
+
+    /// Checks that a queue backend answers and optionally that its backlog is bounded
+    pub struct QueueCheck {
+        name: String,
+        backend: Arc<dyn QueueBackend>,
+        max_pending: Option<u64>,
+    }
+
+    impl QueueCheck {
+        /// Check a queue, reported under the given name
+        pub fn new<N: Into<String>>(name: N, backend: Arc<dyn QueueBackend>) -> Self {
+            Self {
+                name: name.into(),
+                backend,
+                max_pending: None,
+            }
+        }
+
+        /// Report the queue degraded when more jobs than this are pending
+        pub fn with_max_pending(mut self, max_pending: u64) -> Self {
+            self.max_pending = Some(max_pending);
+            self
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for QueueCheck {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn check(&self) -> CheckResult {
+            let stats = match self.backend.stats().await {
+                Ok(stats) => stats,
+                Err(e) => return CheckResult::unhealthy(e.to_string()),
+            };
+
+            let result = match self.max_pending {
+                Some(max) if stats.pending_jobs > max => {
+                    CheckResult::degraded(format!("{} jobs pending", stats.pending_jobs))
+                }
+                _ => CheckResult::healthy(),
+            };
+
+            result
+                .with_detail("pending_jobs", stats.pending_jobs)
+                .with_detail("processing_jobs", stats.processing_jobs)
+                .with_detail("dead_jobs", stats.dead_jobs)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::server::health::HealthState;
+        use elif_queue::{MemoryBackend, QueueConfig};
+
+        #[tokio::test]
+        async fn test_checks_queue_backlog() {
+            let backend = Arc::new(MemoryBackend::new(QueueConfig::default()));
+            let result = QueueCheck::new("queue", backend.clone()).check().await;
+            assert_eq!(result.status, HealthState::Healthy);
+            assert_eq!(result.details["pending_jobs"], 0);
+        }
+    }
+}
+
+#[cfg(feature = "storage")]
+mod storage {
+    use super::super::{CheckResult, HealthCheck};
+    use super::PROBE_KEY;
+    use async_trait::async_trait;
+    use elif_storage::StorageBackend;
+    use std::sync::Arc;
+
+    /// Checks that a storage backend answers lookups
+    pub struct StorageCheck<B: StorageBackend> {
+        name: String,
+        backend: Arc<B>,
+    }
+
+    impl<B: StorageBackend> StorageCheck<B> {
+        /// Check a storage backend, reported under the given name
+        pub fn new<N: Into<String>>(name: N, backend: Arc<B>) -> Self {
+            Self {
+                name: name.into(),
+                backend,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl<B: StorageBackend + 'static> HealthCheck for StorageCheck<B> {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn check(&self) -> CheckResult {
+            match self.backend.exists(PROBE_KEY).await {
+                Ok(_) => CheckResult::healthy(),
+                Err(e) => CheckResult::unhealthy(e.to_string()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "email")]
+mod email {
+    use super::super::{CheckResult, HealthCheck};
+    use async_trait::async_trait;
+    use elif_email::EmailProvider;
+    use std::sync::Arc;
+
+    /// Checks an email provider with `EmailProvider::validate_config`
+    ///
+    /// The SMTP provider opens a connection to its server to validate it.
+    pub struct EmailCheck {
+        name: String,
+        provider: Arc<dyn EmailProvider>,
+    }
+
+    impl EmailCheck {
+        /// Check a provider, reported under the given name
+        pub fn new<N: Into<String>>(name: N, provider: Arc<dyn EmailProvider>) -> Self {
+            Self {
+                name: name.into(),
+                provider,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for EmailCheck {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn check(&self) -> CheckResult {
+            match self.provider.validate_config().await {
+                Ok(()) => CheckResult::healthy(),
+                Err(e) => CheckResult::unhealthy(e.to_string()),
+            }
+            .with_detail("provider", self.provider.provider_name())
+        }
+
+        // Mail is usually sent from jobs that retry, so requests can still be served
+        fn critical(&self) -> bool {
+            false
+        }
+    }
+}
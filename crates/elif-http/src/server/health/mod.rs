@@ -0,0 +1,201 @@
+//! Health check endpoint implementation
+//!
+//! The server answers on three endpoints, configured in [`HttpConfig`]:
+//!
+//! - `health_check_path` and `readiness_path` run every registered [`HealthCheck`]
+//! - `liveness_path` runs only the checks that opt into liveness
+//!
+//! They answer `503 Service Unavailable` when the application is unhealthy, and readiness
+//! fails as soon as the server starts shutting down. Checks are
+//! registered in the [`HealthRegistry`] bound in the IoC container:
+//!
+//! ```rust,ignore
+//! let health = HealthRegistry::new();
+//! health.register(PoolCheck::new("database", pool));
+//! container.bind_instance::<HealthRegistry, HealthRegistry>(health);
+//! ```
+
+pub mod checks;
+pub mod registry;
+
+#[cfg(any(
+    feature = "orm",
+    feature = "cache",
+    feature = "queue",
+    feature = "storage",
+    feature = "email"
+))]
+pub use checks::*;
+pub use registry::{
+    CheckResult, HealthCheck, HealthRegistry, HealthReport, HealthState, Probe, DEFAULT_CACHE_TTL,
+    DEFAULT_CHECK_TIMEOUT,
+};
+
+use crate::config::HttpConfig;
+use crate::request::ElifRequest;
+use crate::response::{ElifResponse, ElifStatusCode};
+use elif_core::container::IocContainer;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Default health check handler, running the readiness checks of the container's registry
+pub async fn health_check_handler(
+    container: Arc<IocContainer>,
+    _config: HttpConfig,
+) -> axum::response::Json<serde_json::Value> {
+    let report = resolve_registry(&container).run(Probe::Readiness).await;
+    axum::response::Json(serde_json::to_value(report).unwrap_or_default())
+}
+
+/// Get the registry bound in the container, or an empty one
+pub fn resolve_registry(container: &IocContainer) -> HealthRegistry {
+    container
+        .try_resolve::<HealthRegistry>()
+        .map(|registry| (*registry).clone())
+        .unwrap_or_default()
+}
+
+/// Handler running the checks of a probe, answering 503 when the application is unhealthy
+pub fn health_handler(
+    registry: HealthRegistry,
+    probe: Probe,
+) -> impl Fn(
+    ElifRequest,
+) -> Pin<Box<dyn Future<Output = crate::errors::HttpResult<ElifResponse>> + Send>>
+       + Clone
+       + Send
+       + 'static {
+    move |_request| {
+        let registry = registry.clone();
+        Box::pin(async move {
+            let report = registry.run(probe).await;
+            let status = if report.is_ready() {
+                ElifStatusCode::OK
+            } else {
+                ElifStatusCode::SERVICE_UNAVAILABLE
+            };
+            ElifResponse::with_status(status).json(&report)
+        })
+    }
+}
+
+/// Health check response structure
+#[derive(serde::Serialize)]
+pub struct HealthStatus {
+    pub status: String,
+    pub framework: String,
+    pub version: String,
+    pub timestamp: u64,
+    pub server: ServerStatus,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ServerStatus {
+    pub ready: bool,
+    pub uptime: String,
+}
+
+impl Default for HealthStatus {
+    fn default() -> Self {
+        Self {
+            status: "healthy".to_string(),
+            framework: "Elif.rs".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            server: ServerStatus {
+                ready: true,
+                uptime: "N/A".to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifMethod;
+    use crate::response::ElifHeaderMap;
+    use crate::routing::ElifRouter;
+    use crate::testing::create_test_container;
+    use async_trait::async_trait;
+    use elif_core::container::{IocContainer, ServiceBinder};
+    use tower::ServiceExt;
+
+    struct DownCheck;
+
+    #[async_trait]
+    impl HealthCheck for DownCheck {
+        fn name(&self) -> &str {
+            "database"
+        }
+
+        async fn check(&self) -> CheckResult {
+            CheckResult::unhealthy("Connection refused")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_handler() {
+        let container = create_test_container();
+        let config = HttpConfig::default();
+
+        let response = health_check_handler(container, config).await;
+        // Test that response is properly formatted JSON
+        assert!(response.0.get("status").is_some());
+        assert_eq!(response.0["status"], "healthy");
+    }
+
+    #[tokio::test]
+    async fn test_health_check_handler_runs_container_checks() {
+        let registry = HealthRegistry::new();
+        registry.register(DownCheck);
+        let mut container = IocContainer::new();
+        container.bind_instance::<HealthRegistry, HealthRegistry>(registry);
+        container.build().unwrap();
+
+        let response = health_check_handler(Arc::new(container), HttpConfig::default()).await;
+        assert_eq!(response.0["status"], "unhealthy");
+        assert_eq!(
+            response.0["checks"]["database"]["message"],
+            "Connection refused"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_probe_endpoints() {
+        let registry = HealthRegistry::new();
+        registry.register(DownCheck);
+        let app = ElifRouter::<()>::new()
+            .get(
+                "/health/live",
+                health_handler(registry.clone(), Probe::Liveness),
+            )
+            .get("/health/ready", health_handler(registry, Probe::Readiness))
+            .into_axum_router();
+
+        let status = |path: &'static str| {
+            let app = app.clone();
+            async move {
+                let request =
+                    ElifRequest::new(ElifMethod::GET, path.parse().unwrap(), ElifHeaderMap::new())
+                        .into_axum_request();
+                app.oneshot(request).await.unwrap().status().as_u16()
+            }
+        };
+
+        assert_eq!(status("/health/live").await, 200);
+        assert_eq!(status("/health/ready").await, 503);
+    }
+
+    #[test]
+    fn test_health_status_default() {
+        let status = HealthStatus::default();
+        assert_eq!(status.status, "healthy");
+        assert_eq!(status.framework, "Elif.rs");
+        assert!(status.server.ready);
+    }
+}
@@ -0,0 +1,449 @@
+//! Health checks of the components an application depends on
+
+use super::ServerStatus;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Default time a check may take before it is reported unhealthy
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time a check result is reused for
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Health of a component or of the whole application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    /// Working normally
+    Healthy,
+    /// Working with reduced capacity or a non-critical component failing
+    Degraded,
+    /// Not able to serve requests
+    Unhealthy,
+}
+
+/// Which endpoint runs a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Probe {
+    /// Whether the process is alive and should not be restarted
+    Liveness,
+    /// Whether the application can serve traffic
+    Readiness,
+}
+
+/// Outcome of a health check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckResult {
+    pub status: HealthState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub details: Map<String, Value>,
+    /// Time the check took, set by the registry
+    pub duration_ms: u64,
+}
+
+impl CheckResult {
+    fn with_status(status: HealthState, message: Option<String>) -> Self {
+        Self {
+            status,
+            message,
+            details: Map::new(),
+            duration_ms: 0,
+        }
+    }
+
+    /// Create a healthy result
+    pub fn healthy() -> Self {
+        Self::with_status(HealthState::Healthy, None)
+    }
+
+    /// Create a degraded result
+    pub fn degraded<M: Into<String>>(message: M) -> Self {
+        Self::with_status(HealthState::Degraded, Some(message.into()))
+    }
+
+    /// Create an unhealthy result
+    pub fn unhealthy<M: Into<String>>(message: M) -> Self {
+        Self::with_status(HealthState::Unhealthy, Some(message.into()))
+    }
+
+    /// Add a detail, such as a connection count, to the result
+    pub fn with_detail<T: Serialize>(mut self, name: &str, value: T) -> Self {
+        self.details.insert(
+            name.to_string(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+        self
+    }
+}
+
+/// Check of a component the application depends on
+///
+/// Register checks in a [`HealthRegistry`] bound in the IoC container; the server runs
+/// them for its health, liveness and readiness endpoints.
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    /// Name the result is reported under
+    fn name(&self) -> &str;
+
+    /// Check the component
+    async fn check(&self) -> CheckResult;
+
+    /// Whether a failure makes the application unhealthy rather than degraded
+    fn critical(&self) -> bool {
+        true
+    }
+
+    /// Whether the check also runs for the liveness probe
+    ///
+    /// Liveness failures make orchestrators restart the process, so only checks that a
+    /// restart can fix should opt in.
+    fn liveness(&self) -> bool {
+        false
+    }
+
+    /// Time the check may take, the registry's default when `None`
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Aggregated result of the checks of a probe
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: HealthState,
+    pub framework: String,
+    pub version: String,
+    pub timestamp: u64,
+    pub server: ServerStatus,
+    pub checks: BTreeMap<String, CheckResult>,
+}
+
+impl HealthReport {
+    /// Check whether the application can serve requests, possibly degraded
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthState::Unhealthy
+    }
+}
+
+struct RegisteredCheck {
+    check: Arc<dyn HealthCheck>,
+    cached: Mutex<Option<(Instant, CheckResult)>>,
+}
+
+struct Inner {
+    checks: RwLock<Vec<Arc<RegisteredCheck>>>,
+    default_timeout: Duration,
+    cache_ttl: Duration,
+    started_at: Instant,
+    shutting_down: AtomicBool,
+}
+
+/// Health checks run by the server's health endpoints
+///
+/// Clones share their checks, so a registry can be bound in the container with
+/// `bind_instance` and extended by service providers afterwards. Check results are
+/// reused for the cache TTL so that frequent probes do not load the components.
+#[derive(Clone)]
+pub struct HealthRegistry {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for HealthRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthRegistry")
+            .field("default_timeout", &self.inner.default_timeout)
+            .field("cache_ttl", &self.inner.cache_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for HealthRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthRegistry {
+    /// Create an empty registry with the default timeout and cache TTL
+    pub fn new() -> Self {
+        Self::with_settings(DEFAULT_CHECK_TIMEOUT, DEFAULT_CACHE_TTL)
+    }
+
+    /// Create an empty registry with the given default check timeout and cache TTL
+    pub fn with_settings(default_timeout: Duration, cache_ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                checks: RwLock::new(Vec::new()),
+                default_timeout,
+                cache_ttl,
+                started_at: Instant::now(),
+                shutting_down: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Add a check
+    pub fn register<C: HealthCheck + 'static>(&self, check: C) {
+        self.register_arc(Arc::new(check));
+    }
+
+    /// Add a shared check
+    pub fn register_arc(&self, check: Arc<dyn HealthCheck>) {
+        self.inner
+            .checks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::new(RegisteredCheck {
+                check,
+                cached: Mutex::new(None),
+            }));
+    }
+
+    /// Get the names of the registered checks
+    pub fn names(&self) -> Vec<String> {
+        self.checks()
+            .iter()
+            .map(|registered| registered.check.name().to_string())
+            .collect()
+    }
+
+    /// Make readiness fail from now on, so load balancers stop sending traffic
+    pub fn mark_shutting_down(&self) {
+        self.inner.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    /// Check whether the application is shutting down
+    pub fn is_shutting_down(&self) -> bool {
+        self.inner.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Run the checks of a probe concurrently and aggregate their results
+    ///
+    /// Readiness is unhealthy, without running the checks, once the application is
+    /// shutting down.
+    pub async fn run(&self, probe: Probe) -> HealthReport {
+        let shutting_down = probe == Probe::Readiness && self.is_shutting_down();
+        let checks: Vec<_> = self
+            .checks()
+            .into_iter()
+            .filter(|_| !shutting_down)
+            .filter(|registered| probe == Probe::Readiness || registered.check.liveness())
+            .collect();
+
+        let results = futures_util::future::join_all(
+            checks.iter().map(|registered| self.run_check(registered)),
+        )
+        .await;
+
+        let mut status = HealthState::Healthy;
+        let mut report_checks = BTreeMap::new();
+        for (registered, result) in checks.iter().zip(results) {
+            let impact = match result.status {
+                HealthState::Unhealthy if !registered.check.critical() => HealthState::Degraded,
+                other => other,
+            };
+            status = status.max(impact);
+            report_checks.insert(registered.check.name().to_string(), result);
+        }
+        if shutting_down {
+            status = HealthState::Unhealthy;
+            report_checks.insert(
+                "shutdown".to_string(),
+                CheckResult::unhealthy("Server is shutting down"),
+            );
+        }
+
+        HealthReport {
+            status,
+            framework: "Elif.rs".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            server: ServerStatus {
+                ready: status != HealthState::Unhealthy,
+                uptime: format!("{}s", self.inner.started_at.elapsed().as_secs()),
+            },
+            checks: report_checks,
+        }
+    }
+
+    fn checks(&self) -> Vec<Arc<RegisteredCheck>> {
+        self.inner
+            .checks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    async fn run_check(&self, registered: &RegisteredCheck) -> CheckResult {
+        if let Some((at, result)) = &*registered.cached.lock().unwrap_or_else(|e| e.into_inner()) {
+            if at.elapsed() < self.inner.cache_ttl {
+                return result.clone();
+            }
+        }
+
+        let timeout = registered
+            .check
+            .timeout()
+            .unwrap_or(self.inner.default_timeout);
+        let start = Instant::now();
+        let mut result = match tokio::time::timeout(timeout, registered.check.check()).await {
+            Ok(result) => result,
+            Err(_) => CheckResult::unhealthy(format!("Timed out after {}ms", timeout.as_millis())),
+        };
+        result.duration_ms = start.elapsed().as_millis() as u64;
+
+        if result.status != HealthState::Healthy {
+            tracing::warn!(
+                "Health check '{}' is {:?}: {}",
+                registered.check.name(),
+                result.status,
+                result.message.as_deref().unwrap_or("no details")
+            );
+        }
+
+        *registered.cached.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((Instant::now(), result.clone()));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct StaticCheck {
+        name: &'static str,
+        result: CheckResult,
+        critical: bool,
+        liveness: bool,
+        calls: AtomicUsize,
+    }
+
+    impl StaticCheck {
+        fn new(name: &'static str, result: CheckResult) -> Self {
+            Self {
+                name,
+                result,
+                critical: true,
+                liveness: false,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for StaticCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn check(&self) -> CheckResult {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.result.clone()
+        }
+
+        fn critical(&self) -> bool {
+            self.critical
+        }
+
+        fn liveness(&self) -> bool {
+            self.liveness
+        }
+    }
+
+    struct SlowCheck;
+
+    #[async_trait]
+    impl HealthCheck for SlowCheck {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        async fn check(&self) -> CheckResult {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            CheckResult::healthy()
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            Some(Duration::from_millis(10))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_check_results() {
+        let registry = HealthRegistry::with_settings(DEFAULT_CHECK_TIMEOUT, Duration::ZERO);
+        registry.register(StaticCheck {
+            liveness: true,
+            ..StaticCheck::new("database", CheckResult::healthy().with_detail("idle", 4))
+        });
+        registry.register(StaticCheck {
+            critical: false,
+            ..StaticCheck::new("cache", CheckResult::unhealthy("Connection refused"))
+        });
+
+        let report = registry.run(Probe::Readiness).await;
+        assert_eq!(report.status, HealthState::Degraded);
+        assert!(report.is_ready());
+        assert_eq!(report.checks["cache"].status, HealthState::Unhealthy);
+        assert_eq!(report.checks["database"].details["idle"], 4);
+
+        // Liveness only runs the checks that opt in
+        let report = registry.run(Probe::Liveness).await;
+        assert_eq!(report.status, HealthState::Healthy);
+        assert_eq!(report.checks.len(), 1);
+
+        registry.register(SlowCheck);
+        let report = registry.run(Probe::Readiness).await;
+        assert_eq!(report.status, HealthState::Unhealthy);
+        assert!(!report.server.ready);
+        assert_eq!(
+            report.checks["slow"].message.as_deref(),
+            Some("Timed out after 10ms")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_caches_check_results() {
+        let check = Arc::new(StaticCheck::new("database", CheckResult::healthy()));
+        let registry =
+            HealthRegistry::with_settings(DEFAULT_CHECK_TIMEOUT, Duration::from_secs(60));
+        registry.register_arc(check.clone());
+
+        registry.run(Probe::Readiness).await;
+        registry.clone().run(Probe::Readiness).await;
+        assert_eq!(check.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(registry.names(), vec!["database".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_when_shutting_down() {
+        let registry = HealthRegistry::with_settings(DEFAULT_CHECK_TIMEOUT, Duration::ZERO);
+        registry.register(StaticCheck {
+            liveness: true,
+            ..StaticCheck::new("database", CheckResult::healthy())
+        });
+        registry.clone().mark_shutting_down();
+
+        let report = registry.run(Probe::Readiness).await;
+        assert!(!report.is_ready());
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(
+            report.checks["shutdown"].message.as_deref(),
+            Some("Server is shutting down")
+        );
+
+        // The process is still alive while it drains
+        assert!(registry.run(Probe::Liveness).await.is_ready());
+    }
+}
@@ -1,15 +1,18 @@
 //! Server lifecycle management - startup, shutdown, and signal handling
 
 use crate::{
-    config::HttpConfig,
+    config::{HttpConfig, TlsConfig},
     errors::{HttpError, HttpResult},
-    middleware::v2::MiddlewarePipelineV2,
+    metrics::{metrics_handler, MetricsRegistry},
+    middleware::{utils::TrustedProxyMiddleware, v2::MiddlewarePipelineV2},
     routing::ElifRouter,
-    server::health::health_check_handler,
+    server::health::{health_handler, resolve_registry, Probe},
+    server::shutdown::ShutdownCoordinator,
 };
 use elif_core::container::IocContainer;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
 use tracing::{info, warn};
 
@@ -19,51 +22,131 @@ pub async fn build_internal_router(
     config: HttpConfig,
     user_router: Option<ElifRouter>,
     middleware: MiddlewarePipelineV2,
+    shutdown: &ShutdownCoordinator,
 ) -> HttpResult<axum::Router> {
-    // Create health check handler with captured context
-    let health_container = container.clone();
-    let health_config = config.clone();
-    let health_handler = move |_req: crate::request::ElifRequest| {
-        let container = health_container.clone();
-        let config = health_config.clone();
-        async move {
-            crate::response::ElifResponse::ok()
-                .json(&health_check_handler(container, config).await.0)
-        }
-    };
-
     // Start with framework router
     let mut router = user_router.unwrap_or_default();
 
-    // Add health check route
-    router = router.get(&config.health_check_path, health_handler);
+    // Add health check and probe routes, running the checks registered in the container
+    let health = resolve_registry(&container);
+    let readiness = health.clone();
+    shutdown.on_drain("readiness", move || async move {
+        readiness.mark_shutting_down()
+    });
+    router = router.get(
+        &config.health_check_path,
+        health_handler(health.clone(), Probe::Readiness),
+    );
+    for (path, probe) in [
+        (&config.liveness_path, Probe::Liveness),
+        (&config.readiness_path, Probe::Readiness),
+    ] {
+        if *path != config.health_check_path {
+            router = router.get(path, health_handler(health.clone(), probe));
+        }
+    }
+
+    // Serve metrics and record them for every route
+    if let Some(metrics_path) = &config.metrics_path {
+        let registry = container
+            .try_resolve::<MetricsRegistry>()
+            .unwrap_or_else(MetricsRegistry::global);
+        router = router
+            .get(metrics_path, metrics_handler(Arc::clone(&registry)))
+            .with_metrics(registry);
+    }
+
+    // Resolve client details from trusted proxies before any other middleware runs
+    // and when choosing the domain routes of a request
+    let middleware = if config.trusted_proxies.is_empty() {
+        middleware
+    } else {
+        let proxies = TrustedProxyMiddleware::from_config(&config)?;
+        router = router.with_trusted_proxies(proxies.clone());
+        MiddlewarePipelineV2::new().add(proxies).extend(middleware)
+    };
 
     // Apply server middleware to router
     router = router.extend_middleware(middleware);
 
-    // Convert to Axum router
-    Ok(router.into_axum_router())
+    // Convert to Axum router, rendering errors in the configured format
+    Ok(router
+        .into_axum_router()
+        .layer(axum::Extension(config.error_format)))
 }
 
-/// Start the server with graceful shutdown
-pub async fn start_server(addr: SocketAddr, router: axum::Router) -> HttpResult<()> {
+/// Start the server with graceful shutdown, serving HTTPS when TLS is configured
+///
+/// On Ctrl+C, SIGTERM or [`ShutdownCoordinator::trigger`] the server stops accepting
+/// connections after the coordinator's pre-stop delay and waits up to `shutdown_timeout`
+/// for in-flight requests and drain hooks.
+pub async fn start_server(
+    addr: SocketAddr,
+    router: axum::Router,
+    tls: Option<&TlsConfig>,
+    shutdown: &ShutdownCoordinator,
+    shutdown_timeout: Duration,
+) -> HttpResult<()> {
     // Create TCP listener
     let listener = tokio::net::TcpListener::bind(addr)
         .await
         .map_err(|e| HttpError::startup(format!("Failed to bind to {}: {}", addr, e)))?;
 
+    // Turn termination signals into a coordinated shutdown
+    let signalled = shutdown.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = shutdown_signal() => signalled.trigger(),
+            _ = signalled.wait() => {},
+        }
+    });
+
+    let stopped = shutdown.clone();
+    let stop_accepting = async move { stopped.wait_to_stop_accepting().await };
+
+    if let Some(tls) = tls {
+        let serve = start_tls_server(addr, listener, router, tls, stop_accepting);
+        return shutdown.run(serve, shutdown_timeout).await;
+    }
+
     info!("✅ Server listening on {}", addr);
 
-    // Serve with graceful shutdown
-    axum::serve(
-        listener,
-        router.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .with_graceful_shutdown(shutdown_signal())
-    .await
-    .map_err(|e| HttpError::internal(format!("Server error: {}", e)))?;
+    // Serve until shutdown, letting in-flight requests finish
+    let serve = async move {
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(stop_accepting)
+        .await
+        .map_err(|e| HttpError::internal(format!("Server error: {}", e)))
+    };
+    shutdown.run(serve, shutdown_timeout).await
+}
+
+#[cfg(feature = "tls")]
+async fn start_tls_server(
+    addr: SocketAddr,
+    listener: tokio::net::TcpListener,
+    router: axum::Router,
+    tls: &TlsConfig,
+    stop_accepting: impl std::future::Future<Output = ()> + Send,
+) -> HttpResult<()> {
+    info!("✅ Server listening on {} (TLS)", addr);
+    super::tls::serve_tls(listener, router, tls, stop_accepting).await
+}
 
-    Ok(())
+#[cfg(not(feature = "tls"))]
+async fn start_tls_server(
+    _addr: SocketAddr,
+    _listener: tokio::net::TcpListener,
+    _router: axum::Router,
+    _tls: &TlsConfig,
+    _stop_accepting: impl std::future::Future<Output = ()> + Send,
+) -> HttpResult<()> {
+    Err(HttpError::config(
+        "TLS is configured but elif-http was built without the `tls` feature",
+    ))
 }
 
 /// Graceful shutdown signal handler
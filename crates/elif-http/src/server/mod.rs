@@ -1,7 +1,13 @@
 pub mod health;
 pub mod lifecycle;
 pub mod server;
+pub mod shutdown;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 pub use health::*;
 pub use lifecycle::*;
 pub use server::*;
+pub use shutdown::*;
+#[cfg(feature = "tls")]
+pub use tls::*;
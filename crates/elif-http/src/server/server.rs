@@ -4,6 +4,7 @@
 //! Users interact only with framework types - Axum is completely abstracted away.
 
 use super::lifecycle::{build_internal_router, start_server};
+use super::shutdown::ShutdownCoordinator;
 use crate::{
     config::HttpConfig,
     errors::{HttpError, HttpResult},
@@ -38,26 +39,30 @@ pub struct Server {
     config: HttpConfig,
     router: Option<ElifRouter>,
     middleware: MiddlewarePipelineV2,
+    shutdown: ShutdownCoordinator,
 }
 
 impl Server {
     /// Create a new server instance
     pub fn new(container: IocContainer, config: HttpConfig) -> HttpResult<Self> {
-        Ok(Self {
-            container: Arc::new(container),
-            config,
-            router: None,
-            middleware: MiddlewarePipelineV2::new(),
-        })
+        Self::with_container(Arc::new(container), config)
     }
 
     /// Create a new server with existing Arc<IocContainer>
+    ///
+    /// The server uses the [`ShutdownCoordinator`] bound in the container, if any.
     pub fn with_container(container: Arc<IocContainer>, config: HttpConfig) -> HttpResult<Self> {
+        let shutdown = container
+            .try_resolve::<ShutdownCoordinator>()
+            .map(|shutdown| (*shutdown).clone())
+            .unwrap_or_default();
+
         Ok(Self {
             container,
             config,
             router: None,
             middleware: MiddlewarePipelineV2::new(),
+            shutdown,
         })
     }
 
@@ -254,11 +259,23 @@ impl Server {
             self.config.clone(),
             self.router,
             self.middleware,
+            &self.shutdown,
         )
         .await?;
 
+        // Keep accepting connections while load balancers notice the failing readiness probe
+        self.shutdown
+            .set_pre_stop_delay(self.config.pre_stop_delay());
+
         // Start the server
-        start_server(addr, axum_router).await?;
+        start_server(
+            addr,
+            axum_router,
+            self.config.tls.as_ref(),
+            &self.shutdown,
+            self.config.shutdown_timeout(),
+        )
+        .await?;
 
         info!("🛑 Server shut down gracefully");
         Ok(())
@@ -280,6 +297,11 @@ impl Server {
     pub fn middleware(&self) -> &MiddlewarePipelineV2 {
         &self.middleware
     }
+
+    /// Get the shutdown coordinator, to register shutdown hooks or trigger a shutdown
+    pub fn shutdown(&self) -> &ShutdownCoordinator {
+        &self.shutdown
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,432 @@
+//! Coordinated graceful shutdown
+//!
+//! When the server receives Ctrl+C or SIGTERM, or [`ShutdownCoordinator::trigger`] is
+//! called, it shuts down in two stages:
+//!
+//! 1. **Drain** - the readiness probe starts failing and, after the pre-stop delay
+//!    (`HttpConfig::pre_stop_delay_secs`), the server stops accepting connections while
+//!    in-flight requests finish. Drain hooks run from the start of the stage: WebSocket
+//!    clients receive a going-away close frame and queue workers finish their current
+//!    jobs. The stage ends after `HttpConfig::shutdown_timeout_secs` at the latest.
+//! 2. **Shutdown** - shutdown hooks run one after another in reverse registration order,
+//!    e.g. to shut down the service providers.
+//!
+//! ```rust,ignore
+//! let server = Server::new(container, config)?;
+//! server.shutdown().close_websockets(websocket_server.registry());
+//! server.shutdown().spawn_worker(worker);
+//! server.shutdown().on_shutdown("providers", move || async move {
+//!     if let Err(e) = providers.shutdown(&app_container) {
+//!         tracing::error!("{}", e);
+//!     }
+//! });
+//! server.listen("0.0.0.0:3000").await?;
+//! ```
+
+use crate::errors::HttpResult;
+use crate::websocket::{CloseFrame, ConnectionRegistry};
+use futures_util::future::BoxFuture;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+type Hook = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+struct Inner {
+    triggered: watch::Sender<bool>,
+    stopped_accepting: watch::Sender<bool>,
+    pre_stop_delay: Mutex<Duration>,
+    drain_hooks: Mutex<Vec<(String, Hook)>>,
+    shutdown_hooks: Mutex<Vec<(String, Hook)>>,
+}
+
+/// Coordinates the graceful shutdown of the server and the components it runs
+///
+/// Clones share their state, so the coordinator can be bound in the container with
+/// `bind_instance` and used by the server and service providers alike.
+#[derive(Clone)]
+pub struct ShutdownCoordinator {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for ShutdownCoordinator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownCoordinator")
+            .field("shutting_down", &self.is_shutting_down())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownCoordinator {
+    /// Create a coordinator without hooks
+    pub fn new() -> Self {
+        let (triggered, _) = watch::channel(false);
+        let (stopped_accepting, _) = watch::channel(false);
+        Self {
+            inner: Arc::new(Inner {
+                triggered,
+                stopped_accepting,
+                pre_stop_delay: Mutex::new(Duration::ZERO),
+                drain_hooks: Mutex::new(Vec::new()),
+                shutdown_hooks: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Start shutting down
+    pub fn trigger(&self) {
+        if !self.inner.triggered.send_replace(true) {
+            info!("🛑 Shutdown started, draining connections...");
+        }
+    }
+
+    /// Check whether shutdown has started
+    pub fn is_shutting_down(&self) -> bool {
+        *self.inner.triggered.borrow()
+    }
+
+    /// Wait until shutdown starts
+    pub async fn wait(&self) {
+        let mut triggered = self.inner.triggered.subscribe();
+        // The sender lives as long as `self`, so this only returns once triggered
+        let _ = triggered.wait_for(|triggered| *triggered).await;
+    }
+
+    /// Keep accepting connections for `delay` after shutdown starts
+    ///
+    /// The readiness probe fails during the delay, so load balancers can stop routing new
+    /// connections to the server before its listener closes.
+    pub fn set_pre_stop_delay(&self, delay: Duration) {
+        *self
+            .inner
+            .pre_stop_delay
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = delay;
+    }
+
+    /// Get how long connections are still accepted after shutdown starts
+    pub fn pre_stop_delay(&self) -> Duration {
+        *self
+            .inner
+            .pre_stop_delay
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait until the server must stop accepting connections, once the drain hooks have
+    /// started and the pre-stop delay has passed
+    pub async fn wait_to_stop_accepting(&self) {
+        let mut stopped = self.inner.stopped_accepting.subscribe();
+        let _ = stopped.wait_for(|stopped| *stopped).await;
+    }
+
+    /// Run a hook when shutdown starts, while in-flight requests drain
+    pub fn on_drain<N, F, Fut>(&self, name: N, hook: F)
+    where
+        N: Into<String>,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self::add_hook(&self.inner.drain_hooks, name.into(), hook);
+    }
+
+    /// Run a hook once draining is done, in reverse registration order
+    pub fn on_shutdown<N, F, Fut>(&self, name: N, hook: F)
+    where
+        N: Into<String>,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self::add_hook(&self.inner.shutdown_hooks, name.into(), hook);
+    }
+
+    /// Send the connections of a registry a going-away close frame when shutdown starts
+    pub fn close_websockets(&self, registry: Arc<ConnectionRegistry>) {
+        self.on_drain("websockets", move || async move {
+            let result = registry
+                .close_all_connections_with_reason(
+                    CloseFrame::GOING_AWAY,
+                    "Server shutting down".to_string(),
+                )
+                .await;
+            info!("Closed {} WebSocket connections", result.closed_count);
+            for (id, e) in result.failed_connections {
+                warn!("Failed to close WebSocket connection {}: {}", id, e);
+            }
+        });
+    }
+
+    /// Run a queue worker until shutdown, letting it finish its current jobs
+    #[cfg(feature = "queue")]
+    pub fn spawn_worker<B: elif_queue::QueueBackend + 'static>(
+        &self,
+        worker: elif_queue::Worker<B>,
+    ) {
+        let (stop, stopped) = tokio::sync::mpsc::channel(1);
+        let handle = tokio::spawn(async move { worker.start_with_shutdown(stopped).await });
+
+        self.on_drain("queue worker", move || async move {
+            // The worker may already have stopped on its own
+            let _ = stop.send(()).await;
+            match handle.await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => warn!("Queue worker failed: {}", e),
+                Err(e) => warn!("Queue worker panicked: {}", e),
+            }
+        });
+    }
+
+    /// Run `serve` until shutdown, then drain it and run the hooks
+    ///
+    /// `serve` must stop accepting connections once
+    /// [`wait_to_stop_accepting`](Self::wait_to_stop_accepting) returns and resolve when its
+    /// in-flight requests are done. Requests still running after `timeout`, which includes
+    /// the pre-stop delay, are abandoned.
+    pub async fn run<F>(&self, serve: F, timeout: Duration) -> HttpResult<()>
+    where
+        F: Future<Output = HttpResult<()>>,
+    {
+        let mut serve = std::pin::pin!(serve);
+        let stopped = tokio::select! {
+            result = &mut serve => Some(result),
+            _ = self.wait() => None,
+        };
+        self.trigger();
+
+        let drain = async {
+            let serve = async {
+                match stopped {
+                    Some(result) => result,
+                    None => serve.await,
+                }
+            };
+            // Drain hooks are polled first, so the readiness probe already fails when
+            // the pre-stop delay starts
+            let (_, _, result) =
+                futures_util::future::join3(self.run_drain_hooks(), self.stop_accepting(), serve)
+                    .await;
+            result
+        };
+        let result = match tokio::time::timeout(timeout, drain).await {
+            Ok(result) => result,
+            Err(_) => {
+                warn!(
+                    "Connections still open after the {}s shutdown timeout, abandoning them",
+                    timeout.as_secs()
+                );
+                Ok(())
+            }
+        };
+
+        self.run_shutdown_hooks().await;
+        result
+    }
+
+    async fn stop_accepting(&self) {
+        let delay = self.pre_stop_delay();
+        if !delay.is_zero() {
+            debug!("Accepting connections for {:?} before closing", delay);
+            tokio::time::sleep(delay).await;
+        }
+        self.inner.stopped_accepting.send_replace(true);
+    }
+
+    fn add_hook<F, Fut>(hooks: &Mutex<Vec<(String, Hook)>>, name: String, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let hook: Hook = Box::new(move || Box::pin(hook()));
+        hooks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((name, hook));
+    }
+
+    fn take_hooks(hooks: &Mutex<Vec<(String, Hook)>>) -> Vec<(String, Hook)> {
+        std::mem::take(&mut *hooks.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    async fn run_drain_hooks(&self) {
+        let hooks = Self::take_hooks(&self.inner.drain_hooks);
+        futures_util::future::join_all(hooks.into_iter().map(|(name, hook)| async move {
+            hook().await;
+            debug!("Drain hook '{}' finished", name);
+        }))
+        .await;
+    }
+
+    async fn run_shutdown_hooks(&self) {
+        for (name, hook) in Self::take_hooks(&self.inner.shutdown_hooks)
+            .into_iter()
+            .rev()
+        {
+            debug!("Running shutdown hook '{}'", name);
+            hook().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::{WebSocketConfig, WebSocketConnection};
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite;
+
+    fn record(events: &Arc<Mutex<Vec<&'static str>>>, event: &'static str) {
+        events.lock().unwrap().push(event);
+    }
+
+    #[tokio::test]
+    async fn test_drains_requests_before_shutdown_hooks() {
+        let shutdown = ShutdownCoordinator::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        for name in ["providers", "cache"] {
+            let events = events.clone();
+            shutdown.on_shutdown(name, move || async move { record(&events, name) });
+        }
+        let drain_events = events.clone();
+        shutdown.on_drain("workers", move || async move {
+            record(&drain_events, "workers");
+        });
+
+        let serve_events = events.clone();
+        let serve_shutdown = shutdown.clone();
+        let serve = async move {
+            serve_shutdown.wait().await;
+            // An in-flight request finishing
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            record(&serve_events, "request");
+            Ok(())
+        };
+
+        shutdown.trigger();
+        assert!(shutdown.is_shutting_down());
+        shutdown.run(serve, Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["workers", "request", "cache", "providers"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_before_listener_closes() {
+        use crate::server::health::{HealthRegistry, Probe};
+
+        let shutdown = ShutdownCoordinator::new();
+        shutdown.set_pre_stop_delay(Duration::from_millis(50));
+        let health = HealthRegistry::new();
+        let readiness = health.clone();
+        shutdown.on_drain("readiness", move || async move {
+            readiness.mark_shutting_down()
+        });
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let serve_events = events.clone();
+        let serve_shutdown = shutdown.clone();
+        let serve = async move {
+            serve_shutdown.wait().await;
+            let started = std::time::Instant::now();
+            serve_shutdown.wait_to_stop_accepting().await;
+            assert!(started.elapsed() >= Duration::from_millis(50));
+            record(&serve_events, "stop accepting");
+            Ok(())
+        };
+
+        let probe_events = events.clone();
+        let probe_health = health.clone();
+        let probe = async move {
+            // Within the pre-stop delay: still accepting, but no longer ready
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            if !probe_health.run(Probe::Readiness).await.is_ready() {
+                record(&probe_events, "not ready");
+            }
+        };
+
+        shutdown.trigger();
+        let (result, _) = tokio::join!(shutdown.run(serve, Duration::from_secs(5)), probe);
+        result.unwrap();
+
+        assert_eq!(*events.lock().unwrap(), vec!["not ready", "stop accepting"]);
+    }
+
+    #[tokio::test]
+    async fn test_abandons_requests_after_timeout() {
+        let shutdown = ShutdownCoordinator::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let hook_events = events.clone();
+        shutdown.on_shutdown("providers", move || async move {
+            record(&hook_events, "providers")
+        });
+
+        shutdown.trigger();
+        let result = shutdown
+            .run(std::future::pending(), Duration::from_millis(20))
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(*events.lock().unwrap(), vec!["providers"]);
+    }
+
+    #[tokio::test]
+    async fn test_closes_websockets_with_going_away() {
+        let registry = Arc::new(ConnectionRegistry::new());
+        let (server, client) = tokio::io::duplex(64 * 1024);
+        let (connection, client) = tokio::join!(
+            WebSocketConnection::from_stream(server, WebSocketConfig::default()),
+            tokio_tungstenite::client_async("ws://localhost/", client)
+        );
+        registry.add_connection(connection.unwrap()).await;
+        let mut client = client.unwrap().0;
+
+        let shutdown = ShutdownCoordinator::new();
+        shutdown.close_websockets(registry.clone());
+        shutdown.trigger();
+        shutdown
+            .run(async { Ok(()) }, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let message = tokio::time::timeout(Duration::from_secs(1), client.next())
+            .await
+            .expect("no close frame received")
+            .unwrap()
+            .unwrap();
+        match message {
+            tungstenite::Message::Close(Some(frame)) => {
+                assert_eq!(u16::from(frame.code), CloseFrame::GOING_AWAY);
+            }
+            other => panic!("expected a close frame, got {:?}", other),
+        }
+        assert_eq!(registry.connection_count().await, 0);
+    }
+
+    #[cfg(feature = "queue")]
+    #[tokio::test]
+    async fn test_stops_queue_workers() {
+        use elif_queue::{MemoryBackend, Queue, QueueConfig, Worker, WorkerRegistry};
+
+        let config = QueueConfig::default();
+        let queue = Queue::new(MemoryBackend::new(config.clone()));
+        let shutdown = ShutdownCoordinator::new();
+        shutdown.spawn_worker(Worker::new(queue, WorkerRegistry::new(), config));
+
+        shutdown.trigger();
+        let run = shutdown.run(async { Ok(()) }, Duration::from_secs(60));
+        tokio::time::timeout(Duration::from_secs(5), run)
+            .await
+            .expect("worker did not stop")
+            .unwrap();
+    }
+}
@@ -0,0 +1,503 @@
+//! HTTPS serving with rustls
+//!
+//! Connections are accepted with the current [`ServerConfig`], which is swapped when the
+//! certificate, key or client CA files change on disk. HTTP/2 is negotiated via ALPN and
+//! client certificates (mutual TLS) are exposed to handlers through
+//! [`ElifRequest::client_certificate`](crate::request::ElifRequest::client_certificate).
+
+use crate::config::TlsConfig;
+use crate::errors::{HttpError, HttpResult};
+use crate::request::{ClientCertificate, ConnectionInfo};
+use axum::body::Bytes;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::server::graceful::GracefulShutdown;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+use tracing::{debug, info, warn};
+
+/// Build a rustls server config from TLS settings
+pub fn load_server_config(tls: &TlsConfig) -> HttpResult<ServerConfig> {
+    let certs = CertificateDer::pem_file_iter(&tls.cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| {
+            HttpError::config(format!(
+                "Failed to read TLS certificate {}: {}",
+                tls.cert_path.display(),
+                e
+            ))
+        })?;
+    if certs.is_empty() {
+        return Err(HttpError::config(format!(
+            "No certificates found in {}",
+            tls.cert_path.display()
+        )));
+    }
+
+    let key = PrivateKeyDer::from_pem_file(&tls.key_path).map_err(|e| {
+        HttpError::config(format!(
+            "Failed to read TLS private key {}: {}",
+            tls.key_path.display(),
+            e
+        ))
+    })?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| HttpError::config(format!("Invalid TLS protocol configuration: {}", e)))?;
+
+    let builder = match &tls.client_ca_path {
+        Some(client_ca_path) => {
+            let mut roots = RootCertStore::empty();
+            let ca_certs = CertificateDer::pem_file_iter(client_ca_path)
+                .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+                .map_err(|e| {
+                    HttpError::config(format!(
+                        "Failed to read client CA {}: {}",
+                        client_ca_path.display(),
+                        e
+                    ))
+                })?;
+            for cert in ca_certs {
+                roots.add(cert).map_err(|e| {
+                    HttpError::config(format!("Invalid client CA certificate: {}", e))
+                })?;
+            }
+
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = if tls.require_client_cert {
+                verifier.build()
+            } else {
+                verifier.allow_unauthenticated().build()
+            }
+            .map_err(|e| HttpError::config(format!("Invalid client CA configuration: {}", e)))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| HttpError::config(format!("Invalid TLS certificate or key: {}", e)))?;
+
+    config.alpn_protocols = if tls.enable_http2 {
+        vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+    } else {
+        vec![b"http/1.1".to_vec()]
+    };
+
+    Ok(config)
+}
+
+/// Server TLS config that can be replaced while the server is running
+#[derive(Clone)]
+pub struct ReloadableTlsConfig {
+    current: Arc<RwLock<Arc<ServerConfig>>>,
+}
+
+impl ReloadableTlsConfig {
+    /// Load the initial server config
+    pub fn load(tls: &TlsConfig) -> HttpResult<Self> {
+        Ok(Self {
+            current: Arc::new(RwLock::new(Arc::new(load_server_config(tls)?))),
+        })
+    }
+
+    /// Reload certificates from disk, keeping the current config on failure
+    pub fn reload(&self, tls: &TlsConfig) -> HttpResult<()> {
+        let config = Arc::new(load_server_config(tls)?);
+        // A writer can't leave the config half-replaced, so a poisoned lock is still usable
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = config;
+        Ok(())
+    }
+
+    /// Get the current server config
+    pub fn current(&self) -> Arc<ServerConfig> {
+        Arc::clone(&self.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Get an acceptor using the current server config
+    pub fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.current())
+    }
+
+    /// Watch the certificate files and reload when they change
+    pub fn spawn_reloader(&self, tls: TlsConfig) -> Option<JoinHandle<()>> {
+        let interval = tls.reload_interval()?;
+        let reloadable = self.clone();
+        // Taken before the task runs, so changes made in the meantime are noticed
+        let mut last_seen = file_stamps(&tls);
+
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                let stamps = file_stamps(&tls);
+                if stamps == last_seen {
+                    continue;
+                }
+
+                match reloadable.reload(&tls) {
+                    Ok(()) => {
+                        info!(
+                            "🔐 Reloaded TLS certificate from {}",
+                            tls.cert_path.display()
+                        );
+                        last_seen = stamps;
+                    }
+                    // Files may be mid-rotation, try again on the next change check
+                    Err(e) => warn!("Failed to reload TLS certificate: {}", e),
+                }
+            }
+        }))
+    }
+}
+
+impl std::fmt::Debug for ReloadableTlsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReloadableTlsConfig")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Modification times of the files making up the TLS config
+fn file_stamps(tls: &TlsConfig) -> Vec<Option<SystemTime>> {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut stamps = vec![modified(&tls.cert_path), modified(&tls.key_path)];
+    if let Some(client_ca_path) = &tls.client_ca_path {
+        stamps.push(modified(client_ca_path));
+    }
+    stamps
+}
+
+/// Serve HTTPS connections until the shutdown future resolves
+pub async fn serve_tls<F>(
+    listener: TcpListener,
+    router: axum::Router,
+    tls: &TlsConfig,
+    shutdown: F,
+) -> HttpResult<()>
+where
+    F: Future<Output = ()> + Send,
+{
+    let reloadable = ReloadableTlsConfig::load(tls)?;
+    let reloader = reloadable.spawn_reloader(tls.clone());
+    let graceful = GracefulShutdown::new();
+    let enable_http2 = tls.enable_http2;
+    let handshake_timeout = tls.handshake_timeout();
+    tokio::pin!(shutdown);
+
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("Failed to accept connection: {}", e);
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+
+        let acceptor = reloadable.acceptor();
+        let router = router.clone();
+        let watcher = graceful.watcher();
+
+        tokio::spawn(async move {
+            // Clients that never finish the handshake mustn't hold the connection open
+            let handshake = tokio::time::timeout(handshake_timeout, acceptor.accept(stream));
+            let stream = match handshake.await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => {
+                    debug!("TLS handshake with {} failed: {}", remote_addr, e);
+                    return;
+                }
+                Err(_) => {
+                    debug!("TLS handshake with {} timed out", remote_addr);
+                    return;
+                }
+            };
+
+            let client_certificate = stream.get_ref().1.peer_certificates().map(|chain| {
+                ClientCertificate::new(
+                    chain
+                        .iter()
+                        .map(|cert| Bytes::copy_from_slice(cert.as_ref()))
+                        .collect(),
+                )
+            });
+            let connection = ConnectionInfo::tls(remote_addr, client_certificate);
+
+            let service = hyper::service::service_fn(move |mut request: hyper::Request<_>| {
+                request
+                    .extensions_mut()
+                    .insert(axum::extract::ConnectInfo(remote_addr));
+                request.extensions_mut().insert(connection.clone());
+                router.clone().oneshot(request.map(axum::body::Body::new))
+            });
+
+            let mut builder = Builder::new(TokioExecutor::new());
+            if !enable_http2 {
+                builder = builder.http1_only();
+            }
+            let connection = builder
+                .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                .into_owned();
+
+            if let Err(e) = watcher.watch(connection).await {
+                debug!("Connection from {} closed with error: {}", remote_addr, e);
+            }
+        });
+    }
+
+    drop(listener);
+    if let Some(reloader) = reloader {
+        reloader.abort();
+    }
+    graceful.shutdown().await;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::ElifRequest;
+    use crate::response::ElifResponse;
+    use crate::routing::ElifRouter;
+    use rustls::pki_types::ServerName;
+    use rustls::ClientConfig;
+    use std::path::PathBuf;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsConnector;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    fn server_tls() -> TlsConfig {
+        TlsConfig::new(fixture("server.pem"), fixture("server.key"))
+    }
+
+    fn client_config(with_certificate: bool, alpn: &[u8]) -> ClientConfig {
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_file_iter(fixture("ca.pem")).unwrap() {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let builder =
+            ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .unwrap()
+                .with_root_certificates(roots);
+
+        let mut config = if with_certificate {
+            let certs = CertificateDer::pem_file_iter(fixture("client.pem"))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let key = PrivateKeyDer::from_pem_file(fixture("client.key")).unwrap();
+            builder.with_client_auth_cert(certs, key).unwrap()
+        } else {
+            builder.with_no_client_auth()
+        };
+        config.alpn_protocols = vec![alpn.to_vec()];
+        config
+    }
+
+    async fn whoami(request: ElifRequest) -> crate::errors::HttpResult<ElifResponse> {
+        let certificate_len = request
+            .client_certificate()
+            .map_or(0, |cert| cert.der().len());
+        Ok(ElifResponse::ok().text(format!(
+            "secure={} certificate={}",
+            request.is_secure(),
+            certificate_len
+        )))
+    }
+
+    async fn start(tls: TlsConfig) -> (std::net::SocketAddr, tokio::sync::oneshot::Sender<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = ElifRouter::<()>::new()
+            .get("/whoami", whoami)
+            .into_axum_router();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+
+        tokio::spawn(async move {
+            serve_tls(listener, router, &tls, async {
+                let _ = shutdown_rx.await;
+            })
+            .await
+            .unwrap();
+        });
+
+        (addr, shutdown_tx)
+    }
+
+    async fn get_whoami(
+        addr: std::net::SocketAddr,
+        config: ClientConfig,
+    ) -> std::io::Result<(Option<Vec<u8>>, String)> {
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        let connector = TlsConnector::from(Arc::new(config));
+        let mut stream = connector
+            .connect(ServerName::try_from("localhost").unwrap(), stream)
+            .await?;
+        let alpn = stream.get_ref().1.alpn_protocol().map(|p| p.to_vec());
+
+        stream
+            .write_all(b"GET /whoami HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+        Ok((alpn, String::from_utf8_lossy(&response).to_string()))
+    }
+
+    #[test]
+    fn test_load_server_config() {
+        let config = load_server_config(&server_tls()).unwrap();
+        assert_eq!(
+            config.alpn_protocols,
+            vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+        );
+
+        let config = load_server_config(&server_tls().http2(false)).unwrap();
+        assert_eq!(config.alpn_protocols, vec![b"http/1.1".to_vec()]);
+
+        let missing = TlsConfig::new(fixture("missing.pem"), fixture("server.key"));
+        assert!(load_server_config(&missing).is_err());
+        assert!(ReloadableTlsConfig::load(&missing).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_serves_https_with_alpn_and_client_certificates() {
+        let (addr, _shutdown) = start(
+            server_tls()
+                .client_ca(fixture("ca.pem"))
+                .reload_interval_secs(0),
+        )
+        .await;
+
+        let (alpn, response) = get_whoami(addr, client_config(false, b"http/1.1"))
+            .await
+            .unwrap();
+        assert_eq!(alpn.as_deref(), Some(&b"http/1.1"[..]));
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("secure=true certificate=0"));
+
+        let (_, response) = get_whoami(addr, client_config(true, b"http/1.1"))
+            .await
+            .unwrap();
+        assert!(!response.ends_with("certificate=0"));
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let connector = TlsConnector::from(Arc::new(client_config(false, b"h2")));
+        let stream = connector
+            .connect(ServerName::try_from("localhost").unwrap(), stream)
+            .await
+            .unwrap();
+        assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+    }
+
+    #[tokio::test]
+    async fn test_required_client_certificate() {
+        let (addr, _shutdown) = start(
+            server_tls()
+                .client_ca(fixture("ca.pem"))
+                .require_client_cert(true),
+        )
+        .await;
+
+        assert!(get_whoami(addr, client_config(false, b"http/1.1"))
+            .await
+            .is_err());
+        let (_, response) = get_whoami(addr, client_config(true, b"http/1.1"))
+            .await
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_working_config_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("server.pem");
+        let key_path = dir.path().join("server.key");
+        std::fs::copy(fixture("server.pem"), &cert_path).unwrap();
+        std::fs::copy(fixture("server.key"), &key_path).unwrap();
+        let tls = TlsConfig::new(&cert_path, &key_path);
+
+        let reloadable = ReloadableTlsConfig::load(&tls).unwrap();
+        let before = file_stamps(&tls);
+
+        std::fs::write(&cert_path, "not a certificate").unwrap();
+        assert!(reloadable.reload(&tls).is_err());
+        let _ = reloadable.acceptor();
+
+        std::fs::copy(fixture("server.pem"), &cert_path).unwrap();
+        assert!(reloadable.reload(&tls).is_ok());
+        assert_eq!(file_stamps(&tls).len(), before.len());
+    }
+
+    #[tokio::test]
+    async fn test_reloader_picks_up_changed_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("server.pem");
+        let key_path = dir.path().join("server.key");
+        std::fs::copy(fixture("server.pem"), &cert_path).unwrap();
+        std::fs::copy(fixture("server.key"), &key_path).unwrap();
+        let tls = TlsConfig::new(&cert_path, &key_path).reload_interval_secs(1);
+
+        let reloadable = ReloadableTlsConfig::load(&tls).unwrap();
+        let initial = reloadable.current();
+        let reloader = reloadable.spawn_reloader(tls).unwrap();
+
+        // Rewrite the certificate with a later modification time than the loaded one
+        std::fs::copy(fixture("server.pem"), &cert_path).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&cert_path)
+            .unwrap()
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+
+        let reloaded = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while Arc::ptr_eq(&initial, &reloadable.current()) {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+        })
+        .await;
+        reloader.abort();
+        assert!(reloaded.is_ok(), "the changed certificate was not reloaded");
+    }
+
+    #[tokio::test]
+    async fn test_stalled_handshakes_are_closed() {
+        let (addr, _shutdown) = start(server_tls().handshake_timeout_secs(1)).await;
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+
+        // The client never sends a ClientHello
+        let mut buf = [0u8; 1];
+        let read = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            tokio::io::AsyncReadExt::read(&mut stream, &mut buf),
+        )
+        .await
+        .expect("stalled handshake was not closed");
+        assert!(matches!(read, Ok(0) | Err(_)));
+    }
+}
@@ -0,0 +1,273 @@
+//! Authentication of WebSocket upgrade requests
+//!
+//! A [`WebSocketAuth`] runs an authenticator from `elif-auth` (JWT or session) against the
+//! upgrade request and checks the resulting user with an [`AuthGuard`]. Rejected upgrades get
+//! the same 401/403 responses as [`AuthMiddleware`](crate::auth::AuthMiddleware); accepted
+//! ones carry the user on their [`WebSocketConnection`](super::WebSocketConnection).
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use elif_http::websocket::{WebSocketAuth, WebSocketServer};
+//! use elif_http::auth::RequireAuth;
+//!
+//! let auth = WebSocketAuth::new(jwt_middleware).guard(RequireAuth::new().require_role("user"));
+//! let server = WebSocketServer::builder().auth(auth).build();
+//! ```
+
+use crate::auth::{guard_rejection, unauthorized_response, AuthGuard, RequireAuth, UserContext};
+use crate::request::ElifRequest;
+use crate::response::ElifResponse;
+use async_trait::async_trait;
+use elif_auth::middleware::{JwtMiddleware, SessionMiddleware};
+use elif_auth::providers::session::{SessionData, SessionId};
+use elif_auth::traits::{Authenticatable, SessionStorage};
+use elif_auth::AuthResult;
+use std::sync::Arc;
+
+/// Query parameter carrying a token, since browsers can't set headers on WebSocket requests
+pub const TOKEN_QUERY_PARAM: &str = "token";
+
+/// Resolves the user making a WebSocket upgrade request
+#[async_trait]
+pub trait WebSocketAuthenticator: Send + Sync {
+    /// Authenticate the request
+    ///
+    /// Returns `Ok(None)` when the request carries no credentials and `Err` when the
+    /// credentials are invalid.
+    async fn authenticate(&self, request: &ElifRequest) -> AuthResult<Option<UserContext>>;
+}
+
+/// Bearer token from the `Authorization` header, or the `token` query parameter
+#[async_trait]
+impl<User> WebSocketAuthenticator for JwtMiddleware<User>
+where
+    User: Send + Sync,
+{
+    async fn authenticate(&self, request: &ElifRequest) -> AuthResult<Option<UserContext>> {
+        let header = request
+            .header("authorization")
+            .and_then(|value| value.to_str().ok());
+        let token = match self.extract_token(header)? {
+            Some(token) => Some(token),
+            None => request.query_param(TOKEN_QUERY_PARAM).cloned(),
+        };
+
+        match token {
+            Some(token) => {
+                let claims = self.validate_token(&token)?;
+                Ok(Some(self.create_user_context(&claims)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Session id from the session cookie
+#[async_trait]
+impl<S, U> WebSocketAuthenticator for SessionMiddleware<S, U>
+where
+    S: SessionStorage<SessionId = SessionId, SessionData = SessionData>,
+    U: Authenticatable + Clone + Send + Sync,
+{
+    async fn authenticate(&self, request: &ElifRequest) -> AuthResult<Option<UserContext>> {
+        let session_id = request
+            .header("cookie")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|cookies| self.extract_session_id_from_cookie(cookies));
+
+        match session_id {
+            Some(session_id) => {
+                let session = self.validate_session(&session_id).await?;
+                Ok(Some(self.create_user_context(&session)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Authentication required to upgrade a request to a WebSocket
+#[derive(Clone)]
+pub struct WebSocketAuth {
+    authenticator: Arc<dyn WebSocketAuthenticator>,
+    guard: Arc<dyn AuthGuard + Send + Sync>,
+}
+
+impl WebSocketAuth {
+    /// Require an authenticated user, resolved by the given authenticator
+    pub fn new<A>(authenticator: A) -> Self
+    where
+        A: WebSocketAuthenticator + 'static,
+    {
+        Self {
+            authenticator: Arc::new(authenticator),
+            guard: Arc::new(RequireAuth::new()),
+        }
+    }
+
+    /// Check the user against a guard, e.g. to require roles or to make authentication optional
+    pub fn guard<G>(mut self, guard: G) -> Self
+    where
+        G: AuthGuard + Send + Sync + 'static,
+    {
+        self.guard = Arc::new(guard);
+        self
+    }
+
+    /// Authenticate an upgrade request
+    ///
+    /// Returns the user to attach to the connection, or the response rejecting the upgrade.
+    pub async fn authorize(
+        &self,
+        request: &ElifRequest,
+    ) -> Result<Option<UserContext>, ElifResponse> {
+        if self.guard.should_skip_path(request.path()) {
+            return Ok(None);
+        }
+
+        let user = self
+            .authenticator
+            .authenticate(request)
+            .await
+            .map_err(|error| unauthorized_response(&error.to_string()))?;
+        match guard_rejection(self.guard.as_ref(), user.as_ref()) {
+            Some(response) => Err(response),
+            None => Ok(user),
+        }
+    }
+}
+
+impl std::fmt::Debug for WebSocketAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocketAuth").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::OptionalAuth;
+    use crate::request::ElifMethod;
+    use crate::response::status::ElifStatusCode;
+    use crate::response::ElifHeaderMap;
+    use std::collections::HashMap;
+
+    /// Authenticator accepting the token "secret" from the query string
+    struct TokenAuthenticator;
+
+    #[async_trait]
+    impl WebSocketAuthenticator for TokenAuthenticator {
+        async fn authenticate(&self, request: &ElifRequest) -> AuthResult<Option<UserContext>> {
+            match request.query_param(TOKEN_QUERY_PARAM).map(String::as_str) {
+                Some("secret") => Ok(Some(UserContext::new(
+                    "42".to_string(),
+                    "alice".to_string(),
+                    "test".to_string(),
+                ))),
+                Some(_) => Err(elif_auth::AuthError::token_error("Invalid token")),
+                None => Ok(None),
+            }
+        }
+    }
+
+    fn upgrade_request(token: Option<&str>) -> ElifRequest {
+        let query = token
+            .map(|token| HashMap::from([(TOKEN_QUERY_PARAM.to_string(), token.to_string())]))
+            .unwrap_or_default();
+        ElifRequest::new(
+            ElifMethod::GET,
+            "/ws".parse().unwrap(),
+            ElifHeaderMap::new(),
+        )
+        .with_query_params(query)
+    }
+
+    #[tokio::test]
+    async fn test_authorize_upgrade() {
+        let auth = WebSocketAuth::new(TokenAuthenticator);
+
+        let user = auth
+            .authorize(&upgrade_request(Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(user.unwrap().user_id, "42");
+
+        let rejected = auth.authorize(&upgrade_request(None)).await.unwrap_err();
+        assert_eq!(rejected.status_code(), ElifStatusCode::UNAUTHORIZED);
+
+        let rejected = auth
+            .authorize(&upgrade_request(Some("forged")))
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.status_code(), ElifStatusCode::UNAUTHORIZED);
+
+        let admins =
+            WebSocketAuth::new(TokenAuthenticator).guard(RequireAuth::new().require_role("admin"));
+        let rejected = admins
+            .authorize(&upgrade_request(Some("secret")))
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.status_code(), ElifStatusCode::FORBIDDEN);
+
+        let optional = WebSocketAuth::new(TokenAuthenticator).guard(OptionalAuth::new());
+        assert!(optional
+            .authorize(&upgrade_request(None))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_upgrade() {
+        use crate::routing::ElifRouter;
+        use crate::websocket::WebSocketServer;
+        use std::time::Duration;
+
+        let mut server = WebSocketServer::new();
+        server.set_auth(WebSocketAuth::new(TokenAuthenticator));
+        let router = server.add_websocket_route(ElifRouter::new(), "/ws", |_, _| async {});
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router.into_axum_router())
+                .await
+                .unwrap();
+        });
+
+        let rejected = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr)).await;
+        assert!(matches!(
+            rejected,
+            Err(tokio_tungstenite::tungstenite::Error::Http(response)) if response.status() == 401
+        ));
+        assert_eq!(server.connection_count().await, 0);
+
+        let (_client, _) =
+            tokio_tungstenite::connect_async(format!("ws://{}/ws?token=secret", addr))
+                .await
+                .unwrap();
+        let registry = server.registry();
+        let connection = tokio::time::timeout(Duration::from_secs(1), async {
+            loop {
+                if let Some(connection) = registry.get_all_connections().await.pop() {
+                    return connection;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let user = connection.user().await.unwrap();
+        assert_eq!(user.user_id, "42");
+        assert_eq!(
+            registry
+                .channel_manager()
+                .connection_user(connection.id)
+                .await
+                .unwrap()
+                .username,
+            "alice"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! In-process channel broker
+
+use super::{BrokerEnvelope, BrokerSubscription, ChannelBroker};
+use crate::websocket::types::WebSocketResult;
+use async_trait::async_trait;
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
+
+/// Broker delivering envelopes within the current process
+///
+/// Clones share the same bus, so several channel managers created from clones of one broker
+/// behave like separate nodes.
+#[derive(Debug, Clone)]
+pub struct InMemoryBroker {
+    sender: broadcast::Sender<BrokerEnvelope>,
+}
+
+impl InMemoryBroker {
+    /// Create a broker buffering up to 1024 envelopes per slow subscriber
+    pub fn new() -> Self {
+        Self::with_capacity(1024)
+    }
+
+    /// Create a broker with a custom buffer size
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+}
+
+impl Default for InMemoryBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ChannelBroker for InMemoryBroker {
+    async fn publish(&self, envelope: BrokerEnvelope) -> WebSocketResult<()> {
+        // Sending only fails when nobody is subscribed, which is fine for a single node
+        let _ = self.sender.send(envelope);
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> WebSocketResult<BrokerSubscription> {
+        let mut receiver = self.sender.subscribe();
+        let (sender, subscription) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(envelope) => {
+                        if sender.send(envelope).is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("In-memory broker subscriber skipped {} envelopes", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(subscription)
+    }
+
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::broker::{BrokerEvent, NodeId};
+    use crate::websocket::WebSocketMessage;
+
+    #[tokio::test]
+    async fn test_clones_share_bus() {
+        let broker = InMemoryBroker::new();
+        let mut subscription = broker.clone().subscribe().await.unwrap();
+        let origin = NodeId::new();
+
+        broker
+            .publish(BrokerEnvelope::new(
+                origin,
+                BrokerEvent::Broadcast(WebSocketMessage::text("hello")),
+            ))
+            .await
+            .unwrap();
+
+        let envelope = subscription.recv().await.unwrap();
+        assert_eq!(envelope.origin, origin);
+        assert!(matches!(
+            envelope.event,
+            BrokerEvent::Broadcast(WebSocketMessage::Text(ref text)) if text == "hello"
+        ));
+    }
+}
@@ -0,0 +1,566 @@
+//! Pub/sub brokers for sharing WebSocket channels across server instances
+//!
+//! A [`ChannelManager`](super::ChannelManager) publishes every membership change and channel
+//! message through its [`ChannelBroker`], and a [`ConnectionRegistry`](super::ConnectionRegistry)
+//! listening to the broker applies the events published by other nodes and fans messages out
+//! to its local connections.
+//!
+//! - [`InMemoryBroker`] - single process; clones share one bus, which is useful for tests
+//! - [`RedisBroker`] - Redis pub/sub, for running several instances (`redis` feature)
+//!
+//! ## Example
+//!
+//! ```rust
+//! use elif_http::websocket::{ChannelManager, ConnectionRegistry, InMemoryBroker};
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let broker = Arc::new(InMemoryBroker::new());
+//! let manager = Arc::new(ChannelManager::with_broker(broker));
+//! let registry = Arc::new(ConnectionRegistry::with_channel_manager(manager));
+//!
+//! // Apply events published by other nodes sharing the broker
+//! let _listener = registry.listen_to_broker().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod memory;
+#[cfg(feature = "redis")]
+pub mod redis;
+
+#[cfg(feature = "redis")]
+pub use self::redis::RedisBroker;
+pub use memory::InMemoryBroker;
+
+use super::channel::{ChannelId, ChannelMember, ChannelMessage, ChannelMetadata};
+use super::types::{ConnectionId, WebSocketMessage, WebSocketResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Unique identifier for a server instance sharing channels through a broker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NodeId(pub Uuid);
+
+impl NodeId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for NodeId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Channel state changes and messages replicated between nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BrokerEvent {
+    /// Channel was created
+    ChannelCreated(ChannelMetadata),
+    /// Channel was deleted
+    ChannelDeleted(ChannelId),
+    /// Connection on the publishing node joined a channel
+    MemberJoined(ChannelId, ChannelMember),
+    /// Connection on the publishing node left a channel
+    MemberLeft(ChannelId, ConnectionId),
+    /// Message was sent to a channel
+    ChannelMessage(ChannelMessage),
+    /// Message was broadcast to all connections
+    Broadcast(WebSocketMessage),
+    /// Publishing node is alive; nodes not heard from for a while are considered gone
+    Heartbeat,
+    /// Publishing node started listening and asks the others for their channels
+    SnapshotRequest,
+    /// Channels known to the publishing node and the members connected to it, sent to the
+    /// node that requested them
+    Snapshot {
+        to: NodeId,
+        channels: Vec<ChannelMetadata>,
+        members: Vec<(ChannelId, ChannelMember)>,
+    },
+}
+
+/// Event published by a node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokerEnvelope {
+    /// Node that published the event
+    pub origin: NodeId,
+    pub event: BrokerEvent,
+}
+
+impl BrokerEnvelope {
+    pub fn new(origin: NodeId, event: BrokerEvent) -> Self {
+        Self { origin, event }
+    }
+}
+
+/// Stream of envelopes published by all nodes, including the subscriber itself
+pub type BrokerSubscription = mpsc::UnboundedReceiver<BrokerEnvelope>;
+
+/// Pub/sub transport connecting the channel managers of several nodes
+#[async_trait]
+pub trait ChannelBroker: Send + Sync {
+    /// Publish an envelope to every subscribed node
+    async fn publish(&self, envelope: BrokerEnvelope) -> WebSocketResult<()>;
+
+    /// Subscribe to the envelopes published from now on
+    async fn subscribe(&self) -> WebSocketResult<BrokerSubscription>;
+
+    /// Broker name for logging
+    fn name(&self) -> &'static str;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::{
+        Channel, ChannelManager, ChannelType, ConnectionRegistry, PresenceInfo, WebSocketConfig,
+        WebSocketConnection, WebSocketMessage,
+    };
+    use futures_util::StreamExt;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::DuplexStream;
+    use tokio_tungstenite::{tungstenite, WebSocketStream};
+
+    async fn node(broker: &InMemoryBroker) -> Arc<ConnectionRegistry> {
+        let manager = ChannelManager::with_broker(Arc::new(broker.clone()));
+        let registry = Arc::new(ConnectionRegistry::with_channel_manager(Arc::new(manager)));
+        registry.listen_to_broker().await.unwrap();
+        registry
+    }
+
+    const HEARTBEAT_EXPIRY: Duration = Duration::from_millis(100);
+
+    async fn node_with_heartbeat(
+        broker: &InMemoryBroker,
+    ) -> (Arc<ConnectionRegistry>, tokio::task::JoinHandle<()>) {
+        let manager = ChannelManager::with_broker(Arc::new(broker.clone()))
+            .with_heartbeat(Duration::from_millis(20), HEARTBEAT_EXPIRY);
+        let registry = Arc::new(ConnectionRegistry::with_channel_manager(Arc::new(manager)));
+        let listener = registry.listen_to_broker().await.unwrap();
+        (registry, listener)
+    }
+
+    async fn connect(
+        registry: &ConnectionRegistry,
+    ) -> (ConnectionId, WebSocketStream<DuplexStream>) {
+        let (server, client) = tokio::io::duplex(64 * 1024);
+        let (connection, client) = tokio::join!(
+            WebSocketConnection::from_stream(server, WebSocketConfig::default()),
+            tokio_tungstenite::client_async("ws://localhost/", client)
+        );
+        let id = registry.add_connection(connection.unwrap()).await;
+        (id, client.unwrap().0)
+    }
+
+    async fn next_text(client: &mut WebSocketStream<DuplexStream>) -> String {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(1), client.next())
+                .await
+                .expect("no message received")
+                .unwrap()
+                .unwrap();
+            if let tungstenite::Message::Text(text) = message {
+                return text;
+            }
+        }
+    }
+
+    /// Wait until a condition holds, as broker events are applied in the background
+    macro_rules! eventually {
+        ($condition:expr) => {
+            tokio::time::timeout(Duration::from_secs(2), async {
+                while !$condition {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+            })
+            .await
+            .expect(concat!("timed out waiting for ", stringify!($condition)))
+        };
+    }
+
+    async fn replica(registry: &ConnectionRegistry, channel_id: ChannelId) -> Arc<Channel> {
+        eventually!(registry
+            .channel_manager()
+            .get_channel(channel_id)
+            .await
+            .is_some());
+        registry
+            .channel_manager()
+            .get_channel(channel_id)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_channels_span_nodes() {
+        let broker = InMemoryBroker::new();
+        let node_a = node(&broker).await;
+        let node_b = node(&broker).await;
+        let (alice, mut alice_ws) = connect(&node_a).await;
+        let (bob, mut bob_ws) = connect(&node_b).await;
+
+        let channel_id = node_a
+            .channel_manager()
+            .create_channel("chat".to_string(), ChannelType::Public, Some(alice))
+            .await
+            .unwrap();
+        let replica = replica(&node_b, channel_id).await;
+
+        node_b
+            .channel_manager()
+            .join_channel(channel_id, bob, None, Some("bob".to_string()))
+            .await
+            .unwrap();
+
+        let channel = node_a
+            .channel_manager()
+            .get_channel(channel_id)
+            .await
+            .unwrap();
+        eventually!(channel.has_member(bob).await);
+        assert_eq!(channel.member_count().await, 2);
+        assert!(node_a.channel_manager().is_remote_member(bob).await);
+        assert!(!node_a.channel_manager().is_remote_member(alice).await);
+
+        // Each node delivers to its own connections
+        let result = node_a
+            .send_text_to_channel(channel_id, alice, "hello")
+            .await
+            .unwrap();
+        assert_eq!(result.success_count, 1);
+        for client in [&mut alice_ws, &mut bob_ws] {
+            let delivered: serde_json::Value =
+                serde_json::from_str(&next_text(client).await).unwrap();
+            assert_eq!(delivered["event"], "channel_message");
+            assert_eq!(delivered["channel"], "chat");
+            assert_eq!(delivered["sequence"], 1);
+            assert_eq!(delivered["data"], "hello");
+        }
+
+        assert_eq!(replica.get_message_history().await.len(), 1);
+
+        // Without a shared history each node numbers messages itself, so messages sent on
+        // both nodes at once are all kept
+        node_a
+            .send_text_to_channel(channel_id, alice, "from a")
+            .await
+            .unwrap();
+        node_b
+            .send_text_to_channel(channel_id, bob, "from b")
+            .await
+            .unwrap();
+        for channel in [&channel, &replica] {
+            eventually!(channel.get_message_history().await.len() == 3);
+            let sequences: Vec<u64> = channel
+                .get_message_history()
+                .await
+                .iter()
+                .map(|message| message.sequence)
+                .collect();
+            assert_eq!(sequences, vec![1, 2, 3]);
+        }
+        for _ in 0..2 {
+            next_text(&mut alice_ws).await;
+            next_text(&mut bob_ws).await;
+        }
+
+        node_a.broadcast_text("announcement").await;
+        assert_eq!(next_text(&mut bob_ws).await, "announcement");
+
+        node_b.remove_connection(bob).await;
+        eventually!(!channel.has_member(bob).await);
+        assert_eq!(channel.member_count().await, 1);
+        assert!(!node_a.channel_manager().is_remote_member(bob).await);
+    }
+
+    /// History shared by the nodes of a test, sequencing under one lock
+    #[derive(Default)]
+    struct SharedHistory {
+        messages: tokio::sync::Mutex<Vec<ChannelMessage>>,
+    }
+
+    #[async_trait]
+    impl crate::websocket::ChannelHistory for SharedHistory {
+        async fn append(
+            &self,
+            mut message: ChannelMessage,
+            _limit: Option<usize>,
+        ) -> WebSocketResult<ChannelMessage> {
+            let mut messages = self.messages.lock().await;
+            if message.sequence == 0 {
+                message.sequence = messages.len() as u64 + 1;
+                messages.push(message.clone());
+            }
+            Ok(message)
+        }
+
+        async fn since(
+            &self,
+            channel_id: ChannelId,
+            sequence: u64,
+        ) -> WebSocketResult<Vec<ChannelMessage>> {
+            let messages = self.messages.lock().await;
+            Ok(messages
+                .iter()
+                .filter(|m| m.channel_id == channel_id && m.sequence > sequence)
+                .cloned()
+                .collect())
+        }
+
+        async fn clear(&self, _channel_id: ChannelId) -> WebSocketResult<()> {
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "shared"
+        }
+
+        fn is_shared(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resuming_across_nodes_needs_shared_history() {
+        let broker = InMemoryBroker::new();
+        let node_a = node(&broker).await;
+        let (alice, _alice_ws) = connect(&node_a).await;
+        let channel_id = node_a
+            .channel_manager()
+            .create_channel("chat".to_string(), ChannelType::Public, Some(alice))
+            .await
+            .unwrap();
+
+        // Sequence ids are per node, so they can't be resumed from
+        let (bob, _bob_ws) = connect(&node_a).await;
+        assert!(node_a
+            .join_channel_from(channel_id, bob, None, None, 0)
+            .await
+            .is_err());
+        assert!(
+            !node_a
+                .channel_manager()
+                .get_channel(channel_id)
+                .await
+                .unwrap()
+                .has_member(bob)
+                .await
+        );
+
+        let history = Arc::new(SharedHistory::default());
+        let shared_node = || async {
+            let manager =
+                ChannelManager::with_broker(Arc::new(broker.clone())).with_history(history.clone());
+            let registry = Arc::new(ConnectionRegistry::with_channel_manager(Arc::new(manager)));
+            registry.listen_to_broker().await.unwrap();
+            registry
+        };
+        let node_b = shared_node().await;
+        let node_c = shared_node().await;
+        let (carol, _carol_ws) = connect(&node_b).await;
+        let channel_id = node_b
+            .channel_manager()
+            .create_channel("news".to_string(), ChannelType::Public, Some(carol))
+            .await
+            .unwrap();
+        replica(&node_c, channel_id).await;
+        let (dave, mut dave_ws) = connect(&node_c).await;
+        node_c
+            .channel_manager()
+            .join_channel(channel_id, dave, None, None)
+            .await
+            .unwrap();
+        let channel = node_b
+            .channel_manager()
+            .get_channel(channel_id)
+            .await
+            .unwrap();
+        eventually!(channel.has_member(dave).await);
+
+        // Messages sent on both nodes at once get distinct sequence ids
+        let (sent_b, sent_c) = tokio::join!(
+            node_b.send_text_to_channel(channel_id, carol, "from b"),
+            node_c.send_text_to_channel(channel_id, dave, "from c")
+        );
+        sent_b.unwrap();
+        sent_c.unwrap();
+        // Both messages reached the other node before the client resumes there
+        for _ in 0..2 {
+            next_text(&mut dave_ws).await;
+        }
+
+        // A client that saw nothing resumes on the other node
+        let (erin, mut erin_ws) = connect(&node_c).await;
+        let last = node_c
+            .join_channel_from(channel_id, erin, None, None, 0)
+            .await
+            .unwrap();
+        assert_eq!(last, 2);
+        let mut replayed = Vec::new();
+        for sequence in 1..=2 {
+            let delivered: serde_json::Value =
+                serde_json::from_str(&next_text(&mut erin_ws).await).unwrap();
+            assert_eq!(delivered["sequence"], sequence);
+            replayed.push(delivered["data"].as_str().unwrap().to_string());
+        }
+        replayed.sort();
+        assert_eq!(replayed, vec!["from b", "from c"]);
+    }
+
+    #[tokio::test]
+    async fn test_late_node_receives_snapshot() {
+        let broker = InMemoryBroker::new();
+        let node_a = node(&broker).await;
+        let (alice, _alice_ws) = connect(&node_a).await;
+        let channel_id = node_a
+            .channel_manager()
+            .create_channel("chat".to_string(), ChannelType::Public, Some(alice))
+            .await
+            .unwrap();
+
+        // Joins after the channel and its member were announced
+        let node_b = node(&broker).await;
+
+        let replica = replica(&node_b, channel_id).await;
+        eventually!(replica.has_member(alice).await);
+        assert!(node_b.channel_manager().is_remote_member(alice).await);
+
+        let (bob, _bob_ws) = connect(&node_b).await;
+        node_b
+            .channel_manager()
+            .join_channel(channel_id, bob, None, None)
+            .await
+            .unwrap();
+        let channel = node_a
+            .channel_manager()
+            .get_channel(channel_id)
+            .await
+            .unwrap();
+        eventually!(channel.member_count().await == 2);
+    }
+
+    #[tokio::test]
+    async fn test_members_of_silent_nodes_expire() {
+        let broker = InMemoryBroker::new();
+        let (node_a, _listener_a) = node_with_heartbeat(&broker).await;
+        let (node_b, listener_b) = node_with_heartbeat(&broker).await;
+        let (alice, _alice_ws) = connect(&node_a).await;
+        let (bob, _bob_ws) = connect(&node_b).await;
+
+        let channel_id = node_a
+            .channel_manager()
+            .create_channel("chat".to_string(), ChannelType::Public, Some(alice))
+            .await
+            .unwrap();
+        replica(&node_b, channel_id).await;
+        node_b
+            .channel_manager()
+            .join_channel(channel_id, bob, None, None)
+            .await
+            .unwrap();
+        let channel = node_a
+            .channel_manager()
+            .get_channel(channel_id)
+            .await
+            .unwrap();
+        eventually!(channel.has_member(bob).await);
+
+        // Heartbeats keep members of live nodes past the expiry
+        tokio::time::sleep(HEARTBEAT_EXPIRY * 2).await;
+        assert_eq!(channel.member_count().await, 2);
+
+        // A crashed node stops sending heartbeats without announcing its members left
+        listener_b.abort();
+        eventually!(!channel.has_member(bob).await);
+        assert_eq!(channel.member_count().await, 1);
+        assert!(!node_a.channel_manager().is_remote_member(bob).await);
+    }
+
+    #[tokio::test]
+    async fn test_presence_is_announced_to_members() {
+        let broker = InMemoryBroker::new();
+        let node_a = node(&broker).await;
+        let node_b = node(&broker).await;
+        let (alice, mut alice_ws) = connect(&node_a).await;
+        let (bob, mut bob_ws) = connect(&node_b).await;
+        let alice_info = PresenceInfo::new("1", serde_json::json!({ "username": "alice" }));
+        let bob_info = PresenceInfo::new("2", serde_json::json!({ "username": "bob" }));
+        node_a
+            .channel_manager()
+            .set_connection_presence(alice, alice_info.clone())
+            .await;
+        node_b
+            .channel_manager()
+            .set_connection_presence(bob, bob_info.clone())
+            .await;
+
+        let channel_id = node_a
+            .channel_manager()
+            .create_channel("room".to_string(), ChannelType::Presence, None)
+            .await
+            .unwrap();
+        let replica = replica(&node_b, channel_id).await;
+
+        node_a
+            .join_presence_channel(channel_id, alice, None)
+            .await
+            .unwrap();
+        eventually!(replica.has_member(alice).await);
+        node_b
+            .join_presence_channel(channel_id, bob, None)
+            .await
+            .unwrap();
+
+        let parse = |text: String| serde_json::from_str::<serde_json::Value>(&text).unwrap();
+        let added = parse(next_text(&mut alice_ws).await);
+        assert_eq!(added["event"], "presence_added");
+        assert_eq!(added["member"]["user_id"], "1");
+        // Users arriving on other nodes are announced too
+        let added = parse(next_text(&mut alice_ws).await);
+        assert_eq!(added["event"], "presence_added");
+        assert_eq!(added["channel"], "room");
+        assert_eq!(added["member"]["user_id"], "2");
+        assert_eq!(
+            parse(next_text(&mut bob_ws).await)["member"]["user_id"],
+            "2"
+        );
+
+        // Clients ask for the member list
+        let request = WebSocketMessage::Text(
+            serde_json::json!({ "event": "presence_members", "channel": "room" }).to_string(),
+        );
+        assert!(node_a
+            .handle_channel_request(alice, &request)
+            .await
+            .unwrap());
+        let members = parse(next_text(&mut alice_ws).await);
+        assert_eq!(members["event"], "presence_members");
+        assert_eq!(
+            members["members"],
+            serde_json::json!([alice_info, bob_info])
+        );
+        assert!(!node_a
+            .handle_channel_request(alice, &WebSocketMessage::Text("hello".to_string()))
+            .await
+            .unwrap());
+
+        node_b.remove_connection(bob).await;
+        let removed = parse(next_text(&mut alice_ws).await);
+        assert_eq!(removed["event"], "presence_removed");
+        assert_eq!(removed["member"]["user_id"], "2");
+    }
+}
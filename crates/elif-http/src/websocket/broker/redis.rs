@@ -0,0 +1,152 @@
+//! Redis pub/sub channel broker
+
+use super::{BrokerEnvelope, BrokerSubscription, ChannelBroker};
+use crate::websocket::types::{WebSocketError, WebSocketResult};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use redis::aio::{ConnectionManager, PubSub};
+use redis::AsyncCommands;
+use std::time::Duration;
+use tokio::sync::{mpsc, OnceCell};
+use tracing::{debug, info, warn};
+
+/// Delay before the first attempt to restore a lost subscription, doubled after each failure
+const RECONNECT_DELAY: Duration = Duration::from_millis(100);
+/// Longest delay between attempts to restore a lost subscription
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Broker publishing envelopes as JSON on a Redis pub/sub channel
+pub struct RedisBroker {
+    client: redis::Client,
+    channel: String,
+    publisher: OnceCell<ConnectionManager>,
+}
+
+impl RedisBroker {
+    /// Create a broker for the given Redis URL using the `elif:websocket` channel
+    pub fn new(url: &str) -> WebSocketResult<Self> {
+        let client = redis::Client::open(url).map_err(redis_error)?;
+        Ok(Self::with_client(client))
+    }
+
+    /// Create a broker from an existing Redis client
+    pub fn with_client(client: redis::Client) -> Self {
+        Self {
+            client,
+            channel: "elif:websocket".to_string(),
+            publisher: OnceCell::new(),
+        }
+    }
+
+    /// Use a different pub/sub channel, e.g. to isolate applications sharing a Redis server
+    pub fn channel<T: Into<String>>(mut self, channel: T) -> Self {
+        self.channel = channel.into();
+        self
+    }
+
+    async fn pubsub(&self) -> WebSocketResult<PubSub> {
+        subscribe_pubsub(&self.client, &self.channel)
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn publisher(&self) -> WebSocketResult<ConnectionManager> {
+        self.publisher
+            .get_or_try_init(|| self.client.get_tokio_connection_manager())
+            .await
+            .cloned()
+            .map_err(redis_error)
+    }
+}
+
+impl std::fmt::Debug for RedisBroker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisBroker")
+            .field("channel", &self.channel)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ChannelBroker for RedisBroker {
+    async fn publish(&self, envelope: BrokerEnvelope) -> WebSocketResult<()> {
+        let payload = serde_json::to_string(&envelope)
+            .map_err(|e| WebSocketError::Serialization(e.to_string()))?;
+        let mut publisher = self.publisher().await?;
+        publisher
+            .publish::<_, _, ()>(&self.channel, payload)
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn subscribe(&self) -> WebSocketResult<BrokerSubscription> {
+        let mut pubsub = self.pubsub().await?;
+
+        let (sender, subscription) = mpsc::unbounded_channel();
+        let client = self.client.clone();
+        let channel = self.channel.clone();
+        tokio::spawn(async move {
+            loop {
+                let mut messages = pubsub.into_on_message();
+                while let Some(message) = messages.next().await {
+                    let envelope = message
+                        .get_payload::<String>()
+                        .ok()
+                        .and_then(|payload| serde_json::from_str::<BrokerEnvelope>(&payload).ok());
+
+                    match envelope {
+                        Some(envelope) => {
+                            if sender.send(envelope).is_err() {
+                                debug!("Redis broker subscription to {} ended", channel);
+                                return;
+                            }
+                        }
+                        None => warn!("Ignoring malformed envelope on Redis channel {}", channel),
+                    }
+                }
+                drop(messages);
+
+                // Envelopes published until the subscription is restored are lost
+                warn!(
+                    "Lost Redis broker subscription to {}, reconnecting",
+                    channel
+                );
+                let mut delay = RECONNECT_DELAY;
+                pubsub = loop {
+                    tokio::time::sleep(delay).await;
+                    if sender.is_closed() {
+                        return;
+                    }
+                    match subscribe_pubsub(&client, &channel).await {
+                        Ok(pubsub) => break pubsub,
+                        Err(e) => {
+                            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                            warn!(
+                                "Failed to resubscribe to Redis channel {}, retrying in {:?}: {}",
+                                channel, delay, e
+                            );
+                        }
+                    }
+                };
+                info!("Resubscribed to Redis channel {}", channel);
+            }
+        });
+
+        Ok(subscription)
+    }
+
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+}
+
+/// Open a pub/sub connection subscribed to `channel`
+async fn subscribe_pubsub(client: &redis::Client, channel: &str) -> redis::RedisResult<PubSub> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(channel).await?;
+    Ok(pubsub)
+}
+
+fn redis_error(error: redis::RedisError) -> WebSocketError {
+    WebSocketError::Connection(format!("Redis broker error: {}", error))
+}
@@ -0,0 +1,141 @@
+//! Authorization callbacks for joining channels by name pattern
+
+use super::super::types::ConnectionId;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Future returned by a channel authorization callback
+pub type ChannelAuthFuture = Pin<Box<dyn Future<Output = bool> + Send>>;
+
+type ChannelAuthCallback = Arc<dyn Fn(ChannelAuthRequest) -> ChannelAuthFuture + Send + Sync>;
+
+/// Connection asking to join a channel whose name matches an authorization pattern
+#[derive(Debug, Clone)]
+pub struct ChannelAuthRequest {
+    /// Connection joining the channel
+    pub connection_id: ConnectionId,
+    /// Name of the channel
+    pub channel: String,
+    /// Parameters captured by the pattern, e.g. `id` for `orders.{id}`
+    pub params: HashMap<String, String>,
+    /// User authenticated when the connection was upgraded
+    #[cfg(feature = "auth")]
+    pub user: Option<crate::auth::UserContext>,
+}
+
+impl ChannelAuthRequest {
+    /// Get a parameter captured by the pattern
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+}
+
+/// Channel name pattern made of `.`-separated segments, where `{name}` matches any segment
+///
+/// `orders.{id}` matches `orders.42` and captures `id = "42"`, but neither `orders` nor
+/// `orders.42.items`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelPattern {
+    pattern: String,
+    segments: Vec<PatternSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternSegment {
+    Literal(String),
+    Param(String),
+}
+
+impl ChannelPattern {
+    /// Parse a pattern
+    pub fn new(pattern: &str) -> Self {
+        let segments = pattern
+            .split('.')
+            .map(|segment| {
+                match segment
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.strip_suffix('}'))
+                {
+                    Some(name) => PatternSegment::Param(name.to_string()),
+                    None => PatternSegment::Literal(segment.to_string()),
+                }
+            })
+            .collect();
+
+        Self {
+            pattern: pattern.to_string(),
+            segments,
+        }
+    }
+
+    /// Get the pattern as written
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Match a channel name, returning the captured parameters
+    pub fn matches(&self, channel: &str) -> Option<HashMap<String, String>> {
+        let parts: Vec<&str> = channel.split('.').collect();
+        if parts.len() != self.segments.len() {
+            return None;
+        }
+
+        let mut params = HashMap::new();
+        for (segment, part) in self.segments.iter().zip(parts) {
+            match segment {
+                PatternSegment::Literal(literal) if literal == part => {}
+                PatternSegment::Param(name) if !part.is_empty() => {
+                    params.insert(name.clone(), part.to_string());
+                }
+                _ => return None,
+            }
+        }
+        Some(params)
+    }
+}
+
+/// Callback deciding who may join the channels matching a pattern
+#[derive(Clone)]
+pub(crate) struct ChannelAuthorizer {
+    pub(crate) pattern: ChannelPattern,
+    pub(crate) callback: ChannelAuthCallback,
+}
+
+impl ChannelAuthorizer {
+    pub(crate) fn new<F, Fut>(pattern: &str, callback: F) -> Self
+    where
+        F: Fn(ChannelAuthRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        Self {
+            pattern: ChannelPattern::new(pattern),
+            callback: Arc::new(move |request| Box::pin(callback(request))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matching() {
+        let pattern = ChannelPattern::new("orders.{id}");
+
+        let params = pattern.matches("orders.42").unwrap();
+        assert_eq!(params.get("id").map(String::as_str), Some("42"));
+
+        assert!(pattern.matches("orders").is_none());
+        assert!(pattern.matches("orders.").is_none());
+        assert!(pattern.matches("orders.42.items").is_none());
+        assert!(pattern.matches("invoices.42").is_none());
+
+        let pattern = ChannelPattern::new("teams.{team}.members.{user}");
+        let params = pattern.matches("teams.7.members.alice").unwrap();
+        assert_eq!(params.get("team").map(String::as_str), Some("7"));
+        assert_eq!(params.get("user").map(String::as_str), Some("alice"));
+        assert!(ChannelPattern::new("lobby").matches("lobby").is_some());
+    }
+}
@@ -4,11 +4,12 @@ use super::super::types::{ConnectionId, WebSocketError, WebSocketResult};
 use super::message::ChannelMessage;
 use super::types::{
     ChannelId, ChannelMember, ChannelMetadata, ChannelPermissions, ChannelStats, ChannelType,
+    PresenceInfo,
 };
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::SystemTime;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, MutexGuard, RwLock};
 use tracing::{debug, info};
 
 /// A WebSocket channel that manages members and message distribution
@@ -17,7 +18,16 @@ pub struct Channel {
     pub id: ChannelId,
     pub metadata: ChannelMetadata,
     members: Arc<RwLock<HashMap<ConnectionId, ChannelMember>>>,
-    message_history: Arc<RwLock<VecDeque<ChannelMessage>>>,
+    message_history: Arc<RwLock<MessageLog>>,
+    /// Held while sequencing and delivering messages so members receive them in order
+    delivery: Mutex<()>,
+}
+
+/// Recent messages of a channel and the last sequence id handed out
+#[derive(Debug, Default)]
+struct MessageLog {
+    messages: VecDeque<ChannelMessage>,
+    last_sequence: u64,
 }
 
 impl Channel {
@@ -38,7 +48,8 @@ impl Channel {
             id,
             metadata,
             members: Arc::new(RwLock::new(HashMap::new())),
-            message_history: Arc::new(RwLock::new(VecDeque::new())),
+            message_history: Arc::new(RwLock::new(MessageLog::default())),
+            delivery: Mutex::new(()),
         }
     }
 
@@ -50,7 +61,8 @@ impl Channel {
             id,
             metadata,
             members: Arc::new(RwLock::new(HashMap::new())),
-            message_history: Arc::new(RwLock::new(VecDeque::new())),
+            message_history: Arc::new(RwLock::new(MessageLog::default())),
+            delivery: Mutex::new(()),
         }
     }
 
@@ -61,6 +73,32 @@ impl Channel {
         permissions: ChannelPermissions,
         nickname: Option<String>,
     ) -> WebSocketResult<()> {
+        self.add_member_with_presence(connection_id, permissions, nickname, None)
+            .await
+            .map(|_| ())
+    }
+
+    /// Add a member of a presence channel
+    ///
+    /// Returns `true` if this is the user's first connection in the channel.
+    pub async fn add_presence_member(
+        &self,
+        connection_id: ConnectionId,
+        permissions: ChannelPermissions,
+        nickname: Option<String>,
+        presence: PresenceInfo,
+    ) -> WebSocketResult<bool> {
+        self.add_member_with_presence(connection_id, permissions, nickname, Some(presence))
+            .await
+    }
+
+    async fn add_member_with_presence(
+        &self,
+        connection_id: ConnectionId,
+        permissions: ChannelPermissions,
+        nickname: Option<String>,
+        presence: Option<PresenceInfo>,
+    ) -> WebSocketResult<bool> {
         let mut members = self.members.write().await;
 
         // Check if channel is at capacity
@@ -79,29 +117,101 @@ impl Channel {
             ));
         }
 
+        let newly_present = presence
+            .as_ref()
+            .is_some_and(|presence| !Self::user_present(&members, &presence.user_id));
+
         let member = ChannelMember {
             connection_id,
             joined_at: SystemTime::now(),
             permissions,
             nickname,
+            presence,
         };
 
         members.insert(connection_id, member);
         info!("Added member {} to channel {}", connection_id, self.id);
 
-        Ok(())
+        Ok(newly_present)
     }
 
-    /// Remove a member from the channel
-    pub async fn remove_member(&self, connection_id: ConnectionId) -> Option<ChannelMember> {
+    /// Insert a member admitted on another node, skipping the capacity check
+    ///
+    /// Returns `true` if the member's user became present in the channel.
+    pub(crate) async fn insert_member(&self, member: ChannelMember) -> bool {
         let mut members = self.members.write().await;
-        let member = members.remove(&connection_id);
+        let newly_present = member
+            .presence
+            .as_ref()
+            .is_some_and(|presence| !Self::user_present(&members, &presence.user_id));
+        debug!(
+            "Added remote member {} to channel {}",
+            member.connection_id, self.id
+        );
+        members.insert(member.connection_id, member);
+        newly_present
+    }
+
+    /// Check if a user is present with at least one connection
+    pub async fn is_user_present(&self, user_id: &str) -> bool {
+        let members = self.members.read().await;
+        Self::user_present(&members, user_id)
+    }
 
-        if member.is_some() {
-            info!("Removed member {} from channel {}", connection_id, self.id);
+    /// Get the users present in the channel, once per user, in order of arrival
+    pub async fn presence_members(&self) -> Vec<PresenceInfo> {
+        let members = self.members.read().await;
+        let mut present: Vec<&ChannelMember> = members
+            .values()
+            .filter(|member| member.presence.is_some())
+            .collect();
+        present.sort_by_key(|member| member.joined_at);
+
+        let mut users: Vec<PresenceInfo> = Vec::new();
+        for presence in present
+            .into_iter()
+            .filter_map(|member| member.presence.as_ref())
+        {
+            if !users.iter().any(|user| user.user_id == presence.user_id) {
+                users.push(presence.clone());
+            }
         }
+        users
+    }
+
+    fn user_present(members: &HashMap<ConnectionId, ChannelMember>, user_id: &str) -> bool {
+        members.values().any(|member| {
+            member
+                .presence
+                .as_ref()
+                .is_some_and(|presence| presence.user_id == user_id)
+        })
+    }
+
+    /// Remove a member from the channel
+    pub async fn remove_member(&self, connection_id: ConnectionId) -> Option<ChannelMember> {
+        self.take_member(connection_id)
+            .await
+            .map(|(member, _)| member)
+    }
 
-        member
+    /// Remove a member, also returning its user if that was their last connection
+    ///
+    /// Both happen under one lock, so concurrent removals of a user's connections report
+    /// the user leaving exactly once.
+    pub(crate) async fn take_member(
+        &self,
+        connection_id: ConnectionId,
+    ) -> Option<(ChannelMember, Option<PresenceInfo>)> {
+        let mut members = self.members.write().await;
+        let member = members.remove(&connection_id)?;
+        info!("Removed member {} from channel {}", connection_id, self.id);
+
+        let left = member
+            .presence
+            .clone()
+            .filter(|presence| !Self::user_present(&members, &presence.user_id));
+        Some((member, left))
     }
 
     /// Get a member by connection ID
@@ -187,40 +297,87 @@ impl Channel {
     }
 
     /// Add a message to the channel history
-    pub async fn add_message(&self, message: ChannelMessage) {
+    ///
+    /// A message without a sequence id gets the channel's next one. A message sequenced
+    /// by a shared [`ChannelHistory`](super::ChannelHistory) is kept at its position, as
+    /// replicated messages may arrive after newer local ones; a sequence id already in the
+    /// history is not recorded twice. Returns the sequenced message; with a history limit of
+    /// 0 the message is sequenced but not kept.
+    pub async fn add_message(&self, mut message: ChannelMessage) -> ChannelMessage {
         let mut history = self.message_history.write().await;
 
-        // Add the message to the back (newest)
-        history.push_back(message);
+        if message.sequence == 0 {
+            history.last_sequence += 1;
+            message.sequence = history.last_sequence;
+            history.messages.push_back(message.clone());
+        } else {
+            let position = history
+                .messages
+                .partition_point(|stored| stored.sequence < message.sequence);
+            if history
+                .messages
+                .get(position)
+                .is_some_and(|stored| stored.sequence == message.sequence)
+            {
+                return message;
+            }
+            history.last_sequence = history.last_sequence.max(message.sequence);
+            history.messages.insert(position, message.clone());
+        }
 
         // Trim history if needed - remove from front (oldest) efficiently
         if let Some(limit) = self.metadata.message_history_limit {
-            while history.len() > limit {
-                history.pop_front(); // O(1) operation with VecDeque
+            while history.messages.len() > limit {
+                history.messages.pop_front(); // O(1) operation with VecDeque
             }
         }
+
+        message
     }
 
     /// Get recent messages from the channel
     pub async fn get_recent_messages(&self, count: usize) -> Vec<ChannelMessage> {
         let history = self.message_history.read().await;
-        let start = history.len().saturating_sub(count);
-        history.iter().skip(start).cloned().collect()
+        let start = history.messages.len().saturating_sub(count);
+        history.messages.iter().skip(start).cloned().collect()
     }
 
     /// Get all message history
     pub async fn get_message_history(&self) -> Vec<ChannelMessage> {
         let history = self.message_history.read().await;
-        history.iter().cloned().collect()
+        history.messages.iter().cloned().collect()
+    }
+
+    /// Get the messages in the history sent after the given sequence id
+    pub async fn messages_since(&self, sequence: u64) -> Vec<ChannelMessage> {
+        let history = self.message_history.read().await;
+        history
+            .messages
+            .iter()
+            .filter(|message| message.sequence > sequence)
+            .cloned()
+            .collect()
+    }
+
+    /// Get the sequence id of the last message sent to the channel, or 0
+    pub async fn last_sequence(&self) -> u64 {
+        self.message_history.read().await.last_sequence
     }
 
     /// Clear message history
+    ///
+    /// Sequence ids keep increasing, so clients resuming later don't miss new messages.
     pub async fn clear_message_history(&self) {
         let mut history = self.message_history.write().await;
-        history.clear();
+        history.messages.clear();
         debug!("Cleared message history for channel {}", self.id);
     }
 
+    /// Wait until no other message of the channel is being sequenced and delivered
+    pub(crate) async fn lock_delivery(&self) -> MutexGuard<'_, ()> {
+        self.delivery.lock().await
+    }
+
     /// Check if a member has a specific permission
     pub async fn member_has_permission(
         &self,
@@ -257,7 +414,7 @@ impl Channel {
             id: self.id,
             name: self.metadata.name.clone(),
             member_count: members.len(),
-            message_count: history.len(),
+            message_count: history.messages.len(),
             channel_type: self.metadata.channel_type.clone(),
             created_at: self.metadata.created_at,
             is_empty: members.is_empty(),
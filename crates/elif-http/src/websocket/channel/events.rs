@@ -2,7 +2,7 @@
 
 use super::super::types::ConnectionId;
 use super::message::ChannelMessage;
-use super::types::{ChannelId, ChannelPermissions};
+use super::types::{ChannelId, ChannelPermissions, PresenceInfo};
 
 /// Events that can occur in the channel system
 #[derive(Debug, Clone)]
@@ -19,4 +19,8 @@ pub enum ChannelEvent {
     MessageSent(ChannelId, ChannelMessage),
     /// Member permissions were updated
     PermissionsUpdated(ChannelId, ConnectionId, ChannelPermissions),
+    /// User became present in a presence channel with their first connection
+    MemberAdded(ChannelId, PresenceInfo),
+    /// User left a presence channel with their last connection
+    MemberRemoved(ChannelId, PresenceInfo),
 }
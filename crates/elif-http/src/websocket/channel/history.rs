@@ -0,0 +1,451 @@
+//! Stores for channel message history shared beyond a single process
+//!
+//! Every [`Channel`](super::Channel) keeps its recent messages in memory. A
+//! [`ChannelHistory`] given to the [`ChannelManager`](super::ChannelManager) additionally
+//! sequences and stores the messages in a backend that can outlive the process and be
+//! shared by several nodes, so clients can resume from it after reconnecting elsewhere.
+//!
+//! - [`CacheHistory`] - any `elif-cache` backend, for a single node (`cache` feature)
+//! - `RedisHistory` - Redis, sequencing atomically for all nodes sharing it (`redis` feature)
+
+use super::message::ChannelMessage;
+use super::types::ChannelId;
+use crate::websocket::types::WebSocketResult;
+use async_trait::async_trait;
+
+#[cfg(feature = "cache")]
+pub use self::cache::CacheHistory;
+#[cfg(feature = "redis")]
+pub use self::redis::RedisHistory;
+
+/// Backend storing the bounded message history of channels
+#[async_trait]
+pub trait ChannelHistory: Send + Sync {
+    /// Store a message, keeping at most `limit` messages of its channel
+    ///
+    /// A message without a sequence id gets the channel's next one. A message that already
+    /// has one was sequenced by another node and is stored at its position, unless its
+    /// sequence id is already stored. Returns the sequenced message.
+    async fn append(
+        &self,
+        message: ChannelMessage,
+        limit: Option<usize>,
+    ) -> WebSocketResult<ChannelMessage>;
+
+    /// Get the stored messages of a channel sent after the given sequence id
+    async fn since(
+        &self,
+        channel_id: ChannelId,
+        sequence: u64,
+    ) -> WebSocketResult<Vec<ChannelMessage>>;
+
+    /// Remove the history of a channel
+    async fn clear(&self, channel_id: ChannelId) -> WebSocketResult<()>;
+
+    /// Store name for logging
+    fn name(&self) -> &'static str;
+
+    /// Whether every node using the store gets sequence ids from one atomic counter
+    ///
+    /// Only such stores sequence the messages of channel managers sharing their channels
+    /// through a broker.
+    fn is_shared(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "cache")]
+mod cache {
+    use super::ChannelHistory;
+    use crate::websocket::channel::{ChannelId, ChannelMessage};
+    use crate::websocket::types::{WebSocketError, WebSocketResult};
+    use async_trait::async_trait;
+    use elif_cache::{CacheBackend, CacheError};
+    use serde::{Deserialize, Serialize};
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Mutex;
+
+    /// Channel history kept in an `elif-cache` backend
+    ///
+    /// The history of each channel is stored as one entry under `{prefix}:{channel_id}`.
+    ///
+    /// An append loads the entry, adds the message and puts the entry back. Appends are
+    /// serialized within the process only, so the store suits a single node, e.g. to let
+    /// clients resume after a restart. Channel managers sharing their channels through a
+    /// broker need a store that [is shared](ChannelHistory::is_shared), such as
+    /// `RedisHistory`.
+    pub struct CacheHistory {
+        backend: Arc<dyn CacheBackend>,
+        prefix: String,
+        ttl: Option<Duration>,
+        lock: Mutex<()>,
+    }
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct StoredLog {
+        last_sequence: u64,
+        messages: VecDeque<ChannelMessage>,
+    }
+
+    impl CacheHistory {
+        /// Store histories in a cache backend
+        pub fn new(backend: Arc<dyn CacheBackend>) -> Self {
+            Self {
+                backend,
+                prefix: "ws_channel_history".to_string(),
+                ttl: None,
+                lock: Mutex::new(()),
+            }
+        }
+
+        /// Set the prefix of the cache keys
+        pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+            self.prefix = prefix.into();
+            self
+        }
+
+        /// Expire the history of a channel when no message was sent for a while
+        pub fn ttl(mut self, ttl: Duration) -> Self {
+            self.ttl = Some(ttl);
+            self
+        }
+
+        fn key(&self, channel_id: ChannelId) -> String {
+            format!("{}:{}", self.prefix, channel_id)
+        }
+
+        async fn load(&self, key: &str) -> WebSocketResult<StoredLog> {
+            match self.backend.get(key).await.map_err(cache_error)? {
+                Some(bytes) => serde_json::from_slice(&bytes)
+                    .map_err(|e| WebSocketError::Serialization(e.to_string())),
+                None => Ok(StoredLog::default()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ChannelHistory for CacheHistory {
+        async fn append(
+            &self,
+            mut message: ChannelMessage,
+            limit: Option<usize>,
+        ) -> WebSocketResult<ChannelMessage> {
+            let _guard = self.lock.lock().await;
+            let key = self.key(message.channel_id);
+            let mut log = self.load(&key).await?;
+
+            if message.sequence == 0 {
+                log.last_sequence += 1;
+                message.sequence = log.last_sequence;
+                log.messages.push_back(message.clone());
+            } else {
+                let position = log
+                    .messages
+                    .partition_point(|stored| stored.sequence < message.sequence);
+                if log
+                    .messages
+                    .get(position)
+                    .is_some_and(|stored| stored.sequence == message.sequence)
+                {
+                    return Ok(message);
+                }
+                log.last_sequence = log.last_sequence.max(message.sequence);
+                log.messages.insert(position, message.clone());
+            }
+
+            if let Some(limit) = limit {
+                while log.messages.len() > limit {
+                    log.messages.pop_front();
+                }
+            }
+
+            let bytes = serde_json::to_vec(&log)
+                .map_err(|e| WebSocketError::Serialization(e.to_string()))?;
+            self.backend
+                .put(&key, bytes, self.ttl)
+                .await
+                .map_err(cache_error)?;
+
+            Ok(message)
+        }
+
+        async fn since(
+            &self,
+            channel_id: ChannelId,
+            sequence: u64,
+        ) -> WebSocketResult<Vec<ChannelMessage>> {
+            let log = self.load(&self.key(channel_id)).await?;
+            Ok(log
+                .messages
+                .into_iter()
+                .filter(|message| message.sequence > sequence)
+                .collect())
+        }
+
+        async fn clear(&self, channel_id: ChannelId) -> WebSocketResult<()> {
+            let _guard = self.lock.lock().await;
+            let key = self.key(channel_id);
+            let mut log = self.load(&key).await?;
+            if log.last_sequence == 0 {
+                return Ok(());
+            }
+
+            // Keep the sequence so resuming clients don't confuse new messages with old ones
+            log.messages.clear();
+            let bytes = serde_json::to_vec(&log)
+                .map_err(|e| WebSocketError::Serialization(e.to_string()))?;
+            self.backend
+                .put(&key, bytes, self.ttl)
+                .await
+                .map_err(cache_error)
+        }
+
+        fn name(&self) -> &'static str {
+            "cache"
+        }
+    }
+
+    fn cache_error(error: CacheError) -> WebSocketError {
+        WebSocketError::Connection(format!("Channel history unavailable: {}", error))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::websocket::types::{ConnectionId, WebSocketMessage};
+        use elif_cache::{CacheConfig, MemoryBackend};
+
+        fn message(channel_id: ChannelId, text: &str) -> ChannelMessage {
+            ChannelMessage::new(
+                channel_id,
+                ConnectionId::new(),
+                WebSocketMessage::text(text),
+                None,
+            )
+        }
+
+        #[tokio::test]
+        async fn test_sequences_and_bounds_history() {
+            let history = CacheHistory::new(Arc::new(MemoryBackend::new(CacheConfig::default())));
+            let channel_id = ChannelId::from_name("general");
+
+            for text in ["one", "two", "three"] {
+                history
+                    .append(message(channel_id, text), Some(2))
+                    .await
+                    .unwrap();
+            }
+
+            let messages = history.since(channel_id, 0).await.unwrap();
+            let sequences: Vec<u64> = messages.iter().map(|m| m.sequence).collect();
+            assert_eq!(sequences, vec![2, 3]);
+            assert_eq!(history.since(channel_id, 2).await.unwrap().len(), 1);
+
+            // Messages sequenced elsewhere are stored once
+            let mut replicated = message(channel_id, "four");
+            replicated.sequence = 4;
+            history.append(replicated.clone(), Some(2)).await.unwrap();
+            history.append(replicated, Some(2)).await.unwrap();
+            assert_eq!(history.since(channel_id, 3).await.unwrap().len(), 1);
+
+            // Clearing keeps the sequence going
+            history.clear(channel_id).await.unwrap();
+            assert!(history.since(channel_id, 0).await.unwrap().is_empty());
+            let next = history.append(message(channel_id, "five"), Some(2)).await;
+            assert_eq!(next.unwrap().sequence, 5);
+        }
+    }
+}
+
+#[cfg(feature = "redis")]
+mod redis {
+    use super::ChannelHistory;
+    use crate::websocket::channel::{ChannelId, ChannelMessage};
+    use crate::websocket::types::{WebSocketError, WebSocketResult};
+    use async_trait::async_trait;
+    use redis::aio::ConnectionManager;
+    use redis::{AsyncCommands, Script};
+    use std::time::Duration;
+    use tokio::sync::OnceCell;
+
+    /// Sequences a message and stores it, trimming the history and refreshing the TTL
+    ///
+    /// KEYS: sequence counter, messages sorted by sequence id.
+    /// ARGV: sequence id (0 to take the next one), message, limit (-1 for none), TTL in ms
+    /// (0 for none). Returns the sequence id, or 0 if the message was already stored.
+    const APPEND_SCRIPT: &str = r#"
+local sequence = tonumber(ARGV[1])
+if sequence == 0 then
+    sequence = redis.call('INCR', KEYS[1])
+else
+    if #redis.call('ZRANGEBYSCORE', KEYS[2], sequence, sequence) > 0 then
+        return 0
+    end
+    if sequence > tonumber(redis.call('GET', KEYS[1]) or '0') then
+        redis.call('SET', KEYS[1], sequence)
+    end
+end
+redis.call('ZADD', KEYS[2], sequence, ARGV[2])
+local limit = tonumber(ARGV[3])
+if limit >= 0 then
+    redis.call('ZREMRANGEBYRANK', KEYS[2], 0, -limit - 1)
+end
+local ttl = tonumber(ARGV[4])
+if ttl > 0 then
+    redis.call('PEXPIRE', KEYS[1], ttl)
+    redis.call('PEXPIRE', KEYS[2], ttl)
+end
+return sequence
+"#;
+
+    /// Channel history kept in Redis
+    ///
+    /// Each channel has a counter under `{prefix}:{channel_id}:sequence` and its messages in
+    /// a sorted set under `{prefix}:{channel_id}:messages`. Appends run as one script, so
+    /// every node sharing the server gets distinct, increasing sequence ids.
+    pub struct RedisHistory {
+        client: redis::Client,
+        prefix: String,
+        ttl: Option<Duration>,
+        append: Script,
+        connection: OnceCell<ConnectionManager>,
+    }
+
+    impl RedisHistory {
+        /// Store histories on the Redis server at the given URL
+        pub fn new(url: &str) -> WebSocketResult<Self> {
+            let client = redis::Client::open(url).map_err(redis_error)?;
+            Ok(Self::with_client(client))
+        }
+
+        /// Store histories using an existing Redis client
+        pub fn with_client(client: redis::Client) -> Self {
+            Self {
+                client,
+                prefix: "ws_channel_history".to_string(),
+                ttl: None,
+                append: Script::new(APPEND_SCRIPT),
+                connection: OnceCell::new(),
+            }
+        }
+
+        /// Set the prefix of the Redis keys
+        pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+            self.prefix = prefix.into();
+            self
+        }
+
+        /// Expire the history of a channel when no message was sent for a while
+        pub fn ttl(mut self, ttl: Duration) -> Self {
+            self.ttl = Some(ttl);
+            self
+        }
+
+        fn sequence_key(&self, channel_id: ChannelId) -> String {
+            format!("{}:{}:sequence", self.prefix, channel_id)
+        }
+
+        fn messages_key(&self, channel_id: ChannelId) -> String {
+            format!("{}:{}:messages", self.prefix, channel_id)
+        }
+
+        async fn connection(&self) -> WebSocketResult<ConnectionManager> {
+            self.connection
+                .get_or_try_init(|| self.client.get_tokio_connection_manager())
+                .await
+                .cloned()
+                .map_err(redis_error)
+        }
+    }
+
+    impl std::fmt::Debug for RedisHistory {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RedisHistory")
+                .field("prefix", &self.prefix)
+                .field("ttl", &self.ttl)
+                .finish()
+        }
+    }
+
+    #[async_trait]
+    impl ChannelHistory for RedisHistory {
+        async fn append(
+            &self,
+            mut message: ChannelMessage,
+            limit: Option<usize>,
+        ) -> WebSocketResult<ChannelMessage> {
+            // The sequence id is the message's score, so it is stored without one
+            let mut stored = message.clone();
+            stored.sequence = 0;
+            let payload = serde_json::to_string(&stored)
+                .map_err(|e| WebSocketError::Serialization(e.to_string()))?;
+
+            let mut connection = self.connection().await?;
+            let sequence: u64 = self
+                .append
+                .key(self.sequence_key(message.channel_id))
+                .key(self.messages_key(message.channel_id))
+                .arg(message.sequence)
+                .arg(payload)
+                .arg(limit.map_or(-1, |limit| limit as i64))
+                .arg(self.ttl.map_or(0, |ttl| ttl.as_millis() as u64))
+                .invoke_async(&mut connection)
+                .await
+                .map_err(redis_error)?;
+
+            if sequence != 0 {
+                message.sequence = sequence;
+            }
+            Ok(message)
+        }
+
+        async fn since(
+            &self,
+            channel_id: ChannelId,
+            sequence: u64,
+        ) -> WebSocketResult<Vec<ChannelMessage>> {
+            let mut connection = self.connection().await?;
+            let stored: Vec<(String, f64)> = connection
+                .zrangebyscore_withscores(
+                    self.messages_key(channel_id),
+                    format!("({}", sequence),
+                    "+inf",
+                )
+                .await
+                .map_err(redis_error)?;
+
+            stored
+                .into_iter()
+                .map(|(payload, score)| {
+                    let mut message: ChannelMessage = serde_json::from_str(&payload)
+                        .map_err(|e| WebSocketError::Serialization(e.to_string()))?;
+                    message.sequence = score as u64;
+                    Ok(message)
+                })
+                .collect()
+        }
+
+        async fn clear(&self, channel_id: ChannelId) -> WebSocketResult<()> {
+            // Keep the counter so resuming clients don't confuse new messages with old ones
+            let mut connection = self.connection().await?;
+            connection
+                .del::<_, ()>(self.messages_key(channel_id))
+                .await
+                .map_err(redis_error)
+        }
+
+        fn name(&self) -> &'static str {
+            "redis"
+        }
+
+        fn is_shared(&self) -> bool {
+            true
+        }
+    }
+
+    fn redis_error(error: redis::RedisError) -> WebSocketError {
+        WebSocketError::Connection(format!("Channel history unavailable: {}", error))
+    }
+}
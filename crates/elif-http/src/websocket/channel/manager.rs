@@ -1,16 +1,22 @@
 //! Channel manager for WebSocket channel operations
 
+use super::super::broker::{BrokerEnvelope, BrokerEvent, ChannelBroker, InMemoryBroker, NodeId};
 use super::super::types::{ConnectionId, WebSocketError, WebSocketMessage, WebSocketResult};
+use super::authorization::{ChannelAuthRequest, ChannelAuthorizer};
 use super::channel::Channel;
 use super::events::ChannelEvent;
+use super::history::ChannelHistory;
 use super::message::ChannelMessage;
 use super::types::{
-    ChannelId, ChannelManagerStats, ChannelMetadata, ChannelPermissions, ChannelStats, ChannelType,
+    ChannelId, ChannelManagerStats, ChannelMember, ChannelMetadata, ChannelPermissions,
+    ChannelStats, ChannelType, PresenceInfo,
 };
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{debug, info, warn};
 
 /// High-performance channel manager for WebSocket channel operations
 pub struct ChannelManager {
@@ -18,18 +24,133 @@ pub struct ChannelManager {
     channels: Arc<RwLock<HashMap<ChannelId, Arc<Channel>>>>,
     /// Connection to channel mapping for quick lookup
     connection_channels: Arc<RwLock<HashMap<ConnectionId, HashSet<ChannelId>>>>,
+    /// Channel memberships of connections living on other nodes
+    remote_channels: Arc<RwLock<HashMap<ConnectionId, HashSet<ChannelId>>>>,
+    /// Other nodes, when they were last heard from and the connections they host
+    remote_nodes: Arc<RwLock<HashMap<NodeId, RemoteNode>>>,
     /// Event handlers
     event_handlers: Arc<RwLock<Vec<Box<dyn Fn(ChannelEvent) + Send + Sync>>>>,
+    /// Authorization callbacks for joining channels, by channel name pattern
+    authorizers: Arc<RwLock<Vec<ChannelAuthorizer>>>,
+    /// Users authenticated on local connections
+    #[cfg(feature = "auth")]
+    connection_users: Arc<RwLock<HashMap<ConnectionId, crate::auth::UserContext>>>,
+    /// Users local connections join presence channels as
+    connection_presence: Arc<RwLock<HashMap<ConnectionId, PresenceInfo>>>,
+    /// Broker replicating channel state to other nodes
+    broker: Arc<dyn ChannelBroker>,
+    /// Whether channels are shared with other nodes through the broker
+    distributed: bool,
+    /// Identifier of this node on the broker
+    node_id: NodeId,
+    /// Store sequencing channel messages beyond the in-memory history of each channel
+    history: Option<Arc<dyn ChannelHistory>>,
+    /// How often this node announces itself to the others
+    heartbeat_interval: Duration,
+    /// How long a silent node keeps its members before they are dropped
+    node_timeout: Duration,
+}
+
+/// Node sharing channels through the broker
+#[derive(Debug)]
+struct RemoteNode {
+    last_seen: Instant,
+    connections: HashSet<ConnectionId>,
 }
 
 impl ChannelManager {
-    /// Create a new channel manager
+    /// Create a new channel manager for a single node
     pub fn new() -> Self {
+        Self {
+            distributed: false,
+            ..Self::with_broker(Arc::new(InMemoryBroker::new()))
+        }
+    }
+
+    /// Create a channel manager sharing its channels with other nodes through a broker
+    pub fn with_broker(broker: Arc<dyn ChannelBroker>) -> Self {
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
             connection_channels: Arc::new(RwLock::new(HashMap::new())),
+            remote_channels: Arc::new(RwLock::new(HashMap::new())),
+            remote_nodes: Arc::new(RwLock::new(HashMap::new())),
             event_handlers: Arc::new(RwLock::new(Vec::new())),
+            authorizers: Arc::new(RwLock::new(Vec::new())),
+            #[cfg(feature = "auth")]
+            connection_users: Arc::new(RwLock::new(HashMap::new())),
+            connection_presence: Arc::new(RwLock::new(HashMap::new())),
+            broker,
+            distributed: true,
+            node_id: NodeId::new(),
+            history: None,
+            heartbeat_interval: Duration::from_secs(10),
+            node_timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Announce this node every `interval` and drop the members of nodes not heard from
+    /// within `timeout`, e.g. after a crash
+    ///
+    /// Defaults to a heartbeat every 10 seconds and a 30 second timeout.
+    pub fn with_heartbeat(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.heartbeat_interval = interval;
+        self.node_timeout = timeout;
+        self
+    }
+
+    /// Get how often this node announces itself to the others
+    pub fn heartbeat_interval(&self) -> Duration {
+        self.heartbeat_interval
+    }
+
+    /// Sequence and store channel messages in a history store
+    ///
+    /// A manager sharing its channels through a broker only uses a store that
+    /// [is shared](ChannelHistory::is_shared) by all nodes, so sequence ids are allocated
+    /// in one place. With any other store, messages are sequenced per node and resuming
+    /// is refused.
+    pub fn with_history(mut self, history: Arc<dyn ChannelHistory>) -> Self {
+        if self.distributed && !history.is_shared() {
+            warn!(
+                "Channel history {} is not shared by nodes, messages are sequenced per node",
+                history.name()
+            );
+        }
+        self.history = Some(history);
+        self
+    }
+
+    /// Get the store channel messages are recorded in, if any
+    pub fn history(&self) -> Option<&Arc<dyn ChannelHistory>> {
+        self.history.as_ref()
+    }
+
+    /// Get the history store sequencing channel messages, if this manager can use it
+    fn sequencing_history(&self) -> Option<&Arc<dyn ChannelHistory>> {
+        self.history
+            .as_ref()
+            .filter(|history| !self.distributed || history.is_shared())
+    }
+
+    /// Refuse to resume when sequence ids may differ between nodes
+    fn ensure_resumable(&self) -> WebSocketResult<()> {
+        if self.distributed && self.sequencing_history().is_none() {
+            return Err(WebSocketError::Connection(
+                "Resuming channels shared through a broker requires a shared channel history"
+                    .to_string(),
+            ));
         }
+        Ok(())
+    }
+
+    /// Get the broker channel state is replicated through
+    pub fn broker(&self) -> &Arc<dyn ChannelBroker> {
+        &self.broker
+    }
+
+    /// Get the identifier of this node
+    pub fn node_id(&self) -> NodeId {
+        self.node_id
     }
 
     /// Create a new channel
@@ -57,10 +178,12 @@ impl ChannelManager {
         }
 
         // Store the channel
+        let channel = Arc::new(channel);
         {
             let mut channels = self.channels.write().await;
-            channels.insert(channel_id, Arc::new(channel));
+            channels.insert(channel_id, channel.clone());
         }
+        self.publish_channel(&channel).await;
 
         info!("Created channel '{}' with ID {}", name, channel_id);
         self.emit_event(ChannelEvent::ChannelCreated(channel_id, name))
@@ -92,10 +215,12 @@ impl ChannelManager {
         }
 
         // Store the channel
+        let channel = Arc::new(channel);
         {
             let mut channels = self.channels.write().await;
-            channels.insert(channel_id, Arc::new(channel));
+            channels.insert(channel_id, channel.clone());
         }
+        self.publish_channel(&channel).await;
 
         info!("Created channel '{}' with ID {}", metadata.name, channel_id);
         self.emit_event(ChannelEvent::ChannelCreated(channel_id, metadata.name))
@@ -106,6 +231,18 @@ impl ChannelManager {
 
     /// Delete a channel
     pub async fn delete_channel(&self, channel_id: ChannelId) -> WebSocketResult<()> {
+        self.remove_channel(channel_id).await?;
+        if let Some(history) = self.sequencing_history() {
+            if let Err(e) = history.clear(channel_id).await {
+                warn!("Failed to clear history of channel {}: {}", channel_id, e);
+            }
+        }
+        self.publish(BrokerEvent::ChannelDeleted(channel_id)).await;
+        Ok(())
+    }
+
+    /// Remove a channel from this node without notifying other nodes
+    async fn remove_channel(&self, channel_id: ChannelId) -> WebSocketResult<()> {
         let channel = {
             let mut channels = self.channels.write().await;
             channels.remove(&channel_id)
@@ -117,12 +254,14 @@ impl ChannelManager {
 
             // Remove channel from all members' tracking
             if !member_ids.is_empty() {
-                let mut connection_channels = self.connection_channels.write().await;
-                for member_id in member_ids {
-                    if let Some(member_channels) = connection_channels.get_mut(&member_id) {
-                        member_channels.remove(&channel_id);
-                        if member_channels.is_empty() {
-                            connection_channels.remove(&member_id);
+                for tracking in [&self.connection_channels, &self.remote_channels] {
+                    let mut tracking = tracking.write().await;
+                    for member_id in &member_ids {
+                        if let Some(member_channels) = tracking.get_mut(member_id) {
+                            member_channels.remove(&channel_id);
+                            if member_channels.is_empty() {
+                                tracking.remove(member_id);
+                            }
                         }
                     }
                 }
@@ -189,17 +328,21 @@ impl ChannelManager {
                 channel_id
             )))?;
 
+        // Authorization callbacks apply to every channel type
+        let authorized = self.authorize_join(&channel, connection_id).await?;
+
         // Check access permissions
         match &channel.metadata.channel_type {
             ChannelType::Public => {
                 // Anyone can join public channels
             }
             ChannelType::Private => {
-                // For private channels, we'd need invitation logic
-                // For now, reject all attempts
-                return Err(WebSocketError::Connection(
-                    "Channel is private and requires invitation".to_string(),
-                ));
+                // Private channels are only open to connections granted by an authorizer
+                if !authorized {
+                    return Err(WebSocketError::Connection(
+                        "Channel is private and requires invitation".to_string(),
+                    ));
+                }
             }
             ChannelType::Protected { .. } => {
                 let provided_password = password.ok_or(WebSocketError::Connection(
@@ -210,6 +353,11 @@ impl ChannelManager {
                     return Err(WebSocketError::Connection("Invalid password".to_string()));
                 }
             }
+            ChannelType::Presence => {
+                return Err(WebSocketError::Connection(
+                    "Presence channel requires user info; use join_presence_channel".to_string(),
+                ));
+            }
         }
 
         // Add member to channel
@@ -222,6 +370,10 @@ impl ChannelManager {
         channel
             .add_member(connection_id, permissions, nickname.clone())
             .await?;
+        if let Some(member) = channel.get_member(connection_id).await {
+            self.publish(BrokerEvent::MemberJoined(channel_id, member))
+                .await;
+        }
 
         // Track the connection's channel membership
         {
@@ -243,12 +395,178 @@ impl ChannelManager {
         Ok(())
     }
 
+    /// Join a connection to a channel, resuming after the last message it received
+    ///
+    /// Returns the messages of the channel's history sent after `sequence`, which the
+    /// caller should deliver before any live message. If the oldest returned message isn't
+    /// `sequence + 1`, older messages were already dropped from the history. Fails without
+    /// joining when the channels are shared through a broker without a shared history.
+    pub async fn join_channel_from(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+        password: Option<&str>,
+        nickname: Option<String>,
+        sequence: u64,
+    ) -> WebSocketResult<Vec<ChannelMessage>> {
+        self.ensure_resumable()?;
+        self.join_channel(channel_id, connection_id, password, nickname)
+            .await?;
+        self.messages_since(channel_id, sequence).await
+    }
+
+    /// Get the messages of a channel's history sent after the given sequence id
+    ///
+    /// Reads from the history store when one is configured, otherwise from the channel.
+    /// Fails when the channels are shared through a broker without a shared history.
+    pub async fn messages_since(
+        &self,
+        channel_id: ChannelId,
+        sequence: u64,
+    ) -> WebSocketResult<Vec<ChannelMessage>> {
+        self.ensure_resumable()?;
+        match self.sequencing_history() {
+            Some(history) => history.since(channel_id, sequence).await,
+            None => {
+                let channel =
+                    self.get_channel(channel_id)
+                        .await
+                        .ok_or(WebSocketError::Connection(format!(
+                            "Channel {} not found",
+                            channel_id
+                        )))?;
+                Ok(channel.messages_since(sequence).await)
+            }
+        }
+    }
+
+    /// Join a connection to a presence channel as the user recorded for it
+    ///
+    /// The user comes from [`set_connection_presence`](Self::set_connection_presence), or
+    /// with the `auth` feature from the user authenticated on the connection; connections
+    /// without one are rejected. Emits [`ChannelEvent::MemberAdded`] when this is the user's
+    /// first connection in the channel; further connections of the same user (e.g. other
+    /// tabs) only join silently. Join through
+    /// [`ConnectionRegistry::join_presence_channel`](crate::websocket::ConnectionRegistry::join_presence_channel)
+    /// to also announce the user to the other members.
+    pub async fn join_presence_channel(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+        nickname: Option<String>,
+    ) -> WebSocketResult<()> {
+        self.join_presence(channel_id, connection_id, nickname)
+            .await
+            .map(|_| ())
+    }
+
+    /// Join a presence channel, returning [`ChannelEvent::MemberAdded`] if the user became
+    /// present
+    pub(crate) async fn join_presence(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+        nickname: Option<String>,
+    ) -> WebSocketResult<Option<ChannelEvent>> {
+        let channel = self
+            .get_channel(channel_id)
+            .await
+            .ok_or(WebSocketError::Connection(format!(
+                "Channel {} not found",
+                channel_id
+            )))?;
+
+        if channel.metadata.channel_type != ChannelType::Presence {
+            return Err(WebSocketError::Connection(
+                "Channel is not a presence channel".to_string(),
+            ));
+        }
+        let presence =
+            self.connection_presence(connection_id)
+                .await
+                .ok_or(WebSocketError::Connection(
+                    "Presence channels require an authenticated connection".to_string(),
+                ))?;
+        self.authorize_join(&channel, connection_id).await?;
+
+        let permissions = if Some(connection_id) == channel.metadata.created_by {
+            ChannelPermissions::admin()
+        } else {
+            ChannelPermissions::default()
+        };
+
+        let newly_present = channel
+            .add_presence_member(
+                connection_id,
+                permissions,
+                nickname.clone(),
+                presence.clone(),
+            )
+            .await?;
+
+        // Track the connection's channel membership
+        {
+            let mut connection_channels = self.connection_channels.write().await;
+            connection_channels
+                .entry(connection_id)
+                .or_insert_with(HashSet::new)
+                .insert(channel_id);
+        }
+        if let Some(member) = channel.get_member(connection_id).await {
+            self.publish(BrokerEvent::MemberJoined(channel_id, member))
+                .await;
+        }
+
+        info!(
+            "Connection {} of user {} joined presence channel {}",
+            connection_id, presence.user_id, channel_id
+        );
+        self.emit_event(ChannelEvent::MemberJoined(
+            channel_id,
+            connection_id,
+            nickname,
+        ))
+        .await;
+        if !newly_present {
+            return Ok(None);
+        }
+        let added = ChannelEvent::MemberAdded(channel_id, presence);
+        self.emit_event(added.clone()).await;
+        Ok(Some(added))
+    }
+
+    /// Get the users present in a presence channel, once per user
+    pub async fn presence_members(
+        &self,
+        channel_id: ChannelId,
+    ) -> WebSocketResult<Vec<PresenceInfo>> {
+        let channel = self
+            .get_channel(channel_id)
+            .await
+            .ok_or(WebSocketError::Connection(format!(
+                "Channel {} not found",
+                channel_id
+            )))?;
+
+        Ok(channel.presence_members().await)
+    }
+
     /// Remove a connection from a channel
     pub async fn leave_channel(
         &self,
         channel_id: ChannelId,
         connection_id: ConnectionId,
     ) -> WebSocketResult<()> {
+        self.leave(channel_id, connection_id).await.map(|_| ())
+    }
+
+    /// Leave a channel, returning [`ChannelEvent::MemberRemoved`] if the user is no longer
+    /// present
+    pub(crate) async fn leave(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+    ) -> WebSocketResult<Option<ChannelEvent>> {
         let channel = self
             .get_channel(channel_id)
             .await
@@ -257,17 +575,15 @@ impl ChannelManager {
                 channel_id
             )))?;
 
-        // Get member info before removal
-        let member = channel.get_member(connection_id).await;
-        let nickname = member.as_ref().and_then(|m| m.nickname.clone());
-
         // Remove member from channel
-        channel
-            .remove_member(connection_id)
-            .await
-            .ok_or(WebSocketError::Connection(
-                "Connection not a member of channel".to_string(),
-            ))?;
+        let (member, left) =
+            channel
+                .take_member(connection_id)
+                .await
+                .ok_or(WebSocketError::Connection(
+                    "Connection not a member of channel".to_string(),
+                ))?;
+        let nickname = member.nickname.clone();
 
         // Remove from connection tracking
         {
@@ -281,23 +597,35 @@ impl ChannelManager {
         }
 
         info!("Connection {} left channel {}", connection_id, channel_id);
+        self.publish(BrokerEvent::MemberLeft(channel_id, connection_id))
+            .await;
         self.emit_event(ChannelEvent::MemberLeft(
             channel_id,
             connection_id,
             nickname,
         ))
         .await;
+        let removed = self.emit_presence_removed(channel_id, left).await;
 
         // Auto-delete empty channels (except those with explicit creators)
         if channel.is_empty().await && channel.metadata.created_by.is_none() {
             self.delete_channel(channel_id).await?;
         }
 
-        Ok(())
+        Ok(removed)
     }
 
     /// Remove a connection from all channels (useful for cleanup on disconnect)
     pub async fn leave_all_channels(&self, connection_id: ConnectionId) -> Vec<ChannelId> {
+        self.leave_all(connection_id).await.0
+    }
+
+    /// Leave all channels, returning them and a [`ChannelEvent::MemberRemoved`] for each
+    /// presence channel the user is no longer present in
+    pub(crate) async fn leave_all(
+        &self,
+        connection_id: ConnectionId,
+    ) -> (Vec<ChannelId>, Vec<ChannelEvent>) {
         // Acquire write lock once and remove all channel entries for this connection
         let channel_ids = {
             let mut connection_channels = self.connection_channels.write().await;
@@ -307,25 +635,25 @@ impl ChannelManager {
         };
 
         let mut left_channels = Vec::new();
+        let mut removed = Vec::new();
 
         // Now handle cleanup for each channel without repeated lock acquisitions
         for channel_id in channel_ids {
             if let Some(channel) = self.get_channel(channel_id).await {
-                // Get member info before removal for event logging
-                let member = channel.get_member(connection_id).await;
-                let nickname = member.as_ref().and_then(|m| m.nickname.clone());
-
                 // Remove member from channel
-                if channel.remove_member(connection_id).await.is_some() {
+                if let Some((member, left)) = channel.take_member(connection_id).await {
                     left_channels.push(channel_id);
 
                     info!("Connection {} left channel {}", connection_id, channel_id);
+                    self.publish(BrokerEvent::MemberLeft(channel_id, connection_id))
+                        .await;
                     self.emit_event(ChannelEvent::MemberLeft(
                         channel_id,
                         connection_id,
-                        nickname,
+                        member.nickname.clone(),
                     ))
                     .await;
+                    removed.extend(self.emit_presence_removed(channel_id, left).await);
 
                     // Auto-delete empty channels (except those with explicit creators)
                     if channel.is_empty().await && channel.metadata.created_by.is_none() {
@@ -343,16 +671,29 @@ impl ChannelManager {
             );
         }
 
-        left_channels
+        (left_channels, removed)
     }
 
     /// Send a message to a channel
+    ///
+    /// Returns the members connected to this node the message must be delivered to.
     pub async fn send_to_channel(
         &self,
         channel_id: ChannelId,
         sender_id: ConnectionId,
         message: WebSocketMessage,
     ) -> WebSocketResult<Vec<ConnectionId>> {
+        let (_, member_ids) = self.send_sequenced(channel_id, sender_id, message).await?;
+        Ok(member_ids)
+    }
+
+    /// Send a message to a channel, returning it with its sequence id and the local members
+    pub(crate) async fn send_sequenced(
+        &self,
+        channel_id: ChannelId,
+        sender_id: ConnectionId,
+        message: WebSocketMessage,
+    ) -> WebSocketResult<(ChannelMessage, Vec<ConnectionId>)> {
         let channel = self
             .get_channel(channel_id)
             .await
@@ -384,11 +725,19 @@ impl ChannelManager {
             sender_member.nickname.clone(),
         );
 
-        // Add to channel history
-        channel.add_message(channel_message.clone()).await;
+        // Sequence the message and add it to the channel history
+        let channel_message = match self.sequencing_history() {
+            Some(history) => {
+                history
+                    .append(channel_message, channel.metadata.message_history_limit)
+                    .await?
+            }
+            None => channel_message,
+        };
+        let channel_message = channel.add_message(channel_message).await;
 
-        // Get all member IDs for broadcasting
-        let member_ids = channel.get_member_ids().await;
+        // Get the member IDs connected to this node for broadcasting
+        let member_ids = self.local_member_ids(&channel).await;
 
         info!(
             "Message sent to channel {} by {} (broadcasting to {} members)",
@@ -397,10 +746,363 @@ impl ChannelManager {
             member_ids.len()
         );
 
-        self.emit_event(ChannelEvent::MessageSent(channel_id, channel_message))
+        self.publish(BrokerEvent::ChannelMessage(channel_message.clone()))
             .await;
+        self.emit_event(ChannelEvent::MessageSent(
+            channel_id,
+            channel_message.clone(),
+        ))
+        .await;
 
-        Ok(member_ids)
+        Ok((channel_message, member_ids))
+    }
+
+    /// Check whether a connection lives on another node
+    pub async fn is_remote_member(&self, connection_id: ConnectionId) -> bool {
+        let remote_channels = self.remote_channels.read().await;
+        remote_channels.contains_key(&connection_id)
+    }
+
+    /// Apply an event published by another node
+    ///
+    /// Returns the connections on this node a replicated channel message must be delivered to.
+    /// Events published by this node are ignored.
+    pub async fn apply_broker_event(&self, envelope: BrokerEnvelope) -> Vec<ConnectionId> {
+        if envelope.origin == self.node_id {
+            return Vec::new();
+        }
+        if let BrokerEvent::ChannelMessage(message) = envelope.event {
+            self.node_seen(envelope.origin).await;
+            return match self.receive_channel_message(message).await {
+                Some((_, member_ids)) => member_ids,
+                None => Vec::new(),
+            };
+        }
+
+        self.apply_membership_event(envelope).await;
+        Vec::new()
+    }
+
+    /// Apply a channel or membership change published by another node
+    ///
+    /// Returns the [`ChannelEvent::MemberAdded`] and [`ChannelEvent::MemberRemoved`] events
+    /// it caused, for announcing to the members on this node.
+    pub(crate) async fn apply_membership_event(
+        &self,
+        envelope: BrokerEnvelope,
+    ) -> Vec<ChannelEvent> {
+        let origin = envelope.origin;
+        if origin == self.node_id {
+            return Vec::new();
+        }
+        self.node_seen(origin).await;
+
+        let mut presence = Vec::new();
+        match envelope.event {
+            BrokerEvent::ChannelCreated(metadata) => {
+                self.add_remote_channel(metadata, origin).await;
+            }
+            BrokerEvent::ChannelDeleted(channel_id) => {
+                let _ = self.remove_channel(channel_id).await;
+            }
+            BrokerEvent::MemberJoined(channel_id, member) => {
+                presence.extend(self.add_remote_member(origin, channel_id, member).await);
+            }
+            BrokerEvent::MemberLeft(channel_id, connection_id) => {
+                presence.extend(
+                    self.remove_remote_member(origin, channel_id, connection_id)
+                        .await,
+                );
+            }
+            BrokerEvent::SnapshotRequest => {
+                let (channels, members) = self.snapshot().await;
+                self.publish(BrokerEvent::Snapshot {
+                    to: origin,
+                    channels,
+                    members,
+                })
+                .await;
+            }
+            BrokerEvent::Snapshot {
+                to,
+                channels,
+                members,
+            } => {
+                if to != self.node_id {
+                    return Vec::new();
+                }
+                debug!(
+                    "Applying snapshot of {} channels and {} members from node {}",
+                    channels.len(),
+                    members.len(),
+                    origin
+                );
+                for metadata in channels {
+                    self.add_remote_channel(metadata, origin).await;
+                }
+                for (channel_id, member) in members {
+                    presence.extend(self.add_remote_member(origin, channel_id, member).await);
+                }
+            }
+            BrokerEvent::ChannelMessage(_) | BrokerEvent::Broadcast(_) | BrokerEvent::Heartbeat => {
+            }
+        }
+
+        presence
+    }
+
+    /// Record that a node is alive
+    pub(crate) async fn node_seen(&self, node_id: NodeId) {
+        self.remote_nodes
+            .write()
+            .await
+            .entry(node_id)
+            .or_insert_with(|| RemoteNode {
+                last_seen: Instant::now(),
+                connections: HashSet::new(),
+            })
+            .last_seen = Instant::now();
+    }
+
+    /// Ask the other nodes for their channels and members, e.g. after joining late
+    pub(crate) async fn request_snapshot(&self) {
+        self.publish(BrokerEvent::SnapshotRequest).await;
+    }
+
+    /// Announce this node to the others
+    pub(crate) async fn publish_heartbeat(&self) {
+        self.publish(BrokerEvent::Heartbeat).await;
+    }
+
+    /// Drop the members of nodes not heard from within the node timeout
+    ///
+    /// Returns the [`ChannelEvent::MemberRemoved`] events of the users no longer present.
+    pub(crate) async fn expire_nodes(&self) -> Vec<ChannelEvent> {
+        let expired: Vec<(NodeId, HashSet<ConnectionId>)> = {
+            let mut remote_nodes = self.remote_nodes.write().await;
+            let expired: Vec<NodeId> = remote_nodes
+                .iter()
+                .filter(|(_, node)| node.last_seen.elapsed() >= self.node_timeout)
+                .map(|(node_id, _)| *node_id)
+                .collect();
+            expired
+                .into_iter()
+                .filter_map(|node_id| {
+                    let node = remote_nodes.remove(&node_id)?;
+                    Some((node_id, node.connections))
+                })
+                .collect()
+        };
+
+        let mut removed = Vec::new();
+        for (node_id, connections) in expired {
+            warn!(
+                "Node {} missed its heartbeats, dropping its {} connections",
+                node_id,
+                connections.len()
+            );
+            for connection_id in connections {
+                let channel_ids = self
+                    .remote_channels
+                    .read()
+                    .await
+                    .get(&connection_id)
+                    .cloned()
+                    .unwrap_or_default();
+                for channel_id in channel_ids {
+                    removed.extend(
+                        self.remove_remote_member(node_id, channel_id, connection_id)
+                            .await,
+                    );
+                }
+            }
+        }
+        removed
+    }
+
+    /// Add a channel created on another node unless it is already known
+    async fn add_remote_channel(&self, metadata: ChannelMetadata, origin: NodeId) {
+        let channel_id = ChannelId::from_name(&metadata.name);
+        let name = metadata.name.clone();
+        {
+            let mut channels = self.channels.write().await;
+            if channels.contains_key(&channel_id) {
+                return;
+            }
+            channels.insert(channel_id, Arc::new(Channel::with_metadata(metadata)));
+        }
+        debug!("Replicated channel '{}' from node {}", name, origin);
+        self.emit_event(ChannelEvent::ChannelCreated(channel_id, name))
+            .await;
+    }
+
+    /// Add a member connected to another node
+    async fn add_remote_member(
+        &self,
+        origin: NodeId,
+        channel_id: ChannelId,
+        member: ChannelMember,
+    ) -> Option<ChannelEvent> {
+        let Some(channel) = self.get_channel(channel_id).await else {
+            warn!(
+                "Ignoring member of unknown channel {} from node {}",
+                channel_id, origin
+            );
+            return None;
+        };
+        let connection_id = member.connection_id;
+        {
+            let mut remote_channels = self.remote_channels.write().await;
+            remote_channels
+                .entry(connection_id)
+                .or_insert_with(HashSet::new)
+                .insert(channel_id);
+        }
+        if let Some(node) = self.remote_nodes.write().await.get_mut(&origin) {
+            node.connections.insert(connection_id);
+        }
+        // Snapshots repeat members already announced with MemberJoined
+        if channel.has_member(connection_id).await {
+            return None;
+        }
+
+        let nickname = member.nickname.clone();
+        let presence = member.presence.clone();
+        let newly_present = channel.insert_member(member).await;
+        self.emit_event(ChannelEvent::MemberJoined(
+            channel_id,
+            connection_id,
+            nickname,
+        ))
+        .await;
+        let added = ChannelEvent::MemberAdded(channel_id, presence.filter(|_| newly_present)?);
+        self.emit_event(added.clone()).await;
+        Some(added)
+    }
+
+    /// Remove a member connected to another node
+    async fn remove_remote_member(
+        &self,
+        origin: NodeId,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+    ) -> Option<ChannelEvent> {
+        {
+            let mut remote_channels = self.remote_channels.write().await;
+            if let Some(member_channels) = remote_channels.get_mut(&connection_id) {
+                member_channels.remove(&channel_id);
+                if member_channels.is_empty() {
+                    remote_channels.remove(&connection_id);
+                    if let Some(node) = self.remote_nodes.write().await.get_mut(&origin) {
+                        node.connections.remove(&connection_id);
+                    }
+                }
+            }
+        }
+        let channel = self.get_channel(channel_id).await?;
+        let (member, left) = channel.take_member(connection_id).await?;
+        self.emit_event(ChannelEvent::MemberLeft(
+            channel_id,
+            connection_id,
+            member.nickname,
+        ))
+        .await;
+        self.emit_presence_removed(channel_id, left).await
+    }
+
+    /// Channels known to this node and the members connected to it
+    async fn snapshot(&self) -> (Vec<ChannelMetadata>, Vec<(ChannelId, ChannelMember)>) {
+        let channels: Vec<Arc<Channel>> = self.channels.read().await.values().cloned().collect();
+        let remote_channels = self.remote_channels.read().await;
+
+        let mut metadata = Vec::with_capacity(channels.len());
+        let mut members = Vec::new();
+        for channel in channels {
+            metadata.push(channel.metadata.clone());
+            members.extend(
+                channel
+                    .get_members()
+                    .await
+                    .into_iter()
+                    .filter(|member| !remote_channels.contains_key(&member.connection_id))
+                    .map(|member| (channel.id, member)),
+            );
+        }
+        (metadata, members)
+    }
+
+    /// Record a message sent to a channel on another node
+    ///
+    /// With a shared [`ChannelHistory`] the message keeps the sequence id the history gave
+    /// it. Without one, each node numbers the messages in the order it delivers them, which
+    /// is why resuming is refused. Returns the sequenced message and the local members to
+    /// deliver it to, or `None` if the channel is unknown.
+    pub(crate) async fn receive_channel_message(
+        &self,
+        mut message: ChannelMessage,
+    ) -> Option<(ChannelMessage, Vec<ConnectionId>)> {
+        let channel_id = message.channel_id;
+        let channel = self.get_channel(channel_id).await?;
+
+        match self.sequencing_history() {
+            Some(history) => {
+                let limit = channel.metadata.message_history_limit;
+                if let Err(e) = history.append(message.clone(), limit).await {
+                    warn!("Failed to record message of channel {}: {}", channel_id, e);
+                }
+            }
+            None => message.sequence = 0,
+        }
+        let message = channel.add_message(message).await;
+        let member_ids = self.local_member_ids(&channel).await;
+        self.emit_event(ChannelEvent::MessageSent(channel_id, message.clone()))
+            .await;
+
+        Some((message, member_ids))
+    }
+
+    /// Publish an event to the other nodes
+    pub(crate) async fn publish(&self, event: BrokerEvent) {
+        let envelope = BrokerEnvelope::new(self.node_id, event);
+        if let Err(e) = self.broker.publish(envelope).await {
+            warn!(
+                "Failed to publish channel event via {} broker: {}",
+                self.broker.name(),
+                e
+            );
+        }
+    }
+
+    /// Emit and return [`ChannelEvent::MemberRemoved`] for a user who left with their last
+    /// connection, as reported by [`Channel::take_member`]
+    async fn emit_presence_removed(
+        &self,
+        channel_id: ChannelId,
+        left: Option<PresenceInfo>,
+    ) -> Option<ChannelEvent> {
+        let removed = ChannelEvent::MemberRemoved(channel_id, left?);
+        self.emit_event(removed.clone()).await;
+        Some(removed)
+    }
+
+    /// Publish a newly created channel together with its initial members
+    async fn publish_channel(&self, channel: &Channel) {
+        self.publish(BrokerEvent::ChannelCreated(channel.metadata.clone()))
+            .await;
+        for member in channel.get_members().await {
+            self.publish(BrokerEvent::MemberJoined(channel.id, member))
+                .await;
+        }
+    }
+
+    /// Get the members of a channel connected to this node
+    pub(crate) async fn local_member_ids(&self, channel: &Channel) -> Vec<ConnectionId> {
+        let member_ids = channel.get_member_ids().await;
+        let remote_channels = self.remote_channels.read().await;
+        member_ids
+            .into_iter()
+            .filter(|id| !remote_channels.contains_key(id))
+            .collect()
     }
 
     /// Get channel statistics for all channels
@@ -440,6 +1142,7 @@ impl ChannelManager {
             public_channels: 0,
             private_channels: 0,
             protected_channels: 0,
+            presence_channels: 0,
             empty_channels: 0,
         };
 
@@ -448,6 +1151,7 @@ impl ChannelManager {
                 ChannelType::Public => stats.public_channels += 1,
                 ChannelType::Private => stats.private_channels += 1,
                 ChannelType::Protected { .. } => stats.protected_channels += 1,
+                ChannelType::Presence => stats.presence_channels += 1,
             }
 
             if channel.is_empty().await {
@@ -480,6 +1184,134 @@ impl ChannelManager {
         cleaned_up
     }
 
+    /// Register an authorization callback for the channels matching a pattern
+    ///
+    /// Patterns are `.`-separated, with `{name}` matching any one segment. When a connection
+    /// joins a channel, the first matching pattern's callback decides whether it may; private
+    /// channels can only be joined through an authorizer granting access.
+    ///
+    /// ```rust
+    /// # use elif_http::websocket::ChannelManager;
+    /// # async fn example(manager: ChannelManager) {
+    /// manager
+    ///     .authorize("orders.{id}", |request| async move {
+    ///         // e.g. look up the order and compare its owner with the connection's user
+    ///         request.param("id").is_some_and(|id| id.parse::<u64>().is_ok())
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn authorize<F, Fut>(&self, pattern: &str, callback: F)
+    where
+        F: Fn(ChannelAuthRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        let mut authorizers = self.authorizers.write().await;
+        authorizers.push(ChannelAuthorizer::new(pattern, callback));
+    }
+
+    /// Run the authorization callback matching a channel, if any
+    ///
+    /// Returns `Ok(true)` when access was granted, `Ok(false)` when no pattern matches and an
+    /// error when access was denied.
+    async fn authorize_join(
+        &self,
+        channel: &Channel,
+        connection_id: ConnectionId,
+    ) -> WebSocketResult<bool> {
+        let name = &channel.metadata.name;
+        let matched = {
+            let authorizers = self.authorizers.read().await;
+            authorizers.iter().find_map(|authorizer| {
+                authorizer
+                    .pattern
+                    .matches(name)
+                    .map(|params| (authorizer.callback.clone(), params))
+            })
+        };
+        let Some((callback, params)) = matched else {
+            return Ok(false);
+        };
+
+        let request = ChannelAuthRequest {
+            connection_id,
+            channel: name.clone(),
+            params,
+            #[cfg(feature = "auth")]
+            user: self.connection_user(connection_id).await,
+        };
+        if callback(request).await {
+            Ok(true)
+        } else {
+            warn!(
+                "Connection {} was denied access to channel {}",
+                connection_id, channel.id
+            );
+            Err(WebSocketError::Connection(format!(
+                "Not authorized to join channel {}",
+                name
+            )))
+        }
+    }
+
+    /// Record the user authenticated on a local connection, for authorization callbacks
+    ///
+    /// The connection also joins presence channels as this user.
+    #[cfg(feature = "auth")]
+    pub async fn set_connection_user(
+        &self,
+        connection_id: ConnectionId,
+        user: crate::auth::UserContext,
+    ) {
+        self.set_connection_presence(connection_id, PresenceInfo::from(&user))
+            .await;
+        let mut users = self.connection_users.write().await;
+        users.insert(connection_id, user);
+    }
+
+    /// Get the user authenticated on a local connection
+    #[cfg(feature = "auth")]
+    pub async fn connection_user(
+        &self,
+        connection_id: ConnectionId,
+    ) -> Option<crate::auth::UserContext> {
+        let users = self.connection_users.read().await;
+        users.get(&connection_id).cloned()
+    }
+
+    /// Forget the user of a closed connection
+    #[cfg(feature = "auth")]
+    pub async fn remove_connection_user(&self, connection_id: ConnectionId) {
+        self.remove_connection_presence(connection_id).await;
+        let mut users = self.connection_users.write().await;
+        users.remove(&connection_id);
+    }
+
+    /// Record the user a local connection joins presence channels as
+    ///
+    /// For connections authenticated by the application itself; with the `auth` feature,
+    /// the user authenticated on upgrade is recorded automatically.
+    pub async fn set_connection_presence(
+        &self,
+        connection_id: ConnectionId,
+        presence: PresenceInfo,
+    ) {
+        let mut connection_presence = self.connection_presence.write().await;
+        connection_presence.insert(connection_id, presence);
+    }
+
+    /// Get the user a local connection joins presence channels as
+    pub async fn connection_presence(&self, connection_id: ConnectionId) -> Option<PresenceInfo> {
+        let connection_presence = self.connection_presence.read().await;
+        connection_presence.get(&connection_id).cloned()
+    }
+
+    /// Forget the presence user of a closed connection
+    pub async fn remove_connection_presence(&self, connection_id: ConnectionId) {
+        let mut connection_presence = self.connection_presence.write().await;
+        connection_presence.remove(&connection_id);
+    }
+
     /// Add an event handler
     pub async fn add_event_handler<F>(&self, handler: F)
     where
@@ -2,6 +2,7 @@
 
 use super::super::types::{ConnectionId, WebSocketMessage};
 use super::types::ChannelId;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use uuid::Uuid;
@@ -15,6 +16,9 @@ pub struct ChannelMessage {
     pub content: WebSocketMessage,
     pub timestamp: SystemTime,
     pub sender_nickname: Option<String>,
+    /// Position of the message in its channel, starting at 1; 0 until the message is sent
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 impl ChannelMessage {
@@ -32,6 +36,32 @@ impl ChannelMessage {
             content,
             timestamp: SystemTime::now(),
             sender_nickname,
+            sequence: 0,
         }
     }
+
+    /// Encode the message as it is delivered to the members of its channel
+    ///
+    /// Text and binary content is wrapped in
+    /// `{"event": "channel_message", "channel": ..., "sequence": 3, "data": ...}` so clients
+    /// know the sequence id to resume from; binary data is base64-encoded and marked with
+    /// `"binary": true`. Other messages are delivered as they are.
+    pub fn to_delivery(&self, channel_name: &str) -> WebSocketMessage {
+        let mut payload = serde_json::json!({
+            "event": "channel_message",
+            "channel": channel_name,
+            "sequence": self.sequence,
+        });
+        match &self.content {
+            WebSocketMessage::Text(text) => payload["data"] = text.as_str().into(),
+            WebSocketMessage::Binary(data) => {
+                payload["data"] = base64::engine::general_purpose::STANDARD
+                    .encode(data)
+                    .into();
+                payload["binary"] = true.into();
+            }
+            other => return other.clone(),
+        }
+        WebSocketMessage::text(payload.to_string())
+    }
 }
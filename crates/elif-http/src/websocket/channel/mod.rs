@@ -8,10 +8,12 @@
 //! The channel system is organized into several logical modules:
 //!
 //! - [`types`] - Core types and data structures
+//! - [`authorization`] - Authorization callbacks for joining channels
 //! - [`channel`] - Individual channel implementation
 //! - [`manager`] - Channel lifecycle and management
 //! - [`message`] - Channel message types
 //! - [`events`] - Event system for channel operations
+//! - [`history`] - Stores for message history beyond a single process
 //!
 //! ## Quick Start
 //!
@@ -43,8 +45,10 @@
 //! }
 //! ```
 
+pub mod authorization;
 pub mod channel;
 pub mod events;
+pub mod history;
 pub mod manager;
 pub mod message;
 pub mod password;
@@ -54,11 +58,17 @@ pub mod types;
 mod tests;
 
 // Re-export main types for convenience
+pub use authorization::{ChannelAuthFuture, ChannelAuthRequest, ChannelPattern};
 pub use channel::Channel;
 pub use events::ChannelEvent;
+#[cfg(feature = "cache")]
+pub use history::CacheHistory;
+pub use history::ChannelHistory;
+#[cfg(feature = "redis")]
+pub use history::RedisHistory;
 pub use manager::ChannelManager;
 pub use message::ChannelMessage;
 pub use types::{
     ChannelId, ChannelManagerStats, ChannelMember, ChannelMetadata, ChannelPermissions,
-    ChannelStats, ChannelType,
+    ChannelStats, ChannelType, PresenceInfo,
 };
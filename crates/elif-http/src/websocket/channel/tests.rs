@@ -105,6 +105,7 @@ mod tests {
             content: WebSocketMessage::text("Hello"),
             timestamp: SystemTime::now(),
             sender_nickname: None,
+            sequence: 0,
         };
 
         let message2 = ChannelMessage {
@@ -114,6 +115,7 @@ mod tests {
             content: WebSocketMessage::text("World"),
             timestamp: SystemTime::now(),
             sender_nickname: None,
+            sequence: 0,
         };
 
         channel.add_message(message1.clone()).await;
@@ -125,6 +127,86 @@ mod tests {
         let recent = channel.get_recent_messages(1).await;
         assert_eq!(recent.len(), 1);
         assert_eq!(recent[0].id, message2.id);
+
+        // Messages are sequenced in the order they were added
+        assert_eq!(history[0].sequence, 1);
+        assert_eq!(recent[0].sequence, 2);
+        assert_eq!(channel.last_sequence().await, 2);
+        assert_eq!(channel.messages_since(1).await[0].id, message2.id);
+
+        // Messages sequenced by a shared history keep their position, once
+        for sequence in [5, 4, 5] {
+            let mut replicated = message1.clone();
+            replicated.sequence = sequence;
+            channel.add_message(replicated).await;
+        }
+        let sequences: Vec<u64> = channel
+            .get_message_history()
+            .await
+            .iter()
+            .map(|message| message.sequence)
+            .collect();
+        assert_eq!(sequences, vec![1, 2, 4, 5]);
+        assert_eq!(channel.last_sequence().await, 5);
+    }
+
+    #[test]
+    fn test_delivered_messages_carry_sequence() {
+        let channel_id = ChannelId::from_name("chat");
+        let mut message = ChannelMessage::new(
+            channel_id,
+            ConnectionId::new(),
+            WebSocketMessage::binary(vec![1, 2, 3]),
+            None,
+        );
+        message.sequence = 7;
+
+        let WebSocketMessage::Text(delivered) = message.to_delivery("chat") else {
+            panic!("channel messages are delivered as text");
+        };
+        let delivered: serde_json::Value = serde_json::from_str(&delivered).unwrap();
+        assert_eq!(delivered["sequence"], 7);
+        assert_eq!(delivered["channel"], "chat");
+        assert_eq!(delivered["data"], "AQID");
+        assert_eq!(delivered["binary"], true);
+    }
+
+    #[tokio::test]
+    async fn test_join_channel_from_sequence() {
+        let manager = ChannelManager::new();
+        let sender = ConnectionId::new();
+        let returning = ConnectionId::new();
+
+        let channel_id = manager
+            .create_channel("general".to_string(), ChannelType::Public, Some(sender))
+            .await
+            .unwrap();
+        for text in ["one", "two", "three"] {
+            manager
+                .send_to_channel(channel_id, sender, WebSocketMessage::text(text))
+                .await
+                .unwrap();
+        }
+
+        // The client saw "one" before disconnecting
+        let missed = manager
+            .join_channel_from(channel_id, returning, None, None, 1)
+            .await
+            .unwrap();
+        let sequences: Vec<u64> = missed.iter().map(|message| message.sequence).collect();
+        assert_eq!(sequences, vec![2, 3]);
+        assert_eq!(missed[0].content, WebSocketMessage::text("two"));
+
+        // Sequence ids keep increasing after the history is cleared
+        let channel = manager.get_channel(channel_id).await.unwrap();
+        channel.clear_message_history().await;
+        manager
+            .send_to_channel(channel_id, sender, WebSocketMessage::text("four"))
+            .await
+            .unwrap();
+        let missed = manager.messages_since(channel_id, 3).await.unwrap();
+        assert_eq!(missed.len(), 1);
+        assert_eq!(missed[0].sequence, 4);
     }
 
     #[tokio::test]
@@ -445,4 +527,161 @@ mod tests {
         assert_eq!(manager_stats.private_channels, 0);
         assert_eq!(manager_stats.protected_channels, 0);
     }
+
+    #[tokio::test]
+    async fn test_presence_channel_tracks_users() {
+        use crate::websocket::{ChannelEvent, PresenceInfo};
+        use std::sync::{Arc, Mutex};
+
+        let manager = ChannelManager::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        manager
+            .add_event_handler(move |event| match event {
+                ChannelEvent::MemberAdded(_, user) => recorded
+                    .lock()
+                    .unwrap()
+                    .push(format!("added:{}", user.user_id)),
+                ChannelEvent::MemberRemoved(_, user) => recorded
+                    .lock()
+                    .unwrap()
+                    .push(format!("removed:{}", user.user_id)),
+                _ => {}
+            })
+            .await;
+
+        let channel_id = manager
+            .create_channel("room".to_string(), ChannelType::Presence, None)
+            .await
+            .unwrap();
+        let alice = PresenceInfo::new("1", serde_json::json!({ "username": "alice" }));
+        let bob = PresenceInfo::new("2", serde_json::json!({ "username": "bob" }));
+        let (alice_tab1, alice_tab2, bob_tab) = (
+            ConnectionId::new(),
+            ConnectionId::new(),
+            ConnectionId::new(),
+        );
+
+        // Presence channels require user info
+        assert!(manager
+            .join_channel(channel_id, bob_tab, None, None)
+            .await
+            .is_err());
+
+        // Presence is taken from the connection, which must have some
+        assert!(manager
+            .join_presence_channel(channel_id, bob_tab, None)
+            .await
+            .is_err());
+
+        for (connection_id, user) in [(alice_tab1, &alice), (alice_tab2, &alice), (bob_tab, &bob)] {
+            manager
+                .set_connection_presence(connection_id, user.clone())
+                .await;
+            manager
+                .join_presence_channel(channel_id, connection_id, None)
+                .await
+                .unwrap();
+        }
+
+        // Two tabs of the same user count as one presence
+        let members = manager.presence_members(channel_id).await.unwrap();
+        assert_eq!(members, vec![alice.clone(), bob.clone()]);
+
+        manager.leave_channel(channel_id, alice_tab1).await.unwrap();
+        assert_eq!(manager.presence_members(channel_id).await.unwrap().len(), 2);
+
+        manager.leave_all_channels(alice_tab2).await;
+        assert_eq!(
+            manager.presence_members(channel_id).await.unwrap(),
+            vec![bob.clone()]
+        );
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["added:1", "added:2", "removed:1"]
+        );
+        assert_eq!(manager.stats().await.presence_channels, 1);
+    }
+
+    #[tokio::test]
+    async fn test_channel_authorization_callbacks() {
+        let manager = ChannelManager::new();
+        let owner = ConnectionId::new();
+        let stranger = ConnectionId::new();
+
+        // Only the connection recorded as the order's owner may follow it
+        manager
+            .authorize("orders.{id}", move |request| async move {
+                request.param("id") == Some("42") && request.connection_id == owner
+            })
+            .await;
+
+        let order = manager
+            .create_channel("orders.42".to_string(), ChannelType::Private, None)
+            .await
+            .unwrap();
+        manager
+            .join_channel(order, owner, None, None)
+            .await
+            .unwrap();
+        assert!(manager
+            .join_channel(order, stranger, None, None)
+            .await
+            .is_err());
+
+        // Channels outside the pattern keep their own rules
+        let lobby = manager
+            .create_channel("lobby".to_string(), ChannelType::Public, None)
+            .await
+            .unwrap();
+        manager
+            .join_channel(lobby, stranger, None, None)
+            .await
+            .unwrap();
+        let secret = manager
+            .create_channel("secret".to_string(), ChannelType::Private, None)
+            .await
+            .unwrap();
+        assert!(manager
+            .join_channel(secret, owner, None, None)
+            .await
+            .is_err());
+    }
+
+    #[cfg(feature = "auth")]
+    #[tokio::test]
+    async fn test_channel_authorization_sees_connection_user() {
+        use crate::auth::UserContext;
+
+        let manager = ChannelManager::new();
+        let alice = ConnectionId::new();
+        let anonymous = ConnectionId::new();
+        manager
+            .set_connection_user(
+                alice,
+                UserContext::new("7".to_string(), "alice".to_string(), "test".to_string()),
+            )
+            .await;
+
+        manager
+            .authorize("users.{id}", |request| async move {
+                let user_id = request.user.as_ref().map(|user| user.user_id.as_str());
+                user_id.is_some() && user_id == request.param("id")
+            })
+            .await;
+
+        let channel = manager
+            .create_channel("users.7".to_string(), ChannelType::Private, None)
+            .await
+            .unwrap();
+        manager
+            .join_channel(channel, alice, None, None)
+            .await
+            .unwrap();
+        assert!(manager
+            .join_channel(channel, anonymous, None, None)
+            .await
+            .is_err());
+    }
 }
@@ -57,6 +57,8 @@ pub enum ChannelType {
         #[serde(deserialize_with = "deserialize_password_hash")]
         password_hash: SecurePasswordHash,
     },
+    /// Presence channel - members join with user info and see who else is present
+    Presence,
 }
 
 // Custom serialization for SecurePasswordHash
@@ -135,6 +137,41 @@ pub struct ChannelMember {
     pub joined_at: SystemTime,
     pub permissions: ChannelPermissions,
     pub nickname: Option<String>,
+    /// User behind the connection, for members of presence channels
+    #[serde(default)]
+    pub presence: Option<PresenceInfo>,
+}
+
+/// User present in a presence channel
+///
+/// A user connected several times (e.g. from multiple tabs) is present once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresenceInfo {
+    pub user_id: String,
+    /// Public user details shared with the other members
+    pub info: serde_json::Value,
+}
+
+impl PresenceInfo {
+    pub fn new<T: Into<String>>(user_id: T, info: serde_json::Value) -> Self {
+        Self {
+            user_id: user_id.into(),
+            info,
+        }
+    }
+}
+
+#[cfg(feature = "auth")]
+impl From<&crate::auth::UserContext> for PresenceInfo {
+    fn from(user: &crate::auth::UserContext) -> Self {
+        Self::new(
+            user.user_id.clone(),
+            serde_json::json!({
+                "username": user.username,
+                "roles": user.roles,
+            }),
+        )
+    }
 }
 
 /// Channel metadata and configuration
@@ -169,5 +206,6 @@ pub struct ChannelManagerStats {
     pub public_channels: usize,
     pub private_channels: usize,
     pub protected_channels: usize,
+    pub presence_channels: usize,
     pub empty_channels: usize,
 }
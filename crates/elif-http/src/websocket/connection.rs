@@ -4,13 +4,14 @@ use super::types::{
     ConnectionId, ConnectionState, WebSocketConfig, WebSocketError, WebSocketMessage,
     WebSocketResult,
 };
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{future, Sink, SinkExt, Stream, StreamExt};
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::time;
-use tokio_tungstenite::{accept_async, tungstenite, WebSocketStream};
+use tokio_tungstenite::{accept_async, tungstenite};
 use tracing::{debug, error, info};
 
 /// WebSocket connection wrapper - clean API over tokio-tungstenite
@@ -24,10 +25,20 @@ pub struct WebSocketConnection {
     metadata: Arc<RwLock<ConnectionMetadata>>,
     /// Message sender channel
     sender: mpsc::UnboundedSender<WebSocketMessage>,
+    /// Data messages received from the peer
+    incoming: Arc<Mutex<mpsc::UnboundedReceiver<WebSocketMessage>>>,
     /// Configuration
     _config: WebSocketConfig,
 }
 
+/// Channels between a connection and its handler task
+struct ConnectionChannels {
+    /// Messages queued by the application, to send to the peer
+    outgoing: mpsc::UnboundedReceiver<WebSocketMessage>,
+    /// Data messages received from the peer, for the application
+    incoming: mpsc::UnboundedSender<WebSocketMessage>,
+}
+
 /// Connection metadata for tracking and debugging
 #[derive(Debug, Clone)]
 pub struct ConnectionMetadata {
@@ -39,6 +50,9 @@ pub struct ConnectionMetadata {
     pub user_agent: Option<String>,
     /// Custom metadata
     pub custom: HashMap<String, String>,
+    /// User authenticated when the connection was upgraded
+    #[cfg(feature = "auth")]
+    pub user: Option<crate::auth::UserContext>,
     /// Message statistics
     pub stats: ConnectionStats,
 }
@@ -64,16 +78,50 @@ impl WebSocketConnection {
     where
         S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
     {
-        let id = ConnectionId::new();
         let ws_stream = accept_async(stream).await?;
+        let (sink, stream) = ws_stream.split();
+        let sink = sink
+            .sink_map_err(WebSocketError::from)
+            .with(|msg: WebSocketMessage| {
+                future::ready(Ok::<_, WebSocketError>(tungstenite::Message::from(msg)))
+            });
+        let stream = stream.map(|msg| {
+            msg.map(WebSocketMessage::from)
+                .map_err(WebSocketError::from)
+        });
+
+        Ok(Self::spawn(sink, stream, config))
+    }
 
-        let (sender, receiver) = mpsc::unbounded_channel();
+    /// Create a new WebSocket connection from a socket upgraded by Axum
+    pub fn from_axum(socket: axum::extract::ws::WebSocket, config: WebSocketConfig) -> Self {
+        let (sink, stream) = socket.split();
+        let sink = sink.with(|msg: WebSocketMessage| {
+            future::ready(Ok::<_, axum::Error>(axum::extract::ws::Message::from(msg)))
+        });
+        let stream = stream.map(|msg| msg.map(WebSocketMessage::from));
+
+        Self::spawn(sink, stream, config)
+    }
+
+    /// Wrap an established WebSocket and start its handler task
+    fn spawn<Si, St, E>(sink: Si, stream: St, config: WebSocketConfig) -> Self
+    where
+        Si: Sink<WebSocketMessage, Error = E> + Unpin + Send + 'static,
+        St: Stream<Item = Result<WebSocketMessage, E>> + Unpin + Send + 'static,
+        E: fmt::Display + Send + 'static,
+    {
+        let id = ConnectionId::new();
+        let (sender, outgoing) = mpsc::unbounded_channel();
+        let (incoming_sender, incoming) = mpsc::unbounded_channel();
         let state = Arc::new(RwLock::new(ConnectionState::Connected));
         let metadata = Arc::new(RwLock::new(ConnectionMetadata {
             connected_at: Instant::now(),
             remote_addr: None,
             user_agent: None,
             custom: HashMap::new(),
+            #[cfg(feature = "auth")]
+            user: None,
             stats: ConnectionStats::default(),
         }));
 
@@ -83,16 +131,21 @@ impl WebSocketConnection {
             state: state.clone(),
             metadata: metadata.clone(),
             sender,
+            incoming: Arc::new(Mutex::new(incoming)),
             _config: config.clone(),
         };
+        let channels = ConnectionChannels {
+            outgoing,
+            incoming: incoming_sender,
+        };
 
         // Spawn the connection handler
         tokio::spawn(Self::handle_connection(
-            id, ws_stream, receiver, state, metadata, config,
+            id, sink, stream, channels, state, metadata, config,
         ));
 
         info!("WebSocket connection established: {}", id);
-        Ok(connection)
+        connection
     }
 
     /// Send a message to the WebSocket
@@ -108,6 +161,14 @@ impl WebSocketConnection {
         Ok(())
     }
 
+    /// Receive the next text or binary message sent by the peer
+    ///
+    /// Returns `None` once the connection is closed. Messages are buffered until read, and
+    /// each message is received by one reader only.
+    pub async fn recv(&self) -> Option<WebSocketMessage> {
+        self.incoming.lock().await.recv().await
+    }
+
     /// Send a text message
     pub async fn send_text<T: Into<String>>(&self, text: T) -> WebSocketResult<()> {
         self.send(WebSocketMessage::text(text)).await
@@ -125,20 +186,28 @@ impl WebSocketConnection {
 
     /// Close the connection
     pub async fn close(&self) -> WebSocketResult<()> {
-        self.send(WebSocketMessage::close()).await?;
-
-        let mut state = self.state.write().await;
-        *state = ConnectionState::Closing;
-
-        Ok(())
+        self.send_close(WebSocketMessage::close()).await
     }
 
     /// Close the connection with a reason
     pub async fn close_with_reason(&self, code: u16, reason: String) -> WebSocketResult<()> {
-        self.send(WebSocketMessage::close_with_reason(code, reason))
-            .await?;
+        self.send_close(WebSocketMessage::close_with_reason(code, reason))
+            .await
+    }
 
+    /// Queue a close frame and mark the connection as closing
+    ///
+    /// The state lock is held throughout, so the handler task finishing meanwhile can't have
+    /// its final state overwritten with `Closing`.
+    async fn send_close(&self, message: WebSocketMessage) -> WebSocketResult<()> {
         let mut state = self.state.write().await;
+        if !state.is_active() {
+            return Err(WebSocketError::ConnectionClosed);
+        }
+
+        self.sender
+            .send(message)
+            .map_err(|_| WebSocketError::SendQueueFull)?;
         *state = ConnectionState::Closing;
 
         Ok(())
@@ -175,31 +244,47 @@ impl WebSocketConnection {
         self.metadata.read().await.stats.clone()
     }
 
+    /// Get the user authenticated when the connection was upgraded
+    #[cfg(feature = "auth")]
+    pub async fn user(&self) -> Option<crate::auth::UserContext> {
+        self.metadata.read().await.user.clone()
+    }
+
+    /// Attach an authenticated user to the connection
+    #[cfg(feature = "auth")]
+    pub async fn set_user(&self, user: crate::auth::UserContext) {
+        let mut metadata = self.metadata.write().await;
+        metadata.user = Some(user);
+    }
+
     /// Connection handler - runs the actual WebSocket loop
-    async fn handle_connection<S>(
+    async fn handle_connection<Si, St, E>(
         id: ConnectionId,
-        mut ws_stream: WebSocketStream<S>,
-        mut receiver: mpsc::UnboundedReceiver<WebSocketMessage>,
+        mut sink: Si,
+        mut stream: St,
+        mut channels: ConnectionChannels,
         state: Arc<RwLock<ConnectionState>>,
         metadata: Arc<RwLock<ConnectionMetadata>>,
         config: WebSocketConfig,
     ) where
-        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+        Si: Sink<WebSocketMessage, Error = E> + Unpin,
+        St: Stream<Item = Result<WebSocketMessage, E>> + Unpin,
+        E: fmt::Display,
     {
         debug!("Starting WebSocket handler for connection: {}", id);
 
-        // Set up ping interval if configured
-        let mut ping_interval = config
-            .ping_interval
-            .map(|interval| time::interval(Duration::from_secs(interval)));
+        // Set up ping interval if configured; the first ping is due after one interval
+        let mut ping_interval = config.ping_interval.map(|interval| {
+            let period = Duration::from_secs(interval);
+            time::interval_at(time::Instant::now() + period, period)
+        });
 
         loop {
             tokio::select! {
                 // Handle incoming messages from WebSocket
-                ws_msg = ws_stream.next() => {
+                ws_msg = stream.next() => {
                     match ws_msg {
-                        Some(Ok(msg)) => {
-                            let elif_msg = WebSocketMessage::from(msg);
+                        Some(Ok(elif_msg)) => {
 
                             // Update stats
                             {
@@ -217,11 +302,11 @@ impl WebSocketConnection {
                             }
 
                             // Handle control frames automatically
-                            match &elif_msg {
+                            match elif_msg {
                                 WebSocketMessage::Ping(data) => {
                                     if config.auto_pong {
-                                        let pong_msg = tungstenite::Message::Pong(data.clone());
-                                        if let Err(e) = ws_stream.send(pong_msg).await {
+                                        let pong_msg = WebSocketMessage::Pong(data);
+                                        if let Err(e) = sink.send(pong_msg).await {
                                             error!("Failed to send pong for {}: {}", id, e);
                                             break;
                                         }
@@ -231,10 +316,11 @@ impl WebSocketConnection {
                                     info!("Received close frame for connection: {}", id);
                                     break;
                                 }
-                                _ => {
-                                    // For now, we just log other messages
-                                    // In a full implementation, we'd route these to handlers
-                                    debug!("Received message on {}: {:?}", id, elif_msg.message_type());
+                                WebSocketMessage::Pong(_) => {}
+                                data_msg => {
+                                    debug!("Received message on {}: {:?}", id, data_msg.message_type());
+                                    // Nobody reading the connection is not an error
+                                    let _ = channels.incoming.send(data_msg);
                                 }
                             }
                         }
@@ -252,7 +338,7 @@ impl WebSocketConnection {
                 }
 
                 // Handle outgoing messages from application
-                app_msg = receiver.recv() => {
+                app_msg = channels.outgoing.recv() => {
                     match app_msg {
                         Some(msg) => {
                             // Update stats
@@ -270,8 +356,7 @@ impl WebSocketConnection {
                                 meta.stats.bytes_sent += bytes;
                             }
 
-                            let tungstenite_msg = tungstenite::Message::from(msg);
-                            if let Err(e) = ws_stream.send(tungstenite_msg).await {
+                            if let Err(e) = sink.send(msg).await {
                                 error!("Failed to send message for {}: {}", id, e);
                                 let mut state_lock = state.write().await;
                                 *state_lock = ConnectionState::Failed(e.to_string());
@@ -295,8 +380,8 @@ impl WebSocketConnection {
                     }
                 } => {
                     // Send ping
-                    let ping_msg = tungstenite::Message::Ping(vec![]);
-                    if let Err(e) = ws_stream.send(ping_msg).await {
+                    let ping_msg = WebSocketMessage::Ping(vec![]);
+                    if let Err(e) = sink.send(ping_msg).await {
                         error!("Failed to send ping for {}: {}", id, e);
                         break;
                     }
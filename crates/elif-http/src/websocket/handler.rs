@@ -1,51 +1,92 @@
 //! WebSocket handler and upgrade mechanism - clean API for elif framework
 
+#[cfg(feature = "auth")]
+use super::auth::WebSocketAuth;
 use super::connection::WebSocketConnection;
 use super::registry::ConnectionRegistry;
 use super::types::{ConnectionId, WebSocketConfig, WebSocketResult};
+use crate::request::ElifRequest;
 use axum::extract::ws::WebSocketUpgrade as AxumWebSocketUpgrade;
 use std::sync::Arc;
 
 /// WebSocket upgrade handler - provides clean API over Axum WebSocket
-/// This is a simplified version for the foundation
 pub struct WebSocketUpgrade {
     /// WebSocket configuration
-    _config: WebSocketConfig,
+    config: WebSocketConfig,
     /// Connection registry
-    _registry: Arc<ConnectionRegistry>,
+    registry: Arc<ConnectionRegistry>,
+    /// Authentication required to upgrade
+    #[cfg(feature = "auth")]
+    auth: Option<WebSocketAuth>,
 }
 
 impl WebSocketUpgrade {
     /// Create a new WebSocket upgrade handler
     pub fn new(registry: Arc<ConnectionRegistry>) -> Self {
-        Self {
-            _config: WebSocketConfig::default(),
-            _registry: registry,
-        }
+        Self::with_config(registry, WebSocketConfig::default())
     }
 
     /// Create with custom configuration
     pub fn with_config(registry: Arc<ConnectionRegistry>, config: WebSocketConfig) -> Self {
         Self {
-            _config: config,
-            _registry: registry,
+            config,
+            registry,
+            #[cfg(feature = "auth")]
+            auth: None,
         }
     }
 
+    /// Authenticate requests before upgrading them
+    #[cfg(feature = "auth")]
+    pub fn auth(mut self, auth: WebSocketAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
     /// Upgrade an HTTP connection to WebSocket
-    /// Simplified for foundation - full implementation in later iterations
+    ///
+    /// The new connection is added to the registry and handed to `handler`. With
+    /// [`auth`](Self::auth) configured, the request is authenticated first: rejected requests
+    /// get a 401/403 response instead of an upgrade, and the user of accepted ones is attached
+    /// to the connection.
     pub async fn upgrade<H, F>(
         self,
         ws: AxumWebSocketUpgrade,
-        _handler: H,
+        request: &ElifRequest,
+        handler: H,
     ) -> axum::response::Response
     where
         H: FnOnce(ConnectionId, Arc<WebSocketConnection>) -> F + Send + 'static,
         F: std::future::Future<Output = ()> + Send + 'static,
     {
-        ws.on_upgrade(|_socket| async move {
-            tracing::info!("WebSocket connection upgraded (foundation mode)");
-            // Full implementation will be added in later iterations
+        #[cfg(feature = "auth")]
+        let user = match &self.auth {
+            Some(auth) => match auth.authorize(request).await {
+                Ok(user) => user,
+                Err(response) => {
+                    tracing::debug!("Rejected WebSocket upgrade of {}", request.path());
+                    return response.into_axum_response();
+                }
+            },
+            None => None,
+        };
+        #[cfg(not(feature = "auth"))]
+        let _ = request;
+
+        let Self {
+            config, registry, ..
+        } = self;
+        ws.on_upgrade(move |socket| async move {
+            let connection = WebSocketConnection::from_axum(socket, config);
+            #[cfg(feature = "auth")]
+            if let Some(user) = user {
+                connection.set_user(user).await;
+            }
+
+            let id = registry.add_connection(connection).await;
+            if let Some(connection) = registry.get_connection(id).await {
+                handler(id, connection).await;
+            }
         })
     }
 }
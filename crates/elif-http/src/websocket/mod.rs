@@ -3,23 +3,40 @@
 //! This module provides WebSocket server capabilities integrated with the HTTP server,
 //! including connection management, lifecycle handling, and message routing.
 
+#[cfg(feature = "auth")]
+pub mod auth;
+pub mod broker;
 pub mod channel;
 pub mod connection;
 pub mod handler;
+pub mod protocol;
 pub mod registry;
 pub mod server;
 pub mod types;
 
 // Re-export main types
+#[cfg(feature = "auth")]
+pub use auth::{WebSocketAuth, WebSocketAuthenticator};
+#[cfg(feature = "redis")]
+pub use broker::RedisBroker;
+pub use broker::{
+    BrokerEnvelope, BrokerEvent, BrokerSubscription, ChannelBroker, InMemoryBroker, NodeId,
+};
+#[cfg(feature = "cache")]
+pub use channel::CacheHistory;
+#[cfg(feature = "redis")]
+pub use channel::RedisHistory;
 pub use channel::{
-    Channel, ChannelEvent, ChannelId, ChannelManager, ChannelManagerStats, ChannelMember,
-    ChannelMessage, ChannelMetadata, ChannelPermissions, ChannelStats, ChannelType,
+    Channel, ChannelAuthRequest, ChannelEvent, ChannelHistory, ChannelId, ChannelManager,
+    ChannelManagerStats, ChannelMember, ChannelMessage, ChannelMetadata, ChannelPattern,
+    ChannelPermissions, ChannelStats, ChannelType, PresenceInfo,
 };
 pub use connection::WebSocketConnection;
 pub use handler::{SimpleWebSocketHandler, WebSocketHandler, WebSocketUpgrade};
+pub use protocol::{Codec, EventContext, Frame, ProtocolError, WebSocketProtocol};
 pub use registry::{ConnectionEvent, ConnectionRegistry};
 pub use server::WebSocketServer;
 pub use types::{
-    ConnectionId, ConnectionState, MessageType, WebSocketConfig, WebSocketError, WebSocketMessage,
-    WebSocketResult,
+    CloseFrame, ConnectionId, ConnectionState, MessageType, WebSocketConfig, WebSocketError,
+    WebSocketMessage, WebSocketResult,
 };
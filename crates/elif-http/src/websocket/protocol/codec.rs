@@ -0,0 +1,111 @@
+//! Encoding of protocol frames into WebSocket messages
+
+use super::frame::{Frame, ProtocolError};
+use crate::websocket::types::{WebSocketError, WebSocketMessage, WebSocketResult};
+
+/// Wire format of protocol frames
+///
+/// JSON frames travel as text messages and MessagePack frames as binary messages, so the
+/// format of an incoming frame is known from its message type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Codec {
+    #[default]
+    Json,
+    /// MessagePack with named fields (`msgpack` feature)
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+impl Codec {
+    /// Get the codec of a received message, if it can carry frames
+    pub fn of(message: &WebSocketMessage) -> Option<Self> {
+        match message {
+            WebSocketMessage::Text(_) => Some(Self::Json),
+            #[cfg(feature = "msgpack")]
+            WebSocketMessage::Binary(_) => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Encode a frame
+    pub fn encode(&self, frame: &Frame) -> WebSocketResult<WebSocketMessage> {
+        match self {
+            Self::Json => serde_json::to_string(frame)
+                .map(WebSocketMessage::Text)
+                .map_err(|e| WebSocketError::Serialization(e.to_string())),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::to_vec_named(frame)
+                .map(WebSocketMessage::Binary)
+                .map_err(|e| WebSocketError::Serialization(e.to_string())),
+        }
+    }
+
+    /// Decode a frame
+    pub fn decode(&self, message: &WebSocketMessage) -> Result<Frame, ProtocolError> {
+        let frame = match (self, message) {
+            (Self::Json, WebSocketMessage::Text(text)) => {
+                serde_json::from_str(text).map_err(|e| e.to_string())
+            }
+            #[cfg(feature = "msgpack")]
+            (Self::MessagePack, WebSocketMessage::Binary(data)) => {
+                rmp_serde::from_slice(data).map_err(|e| e.to_string())
+            }
+            _ => Err(format!(
+                "{:?} frames can't be read from {:?} messages",
+                self,
+                message.message_type()
+            )),
+        };
+
+        frame.map_err(ProtocolError::invalid_frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_frames() {
+        let frame = Frame::event("chat.send", json!({ "text": "hi" }), Some("1".to_string()));
+        let message = Codec::Json.encode(&frame).unwrap();
+
+        match &message {
+            WebSocketMessage::Text(text) => assert_eq!(
+                serde_json::from_str::<serde_json::Value>(text).unwrap(),
+                json!({ "type": "event", "event": "chat.send", "payload": { "text": "hi" }, "id": "1" })
+            ),
+            other => panic!("expected a text message, got {:?}", other),
+        }
+        assert_eq!(Codec::of(&message), Some(Codec::Json));
+        assert_eq!(Codec::Json.decode(&message).unwrap(), frame);
+
+        // Payload and id are optional
+        let ack = WebSocketMessage::text(r#"{"type":"event","event":"ping"}"#);
+        assert_eq!(
+            Codec::Json.decode(&ack).unwrap(),
+            Frame::event("ping", serde_json::Value::Null, None)
+        );
+
+        let error = Codec::Json
+            .decode(&WebSocketMessage::text("not json"))
+            .unwrap_err();
+        assert_eq!(error.code, "invalid_frame");
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_message_pack_frames() {
+        let frame = Frame::Error {
+            id: Some("7".to_string()),
+            code: "unknown_event".to_string(),
+            message: "Unknown event 'nope'".to_string(),
+        };
+        let message = Codec::MessagePack.encode(&frame).unwrap();
+
+        assert!(message.is_binary());
+        assert_eq!(Codec::of(&message), Some(Codec::MessagePack));
+        assert_eq!(Codec::MessagePack.decode(&message).unwrap(), frame);
+    }
+}
@@ -0,0 +1,618 @@
+//! Dispatch of protocol frames to typed event handlers
+
+use super::codec::Codec;
+use super::frame::{Frame, ProtocolError};
+use crate::websocket::connection::WebSocketConnection;
+use crate::websocket::types::{ConnectionId, WebSocketError, WebSocketMessage, WebSocketResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+type HandlerFuture = Pin<Box<dyn Future<Output = Result<Value, ProtocolError>> + Send>>;
+type EventHandler = Arc<dyn Fn(EventContext, Value) -> HandlerFuture + Send + Sync>;
+type AckSender = oneshot::Sender<Result<(), ProtocolError>>;
+
+/// Connection and event an event handler is invoked for
+#[derive(Clone)]
+pub struct EventContext {
+    /// Connection that sent the event
+    pub connection: Arc<WebSocketConnection>,
+    /// Name of the event
+    pub event: String,
+    protocol: Arc<WebSocketProtocol>,
+}
+
+impl EventContext {
+    /// Get the id of the connection that sent the event
+    pub fn connection_id(&self) -> ConnectionId {
+        self.connection.id
+    }
+
+    /// Push an event to the connection
+    pub async fn push<T: Serialize>(&self, event: &str, payload: &T) -> WebSocketResult<()> {
+        self.protocol.push(&self.connection, event, payload).await
+    }
+
+    /// Push an event to the connection and wait until the client acknowledges it
+    ///
+    /// See [`WebSocketProtocol::push_with_ack`].
+    pub async fn push_with_ack<T: Serialize>(
+        &self,
+        event: &str,
+        payload: &T,
+    ) -> WebSocketResult<()> {
+        self.protocol
+            .push_with_ack(&self.connection, event, payload)
+            .await
+    }
+
+    /// Get the user authenticated when the connection was upgraded
+    #[cfg(feature = "auth")]
+    pub async fn user(&self) -> Option<crate::auth::UserContext> {
+        self.connection.user().await
+    }
+}
+
+/// Typed request/response protocol on top of WebSocket connections
+///
+/// Incoming events are deserialized into the payload type of the handler registered for
+/// them, and a client event carrying an `id` gets the handler's result back as a response
+/// frame, or an error frame. Events of one connection are handled in the order received,
+/// while acknowledgements are processed as they arrive, so handlers can wait for them.
+/// At most [`max_queued_events`](Self::max_queued_events) events wait for their handlers;
+/// further events are answered with an `overloaded` error frame until the queue drains.
+///
+/// ```rust
+/// use elif_http::websocket::{ConnectionId, ProtocolError, WebSocketConnection, WebSocketProtocol};
+/// use serde::Deserialize;
+/// use std::sync::Arc;
+///
+/// #[derive(Deserialize)]
+/// struct Sum {
+///     a: i64,
+///     b: i64,
+/// }
+///
+/// let protocol = Arc::new(WebSocketProtocol::new().on("math.sum", |_ctx, sum: Sum| async move {
+///     Ok::<_, ProtocolError>(sum.a + sum.b)
+/// }));
+///
+/// // Serve each connection, e.g. from `WebSocketServer::add_websocket_route`
+/// let handler = move |_id: ConnectionId, connection: Arc<WebSocketConnection>| {
+///     protocol.clone().serve(connection)
+/// };
+/// ```
+pub struct WebSocketProtocol {
+    handlers: HashMap<String, EventHandler>,
+    codec: Codec,
+    ack_timeout: Duration,
+    max_queued_events: usize,
+    /// Pushes awaiting an acknowledgement, by correlation id
+    pending_acks: Mutex<HashMap<String, (ConnectionId, AckSender)>>,
+}
+
+impl WebSocketProtocol {
+    /// Create a protocol encoding frames as JSON
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            codec: Codec::default(),
+            ack_timeout: Duration::from_secs(10),
+            max_queued_events: 64,
+            pending_acks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Encode server pushes with another codec; responses use the codec of the request
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Set how long [`push_with_ack`](Self::push_with_ack) waits for the client
+    pub fn ack_timeout(mut self, timeout: Duration) -> Self {
+        self.ack_timeout = timeout;
+        self
+    }
+
+    /// Set how many events of one connection may wait for their handlers
+    pub fn max_queued_events(mut self, max: usize) -> Self {
+        self.max_queued_events = max.max(1);
+        self
+    }
+
+    /// Register the handler of an event
+    ///
+    /// The payload is deserialized into `T`; the handler's result is serialized as the
+    /// response payload when the client asked for one.
+    pub fn on<T, R, F, Fut>(mut self, event: &str, handler: F) -> Self
+    where
+        T: DeserializeOwned + Send + 'static,
+        R: Serialize,
+        F: Fn(EventContext, T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, ProtocolError>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let event_handler: EventHandler = Arc::new(move |context, payload| {
+            let handler = handler.clone();
+            Box::pin(async move {
+                let payload = serde_json::from_value::<T>(payload)
+                    .map_err(|e| ProtocolError::invalid_payload(e.to_string()))?;
+                let result = handler(context, payload).await?;
+                serde_json::to_value(result).map_err(|e| ProtocolError::internal(e.to_string()))
+            })
+        });
+
+        self.handlers.insert(event.to_string(), event_handler);
+        self
+    }
+
+    /// Check if a handler is registered for an event
+    pub fn handles(&self, event: &str) -> bool {
+        self.handlers.contains_key(event)
+    }
+
+    /// Read and dispatch the frames of a connection until it closes
+    pub async fn serve(self: Arc<Self>, connection: Arc<WebSocketConnection>) {
+        // Events queue up for their handlers while replies to pushes are resolved right
+        // away, so a handler awaiting an acknowledgement doesn't block it
+        let (events, mut queue) = mpsc::channel(self.max_queued_events);
+        let read = async {
+            let events = events;
+            while let Some(message) = connection.recv().await {
+                match self.decode(&connection, message).await {
+                    Some((codec, Frame::Event { event, payload, id })) => {
+                        if let Err(TrySendError::Full((codec, event, _, id))) =
+                            events.try_send((codec, event, payload, id))
+                        {
+                            warn!("Event queue of connection {} is full", connection.id);
+                            let error = ProtocolError::overloaded(&event);
+                            self.reply(&connection, codec, error.into_frame(id)).await;
+                        }
+                    }
+                    Some((_, frame)) => self.handle_reply(&connection, frame).await,
+                    None => {}
+                }
+            }
+        };
+        let dispatch = async {
+            while let Some((codec, event, payload, id)) = queue.recv().await {
+                self.handle_event(&connection, codec, event, payload, id)
+                    .await;
+            }
+        };
+        tokio::join!(read, dispatch);
+
+        // Fail the pushes still waiting for this connection
+        let mut pending = self.pending_acks.lock().await;
+        pending.retain(|_, (connection_id, _)| *connection_id != connection.id);
+        debug!("Stopped serving protocol on connection {}", connection.id);
+    }
+
+    /// Handle one message received on a connection
+    pub async fn handle_message(
+        self: &Arc<Self>,
+        connection: &Arc<WebSocketConnection>,
+        message: WebSocketMessage,
+    ) {
+        match self.decode(connection, message).await {
+            Some((codec, Frame::Event { event, payload, id })) => {
+                self.handle_event(connection, codec, event, payload, id)
+                    .await
+            }
+            Some((_, frame)) => self.handle_reply(connection, frame).await,
+            None => {}
+        }
+    }
+
+    /// Decode the frame of a message, answering undecodable messages with an error frame
+    async fn decode(
+        &self,
+        connection: &WebSocketConnection,
+        message: WebSocketMessage,
+    ) -> Option<(Codec, Frame)> {
+        let Some(codec) = Codec::of(&message) else {
+            let error = ProtocolError::invalid_frame(format!(
+                "{:?} messages can't carry frames",
+                message.message_type()
+            ));
+            self.reply(connection, self.codec, error.into_frame(None))
+                .await;
+            return None;
+        };
+
+        match codec.decode(&message) {
+            Ok(frame) => Some((codec, frame)),
+            Err(error) => {
+                self.reply(connection, codec, error.into_frame(None)).await;
+                None
+            }
+        }
+    }
+
+    /// Run the handler of an event and answer the client if it asked for a response
+    async fn handle_event(
+        self: &Arc<Self>,
+        connection: &Arc<WebSocketConnection>,
+        codec: Codec,
+        event: String,
+        payload: Value,
+        id: Option<String>,
+    ) {
+        let result = match self.handlers.get(&event) {
+            Some(handler) => {
+                let context = EventContext {
+                    connection: connection.clone(),
+                    event: event.clone(),
+                    protocol: self.clone(),
+                };
+                handler(context, payload).await
+            }
+            None => Err(ProtocolError::unknown_event(&event)),
+        };
+
+        match (result, id) {
+            (Ok(payload), Some(id)) => {
+                self.reply(connection, codec, Frame::Response { id, payload })
+                    .await
+            }
+            (Ok(_), None) => {}
+            (Err(error), id) => {
+                debug!("Event {} failed on {}: {}", event, connection.id, error);
+                self.reply(connection, codec, error.into_frame(id)).await;
+            }
+        }
+    }
+
+    /// Resolve the push a client's ack, response or error frame answers
+    async fn handle_reply(&self, connection: &WebSocketConnection, frame: Frame) {
+        match frame {
+            Frame::Ack { id } | Frame::Response { id, .. } => {
+                self.resolve_ack(connection.id, &id, Ok(())).await
+            }
+            Frame::Error {
+                id: Some(id),
+                code,
+                message,
+            } => {
+                self.resolve_ack(connection.id, &id, Err(ProtocolError::new(code, message)))
+                    .await
+            }
+            Frame::Error { id: None, .. } => {
+                debug!("Ignoring uncorrelated error frame from {}", connection.id);
+            }
+            Frame::Event { .. } => {}
+        }
+    }
+
+    /// Push an event to a connection
+    pub async fn push<T: Serialize>(
+        &self,
+        connection: &WebSocketConnection,
+        event: &str,
+        payload: &T,
+    ) -> WebSocketResult<()> {
+        let frame = Frame::event(event, to_payload(payload)?, None);
+        connection.send(self.codec.encode(&frame)?).await
+    }
+
+    /// Push an event and wait until the client acknowledges it
+    ///
+    /// Fails when the client answers with an error frame, when the connection closes, or
+    /// when no acknowledgement arrives within the [ack timeout](Self::ack_timeout).
+    pub async fn push_with_ack<T: Serialize>(
+        &self,
+        connection: &WebSocketConnection,
+        event: &str,
+        payload: &T,
+    ) -> WebSocketResult<()> {
+        let id = Uuid::new_v4().to_string();
+        let frame = Frame::event(event, to_payload(payload)?, Some(id.clone()));
+        let message = self.codec.encode(&frame)?;
+
+        let (sender, receiver) = oneshot::channel();
+        self.pending_acks
+            .lock()
+            .await
+            .insert(id.clone(), (connection.id, sender));
+
+        if let Err(error) = connection.send(message).await {
+            self.pending_acks.lock().await.remove(&id);
+            return Err(error);
+        }
+
+        match tokio::time::timeout(self.ack_timeout, receiver).await {
+            Ok(Ok(Ok(()))) => Ok(()),
+            Ok(Ok(Err(error))) => Err(WebSocketError::Protocol(format!(
+                "Client rejected {}: {}",
+                event, error
+            ))),
+            Ok(Err(_)) => Err(WebSocketError::ConnectionClosed),
+            Err(_) => {
+                self.pending_acks.lock().await.remove(&id);
+                Err(WebSocketError::Protocol(format!(
+                    "No acknowledgement for {} within {:?}",
+                    event, self.ack_timeout
+                )))
+            }
+        }
+    }
+
+    async fn resolve_ack(&self, from: ConnectionId, id: &str, result: Result<(), ProtocolError>) {
+        let mut pending = self.pending_acks.lock().await;
+        match pending.get(id) {
+            Some((connection_id, _)) if *connection_id == from => {
+                if let Some((_, sender)) = pending.remove(id) {
+                    let _ = sender.send(result);
+                }
+            }
+            Some(_) => debug!(
+                "Ignoring acknowledgement for push {} from another connection {}",
+                id, from
+            ),
+            None => debug!("Ignoring acknowledgement for unknown push {}", id),
+        }
+    }
+
+    async fn reply(&self, connection: &WebSocketConnection, codec: Codec, frame: Frame) {
+        let sent = match codec.encode(&frame) {
+            Ok(message) => connection.send(message).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = sent {
+            warn!("Failed to reply on connection {}: {}", connection.id, error);
+        }
+    }
+}
+
+impl Default for WebSocketProtocol {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for WebSocketProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut events: Vec<&String> = self.handlers.keys().collect();
+        events.sort();
+        f.debug_struct("WebSocketProtocol")
+            .field("events", &events)
+            .field("codec", &self.codec)
+            .field("ack_timeout", &self.ack_timeout)
+            .field("max_queued_events", &self.max_queued_events)
+            .finish()
+    }
+}
+
+fn to_payload<T: Serialize>(payload: &T) -> WebSocketResult<Value> {
+    serde_json::to_value(payload).map_err(|e| WebSocketError::Serialization(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::WebSocketConfig;
+    use futures_util::{SinkExt, StreamExt};
+    use serde::Deserialize;
+    use serde_json::json;
+    use tokio::io::DuplexStream;
+    use tokio_tungstenite::{tungstenite, WebSocketStream};
+
+    #[derive(Deserialize)]
+    struct Sum {
+        a: i64,
+        b: i64,
+    }
+
+    async fn connect() -> (Arc<WebSocketConnection>, WebSocketStream<DuplexStream>) {
+        let (server, client) = tokio::io::duplex(64 * 1024);
+        let (connection, client) = tokio::join!(
+            WebSocketConnection::from_stream(server, WebSocketConfig::default()),
+            tokio_tungstenite::client_async("ws://localhost/", client)
+        );
+        (Arc::new(connection.unwrap()), client.unwrap().0)
+    }
+
+    async fn send(client: &mut WebSocketStream<DuplexStream>, frame: serde_json::Value) {
+        client
+            .send(tungstenite::Message::Text(frame.to_string()))
+            .await
+            .unwrap();
+    }
+
+    async fn next_frame(client: &mut WebSocketStream<DuplexStream>) -> serde_json::Value {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(1), client.next())
+                .await
+                .expect("no frame received")
+                .unwrap()
+                .unwrap();
+            if let tungstenite::Message::Text(text) = message {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_response_and_errors() {
+        let protocol = Arc::new(WebSocketProtocol::new().on(
+            "math.sum",
+            |_ctx, sum: Sum| async move { Ok(sum.a + sum.b) },
+        ));
+        let (connection, mut client) = connect().await;
+        tokio::spawn(protocol.clone().serve(connection));
+
+        send(
+            &mut client,
+            json!({ "type": "event", "event": "math.sum", "payload": { "a": 1, "b": 2 }, "id": "1" }),
+        )
+        .await;
+        assert_eq!(
+            next_frame(&mut client).await,
+            json!({ "type": "response", "id": "1", "payload": 3 })
+        );
+
+        send(
+            &mut client,
+            json!({ "type": "event", "event": "math.sum", "payload": { "a": "one" }, "id": "2" }),
+        )
+        .await;
+        let error = next_frame(&mut client).await;
+        assert_eq!(error["id"], "2");
+        assert_eq!(error["code"], "invalid_payload");
+
+        send(
+            &mut client,
+            json!({ "type": "event", "event": "math.divide", "id": "3" }),
+        )
+        .await;
+        let error = next_frame(&mut client).await;
+        assert_eq!(error["id"], "3");
+        assert_eq!(error["code"], "unknown_event");
+
+        client
+            .send(tungstenite::Message::Text("{".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(next_frame(&mut client).await["code"], "invalid_frame");
+    }
+
+    #[tokio::test]
+    async fn test_push_with_ack() {
+        let protocol = Arc::new(WebSocketProtocol::new().ack_timeout(Duration::from_millis(200)));
+        let (connection, mut client) = connect().await;
+        tokio::spawn(protocol.clone().serve(connection.clone()));
+
+        let push = {
+            let protocol = protocol.clone();
+            let connection = connection.clone();
+            tokio::spawn(async move {
+                protocol
+                    .push_with_ack(&connection, "order.shipped", &json!({ "order": 42 }))
+                    .await
+            })
+        };
+
+        let frame = next_frame(&mut client).await;
+        assert_eq!(frame["event"], "order.shipped");
+        assert_eq!(frame["payload"], json!({ "order": 42 }));
+        send(&mut client, json!({ "type": "ack", "id": frame["id"] })).await;
+        assert!(push.await.unwrap().is_ok());
+
+        // Unacknowledged pushes time out
+        let result = protocol
+            .push_with_ack(&connection, "order.shipped", &json!({ "order": 43 }))
+            .await;
+        assert!(matches!(result, Err(WebSocketError::Protocol(_))));
+    }
+
+    #[tokio::test]
+    async fn test_handlers_wait_for_acks() {
+        let protocol = Arc::new(
+            WebSocketProtocol::new()
+                .ack_timeout(Duration::from_secs(1))
+                .on("order.ship", |ctx, order: i64| async move {
+                    ctx.push_with_ack("order.shipped", &order)
+                        .await
+                        .map_err(|e| ProtocolError::internal(e.to_string()))?;
+                    Ok("delivered")
+                }),
+        );
+        let (connection, mut client) = connect().await;
+        tokio::spawn(protocol.clone().serve(connection));
+
+        send(
+            &mut client,
+            json!({ "type": "event", "event": "order.ship", "payload": 42, "id": "1" }),
+        )
+        .await;
+        let push = next_frame(&mut client).await;
+        assert_eq!(push["event"], "order.shipped");
+        send(&mut client, json!({ "type": "ack", "id": push["id"] })).await;
+        assert_eq!(
+            next_frame(&mut client).await,
+            json!({ "type": "response", "id": "1", "payload": "delivered" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_acks_only_count_from_the_pushed_connection() {
+        let protocol = Arc::new(WebSocketProtocol::new().ack_timeout(Duration::from_millis(200)));
+        let (connection, mut client) = connect().await;
+        let (other, mut other_client) = connect().await;
+        tokio::spawn(protocol.clone().serve(connection.clone()));
+        tokio::spawn(protocol.clone().serve(other));
+
+        let push = {
+            let protocol = protocol.clone();
+            tokio::spawn(async move {
+                protocol
+                    .push_with_ack(&connection, "order.shipped", &json!({ "order": 42 }))
+                    .await
+            })
+        };
+
+        let frame = next_frame(&mut client).await;
+        send(
+            &mut other_client,
+            json!({ "type": "ack", "id": frame["id"] }),
+        )
+        .await;
+        assert!(matches!(
+            push.await.unwrap(),
+            Err(WebSocketError::Protocol(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_events_beyond_the_queue_are_rejected() {
+        let (started, mut handling) = mpsc::unbounded_channel();
+        let release = Arc::new(tokio::sync::Semaphore::new(0));
+        let protocol = Arc::new(WebSocketProtocol::new().max_queued_events(1).on("slow", {
+            let release = release.clone();
+            move |_ctx, n: i64| {
+                let started = started.clone();
+                let release = release.clone();
+                async move {
+                    let _ = started.send(n);
+                    release.acquire().await.unwrap().forget();
+                    Ok(n)
+                }
+            }
+        }));
+        let (connection, mut client) = connect().await;
+        tokio::spawn(protocol.clone().serve(connection));
+
+        send(
+            &mut client,
+            json!({ "type": "event", "event": "slow", "payload": 1, "id": "1" }),
+        )
+        .await;
+        assert_eq!(handling.recv().await, Some(1));
+
+        // The first event is being handled, the second fills the queue
+        for n in 2..=3 {
+            send(
+                &mut client,
+                json!({ "type": "event", "event": "slow", "payload": n, "id": n.to_string() }),
+            )
+            .await;
+        }
+        let error = next_frame(&mut client).await;
+        assert_eq!(error["id"], "3");
+        assert_eq!(error["code"], "overloaded");
+
+        release.add_permits(2);
+        assert_eq!(next_frame(&mut client).await["id"], "1");
+        assert_eq!(next_frame(&mut client).await["id"], "2");
+    }
+}
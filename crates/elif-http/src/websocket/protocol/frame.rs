@@ -0,0 +1,107 @@
+//! Protocol frames and errors
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Frame exchanged between client and server
+///
+/// Frames are tagged by `type`; in JSON an event looks like
+/// `{"type":"event","event":"chat.send","payload":{"text":"hi"},"id":"1"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Frame {
+    /// Named event sent by either side
+    ///
+    /// A client event with an `id` expects a [`Frame::Response`] or [`Frame::Error`]; a server
+    /// push with an `id` expects a [`Frame::Ack`].
+    Event {
+        event: String,
+        #[serde(default)]
+        payload: Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+    },
+    /// Result of the client event with the same id
+    Response {
+        id: String,
+        #[serde(default)]
+        payload: Value,
+    },
+    /// Client acknowledgement of the server push with the same id
+    Ack { id: String },
+    /// Failure, correlated with the event that caused it when known
+    Error {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        code: String,
+        message: String,
+    },
+}
+
+impl Frame {
+    /// Create an event frame
+    pub fn event<E: Into<String>>(event: E, payload: Value, id: Option<String>) -> Self {
+        Self::Event {
+            event: event.into(),
+            payload,
+            id,
+        }
+    }
+}
+
+/// Error reported to the client in an error frame
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{code}: {message}")]
+pub struct ProtocolError {
+    /// Machine-readable code, e.g. `unknown_event`
+    pub code: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ProtocolError {
+    pub fn new<C: Into<String>, M: Into<String>>(code: C, message: M) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Message could not be decoded into a frame
+    pub fn invalid_frame<M: Into<String>>(message: M) -> Self {
+        Self::new("invalid_frame", message)
+    }
+
+    /// No handler is registered for the event
+    pub fn unknown_event(event: &str) -> Self {
+        Self::new("unknown_event", format!("Unknown event '{}'", event))
+    }
+
+    /// Payload doesn't match the type expected by the handler
+    pub fn invalid_payload<M: Into<String>>(message: M) -> Self {
+        Self::new("invalid_payload", message)
+    }
+
+    /// Too many events are waiting for their handlers on the connection
+    pub fn overloaded(event: &str) -> Self {
+        Self::new(
+            "overloaded",
+            format!("Too many pending events, dropped '{}'", event),
+        )
+    }
+
+    /// Handler failed unexpectedly
+    pub fn internal<M: Into<String>>(message: M) -> Self {
+        Self::new("internal_error", message)
+    }
+
+    /// Convert into an error frame correlated with an event
+    pub fn into_frame(self, id: Option<String>) -> Frame {
+        Frame::Error {
+            id,
+            code: self.code,
+            message: self.message,
+        }
+    }
+}
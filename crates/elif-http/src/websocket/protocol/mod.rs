@@ -0,0 +1,20 @@
+//! Typed message protocol for WebSocket connections
+//!
+//! Instead of parsing raw text messages, clients and server exchange [`Frame`]s:
+//!
+//! - `event` - a named event with a payload; an `id` asks for a reply
+//! - `response` - the result of the client event with the same id
+//! - `ack` - the client's acknowledgement of a server push with the same id
+//! - `error` - a failure, correlated with an event when it has an id
+//!
+//! Frames are JSON text messages, or MessagePack binary messages with the `msgpack`
+//! feature. [`WebSocketProtocol`] dispatches incoming events to typed handlers and sends
+//! pushes that can wait for an acknowledgement.
+
+pub mod codec;
+pub mod dispatcher;
+pub mod frame;
+
+pub use codec::Codec;
+pub use dispatcher::{EventContext, WebSocketProtocol};
+pub use frame::{Frame, ProtocolError};
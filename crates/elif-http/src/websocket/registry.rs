@@ -1,11 +1,13 @@
 //! Connection registry for managing WebSocket connections
 
-use super::channel::{ChannelId, ChannelManager};
+use super::broker::BrokerEvent;
+use super::channel::{ChannelEvent, ChannelId, ChannelManager};
 use super::connection::WebSocketConnection;
-use super::types::{ConnectionId, ConnectionState, WebSocketMessage, WebSocketResult};
+use super::types::{CloseFrame, ConnectionId, ConnectionState, WebSocketMessage, WebSocketResult};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::{debug, info};
 
 /// Events that can occur in the connection registry
@@ -58,6 +60,10 @@ impl ConnectionRegistry {
     /// Add a connection to the registry
     pub async fn add_connection(&self, connection: WebSocketConnection) -> ConnectionId {
         let id = connection.id;
+        #[cfg(feature = "auth")]
+        if let Some(user) = connection.user().await {
+            self.channel_manager.set_connection_user(id, user).await;
+        }
         let arc_connection = Arc::new(connection);
 
         {
@@ -82,7 +88,11 @@ impl ConnectionRegistry {
             let state = conn.state().await;
 
             // Clean up channel memberships
-            self.channel_manager.leave_all_channels(id).await;
+            let (_, presence) = self.channel_manager.leave_all(id).await;
+            self.announce_presence(presence).await;
+            self.channel_manager.remove_connection_presence(id).await;
+            #[cfg(feature = "auth")]
+            self.channel_manager.remove_connection_user(id).await;
 
             info!(
                 "Removed connection from registry: {} (state: {:?})",
@@ -161,7 +171,19 @@ impl ConnectionRegistry {
     }
 
     /// Broadcast a message to all active connections
+    ///
+    /// The message is also published to the other nodes sharing the channel manager's broker;
+    /// the result only covers connections on this node.
     pub async fn broadcast(&self, message: WebSocketMessage) -> BroadcastResult {
+        let results = self.broadcast_local(message.clone()).await;
+        self.channel_manager
+            .publish(BrokerEvent::Broadcast(message))
+            .await;
+        results
+    }
+
+    /// Broadcast a message to the active connections on this node
+    async fn broadcast_local(&self, message: WebSocketMessage) -> BroadcastResult {
         let connections = self.get_all_connections().await;
         let mut results = BroadcastResult::new();
 
@@ -199,13 +221,196 @@ impl ConnectionRegistry {
         sender_id: ConnectionId,
         message: WebSocketMessage,
     ) -> WebSocketResult<BroadcastResult> {
-        // Get the member IDs from the channel manager
-        let member_ids = self
+        let channel = self.channel_manager.get_channel(channel_id).await.ok_or(
+            WebSocketError::Connection(format!("Channel {} not found", channel_id)),
+        )?;
+        let _delivery = channel.lock_delivery().await;
+
+        // Get the sequenced message and the member IDs from the channel manager
+        let (message, member_ids) = self
+            .channel_manager
+            .send_sequenced(channel_id, sender_id, message)
+            .await?;
+
+        // Broadcast to the channel members on this node; other nodes deliver to their own
+        Ok(self
+            .deliver_to_members(
+                channel_id,
+                member_ids,
+                message.to_delivery(&channel.metadata.name),
+            )
+            .await)
+    }
+
+    /// Join a connection to a channel and replay the messages it missed since `sequence`
+    ///
+    /// The missed messages are sent before any message sent to the channel after the join.
+    /// Returns the sequence id of the last message sent to the channel before the join, so
+    /// the client can resume from the messages it receives from then on.
+    pub async fn join_channel_from(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+        password: Option<&str>,
+        nickname: Option<String>,
+        sequence: u64,
+    ) -> WebSocketResult<u64> {
+        let connection = self
+            .get_connection(connection_id)
+            .await
+            .ok_or(WebSocketError::ConnectionNotFound(connection_id))?;
+        let channel = self.channel_manager.get_channel(channel_id).await.ok_or(
+            WebSocketError::Connection(format!("Channel {} not found", channel_id)),
+        )?;
+        let _delivery = channel.lock_delivery().await;
+
+        let missed = self
             .channel_manager
-            .send_to_channel(channel_id, sender_id, message.clone())
+            .join_channel_from(channel_id, connection_id, password, nickname, sequence)
+            .await?;
+        debug!(
+            "Replaying {} messages of channel {} to {}",
+            missed.len(),
+            channel_id,
+            connection_id
+        );
+        for message in missed {
+            connection
+                .send(message.to_delivery(&channel.metadata.name))
+                .await?;
+        }
+
+        Ok(channel.last_sequence().await)
+    }
+
+    /// Join a connection to a presence channel and announce its user to the other members
+    ///
+    /// See [`ChannelManager::join_presence_channel`]; members on this node receive
+    /// `{"event": "presence_added", "channel": ..., "member": {...}}` if the user wasn't
+    /// present yet.
+    pub async fn join_presence_channel(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+        nickname: Option<String>,
+    ) -> WebSocketResult<()> {
+        let added = self
+            .channel_manager
+            .join_presence(channel_id, connection_id, nickname)
+            .await?;
+        self.announce_presence(added).await;
+        Ok(())
+    }
+
+    /// Remove a connection from a channel, announcing users who left a presence channel
+    ///
+    /// Members on this node receive `{"event": "presence_removed", "channel": ...,
+    /// "member": {...}}` when the connection was its user's last one in the channel.
+    pub async fn leave_channel(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+    ) -> WebSocketResult<()> {
+        let removed = self
+            .channel_manager
+            .leave(channel_id, connection_id)
+            .await?;
+        self.announce_presence(removed).await;
+        Ok(())
+    }
+
+    /// Handle the channel requests clients send as text messages
+    ///
+    /// Currently `{"event": "presence_members", "channel": "<name>"}`, answered with
+    /// [`send_presence_members`](Self::send_presence_members). Returns `false` for other
+    /// messages, which are left to the application.
+    pub async fn handle_channel_request(
+        &self,
+        connection_id: ConnectionId,
+        message: &WebSocketMessage,
+    ) -> WebSocketResult<bool> {
+        let WebSocketMessage::Text(text) = message else {
+            return Ok(false);
+        };
+        let Ok(request) = serde_json::from_str::<serde_json::Value>(text) else {
+            return Ok(false);
+        };
+        if request["event"] != "presence_members" {
+            return Ok(false);
+        }
+
+        let channel = request["channel"].as_str().ok_or(WebSocketError::Protocol(
+            "presence_members request without channel".to_string(),
+        ))?;
+        self.send_presence_members(ChannelId::from_name(channel), connection_id)
             .await?;
+        Ok(true)
+    }
+
+    /// Answer a member's request for the users present in a presence channel
+    ///
+    /// Sends `{"event": "presence_members", "channel": ..., "members": [...]}` to the connection.
+    pub async fn send_presence_members(
+        &self,
+        channel_id: ChannelId,
+        connection_id: ConnectionId,
+    ) -> WebSocketResult<()> {
+        let channel = self.channel_manager.get_channel(channel_id).await.ok_or(
+            WebSocketError::Connection(format!("Channel {} not found", channel_id)),
+        )?;
+        if !channel.has_member(connection_id).await {
+            return Err(WebSocketError::Connection(
+                "Connection not a member of channel".to_string(),
+            ));
+        }
+
+        let payload = serde_json::json!({
+            "event": "presence_members",
+            "channel": channel.metadata.name,
+            "members": channel.presence_members().await,
+        });
+        self.send_text_to_connection(connection_id, payload.to_string())
+            .await
+    }
+
+    /// Tell the members of presence channels on this node which users arrived or left
+    async fn announce_presence(&self, events: impl IntoIterator<Item = ChannelEvent>) {
+        for event in events {
+            let (channel_id, name, member) = match event {
+                ChannelEvent::MemberAdded(channel_id, member) => {
+                    (channel_id, "presence_added", member)
+                }
+                ChannelEvent::MemberRemoved(channel_id, member) => {
+                    (channel_id, "presence_removed", member)
+                }
+                _ => continue,
+            };
+            let Some(channel) = self.channel_manager.get_channel(channel_id).await else {
+                continue;
+            };
+
+            let payload = serde_json::json!({
+                "event": name,
+                "channel": channel.metadata.name,
+                "member": member,
+            });
+            let member_ids = self.channel_manager.local_member_ids(&channel).await;
+            self.deliver_to_members(
+                channel_id,
+                member_ids,
+                WebSocketMessage::Text(payload.to_string()),
+            )
+            .await;
+        }
+    }
 
-        // Broadcast to all channel members
+    /// Send a channel message to the given members connected to this node
+    async fn deliver_to_members(
+        &self,
+        channel_id: ChannelId,
+        member_ids: Vec<ConnectionId>,
+        message: WebSocketMessage,
+    ) -> BroadcastResult {
         let mut results = BroadcastResult::new();
 
         for member_id in member_ids {
@@ -229,7 +434,100 @@ impl ConnectionRegistry {
             }
         }
 
-        Ok(results)
+        results
+    }
+
+    /// Apply channel events and deliver messages published by other nodes
+    ///
+    /// Subscribes to the channel manager's broker, asks the other nodes for the channels
+    /// they know and returns the task processing the subscription. The task also sends this
+    /// node's heartbeats and drops the members of nodes that stopped sending theirs. It
+    /// stops when the subscription ends or the registry is dropped.
+    pub async fn listen_to_broker(self: &Arc<Self>) -> WebSocketResult<JoinHandle<()>> {
+        let broker = self.channel_manager.broker().clone();
+        let mut subscription = broker.subscribe().await?;
+        let registry: Weak<Self> = Arc::downgrade(self);
+
+        info!(
+            "Listening to {} broker as node {}",
+            broker.name(),
+            self.channel_manager.node_id()
+        );
+        self.channel_manager.request_snapshot().await;
+
+        let mut heartbeat = tokio::time::interval(self.channel_manager.heartbeat_interval());
+        heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        Ok(tokio::spawn(async move {
+            loop {
+                let envelope = tokio::select! {
+                    envelope = subscription.recv() => envelope,
+                    _ = heartbeat.tick() => {
+                        let Some(registry) = registry.upgrade() else {
+                            break;
+                        };
+                        registry.channel_manager.publish_heartbeat().await;
+                        let removed = registry.channel_manager.expire_nodes().await;
+                        registry.announce_presence(removed).await;
+                        continue;
+                    }
+                };
+                let Some(envelope) = envelope else {
+                    break;
+                };
+                let Some(registry) = registry.upgrade() else {
+                    break;
+                };
+                if envelope.origin == registry.channel_manager.node_id() {
+                    continue;
+                }
+
+                let message = match &envelope.event {
+                    BrokerEvent::Broadcast(message) => {
+                        registry.channel_manager.node_seen(envelope.origin).await;
+                        registry.broadcast_local(message.clone()).await;
+                        continue;
+                    }
+                    BrokerEvent::ChannelMessage(message) => {
+                        registry.channel_manager.node_seen(envelope.origin).await;
+                        message.clone()
+                    }
+                    _ => {
+                        let presence = registry
+                            .channel_manager
+                            .apply_membership_event(envelope)
+                            .await;
+                        registry.announce_presence(presence).await;
+                        continue;
+                    }
+                };
+
+                // Keep replicated messages in order with local sends and replays
+                let Some(channel) = registry
+                    .channel_manager
+                    .get_channel(message.channel_id)
+                    .await
+                else {
+                    continue;
+                };
+                let _delivery = channel.lock_delivery().await;
+                let Some((message, member_ids)) = registry
+                    .channel_manager
+                    .receive_channel_message(message)
+                    .await
+                else {
+                    continue;
+                };
+                registry
+                    .deliver_to_members(
+                        channel.id,
+                        member_ids,
+                        message.to_delivery(&channel.metadata.name),
+                    )
+                    .await;
+            }
+            debug!("Broker listener stopped");
+        }))
     }
 
     /// Send a text message to a specific channel
@@ -269,12 +567,33 @@ impl ConnectionRegistry {
 
     /// Close all connections
     pub async fn close_all_connections(&self) -> CloseAllResult {
+        self.close_all(None).await
+    }
+
+    /// Close all connections with a close code and reason
+    pub async fn close_all_connections_with_reason(
+        &self,
+        code: u16,
+        reason: String,
+    ) -> CloseAllResult {
+        self.close_all(Some(CloseFrame { code, reason })).await
+    }
+
+    async fn close_all(&self, frame: Option<CloseFrame>) -> CloseAllResult {
         let connections = self.get_all_connections().await;
         let mut results = CloseAllResult::new();
         let mut to_remove = Vec::new();
 
         for connection in connections {
-            match connection.close().await {
+            let closed = match &frame {
+                Some(frame) => {
+                    connection
+                        .close_with_reason(frame.code, frame.reason.clone())
+                        .await
+                }
+                None => connection.close().await,
+            };
+            match closed {
                 Ok(_) => {
                     to_remove.push(connection.id);
                     results.closed_count += 1;
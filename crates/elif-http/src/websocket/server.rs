@@ -1,8 +1,14 @@
 //! WebSocket server integration with elif HTTP server
 
+#[cfg(feature = "auth")]
+use super::auth::WebSocketAuth;
+use super::broker::ChannelBroker;
+use super::channel::{ChannelHistory, ChannelManager};
 use super::connection::WebSocketConnection;
+use super::handler::WebSocketUpgrade;
 use super::registry::{ConnectionRegistry, RegistryStats};
 use super::types::{ConnectionId, WebSocketConfig, WebSocketMessage, WebSocketResult};
+use crate::request::ElifRequest;
 use crate::routing::ElifRouter;
 use axum::{extract::ws::WebSocketUpgrade as AxumWebSocketUpgrade, routing::get};
 use std::sync::Arc;
@@ -14,27 +20,36 @@ pub struct WebSocketServer {
     /// Connection registry
     registry: Arc<ConnectionRegistry>,
     /// WebSocket configuration
-    _config: WebSocketConfig,
+    config: WebSocketConfig,
+    /// Authentication required to upgrade
+    #[cfg(feature = "auth")]
+    auth: Option<WebSocketAuth>,
     /// Cleanup task handle
     cleanup_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Broker listener task handle
+    broker_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl WebSocketServer {
     /// Create a new WebSocket server
     pub fn new() -> Self {
-        Self {
-            registry: Arc::new(ConnectionRegistry::new()),
-            _config: WebSocketConfig::default(),
-            cleanup_handle: None,
-        }
+        Self::with_config(WebSocketConfig::default())
     }
 
     /// Create with custom configuration
     pub fn with_config(config: WebSocketConfig) -> Self {
+        Self::with_registry(Arc::new(ConnectionRegistry::new()), config)
+    }
+
+    /// Create with an existing connection registry
+    pub fn with_registry(registry: Arc<ConnectionRegistry>, config: WebSocketConfig) -> Self {
         Self {
-            registry: Arc::new(ConnectionRegistry::new()),
-            _config: config,
+            registry,
+            config,
+            #[cfg(feature = "auth")]
+            auth: None,
             cleanup_handle: None,
+            broker_handle: None,
         }
     }
 
@@ -48,41 +63,45 @@ impl WebSocketServer {
         self.registry.stats().await
     }
 
+    /// Authenticate upgrade requests on the routes added from now on
+    #[cfg(feature = "auth")]
+    pub fn set_auth(&mut self, auth: WebSocketAuth) {
+        self.auth = Some(auth);
+    }
+
     /// Add a WebSocket route to the router using a simple closure
-    /// For now, this is a placeholder that will be improved in later iterations
+    ///
+    /// Each upgraded connection is added to the registry and handed to `handler`.
     pub fn add_websocket_route<F, Fut>(
         &self,
         router: ElifRouter,
         path: &str,
-        _handler: F,
+        handler: F,
     ) -> ElifRouter
     where
         F: Fn(ConnectionId, Arc<WebSocketConnection>) -> Fut + Send + Sync + Clone + 'static,
         Fut: std::future::Future<Output = ()> + Send + 'static,
     {
-        // For the foundation, we'll add a simple WebSocket endpoint
-        // Full message handling will be added in later iterations
-
-        // Create a placeholder WebSocket handler
-        let ws_handler = move |ws: AxumWebSocketUpgrade| async move {
-            ws.on_upgrade(|mut socket| async move {
-                tracing::info!("WebSocket connection established");
-                // For now, just keep the connection alive
-                while let Some(_msg) = socket.recv().await {
-                    // Echo back for testing
-                    if let Ok(_) = socket
-                        .send(axum::extract::ws::Message::Text("pong".to_string()))
-                        .await
-                    {
-                        continue;
-                    }
-                    break;
-                }
-                tracing::info!("WebSocket connection closed");
-            })
+        let registry = self.registry.clone();
+        let config = self.config.clone();
+        #[cfg(feature = "auth")]
+        let auth = self.auth.clone();
+
+        let ws_handler = move |ws: AxumWebSocketUpgrade, parts: axum::http::request::Parts| {
+            let upgrade = WebSocketUpgrade::with_config(registry.clone(), config.clone());
+            #[cfg(feature = "auth")]
+            let upgrade = match auth.clone() {
+                Some(auth) => upgrade.auth(auth),
+                None => upgrade,
+            };
+            let handler = handler.clone();
+
+            async move {
+                let request = ElifRequest::from_axum_parts(parts);
+                upgrade.upgrade(ws, &request, handler).await
+            }
         };
 
-        // FIXED: Use the new add_axum_route method to preserve all router state
         router.add_axum_route(path, get(ws_handler))
     }
 
@@ -197,6 +216,29 @@ impl WebSocketServer {
             info!("Stopped WebSocket cleanup task");
         }
     }
+
+    /// Start applying channel events published by other nodes through the broker
+    ///
+    /// Returns once the broker subscription is established, so no event published after
+    /// this call is missed.
+    pub async fn start_broker_listener(&mut self) -> WebSocketResult<()> {
+        if self.broker_handle.is_some() {
+            debug!("Broker listener already running");
+            return Ok(());
+        }
+
+        let handle = self.registry.listen_to_broker().await?;
+        self.broker_handle = Some(handle);
+        Ok(())
+    }
+
+    /// Stop the broker listener
+    pub fn stop_broker_listener(&mut self) {
+        if let Some(handle) = self.broker_handle.take() {
+            handle.abort();
+            info!("Stopped WebSocket broker listener");
+        }
+    }
 }
 
 impl Default for WebSocketServer {
@@ -208,14 +250,32 @@ impl Default for WebSocketServer {
 impl Drop for WebSocketServer {
     fn drop(&mut self) {
         self.stop_cleanup_task();
+        self.stop_broker_listener();
     }
 }
 
 /// Builder for WebSocket server configuration
-#[derive(Debug)]
 pub struct WebSocketServerBuilder {
     _config: WebSocketConfig,
     cleanup_interval: Option<u64>,
+    broker: Option<Arc<dyn ChannelBroker>>,
+    history: Option<Arc<dyn ChannelHistory>>,
+    #[cfg(feature = "auth")]
+    auth: Option<WebSocketAuth>,
+}
+
+impl std::fmt::Debug for WebSocketServerBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocketServerBuilder")
+            .field("config", &self._config)
+            .field("cleanup_interval", &self.cleanup_interval)
+            .field("broker", &self.broker.as_ref().map(|broker| broker.name()))
+            .field(
+                "history",
+                &self.history.as_ref().map(|history| history.name()),
+            )
+            .finish()
+    }
 }
 
 impl WebSocketServerBuilder {
@@ -224,6 +284,10 @@ impl WebSocketServerBuilder {
         Self {
             _config: WebSocketConfig::default(),
             cleanup_interval: Some(300), // 5 minutes default
+            broker: None,
+            history: None,
+            #[cfg(feature = "auth")]
+            auth: None,
         }
     }
 
@@ -269,16 +333,67 @@ impl WebSocketServerBuilder {
         self
     }
 
+    /// Share channels with other server instances through a broker
+    pub fn broker(mut self, broker: Arc<dyn ChannelBroker>) -> Self {
+        self.broker = Some(broker);
+        self
+    }
+
+    /// Record channel messages in a history store, e.g. one shared by all instances
+    pub fn history(mut self, history: Arc<dyn ChannelHistory>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Authenticate upgrade requests, e.g. with the JWT or session middleware of `elif-auth`
+    #[cfg(feature = "auth")]
+    pub fn auth(mut self, auth: WebSocketAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
     /// Build the WebSocket server
+    ///
+    /// With a broker, the server doesn't receive events from other instances until
+    /// [`WebSocketServer::start_broker_listener`] is called; [`Self::start`] does both.
     pub fn build(self) -> WebSocketServer {
-        let mut server = WebSocketServer::with_config(self._config);
+        let mut server = match (self.broker, self.history) {
+            (None, None) => WebSocketServer::with_config(self._config),
+            (broker, history) => {
+                let mut channel_manager = match broker {
+                    Some(broker) => ChannelManager::with_broker(broker),
+                    None => ChannelManager::new(),
+                };
+                if let Some(history) = history {
+                    channel_manager = channel_manager.with_history(history);
+                }
+                let channel_manager = Arc::new(channel_manager);
+                let registry = Arc::new(ConnectionRegistry::with_channel_manager(channel_manager));
+                WebSocketServer::with_registry(registry, self._config)
+            }
+        };
 
+        #[cfg(feature = "auth")]
+        if let Some(auth) = self.auth {
+            server.set_auth(auth);
+        }
         if let Some(interval) = self.cleanup_interval {
             server.start_cleanup_task(interval);
         }
 
         server
     }
+
+    /// Build the WebSocket server and, with a broker, wait until it listens to the
+    /// other instances
+    pub async fn start(self) -> WebSocketResult<WebSocketServer> {
+        let shared = self.broker.is_some();
+        let mut server = self.build();
+        if shared {
+            server.start_broker_listener().await?;
+        }
+        Ok(server)
+    }
 }
 
 impl Default for WebSocketServerBuilder {
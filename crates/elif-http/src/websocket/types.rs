@@ -53,6 +53,11 @@ pub struct CloseFrame {
     pub reason: String,
 }
 
+impl CloseFrame {
+    /// Close code sent when the server is going away, e.g. shutting down
+    pub const GOING_AWAY: u16 = 1001;
+}
+
 /// Message type for routing and handling
 #[derive(Debug, Clone, PartialEq)]
 pub enum MessageType {
@@ -149,6 +154,44 @@ impl From<WebSocketMessage> for tungstenite::Message {
     }
 }
 
+// Conversion from axum message to elif message
+impl From<axum::extract::ws::Message> for WebSocketMessage {
+    fn from(msg: axum::extract::ws::Message) -> Self {
+        use axum::extract::ws::Message;
+
+        match msg {
+            Message::Text(text) => Self::Text(text),
+            Message::Binary(data) => Self::Binary(data),
+            Message::Ping(data) => Self::Ping(data),
+            Message::Pong(data) => Self::Pong(data),
+            Message::Close(frame) => Self::Close(frame.map(|f| CloseFrame {
+                code: f.code,
+                reason: f.reason.into_owned(),
+            })),
+        }
+    }
+}
+
+// Conversion from elif message to axum message
+impl From<WebSocketMessage> for axum::extract::ws::Message {
+    fn from(msg: WebSocketMessage) -> Self {
+        use axum::extract::ws::Message;
+
+        match msg {
+            WebSocketMessage::Text(text) => Message::Text(text),
+            WebSocketMessage::Binary(data) => Message::Binary(data),
+            WebSocketMessage::Ping(data) => Message::Ping(data),
+            WebSocketMessage::Pong(data) => Message::Pong(data),
+            WebSocketMessage::Close(frame) => {
+                Message::Close(frame.map(|f| axum::extract::ws::CloseFrame {
+                    code: f.code,
+                    reason: f.reason.into(),
+                }))
+            }
+        }
+    }
+}
+
 /// WebSocket errors - clean API over tungstenite errors
 #[derive(Debug, Error)]
 pub enum WebSocketError {
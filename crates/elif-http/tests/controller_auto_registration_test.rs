@@ -32,6 +32,7 @@ impl ElifController for AutoRegTestController {
                 handler_name: "index".to_string(),
                 middleware: vec![],
                 params: vec![],
+                limits: Default::default(),
             },
             ControllerRoute {
                 method: HttpMethod::GET,
@@ -39,6 +40,7 @@ impl ElifController for AutoRegTestController {
                 handler_name: "info".to_string(),
                 middleware: vec![],
                 params: vec![],
+                limits: Default::default(),
             },
         ]
     }
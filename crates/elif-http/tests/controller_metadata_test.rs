@@ -36,6 +36,7 @@ impl ElifController for MetadataTestController {
                 handler_name: "index".to_string(),
                 middleware: vec!["auth".to_string()],
                 params: vec![],
+                limits: Default::default(),
             },
             ControllerRoute {
                 method: HttpMethod::POST,
@@ -43,6 +44,7 @@ impl ElifController for MetadataTestController {
                 handler_name: "create".to_string(),
                 middleware: vec!["validate".to_string(), "auth".to_string()],
                 params: vec![],
+                limits: Default::default(),
             },
         ]
     }
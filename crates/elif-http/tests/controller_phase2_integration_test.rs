@@ -39,6 +39,7 @@ impl ElifController for UserController {
                 handler_name: "index".to_string(),
                 middleware: vec!["auth".to_string()],
                 params: vec![],
+                limits: Default::default(),
             },
             ControllerRoute {
                 method: HttpMethod::GET,
@@ -46,6 +47,7 @@ impl ElifController for UserController {
                 handler_name: "show".to_string(),
                 middleware: vec!["auth".to_string()],
                 params: vec![],
+                limits: Default::default(),
             },
             ControllerRoute {
                 method: HttpMethod::POST,
@@ -53,6 +55,7 @@ impl ElifController for UserController {
                 handler_name: "create".to_string(),
                 middleware: vec!["auth".to_string(), "validate".to_string()],
                 params: vec![],
+                limits: Default::default(),
             },
         ]
     }
@@ -102,6 +105,7 @@ impl ElifController for PaymentController {
                 handler_name: "create".to_string(),
                 middleware: vec!["auth".to_string(), "rate_limit".to_string()],
                 params: vec![],
+                limits: Default::default(),
             },
             ControllerRoute {
                 method: HttpMethod::GET,
@@ -109,6 +113,7 @@ impl ElifController for PaymentController {
                 handler_name: "status".to_string(),
                 middleware: vec!["auth".to_string()],
                 params: vec![],
+                limits: Default::default(),
             },
         ]
     }
@@ -209,7 +209,14 @@ async fn create_test_server(
         max_request_size: 1024 * 1024,
         enable_tracing: false,
         health_check_path: "/health".to_string(),
+        liveness_path: "/health/live".to_string(),
+        readiness_path: "/health/ready".to_string(),
         shutdown_timeout_secs: 5,
+        pre_stop_delay_secs: 0,
+        tls: None,
+        trusted_proxies: Vec::new(),
+        metrics_path: None,
+        error_format: ErrorFormat::Json,
     };
 
     let mut server = Server::with_container(container, config)?;
@@ -439,7 +446,14 @@ async fn test_framework_server_configuration() {
         max_request_size: 2 * 1024 * 1024, // 2MB
         enable_tracing: true,
         health_check_path: "/api/health".to_string(),
+        liveness_path: "/api/health/live".to_string(),
+        readiness_path: "/api/health/ready".to_string(),
         shutdown_timeout_secs: 30,
+        pre_stop_delay_secs: 0,
+        tls: None,
+        trusted_proxies: Vec::new(),
+        metrics_path: None,
+        error_format: ErrorFormat::Json,
     };
 
     let mut server =
@@ -31,6 +31,11 @@ pub struct OpenApiConfig {
     /// Custom schema mappings for specific types
     pub custom_schemas: HashMap<String, String>,
 
+    /// Whether error responses are RFC 9457 `application/problem+json` documents,
+    /// matching `elif-http`'s problem error format
+    #[serde(default)]
+    pub problem_details: bool,
+
     /// Export settings
     pub export: ExportConfig,
 }
@@ -197,6 +202,7 @@ impl Default for OpenApiConfig {
             include_examples: true,
             nullable_optional: true,
             custom_schemas: HashMap::new(),
+            problem_details: false,
             export: ExportConfig {
                 formats: vec![ExportFormat::Json, ExportFormat::Yaml],
                 validate: true,
@@ -231,6 +237,12 @@ impl OpenApiConfig {
         self
     }
 
+    /// Document error responses as problem details
+    pub fn with_problem_details(mut self) -> Self {
+        self.problem_details = true;
+        self
+    }
+
     /// Add a tag
     pub fn add_tag(mut self, name: &str, description: Option<&str>) -> Self {
         self.tags.push(TagConfig {
@@ -4,8 +4,12 @@ use crate::{
     schema::{SchemaConfig, SchemaGenerator},
     specification::*,
 };
+use elif_http::errors::PROBLEM_JSON_CONTENT_TYPE;
 use std::collections::HashMap;
 
+/// Component name of the problem details schema
+const PROBLEM_DETAILS_SCHEMA: &str = "ProblemDetails";
+
 /// Main OpenAPI specification generator
 pub struct OpenApiGenerator {
     /// Configuration
@@ -264,21 +268,12 @@ impl OpenApiGenerator {
             );
         } else {
             for (status_code, schema_name) in response_schemas {
-                let schema = Schema {
-                    reference: Some(format!("#/components/schemas/{}", schema_name)),
-                    ..Default::default()
+                let content = if self.config.problem_details && is_error_status(status_code) {
+                    problem_content()
+                } else {
+                    json_content(schema_name)
                 };
 
-                let mut content = HashMap::new();
-                content.insert(
-                    "application/json".to_string(),
-                    MediaType {
-                        schema: Some(schema),
-                        example: None,
-                        examples: HashMap::new(),
-                    },
-                );
-
                 let description = match status_code.as_str() {
                     "200" => "OK",
                     "201" => "Created",
@@ -304,12 +299,27 @@ impl OpenApiGenerator {
             }
         }
 
+        // Any other error is described by problem details
+        if self.config.problem_details {
+            responses
+                .entry("default".to_string())
+                .or_insert_with(|| Response {
+                    description: "Error".to_string(),
+                    headers: HashMap::new(),
+                    content: problem_content(),
+                    links: HashMap::new(),
+                });
+        }
+
         Ok(responses)
     }
 
     /// Generate components section with schemas
     fn generate_components(&mut self, spec: &mut OpenApiSpec) -> OpenApiResult<()> {
-        let schemas = self.schema_generator.get_schemas().clone();
+        let mut schemas = self.schema_generator.get_schemas().clone();
+        if self.config.problem_details {
+            schemas.insert(PROBLEM_DETAILS_SCHEMA.to_string(), problem_details_schema());
+        }
         let security_schemes = self.convert_security_schemes();
 
         if !schemas.is_empty() || !security_schemes.is_empty() {
@@ -497,6 +507,109 @@ impl OpenApiGenerator {
     }
 }
 
+/// Check whether a response key is a 4xx or 5xx status code or range
+fn is_error_status(status_code: &str) -> bool {
+    status_code.starts_with('4') || status_code.starts_with('5')
+}
+
+/// JSON content referencing a component schema
+fn json_content(schema_name: &str) -> HashMap<String, MediaType> {
+    media_content("application/json", schema_name)
+}
+
+/// Problem details content, as rendered by `elif-http`'s problem error format
+fn problem_content() -> HashMap<String, MediaType> {
+    media_content(PROBLEM_JSON_CONTENT_TYPE, PROBLEM_DETAILS_SCHEMA)
+}
+
+fn media_content(media_type: &str, schema_name: &str) -> HashMap<String, MediaType> {
+    let schema = Schema {
+        reference: Some(format!("#/components/schemas/{}", schema_name)),
+        ..Default::default()
+    };
+
+    let mut content = HashMap::new();
+    content.insert(
+        media_type.to_string(),
+        MediaType {
+            schema: Some(schema),
+            example: None,
+            examples: HashMap::new(),
+        },
+    );
+    content
+}
+
+/// RFC 9457 problem details, with the extension members `elif-http` adds
+fn problem_details_schema() -> Schema {
+    let typed = |schema_type: &str, format: Option<&str>, description: &str| Schema {
+        schema_type: Some(schema_type.to_string()),
+        format: format.map(str::to_string),
+        description: Some(description.to_string()),
+        ..Default::default()
+    };
+
+    let mut type_schema = typed(
+        "string",
+        Some("uri-reference"),
+        "URI reference identifying the problem type",
+    );
+    type_schema.default = Some(serde_json::Value::String("about:blank".to_string()));
+
+    let mut errors = typed("object", None, "Errors of each invalid field");
+    errors.additional_properties = Some(Box::new(Schema {
+        schema_type: Some("array".to_string()),
+        items: Some(Box::new(typed("string", None, "Error message"))),
+        ..Default::default()
+    }));
+
+    let properties = HashMap::from([
+        ("type".to_string(), type_schema),
+        (
+            "title".to_string(),
+            typed("string", None, "Short summary of the problem type"),
+        ),
+        (
+            "status".to_string(),
+            typed("integer", Some("int32"), "HTTP status code"),
+        ),
+        (
+            "detail".to_string(),
+            typed("string", None, "Explanation specific to this occurrence"),
+        ),
+        (
+            "instance".to_string(),
+            typed(
+                "string",
+                Some("uri-reference"),
+                "URI reference identifying this occurrence",
+            ),
+        ),
+        (
+            "code".to_string(),
+            typed("string", None, "Framework error code"),
+        ),
+        (
+            "hint".to_string(),
+            typed("string", None, "How to resolve the problem"),
+        ),
+        (
+            "request_id".to_string(),
+            typed("string", None, "Id of the failed request"),
+        ),
+        ("errors".to_string(), errors),
+    ]);
+
+    Schema {
+        title: Some(PROBLEM_DETAILS_SCHEMA.to_string()),
+        schema_type: Some("object".to_string()),
+        description: Some("RFC 9457 problem details".to_string()),
+        properties,
+        required: vec!["title".to_string(), "status".to_string()],
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -553,4 +666,45 @@ mod tests {
         assert_eq!(operation.summary, Some("List users".to_string()));
         assert_eq!(operation.tags, vec!["Users".to_string()]);
     }
+
+    #[test]
+    fn test_problem_details_responses() {
+        let config = OpenApiConfig::new("Test API", "1.0.0").with_problem_details();
+        let mut generator = OpenApiGenerator::new(config);
+
+        let routes = vec![RouteMetadata {
+            method: "GET".to_string(),
+            path: "/users/{id}".to_string(),
+            summary: None,
+            description: None,
+            operation_id: None,
+            tags: Vec::new(),
+            request_schema: None,
+            response_schemas: HashMap::from([
+                ("200".to_string(), "User".to_string()),
+                ("404".to_string(), "Error".to_string()),
+            ]),
+            parameters: Vec::new(),
+            security: Vec::new(),
+            deprecated: false,
+        }];
+
+        let spec = generator.generate(&routes).unwrap();
+        let responses = &spec.paths["/users/{id}"].get.as_ref().unwrap().responses;
+
+        assert!(responses["200"].content.contains_key("application/json"));
+        for status in ["404", "default"] {
+            let problem = &responses[status].content[PROBLEM_JSON_CONTENT_TYPE];
+            assert_eq!(
+                problem.schema.as_ref().unwrap().reference.as_deref(),
+                Some("#/components/schemas/ProblemDetails")
+            );
+        }
+
+        let schemas = &spec.components.as_ref().unwrap().schemas;
+        assert_eq!(
+            schemas[PROBLEM_DETAILS_SCHEMA].required,
+            vec!["title".to_string(), "status".to_string()]
+        );
+    }
 }
@@ -63,6 +63,7 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
@@ -72,6 +73,8 @@ pub mod config;
 pub mod scheduler;
 pub mod worker;
 
+mod telemetry;
+
 pub use backends::*;
 pub use config::*;
 pub use scheduler::*;
@@ -186,6 +189,9 @@ pub struct JobEntry {
     processed_at: Option<DateTime<Utc>>,
     /// Last error message (if any)
     last_error: Option<String>,
+    /// W3C trace context of the span that enqueued the job (`otel` feature)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    trace_context: HashMap<String, String>,
 }
 
 impl JobEntry {
@@ -221,6 +227,7 @@ impl JobEntry {
             run_at,
             processed_at: None,
             last_error: None,
+            trace_context: telemetry::current_trace_context(),
         })
     }
 
@@ -259,6 +266,14 @@ impl JobEntry {
         &self.payload
     }
 
+    /// Get the trace context the job was enqueued in
+    ///
+    /// Holds `traceparent`/`tracestate` entries when the job was created inside a traced
+    /// span with the `otel` feature; workers continue that trace while running the job.
+    pub fn trace_context(&self) -> &HashMap<String, String> {
+        &self.trace_context
+    }
+
     /// Check if job is ready to be processed
     pub fn is_ready(&self) -> bool {
         matches!(self.state, JobState::Pending | JobState::Failed) && self.run_at <= Utc::now()
@@ -333,6 +348,7 @@ impl JobEntry {
             run_at,
             processed_at: None,
             last_error: None,
+            trace_context: telemetry::current_trace_context(),
         })
     }
 }
@@ -0,0 +1,112 @@
+//! Trace context carried from the enqueuing code to the worker running a job
+//!
+//! With the `otel` feature, jobs record the W3C trace context of the span they are created
+//! in, using the globally installed OpenTelemetry propagator (see `elif-http`'s
+//! `LoggingConfig::with_otlp`), and workers run them in a span continuing that trace.
+
+use crate::JobEntry;
+use std::collections::HashMap;
+
+/// Capture the trace context of the current span
+#[cfg(feature = "otel")]
+pub(crate) fn current_trace_context() -> HashMap<String, String> {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let context = tracing::Span::current().context();
+    let mut carrier = HashMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut carrier)
+    });
+    carrier
+}
+
+/// Capture the trace context of the current span
+#[cfg(not(feature = "otel"))]
+pub(crate) fn current_trace_context() -> HashMap<String, String> {
+    HashMap::new()
+}
+
+/// Create the span a worker runs a job in
+///
+/// With the `otel` feature, the span continues the trace the job was enqueued in.
+pub(crate) fn job_span(job: &JobEntry) -> tracing::Span {
+    let span = tracing::info_span!(
+        "job",
+        job.id = %job.id(),
+        job.r#type = %job.job_type(),
+        job.attempt = job.attempts() + 1,
+    );
+
+    #[cfg(feature = "otel")]
+    if !job.trace_context().is_empty() {
+        use opentelemetry::trace::TraceContextExt;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(job.trace_context())
+        });
+        if parent.span().span_context().is_remote() {
+            let _ = span.set_parent(parent);
+        }
+    }
+
+    span
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use super::*;
+    use crate::{Job, JobResult};
+    use async_trait::async_trait;
+    use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use serde::{Deserialize, Serialize};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct TracedJob;
+
+    #[async_trait]
+    impl Job for TracedJob {
+        async fn execute(&self) -> JobResult<()> {
+            Ok(())
+        }
+
+        fn job_type(&self) -> &'static str {
+            "traced"
+        }
+    }
+
+    #[test]
+    fn test_job_continues_enqueuing_trace() {
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let request = tracing::info_span!("http_request");
+        let (entry, trace_id) = request.in_scope(|| {
+            let entry = JobEntry::new(TracedJob, None, None).unwrap();
+            let trace_id = tracing::Span::current()
+                .context()
+                .span()
+                .span_context()
+                .trace_id();
+            (entry, trace_id)
+        });
+        assert!(entry.trace_context().contains_key("traceparent"));
+
+        // The context survives the backend's serialization
+        let stored: JobEntry =
+            serde_json::from_str(&serde_json::to_string(&entry).unwrap()).unwrap();
+        let span = job_span(&stored);
+        assert_eq!(span.context().span().span_context().trace_id(), trace_id);
+
+        // Jobs created outside a trace start their own
+        let untraced = JobEntry::new(TracedJob, None, None).unwrap();
+        assert!(untraced.trace_context().is_empty());
+    }
+}
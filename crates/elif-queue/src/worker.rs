@@ -1,5 +1,6 @@
 //! Worker implementation for processing jobs from the queue
 
+use crate::telemetry::job_span;
 use crate::{JobEntry, JobResult, Queue, QueueBackend, QueueConfig, QueueError, QueueResult};
 use async_trait::async_trait;
 use futures::future::BoxFuture;
@@ -7,7 +8,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{RwLock, Semaphore};
 use tokio::time::{interval, timeout, Duration};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 
 /// Job handler function type
 pub type JobHandler = Arc<dyn Fn(JobEntry) -> BoxFuture<'static, JobResult<()>> + Send + Sync>;
@@ -141,7 +142,8 @@ impl<B: QueueBackend + 'static> Worker<B> {
 
                         let result = if let Some(handler) = registry.get_handler(&job_type).await {
                             // Execute with timeout
-                            match timeout(job_timeout, handler(job_entry)).await {
+                            let span = job_span(&job_entry);
+                            match timeout(job_timeout, handler(job_entry).instrument(span)).await {
                                 Ok(result) => result,
                                 Err(_) => {
                                     error!("Job {} timed out after {:?}", job_id, job_timeout);
@@ -227,7 +229,8 @@ impl<B: QueueBackend + 'static> Worker<B> {
                                 debug!("Processing job {} of type {}", job_id, job_type);
 
                                 let result = if let Some(handler) = registry.get_handler(&job_type).await {
-                                    match timeout(job_timeout, handler(job_entry)).await {
+                                    let span = job_span(&job_entry);
+                                    match timeout(job_timeout, handler(job_entry).instrument(span)).await {
                                         Ok(result) => result,
                                         Err(_) => {
                                             error!("Job {} timed out after {:?}", job_id, job_timeout);
@@ -90,22 +90,9 @@ impl RateLimitMiddleware {
     fn extract_identifier(&self, request: &ElifRequest) -> Option<String> {
         match &self.config.identifier {
             RateLimitIdentifier::IpAddress => {
-                // Try to get real IP from forwarded headers first
-                if let Some(forwarded_for) = request.headers.get_str("x-forwarded-for") {
-                    if let Ok(forwarded_str) = forwarded_for.to_str() {
-                        return forwarded_str
-                            .split(',')
-                            .next()
-                            .map(|ip| ip.trim().to_string());
-                    }
-                }
-                if let Some(real_ip) = request.headers.get_str("x-real-ip") {
-                    if let Ok(real_ip_str) = real_ip.to_str() {
-                        return Some(real_ip_str.to_string());
-                    }
-                }
-                // Fall back to connection info (not available in this context, use placeholder)
-                Some("127.0.0.1".to_string())
+                // Forwarding headers are only honoured from trusted proxies, see
+                // `TrustedProxyMiddleware`; requests without connection info share a bucket
+                Some(request.client_ip().unwrap_or_else(|| "unknown".to_string()))
             }
             RateLimitIdentifier::UserId => {
                 // Extract from Authorization header or custom user header
@@ -307,9 +294,18 @@ impl Middleware for RateLimitMiddleware {
 mod tests {
     use super::*;
     use elif_http::middleware::v2::MiddlewarePipelineV2;
-    use elif_http::request::{ElifMethod, ElifRequest};
+    use elif_http::request::{ConnectionInfo, ElifMethod, ElifRequest};
     use elif_http::response::ElifHeaderMap;
 
+    fn request_from(ip: &str, path: &str) -> ElifRequest {
+        let mut request =
+            ElifRequest::new(ElifMethod::GET, path.parse().unwrap(), ElifHeaderMap::new());
+        request.insert_extension(ConnectionInfo::plain(
+            format!("{}:40000", ip).parse().unwrap(),
+        ));
+        request
+    }
+
     #[tokio::test]
     async fn test_rate_limit_middleware_basic() {
         let config = RateLimitConfig {
@@ -322,13 +318,7 @@ mod tests {
         let middleware = RateLimitMiddleware::new(config);
 
         // First request should be allowed
-        let mut headers1 = ElifHeaderMap::new();
-        headers1.insert(
-            "x-forwarded-for".parse().unwrap(),
-            "192.168.1.1".parse().unwrap(),
-        );
-
-        let request1 = ElifRequest::new(ElifMethod::GET, "/test".parse().unwrap(), headers1);
+        let request1 = request_from("192.168.1.1", "/test");
 
         let pipeline = MiddlewarePipelineV2::new().add(middleware.clone());
         let response1 = pipeline
@@ -340,13 +330,7 @@ mod tests {
         assert_eq!(response1.status_code(), ElifStatusCode::OK);
 
         // Second request should be allowed
-        let mut headers2 = ElifHeaderMap::new();
-        headers2.insert(
-            "x-forwarded-for".parse().unwrap(),
-            "192.168.1.1".parse().unwrap(),
-        );
-
-        let request2 = ElifRequest::new(ElifMethod::GET, "/test".parse().unwrap(), headers2);
+        let request2 = request_from("192.168.1.1", "/test");
 
         let response2 = pipeline
             .execute(request2, |_req| {
@@ -357,13 +341,7 @@ mod tests {
         assert_eq!(response2.status_code(), ElifStatusCode::OK);
 
         // Third request should be rate limited
-        let mut headers3 = ElifHeaderMap::new();
-        headers3.insert(
-            "x-forwarded-for".parse().unwrap(),
-            "192.168.1.1".parse().unwrap(),
-        );
-
-        let request3 = ElifRequest::new(ElifMethod::GET, "/test".parse().unwrap(), headers3);
+        let request3 = request_from("192.168.1.1", "/test");
 
         let response3 = pipeline
             .execute(request3, |_req| {
@@ -381,12 +359,7 @@ mod tests {
         let pipeline = MiddlewarePipelineV2::new().add(middleware);
 
         // Request from first IP
-        let mut headers1 = ElifHeaderMap::new();
-        headers1.insert(
-            "x-forwarded-for".parse().unwrap(),
-            "192.168.1.1".parse().unwrap(),
-        );
-        let request1 = ElifRequest::new(ElifMethod::GET, "/test".parse().unwrap(), headers1);
+        let request1 = request_from("192.168.1.1", "/test");
 
         let response1 = pipeline
             .execute(request1, |_req| {
@@ -396,12 +369,7 @@ mod tests {
         assert_eq!(response1.status_code(), ElifStatusCode::OK);
 
         // Request from different IP should be allowed
-        let mut headers2 = ElifHeaderMap::new();
-        headers2.insert(
-            "x-forwarded-for".parse().unwrap(),
-            "192.168.1.2".parse().unwrap(),
-        );
-        let request2 = ElifRequest::new(ElifMethod::GET, "/test".parse().unwrap(), headers2);
+        let request2 = request_from("192.168.1.2", "/test");
 
         let response2 = pipeline
             .execute(request2, |_req| {
@@ -411,6 +379,38 @@ mod tests {
         assert_eq!(response2.status_code(), ElifStatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_rate_limit_ignores_spoofed_forwarding_headers() {
+        let config = RateLimitConfig {
+            max_requests: 1,
+            window_seconds: 60,
+            identifier: RateLimitIdentifier::IpAddress,
+            exempt_paths: std::collections::HashSet::new(),
+        };
+        let pipeline = MiddlewarePipelineV2::new().add(RateLimitMiddleware::new(config));
+
+        for (attempt, spoofed_ip) in ["1.1.1.1", "2.2.2.2"].into_iter().enumerate() {
+            let mut request = request_from("192.168.1.1", "/test");
+            request
+                .headers
+                .add_header("x-forwarded-for", spoofed_ip)
+                .unwrap();
+
+            let response = pipeline
+                .execute(request, |_req| {
+                    Box::pin(async move { ElifResponse::ok().text("Success") })
+                })
+                .await;
+
+            let expected = if attempt == 0 {
+                ElifStatusCode::OK
+            } else {
+                ElifStatusCode::TOO_MANY_REQUESTS
+            };
+            assert_eq!(response.status_code(), expected);
+        }
+    }
+
     #[tokio::test]
     async fn test_rate_limit_exempt_paths() {
         let mut exempt_paths = std::collections::HashSet::new();
@@ -427,15 +427,8 @@ mod tests {
         let middleware = RateLimitMiddleware::new(config);
         let pipeline = MiddlewarePipelineV2::new().add(middleware);
 
-        let mut headers = ElifHeaderMap::new();
-        headers.insert(
-            "x-forwarded-for".parse().unwrap(),
-            "192.168.1.1".parse().unwrap(),
-        );
-
         // Health check should be exempt
-        let health_request =
-            ElifRequest::new(ElifMethod::GET, "/health".parse().unwrap(), headers.clone());
+        let health_request = request_from("192.168.1.1", "/health");
         let response = pipeline
             .execute(health_request, |_req| {
                 Box::pin(async move { ElifResponse::ok().text("Healthy") })
@@ -444,11 +437,7 @@ mod tests {
         assert_eq!(response.status_code(), ElifStatusCode::OK);
 
         // Public API should be exempt (wildcard match)
-        let public_request = ElifRequest::new(
-            ElifMethod::GET,
-            "/api/v1/public/status".parse().unwrap(),
-            headers.clone(),
-        );
+        let public_request = request_from("192.168.1.1", "/api/v1/public/status");
         let response = pipeline
             .execute(public_request, |_req| {
                 Box::pin(async move { ElifResponse::ok().text("Status") })
@@ -457,11 +446,7 @@ mod tests {
         assert_eq!(response.status_code(), ElifStatusCode::OK);
 
         // Regular API should be rate limited after using up quota (max_requests = 1)
-        let api_request1 = ElifRequest::new(
-            ElifMethod::GET,
-            "/api/v1/users".parse().unwrap(),
-            headers.clone(),
-        );
+        let api_request1 = request_from("192.168.1.1", "/api/v1/users");
         let response1 = pipeline
             .execute(api_request1, |_req| {
                 Box::pin(async move { ElifResponse::ok().text("Users") })
@@ -470,8 +455,7 @@ mod tests {
         assert_eq!(response1.status_code(), ElifStatusCode::OK);
 
         // Second request should be rate limited
-        let api_request2 =
-            ElifRequest::new(ElifMethod::GET, "/api/v1/users".parse().unwrap(), headers);
+        let api_request2 = request_from("192.168.1.1", "/api/v1/users");
         let response2 = pipeline
             .execute(api_request2, |_req| {
                 Box::pin(async move { ElifResponse::ok().text("Should not reach handler") })
@@ -547,14 +531,8 @@ mod tests {
         let middleware = RateLimitMiddleware::new(config);
         let pipeline = MiddlewarePipelineV2::new().add(middleware);
 
-        let mut headers = ElifHeaderMap::new();
-        headers.insert(
-            "x-forwarded-for".parse().unwrap(),
-            "192.168.1.1".parse().unwrap(),
-        );
-
         // Use up the quota
-        let request1 = ElifRequest::new(ElifMethod::GET, "/test".parse().unwrap(), headers.clone());
+        let request1 = request_from("192.168.1.1", "/test");
         let response1 = pipeline
             .execute(request1, |_req| {
                 Box::pin(async move { ElifResponse::ok().text("Success") })
@@ -563,7 +541,7 @@ mod tests {
         assert_eq!(response1.status_code(), ElifStatusCode::OK);
 
         // Second request should return rate limit response
-        let request2 = ElifRequest::new(ElifMethod::GET, "/test".parse().unwrap(), headers);
+        let request2 = request_from("192.168.1.1", "/test");
         let response2 = pipeline
             .execute(request2, |_req| {
                 Box::pin(async move { ElifResponse::ok().text("Should not reach handler") })
@@ -1,7 +1,11 @@
 //! File validation utilities
 
 use crate::{StorageError, StorageResult};
+use bytes::Bytes;
+use futures::Stream;
 use std::collections::HashSet;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 /// File validation configuration
 #[derive(Debug, Clone)]
@@ -441,6 +445,128 @@ impl FileValidator {
 
         Ok(())
     }
+
+    /// Validate everything known about a file before its content arrives
+    pub fn validate_metadata(&self, filename: &str, mime_type: &str) -> StorageResult<()> {
+        self.validate_filename(filename)?;
+        self.validate_extension(filename)?;
+        self.validate_mime_type(mime_type)?;
+
+        Ok(())
+    }
+
+    /// Wrap a content stream so size and content are validated as chunks arrive
+    ///
+    /// Content is checked on the first chunk, size on every chunk and the minimum size
+    /// once the stream ends. Failures are yielded as `io::Error`s wrapping the
+    /// `StorageError`; use [`ValidatedStream::into_storage_error`] to recover it.
+    pub fn validate_stream<S>(
+        &self,
+        filename: &str,
+        mime_type: &str,
+        stream: S,
+    ) -> ValidatedStream<S>
+    where
+        S: Stream<Item = Result<Bytes, std::io::Error>>,
+    {
+        ValidatedStream {
+            inner: stream,
+            validator: FileValidator::new(self.config.clone()),
+            filename: filename.to_string(),
+            mime_type: mime_type.to_string(),
+            bytes_read: 0,
+            finished: false,
+        }
+    }
+}
+
+/// Stream wrapper applying `FileValidator` checks to streamed file content
+pub struct ValidatedStream<S> {
+    inner: S,
+    validator: FileValidator,
+    filename: String,
+    mime_type: String,
+    bytes_read: u64,
+    finished: bool,
+}
+
+impl<S> ValidatedStream<S> {
+    /// Get the total bytes read so far
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Recover the validation error from a storage error raised while consuming the stream
+    pub fn into_storage_error(error: StorageError) -> StorageError {
+        match error {
+            StorageError::Io(io_error)
+                if io_error
+                    .get_ref()
+                    .is_some_and(|inner| inner.is::<StorageError>()) =>
+            {
+                match io_error
+                    .into_inner()
+                    .map(|inner| inner.downcast::<StorageError>())
+                {
+                    Some(Ok(storage_error)) => *storage_error,
+                    _ => StorageError::Validation("Stream validation failed".to_string()),
+                }
+            }
+            other => other,
+        }
+    }
+
+    fn fail(&mut self, error: StorageError) -> Poll<Option<Result<Bytes, std::io::Error>>> {
+        self.finished = true;
+        Poll::Ready(Some(Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            error,
+        ))))
+    }
+}
+
+impl<S> Stream for ValidatedStream<S>
+where
+    S: Stream<Item = Result<Bytes, std::io::Error>> + Unpin,
+{
+    type Item = Result<Bytes, std::io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                if self.bytes_read == 0 {
+                    if let Err(e) =
+                        self.validator
+                            .validate_content(&self.filename, &chunk, &self.mime_type)
+                    {
+                        return self.fail(e);
+                    }
+                }
+                self.bytes_read += chunk.len() as u64;
+
+                if let Some(max_size) = self.validator.config.max_file_size {
+                    if self.bytes_read > max_size {
+                        let error = StorageError::FileTooLarge(self.bytes_read, max_size);
+                        return self.fail(error);
+                    }
+                }
+
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            Poll::Ready(None) => {
+                if let Err(e) = self.validator.validate_size(self.bytes_read) {
+                    return self.fail(e);
+                }
+                self.finished = true;
+                Poll::Ready(None)
+            }
+            other => other,
+        }
+    }
 }
 
 /// Detect MIME type from file content using magic numbers
@@ -548,6 +674,35 @@ mod tests {
         assert!(validator.validate_filename("CON.txt").is_err()); // Reserved name
     }
 
+    #[tokio::test]
+    async fn test_validated_stream() {
+        use futures::StreamExt;
+
+        let validator = FileValidator::new(ValidationConfig::new().max_size(8).min_size(2));
+        assert!(validator
+            .validate_metadata("notes.txt", "text/plain")
+            .is_ok());
+        assert!(validator
+            .validate_metadata("setup.exe", "application/octet-stream")
+            .is_err());
+
+        let chunks = vec![Ok(Bytes::from("hello")), Ok(Bytes::from(" world"))];
+        let mut stream =
+            validator.validate_stream("notes.txt", "text/plain", futures::stream::iter(chunks));
+        assert_eq!(stream.next().await.unwrap().unwrap(), "hello");
+        let error = stream.next().await.unwrap().unwrap_err();
+        assert!(stream.next().await.is_none());
+
+        let error = ValidatedStream::<()>::into_storage_error(StorageError::Io(error));
+        assert!(matches!(error, StorageError::FileTooLarge(11, 8)));
+
+        let chunks = vec![Ok(Bytes::from("x"))];
+        let mut stream =
+            validator.validate_stream("notes.txt", "text/plain", futures::stream::iter(chunks));
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(stream.next().await.unwrap().is_err()); // Below minimum size
+    }
+
     #[test]
     fn test_detect_mime_from_content() {
         // JPEG
@@ -5,6 +5,7 @@
 //! and helper functions.
 
 use crate::{TestError, TestResult};
+use elif_orm::loading::QueryRecorder;
 use serde_json::Value as JsonValue;
 
 /// Collection of test assertions
@@ -177,6 +178,38 @@ impl TestAssertions {
         }
         Ok(())
     }
+
+    /// Assert that no N+1 query pattern was recorded
+    pub fn assert_no_n_plus_one(recorder: &QueryRecorder) -> TestResult<()> {
+        let detections = recorder.detections();
+        if !detections.is_empty() {
+            let details: Vec<String> = detections
+                .iter()
+                .map(|d| format!("{} ({} queries): {}", d.relation, d.count, d.sql))
+                .collect();
+            return Err(TestError::Assertion {
+                message: format!(
+                    "N+1 queries detected, consider eager loading:\n{}",
+                    details.join("\n")
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Assert that at most `max_queries` queries were recorded
+    pub fn assert_max_queries(recorder: &QueryRecorder, max_queries: usize) -> TestResult<()> {
+        let count = recorder.query_count();
+        if count > max_queries {
+            return Err(TestError::Assertion {
+                message: format!(
+                    "Expected at most {} queries, but {} were executed",
+                    max_queries, count
+                ),
+            });
+        }
+        Ok(())
+    }
 }
 
 /// Helper function for JSON containment checking
@@ -284,6 +317,20 @@ macro_rules! assert_time_close {
     };
 }
 
+#[macro_export]
+macro_rules! assert_no_n_plus_one {
+    ($recorder:expr) => {
+        $crate::assertions::TestAssertions::assert_no_n_plus_one($recorder)?
+    };
+}
+
+#[macro_export]
+macro_rules! assert_max_queries {
+    ($recorder:expr, $max:expr) => {
+        $crate::assertions::TestAssertions::assert_max_queries($recorder, $max)?
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +441,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_query_assertions() -> TestResult<()> {
+        use elif_orm::loading::QueryRecorderConfig;
+
+        let recorder = QueryRecorder::with_config(QueryRecorderConfig {
+            enabled: true,
+            ..Default::default()
+        });
+        recorder.record("SELECT * FROM users", None);
+        recorder.record(
+            "SELECT * FROM posts WHERE user_id = $1",
+            Some("users.posts"),
+        );
+
+        TestAssertions::assert_no_n_plus_one(&recorder)?;
+        TestAssertions::assert_max_queries(&recorder, 2)?;
+
+        recorder.record(
+            "SELECT * FROM posts WHERE user_id = $1",
+            Some("users.posts"),
+        );
+        assert!(TestAssertions::assert_no_n_plus_one(&recorder).is_err());
+        assert!(TestAssertions::assert_max_queries(&recorder, 2).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_macro_usage() -> TestResult<()> {
         let json1 = json!({"test": "value"});
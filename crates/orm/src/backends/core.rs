@@ -376,6 +376,25 @@ pub enum SqlDialect {
 }
 
 impl SqlDialect {
+    /// Get the database system name for this dialect
+    pub fn name(&self) -> &'static str {
+        match self {
+            SqlDialect::PostgreSQL => "postgresql",
+            SqlDialect::MySQL => "mysql",
+            SqlDialect::SQLite => "sqlite",
+        }
+    }
+
+    /// Get the dialect of a sqlx database driver
+    pub fn for_driver<DB: sqlx::Database>() -> Option<Self> {
+        match DB::NAME {
+            "PostgreSQL" => Some(SqlDialect::PostgreSQL),
+            "MySQL" => Some(SqlDialect::MySQL),
+            "SQLite" => Some(SqlDialect::SQLite),
+            _ => None,
+        }
+    }
+
     /// Get the parameter placeholder style for this dialect
     pub fn parameter_placeholder(&self, index: usize) -> String {
         match self {
@@ -531,6 +550,16 @@ impl DatabaseBackendRegistry {
         backend.create_pool(database_url, config).await
     }
 
+    /// Get the SQL dialect of the backend handling the given URL
+    pub fn dialect_for_url(&self, database_url: &str) -> OrmResult<SqlDialect> {
+        let backend_type = self.detect_backend_from_url(database_url)?;
+        let backend = self.get(&backend_type).ok_or_else(|| {
+            OrmError::Connection(format!("No backend registered for {}", backend_type))
+        })?;
+
+        Ok(backend.sql_dialect())
+    }
+
     /// Detect database backend type from URL
     fn detect_backend_from_url(
         &self,
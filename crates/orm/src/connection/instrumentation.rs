@@ -0,0 +1,585 @@
+//! Query Instrumentation
+//!
+//! This module emits a `tracing` span for every executed query, logs queries that
+//! exceed a configurable slow-query threshold with their bound SQL, and aggregates
+//! statistics per query shape (the SQL with literals and parameters normalized).
+//!
+//! Query spans are children of the current span, so with an OpenTelemetry layer installed
+//! (see `elif-http`'s `LoggingConfig::with_otlp`) they join the request's trace. With the
+//! `otel` feature and [`QueryLogConfig::trace_comments`], the W3C trace context is also
+//! appended to the SQL as a [sqlcommenter](https://google.github.io/sqlcommenter/) comment.
+
+use crate::backends::{DatabaseValue, SqlDialect};
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+/// Configuration for query logging and slow-query detection
+#[derive(Debug, Clone)]
+pub struct QueryLogConfig {
+    /// Whether queries are instrumented at all
+    pub enabled: bool,
+    /// Queries taking longer than this are logged with their bound SQL
+    pub slow_query_threshold: Option<Duration>,
+    /// Maximum number of distinct query shapes kept in the statistics
+    pub max_tracked_shapes: usize,
+    /// Append the trace context to executed SQL as a comment (requires the `otel` feature)
+    pub trace_comments: bool,
+}
+
+impl Default for QueryLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            slow_query_threshold: Some(Duration::from_millis(500)),
+            max_tracked_shapes: 1000,
+            trace_comments: false,
+        }
+    }
+}
+
+/// Aggregated statistics for a single query shape
+#[derive(Debug, Clone, Default)]
+pub struct QueryShapeStats {
+    pub shape: String,
+    pub count: u64,
+    pub error_count: u64,
+    pub slow_count: u64,
+    pub total_rows: u64,
+    pub total_duration: Duration,
+    pub max_duration: Duration,
+}
+
+impl QueryShapeStats {
+    /// Calculate the average execution time
+    pub fn average_duration(&self) -> Duration {
+        if self.count > 0 {
+            self.total_duration / self.count as u32
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Query instrumentation with per-shape statistics
+#[derive(Debug, Default)]
+pub struct QueryInstrumentation {
+    config: RwLock<QueryLogConfig>,
+    shapes: Mutex<HashMap<String, QueryShapeStats>>,
+}
+
+static GLOBAL_INSTRUMENTATION: Lazy<QueryInstrumentation> = Lazy::new(QueryInstrumentation::new);
+
+impl QueryInstrumentation {
+    /// Create a new instrumentation with default configuration
+    pub fn new() -> Self {
+        Self::with_config(QueryLogConfig::default())
+    }
+
+    /// Create a new instrumentation with custom configuration
+    pub fn with_config(config: QueryLogConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            shapes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the global instrumentation used by `QueryBuilder` execution
+    pub fn global() -> &'static QueryInstrumentation {
+        &GLOBAL_INSTRUMENTATION
+    }
+
+    /// Get the current configuration
+    pub fn config(&self) -> QueryLogConfig {
+        self.config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the configuration
+    pub fn configure(&self, config: QueryLogConfig) {
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+    }
+
+    /// Set the slow query threshold (`None` disables slow-query logging)
+    pub fn set_slow_query_threshold(&self, threshold: Option<Duration>) {
+        self.config
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .slow_query_threshold = threshold;
+    }
+
+    /// Get the SQL to execute, with the trace context of the current span appended
+    ///
+    /// The SQL is unchanged unless `trace_comments` is enabled and the current span is part
+    /// of an OpenTelemetry trace. Pass the original SQL to `instrument_query` so the comment
+    /// doesn't end up in the query shapes.
+    pub fn annotate_sql<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        let config = self.config();
+        if !config.enabled || !config.trace_comments {
+            return Cow::Borrowed(sql);
+        }
+
+        let context = current_trace_context();
+        if context.is_empty() {
+            Cow::Borrowed(sql)
+        } else {
+            Cow::Owned(sql_comment(sql, &context))
+        }
+    }
+
+    /// Execute a query future inside a tracing span and record its statistics
+    ///
+    /// `rows` extracts the number of returned or affected rows from the result.
+    pub async fn instrument_query<T, E, F, R>(
+        &self,
+        dialect: &SqlDialect,
+        sql: &str,
+        params: &[String],
+        query: F,
+        rows: R,
+    ) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+        R: FnOnce(&T) -> u64,
+    {
+        let config = self.config();
+        if !config.enabled {
+            return query.await;
+        }
+
+        let shape = sql_shape(sql);
+        let span = tracing::info_span!(
+            "db.query",
+            db.system = dialect.name(),
+            db.statement = %shape,
+            db.rows = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+
+        let start = Instant::now();
+        let result = query.instrument(span.clone()).await;
+        let duration = start.elapsed();
+
+        span.record("duration_ms", duration.as_millis() as u64);
+        let row_count = match &result {
+            Ok(value) => {
+                let count = rows(value);
+                span.record("db.rows", count);
+                Some(count)
+            }
+            Err(e) => {
+                span.record("error", tracing::field::display(e));
+                None
+            }
+        };
+
+        let is_slow = config
+            .slow_query_threshold
+            .is_some_and(|threshold| duration >= threshold);
+        if is_slow {
+            let _entered = span.enter();
+            tracing::warn!(
+                duration_ms = duration.as_millis() as u64,
+                sql = %bind_sql(sql, params, dialect),
+                "Slow query detected"
+            );
+        }
+
+        self.record(&shape, duration, row_count, is_slow, &config);
+        result
+    }
+
+    fn record(
+        &self,
+        shape: &str,
+        duration: Duration,
+        rows: Option<u64>,
+        is_slow: bool,
+        config: &QueryLogConfig,
+    ) {
+        let mut shapes = self.shapes.lock().unwrap_or_else(|e| e.into_inner());
+        if !shapes.contains_key(shape) && shapes.len() >= config.max_tracked_shapes {
+            return;
+        }
+
+        let stats = shapes
+            .entry(shape.to_string())
+            .or_insert_with(|| QueryShapeStats {
+                shape: shape.to_string(),
+                ..Default::default()
+            });
+        stats.count += 1;
+        stats.total_duration += duration;
+        stats.max_duration = stats.max_duration.max(duration);
+        match rows {
+            Some(rows) => stats.total_rows += rows,
+            None => stats.error_count += 1,
+        }
+        if is_slow {
+            stats.slow_count += 1;
+        }
+    }
+
+    /// Get statistics for all tracked query shapes, slowest in total first
+    pub fn stats(&self) -> Vec<QueryShapeStats> {
+        let shapes = self.shapes.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<QueryShapeStats> = shapes.values().cloned().collect();
+        stats.sort_by_key(|s| std::cmp::Reverse(s.total_duration));
+        stats
+    }
+
+    /// Clear all collected statistics
+    pub fn reset(&self) {
+        self.shapes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Normalize SQL into its shape by replacing literals with `?` and collapsing whitespace
+pub fn sql_shape(sql: &str) -> String {
+    let mut shape = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut prev: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                // Skip the string literal, honoring '' escapes
+                while let Some(next) = chars.next() {
+                    if next == '\'' {
+                        if chars.peek() == Some(&'\'') {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                shape.push('?');
+            }
+            '$' if chars.peek().is_some_and(|n| n.is_ascii_digit()) => {
+                while chars.peek().is_some_and(|n| n.is_ascii_digit()) {
+                    chars.next();
+                }
+                shape.push('?');
+            }
+            c if c.is_ascii_digit()
+                && !prev.is_some_and(|p| p.is_alphanumeric() || p == '_' || p == '"') =>
+            {
+                while chars
+                    .peek()
+                    .is_some_and(|n| n.is_ascii_digit() || *n == '.')
+                {
+                    chars.next();
+                }
+                shape.push('?');
+            }
+            c if c.is_whitespace() => {
+                if !shape.ends_with(' ') && !shape.is_empty() {
+                    shape.push(' ');
+                }
+            }
+            c => shape.push(c),
+        }
+        prev = Some(c);
+    }
+
+    shape.trim_end().to_string()
+}
+
+/// Substitute parameters into SQL placeholders for logging purposes
+pub fn bind_sql(sql: &str, params: &[String], dialect: &SqlDialect) -> String {
+    if params.is_empty() {
+        return sql.to_string();
+    }
+
+    // Substitute in a single pass, so placeholders inside bound values are left alone
+    let mut bound = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut next_param = params.iter();
+    while let Some(c) = chars.next() {
+        match (dialect, c) {
+            (SqlDialect::PostgreSQL, '$') => {
+                let mut index = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    index.push(digit);
+                }
+                let param = index
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| params.get(index.checked_sub(1)?));
+                match param {
+                    Some(param) => bound.push_str(&quote_literal(param)),
+                    None => {
+                        bound.push('$');
+                        bound.push_str(&index);
+                    }
+                }
+            }
+            (SqlDialect::MySQL | SqlDialect::SQLite, '?') => match next_param.next() {
+                Some(param) => bound.push_str(&quote_literal(param)),
+                None => bound.push('?'),
+            },
+            _ => bound.push(c),
+        }
+    }
+    bound
+}
+
+/// Render a database value as a parameter string for `bind_sql`
+pub fn database_value_param(value: &DatabaseValue) -> String {
+    match value.to_json() {
+        serde_json::Value::Null => "NULL".to_string(),
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Append tags to SQL as a sqlcommenter comment: `SELECT 1 /*key='value'*/`
+fn sql_comment(sql: &str, tags: &BTreeMap<String, String>) -> String {
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|(key, value)| format!("{}='{}'", url_encode(key), url_encode(value)))
+        .collect();
+    let sql = sql.trim_end();
+    match sql.strip_suffix(';') {
+        Some(statement) => format!("{} /*{}*/;", statement, tags.join(",")),
+        None => format!("{} /*{}*/", sql, tags.join(",")),
+    }
+}
+
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Get the `traceparent`/`tracestate` entries of the current span
+#[cfg(feature = "otel")]
+fn current_trace_context() -> BTreeMap<String, String> {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let context = tracing::Span::current().context();
+    let mut carrier = HashMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut carrier)
+    });
+    carrier.into_iter().collect()
+}
+
+#[cfg(not(feature = "otel"))]
+fn current_trace_context() -> BTreeMap<String, String> {
+    BTreeMap::new()
+}
+
+fn quote_literal(value: &str) -> String {
+    if value == "NULL" || value.parse::<f64>().is_ok() || value == "true" || value == "false" {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_shape_normalizes_literals() {
+        assert_eq!(
+            sql_shape("SELECT * FROM users WHERE id = 42 AND name = 'O''Brien'"),
+            "SELECT * FROM users WHERE id = ? AND name = ?"
+        );
+        assert_eq!(
+            sql_shape("SELECT * FROM \"users\"\n  WHERE \"id\" = $1 AND \"team2\" = $2"),
+            "SELECT * FROM \"users\" WHERE \"id\" = ? AND \"team2\" = ?"
+        );
+        assert_eq!(
+            sql_shape("SELECT * FROM posts LIMIT 10 OFFSET 20"),
+            sql_shape("SELECT * FROM posts LIMIT 5 OFFSET 0")
+        );
+    }
+
+    #[test]
+    fn test_bind_sql() {
+        let params = vec!["1".to_string(), "bob's".to_string()];
+        assert_eq!(
+            bind_sql(
+                "SELECT * FROM users WHERE id = $1 AND name = $2",
+                &params,
+                &SqlDialect::PostgreSQL
+            ),
+            "SELECT * FROM users WHERE id = 1 AND name = 'bob''s'"
+        );
+        assert_eq!(
+            bind_sql(
+                "SELECT * FROM users WHERE id = ? AND name = ?",
+                &params,
+                &SqlDialect::SQLite
+            ),
+            "SELECT * FROM users WHERE id = 1 AND name = 'bob''s'"
+        );
+
+        let many: Vec<String> = (1..=10).map(|i| i.to_string()).collect();
+        assert!(bind_sql("$1, $10", &many, &SqlDialect::PostgreSQL).ends_with("1, 10"));
+
+        // Placeholders inside bound values are not substituted again
+        let params = vec!["$2".to_string(), "x".to_string()];
+        assert_eq!(
+            bind_sql("a = $1 AND b = $2", &params, &SqlDialect::PostgreSQL),
+            "a = '$2' AND b = 'x'"
+        );
+        let params = vec!["?".to_string(), "x".to_string()];
+        assert_eq!(
+            bind_sql("a = ? AND b = ?", &params, &SqlDialect::MySQL),
+            "a = '?' AND b = 'x'"
+        );
+        assert_eq!(
+            bind_sql("a = $3", &params, &SqlDialect::PostgreSQL),
+            "a = $3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_instrument_aggregates_by_shape() {
+        let instrumentation = QueryInstrumentation::with_config(QueryLogConfig {
+            slow_query_threshold: Some(Duration::ZERO),
+            ..Default::default()
+        });
+
+        for id in 1..=3 {
+            let sql = format!("SELECT * FROM users WHERE id = {}", id);
+            let result: Result<Vec<u32>, String> = instrumentation
+                .instrument_query(
+                    &SqlDialect::PostgreSQL,
+                    &sql,
+                    &[],
+                    async { Ok(vec![1, 2]) },
+                    |rows| rows.len() as u64,
+                )
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let result: Result<u64, String> = instrumentation
+            .instrument_query(
+                &SqlDialect::PostgreSQL,
+                "DELETE FROM users",
+                &[],
+                async { Err("boom".to_string()) },
+                |affected| *affected,
+            )
+            .await;
+        assert!(result.is_err());
+
+        let stats = instrumentation.stats();
+        assert_eq!(stats.len(), 2);
+
+        let select = stats
+            .iter()
+            .find(|s| s.shape == "SELECT * FROM users WHERE id = ?")
+            .unwrap();
+        assert_eq!(select.count, 3);
+        assert_eq!(select.total_rows, 6);
+        assert_eq!(select.slow_count, 3);
+
+        let delete = stats
+            .iter()
+            .find(|s| s.shape == "DELETE FROM users")
+            .unwrap();
+        assert_eq!(delete.error_count, 1);
+
+        instrumentation.reset();
+        assert!(instrumentation.stats().is_empty());
+    }
+
+    #[test]
+    fn test_sql_comment() {
+        let tags = BTreeMap::from([
+            ("tracestate".to_string(), "congo=t61rcWkgMzE".to_string()),
+            (
+                "traceparent".to_string(),
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            sql_comment("SELECT * FROM users;", &tags),
+            "SELECT * FROM users /*traceparent='00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01',tracestate='congo%3Dt61rcWkgMzE'*/;"
+        );
+
+        // Without a trace the SQL is left alone
+        let instrumentation = QueryInstrumentation::with_config(QueryLogConfig {
+            trace_comments: true,
+            ..Default::default()
+        });
+        assert!(matches!(
+            instrumentation.annotate_sql("SELECT 1"),
+            Cow::Borrowed("SELECT 1")
+        ));
+    }
+
+    #[cfg(feature = "otel")]
+    #[test]
+    fn test_annotate_sql_with_current_trace() {
+        use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        opentelemetry::global::set_text_map_propagator(
+            opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+        );
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let instrumentation = QueryInstrumentation::with_config(QueryLogConfig {
+            trace_comments: true,
+            ..Default::default()
+        });
+        let span = tracing::info_span!("http_request");
+        let trace_id = span.context().span().span_context().trace_id();
+        let sql = span.in_scope(|| instrumentation.annotate_sql("SELECT 1").into_owned());
+        assert!(sql.starts_with(&format!("SELECT 1 /*traceparent='00-{}-", trace_id)));
+
+        // Disabled by default
+        let sql = span.in_scope(|| {
+            QueryInstrumentation::new()
+                .annotate_sql("SELECT 1")
+                .into_owned()
+        });
+        assert_eq!(sql, "SELECT 1");
+    }
+
+    #[test]
+    fn test_shape_limit() {
+        let instrumentation = QueryInstrumentation::with_config(QueryLogConfig {
+            max_tracked_shapes: 1,
+            ..Default::default()
+        });
+        let config = instrumentation.config();
+        instrumentation.record("SELECT a", Duration::ZERO, Some(1), false, &config);
+        instrumentation.record("SELECT b", Duration::ZERO, Some(1), false, &config);
+        instrumentation.record("SELECT a", Duration::ZERO, Some(1), false, &config);
+
+        let stats = instrumentation.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].count, 2);
+    }
+}
@@ -4,10 +4,12 @@
 //! and connection lifecycle management.
 
 pub mod health;
+pub mod instrumentation;
 pub mod pool;
 pub mod statistics;
 
 // Re-export for convenience
 pub use health::*;
+pub use instrumentation::*;
 pub use pool::*;
 pub use statistics::*;
@@ -4,8 +4,11 @@
 //! health monitoring, and comprehensive error handling.
 
 use super::health::PoolHealthReport;
+use super::instrumentation::{database_value_param, QueryInstrumentation, QueryLogConfig};
 use super::statistics::ExtendedPoolStats;
-use crate::backends::{DatabasePool as DatabasePoolTrait, DatabasePoolConfig, DatabasePoolStats};
+use crate::backends::{
+    DatabasePool as DatabasePoolTrait, DatabasePoolConfig, DatabasePoolStats, SqlDialect,
+};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -69,6 +72,8 @@ pub struct ManagedPool {
     acquire_count: AtomicU64,
     acquire_errors: AtomicU64,
     created_at: Instant,
+    dialect: SqlDialect,
+    instrumentation: QueryInstrumentation,
 }
 
 impl ManagedPool {
@@ -79,9 +84,28 @@ impl ManagedPool {
             acquire_count: AtomicU64::new(0),
             acquire_errors: AtomicU64::new(0),
             created_at: Instant::now(),
+            dialect: SqlDialect::PostgreSQL,
+            instrumentation: QueryInstrumentation::new(),
         }
     }
 
+    /// Set the SQL dialect reported in query spans
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Configure query logging and slow-query detection
+    pub fn with_query_log_config(self, config: QueryLogConfig) -> Self {
+        self.instrumentation.configure(config);
+        self
+    }
+
+    /// Get the query instrumentation for this pool
+    pub fn query_instrumentation(&self) -> &QueryInstrumentation {
+        &self.instrumentation
+    }
+
     /// Get the underlying pool
     pub fn pool(&self) -> &dyn DatabasePoolTrait {
         &*self.pool
@@ -116,8 +140,16 @@ impl ManagedPool {
         sql: &str,
         params: &[crate::backends::DatabaseValue],
     ) -> Result<u64, PoolError> {
-        self.pool
-            .execute(sql, params)
+        let bound_params: Vec<String> = params.iter().map(database_value_param).collect();
+        let query_sql = self.instrumentation.annotate_sql(sql);
+        self.instrumentation
+            .instrument_query(
+                &self.dialect,
+                sql,
+                &bound_params,
+                self.pool.execute(&query_sql, params),
+                |affected| *affected,
+            )
             .await
             .map_err(|e| PoolError::AcquisitionFailed(e.to_string()))
     }
@@ -149,6 +181,7 @@ impl ManagedPool {
             acquire_count: self.acquire_count.load(Ordering::Relaxed),
             acquire_errors: self.acquire_errors.load(Ordering::Relaxed),
             created_at: self.created_at,
+            query_stats: self.instrumentation.stats(),
         }
     }
 
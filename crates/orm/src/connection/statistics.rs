@@ -2,6 +2,7 @@
 //!
 //! This module provides detailed statistics tracking for connection pools.
 
+use super::instrumentation::QueryShapeStats;
 use crate::backends::DatabasePoolStats;
 use std::time::Instant;
 
@@ -12,6 +13,7 @@ pub struct ExtendedPoolStats {
     pub acquire_count: u64,
     pub acquire_errors: u64,
     pub created_at: Instant,
+    pub query_stats: Vec<QueryShapeStats>,
 }
 
 impl ExtendedPoolStats {
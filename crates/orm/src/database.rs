@@ -5,7 +5,10 @@
 
 use crate::backends::{
     DatabaseBackendRegistry, DatabaseBackendType, DatabasePool as DatabasePoolTrait,
-    DatabasePoolConfig, DatabasePoolStats,
+    DatabasePoolConfig, DatabasePoolStats, SqlDialect,
+};
+use crate::connection::instrumentation::{
+    database_value_param, QueryInstrumentation, QueryLogConfig, QueryShapeStats,
 };
 use crate::error::ModelError;
 use elif_core::providers::ProviderError;
@@ -74,6 +77,7 @@ pub struct ExtendedPoolStats {
     pub acquire_count: u64,
     pub acquire_errors: u64,
     pub created_at: Instant,
+    pub query_stats: Vec<QueryShapeStats>,
 }
 
 /// Detailed pool health report
@@ -97,6 +101,8 @@ pub struct ManagedPool {
     acquire_count: AtomicU64,
     acquire_errors: AtomicU64,
     created_at: Instant,
+    dialect: SqlDialect,
+    instrumentation: QueryInstrumentation,
 }
 
 impl ManagedPool {
@@ -107,9 +113,28 @@ impl ManagedPool {
             acquire_count: AtomicU64::new(0),
             acquire_errors: AtomicU64::new(0),
             created_at: Instant::now(),
+            dialect: SqlDialect::PostgreSQL,
+            instrumentation: QueryInstrumentation::new(),
         }
     }
 
+    /// Set the SQL dialect reported in query spans
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Configure query logging and slow-query detection
+    pub fn with_query_log_config(self, config: QueryLogConfig) -> Self {
+        self.instrumentation.configure(config);
+        self
+    }
+
+    /// Get the query instrumentation for this pool
+    pub fn query_instrumentation(&self) -> &QueryInstrumentation {
+        &self.instrumentation
+    }
+
     /// Get the underlying pool
     pub fn pool(&self) -> &dyn DatabasePoolTrait {
         &*self.pool
@@ -144,8 +169,16 @@ impl ManagedPool {
         sql: &str,
         params: &[crate::backends::DatabaseValue],
     ) -> Result<u64, PoolError> {
-        self.pool
-            .execute(sql, params)
+        let bound_params: Vec<String> = params.iter().map(database_value_param).collect();
+        let query_sql = self.instrumentation.annotate_sql(sql);
+        self.instrumentation
+            .instrument_query(
+                &self.dialect,
+                sql,
+                &bound_params,
+                self.pool.execute(&query_sql, params),
+                |affected| *affected,
+            )
             .await
             .map_err(|e| PoolError::AcquisitionFailed(e.to_string()))
     }
@@ -177,6 +210,7 @@ impl ManagedPool {
             acquire_count: self.acquire_count.load(Ordering::Relaxed),
             acquire_errors: self.acquire_errors.load(Ordering::Relaxed),
             created_at: self.created_at,
+            query_stats: self.instrumentation.stats(),
         }
     }
 
@@ -255,6 +289,7 @@ pub struct DatabaseServiceProvider {
     config: DatabasePoolConfig,
     service_name: String,
     backend_registry: Arc<DatabaseBackendRegistry>,
+    query_log_config: QueryLogConfig,
 }
 
 impl DatabaseServiceProvider {
@@ -270,6 +305,7 @@ impl DatabaseServiceProvider {
             config: DatabasePoolConfig::default(),
             service_name: "database_pool".to_string(),
             backend_registry: Arc::new(registry),
+            query_log_config: QueryLogConfig::default(),
         }
     }
 
@@ -318,6 +354,16 @@ impl DatabaseServiceProvider {
         self
     }
 
+    pub fn with_query_log_config(mut self, config: QueryLogConfig) -> Self {
+        self.query_log_config = config;
+        self
+    }
+
+    pub fn with_slow_query_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.query_log_config.slow_query_threshold = threshold;
+        self
+    }
+
     /// Create a database pool using this provider's configuration
     pub async fn create_pool(&self) -> Result<Arc<dyn DatabasePoolTrait>, ModelError> {
         self.backend_registry
@@ -329,7 +375,13 @@ impl DatabaseServiceProvider {
     /// Create a managed database pool with statistics and health monitoring
     pub async fn create_managed_pool(&self) -> Result<ManagedPool, ModelError> {
         let pool = self.create_pool().await?;
-        Ok(ManagedPool::new(pool, self.config.clone()))
+        let dialect = self
+            .backend_registry
+            .dialect_for_url(&self.database_url)
+            .map_err(|e| ModelError::Connection(e.to_string()))?;
+        Ok(ManagedPool::new(pool, self.config.clone())
+            .with_dialect(dialect)
+            .with_query_log_config(self.query_log_config.clone()))
     }
 
     /// Get the database URL (for diagnostic purposes)
@@ -574,6 +626,28 @@ mod tests {
         assert_eq!(provider.service_name(), "custom_db");
     }
 
+    #[test]
+    fn test_dialect_from_url_and_driver() {
+        let mut registry = DatabaseBackendRegistry::new();
+        registry.register(
+            DatabaseBackendType::PostgreSQL,
+            Arc::new(crate::backends::PostgresBackend::new()),
+        );
+
+        assert_eq!(
+            registry
+                .dialect_for_url("postgres://localhost/app")
+                .unwrap(),
+            SqlDialect::PostgreSQL
+        );
+        // No MySQL backend is registered
+        assert!(registry.dialect_for_url("mysql://localhost/app").is_err());
+        assert_eq!(
+            SqlDialect::for_driver::<sqlx::Postgres>(),
+            Some(SqlDialect::PostgreSQL)
+        );
+    }
+
     #[test]
     fn test_provider_name() {
         let provider = DatabaseServiceProvider::new("postgresql://test".to_string());
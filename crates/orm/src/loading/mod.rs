@@ -5,6 +5,7 @@ pub mod eager_loader;
 pub mod optimizer;
 pub mod query_deduplicator;
 pub mod query_optimizer;
+pub mod query_recorder;
 
 pub use batch_loader::{BatchConfig, BatchLoadResult, BatchLoader};
 pub use eager_loader::{EagerLoadConfig, EagerLoadResult, EagerLoadStats, OptimizedEagerLoader};
@@ -17,3 +18,7 @@ pub use query_deduplicator::{DeduplicationStats, QueryDeduplicator, QueryKey};
 pub use query_optimizer::{
     OptimizedQueryExecutor, PlanAnalysis, QueryNode, QueryOptimizer, QueryPlan,
 };
+pub use query_recorder::{
+    record_query, record_relationship_query, NPlusOneDetection, QueryRecorder, QueryRecorderConfig,
+    RecordedQuery,
+};
@@ -0,0 +1,286 @@
+//! Query Recorder - Per-request query tracking and N+1 detection
+//!
+//! A `QueryRecorder` is installed for the duration of a request (or a test) with
+//! [`QueryRecorder::scope`]. Queries issued by lazy relationship access inside the
+//! scope are recorded by their parameterized SQL shape, and a shape that repeats
+//! for the same relationship is reported as a potential N+1 query.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+tokio::task_local! {
+    static CURRENT_RECORDER: Arc<QueryRecorder>;
+}
+
+/// Configuration for query recording and N+1 detection
+#[derive(Debug, Clone)]
+pub struct QueryRecorderConfig {
+    /// Whether queries are recorded at all (defaults to debug builds only)
+    pub enabled: bool,
+    /// Number of identical relationship queries that triggers a detection
+    pub threshold: usize,
+    /// Whether detections are logged as warnings
+    ///
+    /// `QueryRecorderMiddleware` in elif-http logs them once per request instead.
+    pub log_warnings: bool,
+}
+
+impl Default for QueryRecorderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+            threshold: 2,
+            log_warnings: true,
+        }
+    }
+}
+
+/// A query recorded during a request
+#[derive(Debug, Clone)]
+pub struct RecordedQuery {
+    /// Parameterized SQL shape of the query
+    pub sql: String,
+    /// Relationship that issued the query, if it came from lazy loading
+    pub relation: Option<String>,
+    /// When the query was recorded
+    pub recorded_at: Instant,
+}
+
+/// A repeated relationship query detected within one request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NPlusOneDetection {
+    /// Relationship that issued the repeated query (e.g. `users.posts`)
+    pub relation: String,
+    /// Parameterized SQL shape that was repeated
+    pub sql: String,
+    /// Number of times the shape was executed
+    pub count: usize,
+}
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    queries: Vec<RecordedQuery>,
+    relation_counts: HashMap<(String, String), usize>,
+}
+
+/// Records queries for a single request and detects N+1 access patterns
+#[derive(Debug)]
+pub struct QueryRecorder {
+    request_id: Option<String>,
+    config: QueryRecorderConfig,
+    state: Mutex<RecorderState>,
+}
+
+impl QueryRecorder {
+    /// Create a new recorder with default configuration
+    pub fn new() -> Self {
+        Self::with_config(QueryRecorderConfig::default())
+    }
+
+    /// Create a new recorder with custom configuration
+    pub fn with_config(config: QueryRecorderConfig) -> Self {
+        Self {
+            request_id: None,
+            config,
+            state: Mutex::new(RecorderState::default()),
+        }
+    }
+
+    /// Associate the recorder with a request ID
+    pub fn for_request(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Get the request ID this recorder belongs to
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Get the recorder configuration
+    pub fn config(&self) -> &QueryRecorderConfig {
+        &self.config
+    }
+
+    /// Run a future with this recorder installed as the current recorder
+    pub async fn scope<F>(self: Arc<Self>, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        CURRENT_RECORDER.scope(self, future).await
+    }
+
+    /// Get the recorder installed for the current task, if any
+    pub fn current() -> Option<Arc<QueryRecorder>> {
+        CURRENT_RECORDER.try_with(Arc::clone).ok()
+    }
+
+    /// Record a query, optionally attributing it to a relationship
+    pub fn record(&self, sql: &str, relation: Option<&str>) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.queries.push(RecordedQuery {
+            sql: sql.to_string(),
+            relation: relation.map(str::to_string),
+            recorded_at: Instant::now(),
+        });
+
+        let Some(relation) = relation else {
+            return;
+        };
+
+        let count = state
+            .relation_counts
+            .entry((relation.to_string(), sql.to_string()))
+            .or_insert(0);
+        *count += 1;
+
+        if *count == self.config.threshold && self.config.log_warnings {
+            tracing::warn!(
+                request_id = self.request_id.as_deref().unwrap_or("-"),
+                relation = relation,
+                sql = sql,
+                "Possible N+1 query: relationship '{}' is lazily loaded repeatedly, consider eager loading it",
+                relation
+            );
+        }
+    }
+
+    /// Get all recorded queries
+    pub fn queries(&self) -> Vec<RecordedQuery> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .queries
+            .clone()
+    }
+
+    /// Get the number of recorded queries
+    pub fn query_count(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .queries
+            .len()
+    }
+
+    /// Get the relationship queries that reached the detection threshold
+    pub fn detections(&self) -> Vec<NPlusOneDetection> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut detections: Vec<NPlusOneDetection> = state
+            .relation_counts
+            .iter()
+            .filter(|(_, count)| **count >= self.config.threshold)
+            .map(|((relation, sql), count)| NPlusOneDetection {
+                relation: relation.clone(),
+                sql: sql.clone(),
+                count: *count,
+            })
+            .collect();
+        detections.sort_by(|a, b| a.relation.cmp(&b.relation).then(a.sql.cmp(&b.sql)));
+        detections
+    }
+
+    /// Check whether any N+1 pattern was detected
+    pub fn has_n_plus_one(&self) -> bool {
+        !self.detections().is_empty()
+    }
+
+    /// Clear all recorded queries
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.queries.clear();
+        state.relation_counts.clear();
+    }
+}
+
+impl Default for QueryRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Record a query on the current task's recorder, if one is installed
+pub fn record_query(sql: &str) {
+    if let Some(recorder) = QueryRecorder::current() {
+        recorder.record(sql, None);
+    }
+}
+
+/// Record a lazy relationship query on the current task's recorder, if one is installed
+pub fn record_relationship_query(relation: &str, sql: &str) {
+    if let Some(recorder) = QueryRecorder::current() {
+        recorder.record(sql, Some(relation));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_recorder() -> Arc<QueryRecorder> {
+        Arc::new(
+            QueryRecorder::with_config(QueryRecorderConfig {
+                enabled: true,
+                ..Default::default()
+            })
+            .for_request("req-1"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_detects_repeated_relationship_queries() {
+        let recorder = enabled_recorder();
+        let sql = "SELECT * FROM \"posts\" WHERE \"user_id\" = $1";
+
+        recorder
+            .clone()
+            .scope(async {
+                record_query("SELECT * FROM \"users\"");
+                for _ in 0..3 {
+                    record_relationship_query("users.posts", sql);
+                }
+                record_relationship_query("users.profile", "SELECT * FROM \"profiles\"");
+            })
+            .await;
+
+        assert_eq!(recorder.request_id(), Some("req-1"));
+        assert_eq!(recorder.query_count(), 5);
+        assert_eq!(
+            recorder.detections(),
+            vec![NPlusOneDetection {
+                relation: "users.posts".to_string(),
+                sql: sql.to_string(),
+                count: 3,
+            }]
+        );
+
+        recorder.reset();
+        assert!(!recorder.has_n_plus_one());
+    }
+
+    #[tokio::test]
+    async fn test_recording_outside_scope_is_noop() {
+        let recorder = enabled_recorder();
+        record_relationship_query("users.posts", "SELECT 1");
+        assert!(QueryRecorder::current().is_none());
+        assert_eq!(recorder.query_count(), 0);
+    }
+
+    #[test]
+    fn test_disabled_recorder_ignores_queries() {
+        let recorder = QueryRecorder::with_config(QueryRecorderConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        recorder.record("SELECT 1", Some("users.posts"));
+        recorder.record("SELECT 1", Some("users.posts"));
+        assert_eq!(recorder.query_count(), 0);
+        assert!(!recorder.has_n_plus_one());
+    }
+}
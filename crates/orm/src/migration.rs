@@ -18,6 +18,7 @@ mod tests {
         let config = MigrationConfig {
             migrations_dir: temp_dir.path().to_path_buf(),
             migrations_table: "test_migrations".to_string(),
+            schema_path: temp_dir.path().join("schema").join("schema.sql"),
         };
 
         let manager = MigrationManager::with_config(config);
@@ -46,6 +47,7 @@ mod tests {
         let config = MigrationConfig {
             migrations_dir: temp_dir.path().to_path_buf(),
             migrations_table: "test_migrations".to_string(),
+            schema_path: temp_dir.path().join("schema").join("schema.sql"),
         };
 
         let manager = MigrationManager::with_config(config);
@@ -73,6 +75,69 @@ mod tests {
         assert!(migrations[0].down_sql.contains("DROP TABLE test1"));
     }
 
+    #[tokio::test]
+    async fn test_squash_migrations() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = MigrationConfig {
+            migrations_dir: temp_dir.path().to_path_buf(),
+            migrations_table: "test_migrations".to_string(),
+            schema_path: temp_dir.path().join("schema.sql"),
+        };
+
+        let manager = MigrationManager::with_config(config);
+
+        fs::write(
+            temp_dir.path().join("20240101_120000_users.sql"),
+            "-- Up migration\nCREATE TABLE users (id INTEGER);\n-- Down migration\nDROP TABLE users;",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("20240102_120000_posts.sql"),
+            "-- Up migration\nCREATE TABLE posts (id INTEGER)\n-- Down migration\nDROP TABLE posts;",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("20240103_120000_tags.sql"),
+            "-- Up migration\nCREATE TABLE tags (id INTEGER);\n-- Down migration\nDROP TABLE tags;",
+        )
+        .unwrap();
+
+        // The pending migration in the middle stops the squash
+        let applied = vec![
+            "20240101_120000_users".to_string(),
+            "20240103_120000_tags".to_string(),
+        ];
+        let result = manager.squash_migrations(&applied, true).await.unwrap();
+        assert_eq!(result.squashed_migrations, vec!["20240101_120000_users"]);
+        assert_eq!(result.pruned_files.len(), 1);
+        assert!(!temp_dir.path().join("20240101_120000_users.sql").exists());
+
+        // The snapshot lives in the migrations directory but is not a migration
+        let migrations = manager.load_migrations().await.unwrap();
+        assert_eq!(migrations.len(), 2);
+
+        let applied = vec![
+            "20240101_120000_users".to_string(),
+            "20240102_120000_posts".to_string(),
+        ];
+        let result = manager.squash_migrations(&applied, false).await.unwrap();
+        assert_eq!(result.squashed_count, 1);
+        assert!(result.pruned_files.is_empty());
+
+        let snapshot = manager.load_schema_snapshot().await.unwrap().unwrap();
+        assert_eq!(
+            snapshot.migrations,
+            vec!["20240101_120000_users", "20240102_120000_posts"]
+        );
+        assert!(snapshot.sql.starts_with("CREATE TABLE users (id INTEGER);"));
+        assert!(snapshot.sql.ends_with("CREATE TABLE posts (id INTEGER);"));
+        assert!(!snapshot.sql.contains("tags"));
+
+        // Nothing new to squash leaves the snapshot untouched
+        let result = manager.squash_migrations(&applied, false).await.unwrap();
+        assert_eq!(result.squashed_count, 0);
+    }
+
     #[test]
     fn test_schema_builder() {
         let mut builder = SchemaBuilder::new();
@@ -40,6 +40,8 @@ pub struct MigrationConfig {
     pub migrations_dir: PathBuf,
     /// Table name for tracking migrations
     pub migrations_table: String,
+    /// File where squashed migrations are stored as a schema snapshot
+    pub schema_path: PathBuf,
 }
 
 impl Default for MigrationConfig {
@@ -47,6 +49,7 @@ impl Default for MigrationConfig {
         Self {
             migrations_dir: PathBuf::from("migrations"),
             migrations_table: "elif_migrations".to_string(),
+            schema_path: PathBuf::from("migrations/schema/schema.sql"),
         }
     }
 }
@@ -60,6 +63,8 @@ pub struct MigrationRunResult {
     pub applied_migrations: Vec<String>,
     /// Number of migrations that were skipped (already applied)
     pub skipped_count: usize,
+    /// IDs of migrations marked as applied by loading the schema snapshot
+    pub snapshot_migrations: Vec<String>,
     /// Total execution time in milliseconds
     pub execution_time_ms: u128,
 }
@@ -75,6 +80,28 @@ pub struct RollbackResult {
     pub execution_time_ms: u128,
 }
 
+/// A schema snapshot produced by squashing applied migrations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaSnapshot {
+    /// IDs of the migrations contained in the snapshot, in application order
+    pub migrations: Vec<String>,
+    /// SQL statements that recreate the squashed schema
+    pub sql: String,
+}
+
+/// Result of squashing migrations into a schema snapshot
+#[derive(Debug)]
+pub struct SquashResult {
+    /// Number of migrations newly added to the snapshot
+    pub squashed_count: usize,
+    /// IDs of migrations newly added to the snapshot
+    pub squashed_migrations: Vec<String>,
+    /// Migration files removed after squashing
+    pub pruned_files: Vec<PathBuf>,
+    /// Location of the written schema snapshot
+    pub schema_path: PathBuf,
+}
+
 /// Migration direction for execution
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MigrationDirection {
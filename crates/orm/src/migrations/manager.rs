@@ -5,10 +5,11 @@
 use chrono::{DateTime, Utc};
 use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::Parser;
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
-use super::definitions::{Migration, MigrationConfig};
+use super::definitions::{Migration, MigrationConfig, SchemaSnapshot, SquashResult};
 use crate::error::{OrmError, OrmResult};
 
 /// Migration manager for creating and loading migrations
@@ -71,6 +72,10 @@ impl MigrationManager {
             })?;
 
             let path = entry.path();
+            if path == self.config.schema_path {
+                continue;
+            }
+
             if path.extension().is_some_and(|ext| ext == "sql") {
                 let migration = self.parse_migration_file(&path).await?;
                 migrations.push(migration);
@@ -82,6 +87,150 @@ impl MigrationManager {
         Ok(migrations)
     }
 
+    /// Squash applied migrations into the schema snapshot
+    ///
+    /// The leading run of applied migrations that are not yet part of the snapshot
+    /// is appended to it, stopping at the first pending migration so the snapshot
+    /// never skips over schema changes. With `prune`, the squashed migration files
+    /// are removed from the migrations directory.
+    pub async fn squash_migrations(
+        &self,
+        applied_ids: &[String],
+        prune: bool,
+    ) -> OrmResult<SquashResult> {
+        let applied: HashSet<&str> = applied_ids.iter().map(String::as_str).collect();
+        let mut snapshot = self
+            .load_schema_snapshot()
+            .await?
+            .unwrap_or_else(|| SchemaSnapshot {
+                migrations: Vec::new(),
+                sql: String::new(),
+            });
+        let already_squashed: HashSet<String> = snapshot.migrations.iter().cloned().collect();
+
+        let mut statements = Vec::new();
+        if !snapshot.sql.trim().is_empty() {
+            statements.push(snapshot.sql.trim().to_string());
+        }
+
+        let mut squashed_migrations = Vec::new();
+        for migration in self.load_migrations().await? {
+            if already_squashed.contains(&migration.id) {
+                continue;
+            }
+            if !applied.contains(migration.id.as_str()) {
+                break;
+            }
+
+            let up_sql = migration.up_sql.trim();
+            if !up_sql.is_empty() {
+                if up_sql.ends_with(';') {
+                    statements.push(up_sql.to_string());
+                } else {
+                    statements.push(format!("{};", up_sql));
+                }
+            }
+            squashed_migrations.push(migration.id);
+        }
+
+        let mut pruned_files = Vec::new();
+        if !squashed_migrations.is_empty() {
+            snapshot
+                .migrations
+                .extend(squashed_migrations.iter().cloned());
+            snapshot.sql = statements.join("\n\n");
+            self.write_schema_snapshot(&snapshot)?;
+
+            if prune {
+                for migration_id in &squashed_migrations {
+                    let path = self
+                        .config
+                        .migrations_dir
+                        .join(format!("{}.sql", migration_id));
+                    if path.exists() {
+                        fs::remove_file(&path).map_err(|e| {
+                            OrmError::Migration(format!(
+                                "Failed to remove squashed migration file: {}",
+                                e
+                            ))
+                        })?;
+                        pruned_files.push(path);
+                    }
+                }
+            }
+        }
+
+        Ok(SquashResult {
+            squashed_count: squashed_migrations.len(),
+            squashed_migrations,
+            pruned_files,
+            schema_path: self.config.schema_path.clone(),
+        })
+    }
+
+    /// Load the schema snapshot, if one has been written
+    pub async fn load_schema_snapshot(&self) -> OrmResult<Option<SchemaSnapshot>> {
+        if !self.config.schema_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&self.config.schema_path)
+            .map_err(|e| OrmError::Migration(format!("Failed to read schema snapshot: {}", e)))?;
+
+        Ok(Some(self.parse_schema_snapshot(&content)))
+    }
+
+    /// Parse schema snapshot content into its squashed IDs and SQL
+    fn parse_schema_snapshot(&self, content: &str) -> SchemaSnapshot {
+        let mut migrations = Vec::new();
+        let mut sql = Vec::new();
+        let mut in_header = true;
+
+        for line in content.lines() {
+            if in_header {
+                let trimmed = line.trim();
+                if let Some(id) = trimmed.strip_prefix("-- Squashed:") {
+                    migrations.push(id.trim().to_string());
+                    continue;
+                }
+                if trimmed.is_empty() || trimmed.starts_with("--") {
+                    continue;
+                }
+                in_header = false;
+            }
+            sql.push(line);
+        }
+
+        SchemaSnapshot {
+            migrations,
+            sql: sql.join("\n").trim().to_string(),
+        }
+    }
+
+    /// Write the schema snapshot file
+    fn write_schema_snapshot(&self, snapshot: &SchemaSnapshot) -> OrmResult<()> {
+        if let Some(parent) = self.config.schema_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                OrmError::Migration(format!("Failed to create schema directory: {}", e))
+            })?;
+        }
+
+        let mut content = format!(
+            "-- Schema snapshot\n\
+             -- Generated: {}\n",
+            Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        for migration_id in &snapshot.migrations {
+            content.push_str(&format!("-- Squashed: {}\n", migration_id));
+        }
+        content.push('\n');
+        content.push_str(&snapshot.sql);
+        content.push('\n');
+
+        fs::write(&self.config.schema_path, content)
+            .map_err(|e| OrmError::Migration(format!("Failed to write schema snapshot: {}", e)))
+    }
+
     /// Parse a migration file into a Migration struct
     async fn parse_migration_file(&self, path: &Path) -> OrmResult<Migration> {
         let content = fs::read_to_string(path)
@@ -15,7 +15,7 @@ pub mod schema_builder;
 // Re-export commonly used types
 pub use definitions::{
     Migration, MigrationConfig, MigrationDirection, MigrationRecord, MigrationRunResult,
-    MigrationStatus, RollbackResult,
+    MigrationStatus, RollbackResult, SchemaSnapshot, SquashResult,
 };
 pub use manager::MigrationManager;
 pub use rollback::MigrationRollback;
@@ -6,7 +6,9 @@
 use sqlx::{PgPool, Row};
 use std::collections::HashSet;
 
-use super::definitions::{Migration, MigrationRecord, MigrationRunResult};
+use super::definitions::{
+    Migration, MigrationRecord, MigrationRunResult, SchemaSnapshot, SquashResult,
+};
 use super::manager::MigrationManager;
 use crate::error::{OrmError, OrmResult};
 
@@ -53,7 +55,19 @@ impl MigrationRunner {
 
         // Get applied migrations from database
         let applied_migrations = self.get_applied_migrations().await?;
-        let applied_ids: HashSet<String> = applied_migrations.into_iter().map(|m| m.id).collect();
+        let mut applied_ids: HashSet<String> =
+            applied_migrations.into_iter().map(|m| m.id).collect();
+        let skipped_count = applied_ids.len();
+
+        // Fresh databases start from the schema snapshot, if one exists
+        let mut snapshot_migrations = Vec::new();
+        if applied_ids.is_empty() {
+            if let Some(snapshot) = self.manager.load_schema_snapshot().await? {
+                self.apply_schema_snapshot(&snapshot).await?;
+                applied_ids.extend(snapshot.migrations.iter().cloned());
+                snapshot_migrations = snapshot.migrations;
+            }
+        }
 
         // Filter pending migrations
         let pending_migrations: Vec<_> = all_migrations
@@ -65,7 +79,8 @@ impl MigrationRunner {
             return Ok(MigrationRunResult {
                 applied_count: 0,
                 applied_migrations: Vec::new(),
-                skipped_count: applied_ids.len(),
+                skipped_count,
+                snapshot_migrations,
                 execution_time_ms: start_time.elapsed().as_millis(),
             });
         }
@@ -125,7 +140,8 @@ impl MigrationRunner {
         Ok(MigrationRunResult {
             applied_count: applied_migration_ids.len(),
             applied_migrations: applied_migration_ids,
-            skipped_count: applied_ids.len(),
+            skipped_count,
+            snapshot_migrations,
             execution_time_ms: start_time.elapsed().as_millis(),
         })
     }
@@ -201,6 +217,69 @@ impl MigrationRunner {
         Ok(())
     }
 
+    /// Load the schema snapshot and mark its squashed migrations as applied
+    ///
+    /// Squashed migrations are recorded in batch 0 so that batch rollbacks stop
+    /// at the snapshot instead of looking for pruned migration files.
+    async fn apply_schema_snapshot(&self, snapshot: &SchemaSnapshot) -> OrmResult<()> {
+        println!(
+            "Loading schema snapshot ({} squashed migrations)",
+            snapshot.migrations.len()
+        );
+
+        let mut transaction = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OrmError::Migration(format!("Failed to start transaction: {}", e)))?;
+
+        if !snapshot.sql.trim().is_empty() {
+            for statement in self.manager.split_sql_statements(&snapshot.sql)? {
+                if !statement.trim().is_empty() {
+                    sqlx::query(&statement)
+                        .execute(&mut *transaction)
+                        .await
+                        .map_err(|e| {
+                            OrmError::Migration(format!("Failed to load schema snapshot: {}", e))
+                        })?;
+                }
+            }
+        }
+
+        for migration_id in &snapshot.migrations {
+            let (record_sql, params) = self.record_migration_sql(migration_id, 0);
+            let mut query = sqlx::query(&record_sql);
+            for param in params {
+                query = query.bind(param);
+            }
+            query
+                .execute(&mut *transaction)
+                .await
+                .map_err(|e| OrmError::Migration(format!("Failed to record migration: {}", e)))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| OrmError::Migration(format!("Failed to commit schema snapshot: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Squash all applied migrations into the schema snapshot
+    pub async fn squash_migrations(&self, prune: bool) -> OrmResult<SquashResult> {
+        self.ensure_migrations_table().await?;
+
+        let applied_ids: Vec<String> = self
+            .get_applied_migrations()
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+
+        self.manager.squash_migrations(&applied_ids, prune).await
+    }
+
     /// Ensure migrations table exists
     async fn ensure_migrations_table(&self) -> OrmResult<()> {
         let sql = self.create_migrations_table_sql();
@@ -3,6 +3,10 @@
 //! Provides a fluent interface for building SQL schema modification statements
 //! commonly used in migrations.
 
+use crate::backends::SqlDialect;
+use crate::error::OrmResult;
+use crate::query::FullTextSearch;
+
 /// Basic schema operations for migrations
 pub struct SchemaBuilder {
     statements: Vec<String>,
@@ -84,6 +88,53 @@ impl SchemaBuilder {
         self
     }
 
+    /// Create a full-text index over columns, as expected by `QueryBuilder::where_full_text`
+    ///
+    /// On SQLite this creates an FTS5 table named `<table>_fts` kept in sync by triggers.
+    pub fn create_full_text_index(
+        &mut self,
+        table_name: &str,
+        column_names: &[&str],
+        index_name: Option<&str>,
+        dialect: &SqlDialect,
+    ) -> &mut Self {
+        self.statements
+            .extend(FullTextSearch::new(table_name, column_names).index_sql(index_name, dialect));
+        self
+    }
+
+    /// Create a PostgreSQL full-text index using a specific text search configuration
+    ///
+    /// Fails without adding any statement when the configuration name is invalid.
+    pub fn create_full_text_index_with_language(
+        &mut self,
+        table_name: &str,
+        column_names: &[&str],
+        language: &str,
+        index_name: Option<&str>,
+    ) -> OrmResult<&mut Self> {
+        self.statements.extend(
+            FullTextSearch::new(table_name, column_names)
+                .language(language)?
+                .index_sql(index_name, &SqlDialect::PostgreSQL),
+        );
+        Ok(self)
+    }
+
+    /// Drop a full-text index created with `create_full_text_index`
+    pub fn drop_full_text_index(
+        &mut self,
+        table_name: &str,
+        column_names: &[&str],
+        index_name: Option<&str>,
+        dialect: &SqlDialect,
+    ) -> &mut Self {
+        self.statements.extend(
+            FullTextSearch::new(table_name, column_names).drop_index_sql(index_name, dialect),
+        );
+        self
+    }
+
     /// Get all SQL statements
     pub fn to_sql(&self) -> Vec<String> {
         self.statements.clone()
@@ -250,4 +301,33 @@ mod tests {
         assert!(sql.contains("user_id INTEGER"));
         assert!(sql.contains("FOREIGN KEY (user_id) REFERENCES users (id)"));
     }
+
+    #[test]
+    fn test_full_text_index() {
+        let mut builder = SchemaBuilder::new();
+        builder.create_full_text_index("posts", &["title", "body"], None, &SqlDialect::PostgreSQL);
+        builder.create_full_text_index("posts", &["title", "body"], None, &SqlDialect::MySQL);
+        let sql = builder.to_sql();
+        assert_eq!(
+            sql[0],
+            "CREATE INDEX idx_posts_title_body_fulltext ON posts USING GIN \
+             (to_tsvector('english', coalesce(\"title\", '') || ' ' || coalesce(\"body\", '')));"
+        );
+        assert_eq!(
+            sql[1],
+            "CREATE FULLTEXT INDEX idx_posts_title_body_fulltext ON posts (title, body);"
+        );
+
+        let mut builder = SchemaBuilder::new();
+        builder.create_full_text_index("posts", &["title", "body"], None, &SqlDialect::SQLite);
+        let sql = builder.build();
+        assert!(sql.contains(
+            "CREATE VIRTUAL TABLE posts_fts USING fts5(title, body, content='posts', content_rowid='rowid');"
+        ));
+        assert!(sql.contains("CREATE TRIGGER posts_fts_au AFTER UPDATE ON posts"));
+
+        let mut builder = SchemaBuilder::new();
+        builder.drop_full_text_index("posts", &["title", "body"], None, &SqlDialect::SQLite);
+        assert!(builder.build().contains("DROP TABLE IF EXISTS posts_fts;"));
+    }
 }
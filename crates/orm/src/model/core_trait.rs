@@ -24,6 +24,13 @@ pub trait Model: Send + Sync + Debug + Serialize + for<'de> Deserialize<'de> {
         "id"
     }
 
+    /// Column used to look this model up from route parameters
+    ///
+    /// Defaults to the primary key; override to bind routes by e.g. a `slug` column.
+    fn route_key_name() -> &'static str {
+        Self::primary_key_name()
+    }
+
     /// Get the primary key value for this model instance
     fn primary_key(&self) -> Option<Self::PrimaryKey>;
 
@@ -40,6 +47,11 @@ pub trait Model: Send + Sync + Debug + Serialize + for<'de> Deserialize<'de> {
         false
     }
 
+    /// Column holding the soft-delete timestamp
+    fn deleted_at_column() -> &'static str {
+        "deleted_at"
+    }
+
     /// Get created_at timestamp if available
     fn created_at(&self) -> Option<DateTime<Utc>> {
         None
@@ -22,7 +22,7 @@ pub trait QueryMethods: Model {
 
         // Exclude soft-deleted records by default
         if Self::uses_soft_deletes() {
-            builder.where_null("deleted_at")
+            builder.where_null(Self::deleted_at_column())
         } else {
             builder
         }
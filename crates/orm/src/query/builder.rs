@@ -3,6 +3,8 @@
 use std::marker::PhantomData;
 
 use super::types::*;
+use crate::backends::SqlDialect;
+use crate::error::ModelError;
 
 /// Query builder for constructing database queries
 #[derive(Debug)]
@@ -16,12 +18,15 @@ pub struct QueryBuilder<M = ()> {
     pub(crate) set_clauses: Vec<SetClause>,
     pub(crate) where_conditions: Vec<WhereCondition>,
     pub(crate) joins: Vec<JoinClause>,
-    pub(crate) order_by: Vec<(String, OrderDirection)>,
+    pub(crate) order_by: Vec<(OrderTarget, OrderDirection)>,
     pub(crate) group_by: Vec<String>,
     pub(crate) having_conditions: Vec<WhereCondition>,
     pub(crate) limit_count: Option<i64>,
     pub(crate) offset_value: Option<i64>,
     pub(crate) distinct: bool,
+    pub(crate) dialect: SqlDialect,
+    pub(crate) full_text_language: Option<String>,
+    pub(crate) build_errors: Vec<String>,
     _phantom: PhantomData<M>,
 }
 
@@ -43,6 +48,9 @@ impl<M> Clone for QueryBuilder<M> {
             limit_count: self.limit_count,
             offset_value: self.offset_value,
             distinct: self.distinct,
+            dialect: self.dialect.clone(),
+            full_text_language: self.full_text_language.clone(),
+            build_errors: self.build_errors.clone(),
             _phantom: PhantomData,
         }
     }
@@ -73,7 +81,32 @@ impl<M> QueryBuilder<M> {
             limit_count: None,
             offset_value: None,
             distinct: false,
+            dialect: SqlDialect::PostgreSQL,
+            full_text_language: None,
+            build_errors: Vec::new(),
             _phantom: PhantomData,
         }
     }
+
+    /// Set the SQL dialect used for parameter placeholders and JSON operators
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Get the SQL dialect this query is generated for
+    pub fn dialect(&self) -> &SqlDialect {
+        &self.dialect
+    }
+
+    /// Check that the query was built from valid input
+    ///
+    /// Builder methods can't fail, so invalid input such as an unknown full-text language
+    /// is recorded and reported here, by `to_sql_with_params_secure` and when executing.
+    pub fn validate(&self) -> Result<(), ModelError> {
+        match self.build_errors.first() {
+            Some(error) => Err(ModelError::Query(error.clone())),
+            None => Ok(()),
+        }
+    }
 }
@@ -3,15 +3,36 @@
 use sqlx::Row;
 
 use super::builder::QueryBuilder;
+use crate::backends::SqlDialect;
+use crate::connection::instrumentation::QueryInstrumentation;
 use crate::error::ModelResult;
 use crate::model::Model;
 
 // Implement specialized methods for Model-typed query builders
 impl<M: Model> QueryBuilder<M> {
+    /// Check the query and generate SQL for the database driver of the pool it runs on
+    fn for_pool<DB: sqlx::Database>(self, _pool: &sqlx::Pool<DB>) -> ModelResult<Self> {
+        self.validate()?;
+        Ok(match SqlDialect::for_driver::<DB>() {
+            Some(dialect) => self.with_dialect(dialect),
+            None => self,
+        })
+    }
+
     /// Execute query and return models
     pub async fn get(self, pool: &sqlx::Pool<sqlx::Postgres>) -> ModelResult<Vec<M>> {
-        let sql = self.to_sql();
-        let rows = sqlx::query(&sql).fetch_all(pool).await?;
+        let query = self.for_pool(pool)?;
+        let sql = query.to_sql();
+        let query_sql = QueryInstrumentation::global().annotate_sql(&sql);
+        let rows = QueryInstrumentation::global()
+            .instrument_query(
+                &query.dialect,
+                &sql,
+                &[],
+                sqlx::query(&query_sql).fetch_all(pool),
+                |rows| rows.len() as u64,
+            )
+            .await?;
 
         let mut models = Vec::new();
         for row in rows {
@@ -53,8 +74,18 @@ impl<M: Model> QueryBuilder<M> {
         self,
         pool: &sqlx::Pool<sqlx::Postgres>,
     ) -> ModelResult<Vec<serde_json::Value>> {
-        let sql = self.to_sql();
-        let rows = sqlx::query(&sql).fetch_all(pool).await?;
+        let query = self.for_pool(pool)?;
+        let sql = query.to_sql();
+        let query_sql = QueryInstrumentation::global().annotate_sql(&sql);
+        let rows = QueryInstrumentation::global()
+            .instrument_query(
+                &query.dialect,
+                &sql,
+                &[],
+                sqlx::query(&query_sql).fetch_all(pool),
+                |rows| rows.len() as u64,
+            )
+            .await?;
 
         let mut results = Vec::new();
         for row in rows {
@@ -95,9 +126,19 @@ impl<M: Model> QueryBuilder<M> {
     /// Count query results
     pub async fn count(mut self, pool: &sqlx::Pool<sqlx::Postgres>) -> ModelResult<i64> {
         self.select_fields = vec!["COUNT(*)".to_string()];
-        let sql = self.to_sql();
-
-        let row = sqlx::query(&sql).fetch_one(pool).await?;
+        let query = self.for_pool(pool)?;
+        let sql = query.to_sql();
+        let query_sql = QueryInstrumentation::global().annotate_sql(&sql);
+
+        let row = QueryInstrumentation::global()
+            .instrument_query(
+                &query.dialect,
+                &sql,
+                &[],
+                sqlx::query(&query_sql).fetch_one(pool),
+                |_| 1,
+            )
+            .await?;
 
         let count: i64 = row.try_get(0)?;
         Ok(count)
@@ -108,9 +149,19 @@ impl<M: Model> QueryBuilder<M> {
         self,
         pool: &sqlx::Pool<sqlx::Postgres>,
     ) -> ModelResult<Option<serde_json::Value>> {
-        let sql = self.to_sql();
-
-        let row_opt = sqlx::query(&sql).fetch_optional(pool).await?;
+        let query = self.for_pool(pool)?;
+        let sql = query.to_sql();
+        let query_sql = QueryInstrumentation::global().annotate_sql(&sql);
+
+        let row_opt = QueryInstrumentation::global()
+            .instrument_query(
+                &query.dialect,
+                &sql,
+                &[],
+                sqlx::query(&query_sql).fetch_optional(pool),
+                |row| row.is_some() as u64,
+            )
+            .await?;
 
         if let Some(row) = row_opt {
             // For aggregations, typically return the first column
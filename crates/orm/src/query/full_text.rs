@@ -0,0 +1,286 @@
+//! Query Builder full-text search operations
+//!
+//! Full-text conditions are rendered per dialect: `to_tsvector` matched against
+//! `websearch_to_tsquery` on PostgreSQL, `MATCH ... AGAINST` on MySQL and an FTS5
+//! virtual table named `<table>_fts` on SQLite. The search query is always bound as a
+//! parameter. Use [`SchemaBuilder::create_full_text_index`](crate::migrations::SchemaBuilder)
+//! to create the index each dialect expects.
+
+use serde_json::Value;
+
+use super::builder::QueryBuilder;
+use super::types::*;
+use crate::backends::SqlDialect;
+use crate::error::ModelError;
+use crate::sql::escape_identifier;
+
+/// Text search configuration used on PostgreSQL when none is set
+pub const DEFAULT_FULL_TEXT_LANGUAGE: &str = "english";
+
+/// A set of columns searched together, shared by queries and index definitions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullTextSearch {
+    pub table: String,
+    pub columns: Vec<String>,
+    pub language: String,
+}
+
+impl FullTextSearch {
+    /// Create a full-text search over columns of a table
+    pub fn new(table: &str, columns: &[&str]) -> Self {
+        Self {
+            table: table.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            language: DEFAULT_FULL_TEXT_LANGUAGE.to_string(),
+        }
+    }
+
+    /// Set the PostgreSQL text search configuration (e.g. `english`, `simple`)
+    ///
+    /// The name is inlined into the SQL, so names that aren't [valid](Self::is_valid_language)
+    /// are rejected.
+    pub fn language(mut self, language: &str) -> Result<Self, ModelError> {
+        if !Self::is_valid_language(language) {
+            return Err(ModelError::Query(invalid_language(language)));
+        }
+        self.language = language.to_string();
+        Ok(self)
+    }
+
+    /// Check that a text search configuration name only contains letters, digits and `_`
+    pub fn is_valid_language(language: &str) -> bool {
+        !language.is_empty()
+            && language
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// Name of the FTS5 virtual table backing a table on SQLite
+    pub fn fts_table(&self) -> String {
+        format!("{}_fts", self.table)
+    }
+
+    /// PostgreSQL `tsvector` expression covering all searched columns
+    pub fn document_sql(&self) -> String {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| {
+                format!(
+                    "coalesce({}, '')",
+                    escape_identifier(column, &SqlDialect::PostgreSQL)
+                )
+            })
+            .collect();
+        format!(
+            "to_tsvector('{}', {})",
+            self.language,
+            columns.join(" || ' ' || ")
+        )
+    }
+
+    /// Condition matching rows against the search query in `rhs`
+    pub fn match_sql(&self, dialect: &SqlDialect, rhs: &str) -> String {
+        match dialect {
+            SqlDialect::PostgreSQL => format!(
+                "{} @@ websearch_to_tsquery('{}', {})",
+                self.document_sql(),
+                self.language,
+                rhs
+            ),
+            SqlDialect::MySQL => self.mysql_match(rhs),
+            SqlDialect::SQLite => {
+                let fts_table = escape_identifier(&self.fts_table(), dialect);
+                format!(
+                    "{}.rowid IN (SELECT rowid FROM {} WHERE {} MATCH {})",
+                    escape_identifier(&self.table, dialect),
+                    fts_table,
+                    fts_table,
+                    rhs
+                )
+            }
+        }
+    }
+
+    /// Relevance score of a row for the search query in `rhs`, with the direction that
+    /// puts the best matches first
+    pub fn relevance_sql(&self, dialect: &SqlDialect, rhs: &str) -> (String, OrderDirection) {
+        match dialect {
+            SqlDialect::PostgreSQL => (
+                format!(
+                    "ts_rank({}, websearch_to_tsquery('{}', {}))",
+                    self.document_sql(),
+                    self.language,
+                    rhs
+                ),
+                OrderDirection::Desc,
+            ),
+            SqlDialect::MySQL => (self.mysql_match(rhs), OrderDirection::Desc),
+            SqlDialect::SQLite => {
+                // bm25() scores are negative, lower is more relevant
+                let fts_table = escape_identifier(&self.fts_table(), dialect);
+                (
+                    format!(
+                        "(SELECT bm25({}) FROM {} WHERE {} MATCH {} AND {}.rowid = {}.rowid)",
+                        fts_table,
+                        fts_table,
+                        fts_table,
+                        rhs,
+                        fts_table,
+                        escape_identifier(&self.table, dialect)
+                    ),
+                    OrderDirection::Asc,
+                )
+            }
+        }
+    }
+
+    /// Statements creating the index (or FTS5 table and sync triggers) for this search
+    pub fn index_sql(&self, index_name: Option<&str>, dialect: &SqlDialect) -> Vec<String> {
+        let default_name = format!("idx_{}_{}_fulltext", self.table, self.columns.join("_"));
+        let index_name = index_name.unwrap_or(&default_name);
+        let columns = self.columns.join(", ");
+
+        match dialect {
+            SqlDialect::PostgreSQL => vec![format!(
+                "CREATE INDEX {} ON {} USING GIN ({});",
+                index_name,
+                self.table,
+                self.document_sql()
+            )],
+            SqlDialect::MySQL => vec![format!(
+                "CREATE FULLTEXT INDEX {} ON {} ({});",
+                index_name, self.table, columns
+            )],
+            SqlDialect::SQLite => {
+                let fts = self.fts_table();
+                let new_values: Vec<String> =
+                    self.columns.iter().map(|c| format!("new.{}", c)).collect();
+                let old_values: Vec<String> =
+                    self.columns.iter().map(|c| format!("old.{}", c)).collect();
+                let insert = format!(
+                    "INSERT INTO {fts} (rowid, {columns}) VALUES (new.rowid, {});",
+                    new_values.join(", ")
+                );
+                let delete = format!(
+                    "INSERT INTO {fts} ({fts}, rowid, {columns}) VALUES ('delete', old.rowid, {});",
+                    old_values.join(", ")
+                );
+                let table = &self.table;
+
+                vec![
+                    format!(
+                        "CREATE VIRTUAL TABLE {fts} USING fts5({columns}, content='{table}', content_rowid='rowid');"
+                    ),
+                    format!("INSERT INTO {fts} ({fts}) VALUES ('rebuild');"),
+                    format!(
+                        "CREATE TRIGGER {fts}_ai AFTER INSERT ON {table} BEGIN {insert} END;"
+                    ),
+                    format!(
+                        "CREATE TRIGGER {fts}_ad AFTER DELETE ON {table} BEGIN {delete} END;"
+                    ),
+                    format!(
+                        "CREATE TRIGGER {fts}_au AFTER UPDATE ON {table} BEGIN {delete} {insert} END;"
+                    ),
+                ]
+            }
+        }
+    }
+
+    /// Statements dropping what [`FullTextSearch::index_sql`] created
+    pub fn drop_index_sql(&self, index_name: Option<&str>, dialect: &SqlDialect) -> Vec<String> {
+        let default_name = format!("idx_{}_{}_fulltext", self.table, self.columns.join("_"));
+        let index_name = index_name.unwrap_or(&default_name);
+
+        match dialect {
+            SqlDialect::PostgreSQL => vec![format!("DROP INDEX IF EXISTS {};", index_name)],
+            SqlDialect::MySQL => vec![format!("DROP INDEX {} ON {};", index_name, self.table)],
+            SqlDialect::SQLite => {
+                let fts = self.fts_table();
+                vec![
+                    format!("DROP TRIGGER IF EXISTS {fts}_ai;"),
+                    format!("DROP TRIGGER IF EXISTS {fts}_ad;"),
+                    format!("DROP TRIGGER IF EXISTS {fts}_au;"),
+                    format!("DROP TABLE IF EXISTS {fts};"),
+                ]
+            }
+        }
+    }
+
+    fn mysql_match(&self, rhs: &str) -> String {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| escape_identifier(column, &SqlDialect::MySQL))
+            .collect();
+        format!(
+            "MATCH ({}) AGAINST ({} IN NATURAL LANGUAGE MODE)",
+            columns.join(", "),
+            rhs
+        )
+    }
+}
+
+impl<M> QueryBuilder<M> {
+    /// Add full-text search condition over one or more columns
+    pub fn where_full_text(mut self, columns: &[&str], query: &str) -> Self {
+        self.where_conditions.push(WhereCondition {
+            column: String::new(),
+            operator: QueryOperator::FullText,
+            value: Some(Value::String(query.to_string())),
+            values: columns
+                .iter()
+                .map(|c| Value::String(c.to_string()))
+                .collect(),
+            json: None,
+        });
+        self
+    }
+
+    /// Set the PostgreSQL text search configuration used by full-text conditions
+    ///
+    /// Invalid names are reported by [`validate`](Self::validate).
+    pub fn full_text_language(mut self, language: &str) -> Self {
+        if FullTextSearch::is_valid_language(language) {
+            self.full_text_language = Some(language.to_string());
+        } else {
+            self.build_errors.push(invalid_language(language));
+        }
+        self
+    }
+
+    /// Order results by relevance to the first full-text condition, best matches first
+    pub fn order_by_relevance(mut self) -> Self {
+        self.order_by
+            .push((OrderTarget::Relevance, OrderDirection::Desc));
+        self
+    }
+
+    /// Get the full-text condition used for relevance ordering, with its search query
+    pub(crate) fn full_text_search(&self) -> Option<(FullTextSearch, &Value)> {
+        let condition = self
+            .where_conditions
+            .iter()
+            .find(|condition| condition.operator == QueryOperator::FullText)?;
+        Some((self.full_text_for(condition), condition.value.as_ref()?))
+    }
+
+    /// Build the full-text search described by a full-text condition
+    pub(crate) fn full_text_for(&self, condition: &WhereCondition) -> FullTextSearch {
+        let columns: Vec<&str> = condition.values.iter().filter_map(Value::as_str).collect();
+        let table = self.from_tables.first().map(String::as_str).unwrap_or("");
+        let search = FullTextSearch::new(table, &columns);
+        match &self.full_text_language {
+            // Validated by `full_text_language`
+            Some(language) => FullTextSearch {
+                language: language.clone(),
+                ..search
+            },
+            None => search,
+        }
+    }
+}
+
+fn invalid_language(language: &str) -> String {
+    format!("Invalid full-text language '{}'", language)
+}
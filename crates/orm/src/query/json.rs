@@ -0,0 +1,274 @@
+//! Query Builder JSON column operations
+//!
+//! JSON paths are written as `column->key->key` (e.g. `settings->notifications->email`),
+//! with numeric segments indexing into arrays. They are rendered per dialect: `->`/`->>`
+//! and `@>` on PostgreSQL, `JSON_EXTRACT`/`JSON_CONTAINS` on MySQL and `json_extract`/
+//! `json_each` on SQLite. Compared values are always bound as parameters; ordering
+//! comparisons against numbers compare numerically.
+
+use serde_json::Value;
+
+use super::builder::QueryBuilder;
+use super::types::*;
+use crate::backends::SqlDialect;
+use crate::sql::escape_identifier;
+
+/// A JSON column with an optional path into its document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    pub column: String,
+    pub segments: Vec<String>,
+}
+
+impl JsonPath {
+    /// Parse a `column->key->key` path expression
+    pub fn parse(path: &str) -> Self {
+        let mut parts = path.split("->").map(|part| part.trim().to_string());
+        let column = parts.next().unwrap_or_default();
+        Self {
+            column,
+            segments: parts.filter(|part| !part.is_empty()).collect(),
+        }
+    }
+
+    /// Check whether an expression uses the `column->path` syntax
+    pub fn is_json_path(expression: &str) -> bool {
+        expression.contains("->")
+    }
+
+    /// SQL expression for the value at the path, extracted as text
+    pub fn text_sql(&self, dialect: &SqlDialect) -> String {
+        let column = escape_identifier(&self.column, dialect);
+        match dialect {
+            SqlDialect::PostgreSQL => match self.segments.split_last() {
+                Some((last, init)) => {
+                    let mut sql = column;
+                    for segment in init {
+                        sql.push_str("->");
+                        sql.push_str(&Self::postgres_key(segment));
+                    }
+                    sql.push_str("->>");
+                    sql.push_str(&Self::postgres_key(last));
+                    sql
+                }
+                None => format!("{}::text", column),
+            },
+            SqlDialect::MySQL => format!(
+                "JSON_UNQUOTE(JSON_EXTRACT({}, {}))",
+                column,
+                self.path_literal()
+            ),
+            SqlDialect::SQLite => format!("json_extract({}, {})", column, self.path_literal()),
+        }
+    }
+
+    /// SQL expression for the value at the path as a JSON document
+    pub fn json_sql(&self, dialect: &SqlDialect) -> String {
+        let column = escape_identifier(&self.column, dialect);
+        match dialect {
+            SqlDialect::PostgreSQL => {
+                let mut sql = column;
+                for segment in &self.segments {
+                    sql.push_str("->");
+                    sql.push_str(&Self::postgres_key(segment));
+                }
+                sql
+            }
+            SqlDialect::MySQL if self.segments.is_empty() => column,
+            SqlDialect::MySQL => format!("JSON_EXTRACT({}, {})", column, self.path_literal()),
+            SqlDialect::SQLite => format!("json_extract({}, {})", column, self.path_literal()),
+        }
+    }
+
+    /// SQL path literal in MySQL/SQLite syntax, e.g. `'$.tags[0]'`
+    pub fn path_literal(&self) -> String {
+        let mut path = String::from("$");
+        for segment in &self.segments {
+            if segment.chars().all(|c| c.is_ascii_digit()) {
+                path.push_str(&format!("[{}]", segment));
+            } else if segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                path.push('.');
+                path.push_str(segment);
+            } else {
+                path.push_str(&format!(".\"{}\"", segment.replace('"', "\\\"")));
+            }
+        }
+        quote_literal(&path)
+    }
+
+    fn postgres_key(segment: &str) -> String {
+        if segment.chars().all(|c| c.is_ascii_digit()) {
+            segment.to_string()
+        } else {
+            quote_literal(segment)
+        }
+    }
+
+    fn path_argument(&self) -> String {
+        if self.segments.is_empty() {
+            String::new()
+        } else {
+            format!(", {}", self.path_literal())
+        }
+    }
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Cast an SQL expression to a number, so ordering compares values rather than text
+fn numeric_sql(expression: &str, dialect: &SqlDialect) -> String {
+    match dialect {
+        SqlDialect::PostgreSQL => format!("({})::numeric", expression),
+        SqlDialect::MySQL => format!("CAST({} AS DECIMAL(65,30))", expression),
+        SqlDialect::SQLite => format!("CAST({} AS NUMERIC)", expression),
+    }
+}
+
+/// Render a JSON WHERE condition, with `rhs` as the placeholder or literal to compare against
+pub(crate) fn json_condition_sql(
+    condition: &WhereCondition,
+    operation: JsonOperation,
+    dialect: &SqlDialect,
+    rhs: &str,
+) -> String {
+    let path = JsonPath::parse(&condition.column);
+    match operation {
+        JsonOperation::Extract => match condition.operator {
+            QueryOperator::IsNull | QueryOperator::IsNotNull => {
+                format!("{} {}", path.text_sql(dialect), condition.operator)
+            }
+            operator @ (QueryOperator::GreaterThan
+            | QueryOperator::GreaterThanOrEqual
+            | QueryOperator::LessThan
+            | QueryOperator::LessThanOrEqual)
+                if matches!(condition.value, Some(Value::Number(_))) =>
+            {
+                format!(
+                    "{} {} {}",
+                    numeric_sql(&path.text_sql(dialect), dialect),
+                    operator,
+                    numeric_sql(rhs, dialect)
+                )
+            }
+            operator => format!("{} {} {}", path.text_sql(dialect), operator, rhs),
+        },
+        JsonOperation::Contains => {
+            let column = escape_identifier(&path.column, dialect);
+            match dialect {
+                SqlDialect::PostgreSQL => format!("{} @> {}::jsonb", path.json_sql(dialect), rhs),
+                SqlDialect::MySQL => {
+                    format!("JSON_CONTAINS({}, {}{})", column, rhs, path.path_argument())
+                }
+                SqlDialect::SQLite => format!(
+                    "NOT EXISTS (SELECT 1 FROM json_each({}) AS needle WHERE needle.value NOT IN (SELECT value FROM json_each({}{})))",
+                    rhs,
+                    column,
+                    path.path_argument()
+                ),
+            }
+        }
+        JsonOperation::Length => {
+            let column = escape_identifier(&path.column, dialect);
+            let length = match dialect {
+                SqlDialect::PostgreSQL => format!("jsonb_array_length({})", path.json_sql(dialect)),
+                SqlDialect::MySQL => format!("JSON_LENGTH({}{})", column, path.path_argument()),
+                SqlDialect::SQLite => {
+                    format!("json_array_length({}{})", column, path.path_argument())
+                }
+            };
+            format!("{} {} {}", length, condition.operator, rhs)
+        }
+    }
+}
+
+/// Render a `column->path [AS alias]` select field, if the field is a JSON path
+pub(crate) fn json_select_sql(field: &str, dialect: &SqlDialect) -> Option<String> {
+    if !JsonPath::is_json_path(field) {
+        return None;
+    }
+
+    let (path, alias) = match field.find(" AS ").or_else(|| field.find(" as ")) {
+        Some(index) => (&field[..index], Some(field[index + 4..].trim())),
+        None => (field, None),
+    };
+
+    let mut sql = JsonPath::parse(path).text_sql(dialect);
+    if let Some(alias) = alias {
+        sql.push_str(" AS ");
+        sql.push_str(&escape_identifier(alias, dialect));
+    }
+    Some(sql)
+}
+
+impl<M> QueryBuilder<M> {
+    /// Add WHERE condition comparing the value at a JSON path (`column->key`) for equality
+    pub fn where_json<T: Into<Value>>(self, path: &str, value: T) -> Self {
+        self.where_json_condition(path, "=", value)
+    }
+
+    /// Add WHERE condition comparing the value at a JSON path with a custom operator
+    pub fn where_json_condition<T: Into<Value>>(
+        mut self,
+        path: &str,
+        operator: &str,
+        value: T,
+    ) -> Self {
+        let value = value.into();
+        let operator = QueryOperator::from_symbol(operator).unwrap_or(QueryOperator::Equal);
+        let operator = match (&value, operator) {
+            (Value::Null, QueryOperator::NotEqual) => QueryOperator::IsNotNull,
+            (Value::Null, _) => QueryOperator::IsNull,
+            (_, operator) => operator,
+        };
+
+        self.where_conditions.push(WhereCondition {
+            column: path.to_string(),
+            operator,
+            value: Some(value),
+            values: Vec::new(),
+            json: Some(JsonOperation::Extract),
+        });
+        self
+    }
+
+    /// Add WHERE condition checking that a JSON column or path contains a JSON document
+    pub fn where_json_contains<T: Into<Value>>(mut self, path: &str, value: T) -> Self {
+        self.where_conditions.push(WhereCondition {
+            column: path.to_string(),
+            operator: QueryOperator::Equal,
+            value: Some(value.into()),
+            values: Vec::new(),
+            json: Some(JsonOperation::Contains),
+        });
+        self
+    }
+
+    /// Add WHERE condition on the length of the JSON array at a column or path
+    pub fn where_json_length(mut self, path: &str, operator: &str, length: i64) -> Self {
+        let operator = QueryOperator::from_symbol(operator).unwrap_or(QueryOperator::Equal);
+        self.where_conditions.push(WhereCondition {
+            column: path.to_string(),
+            operator,
+            value: Some(Value::from(length)),
+            values: Vec::new(),
+            json: Some(JsonOperation::Length),
+        });
+        self
+    }
+
+    /// Select the value at a JSON path as text
+    pub fn select_json(mut self, path: &str, alias: Option<&str>) -> Self {
+        let select_expr = if let Some(alias) = alias {
+            format!("{} AS {}", path, alias)
+        } else {
+            path.to_string()
+        };
+        self.select_fields.push(select_expr);
+        self
+    }
+}
@@ -3,7 +3,9 @@
 pub mod builder;
 pub mod dml;
 pub mod execution;
+pub mod full_text;
 pub mod joins;
+pub mod json;
 pub mod ordering;
 pub mod pagination;
 pub mod performance;
@@ -16,8 +18,10 @@ pub mod with;
 
 // Re-export main types and builder (minimal exports to avoid conflicts)
 pub use builder::QueryBuilder;
+pub use full_text::{FullTextSearch, DEFAULT_FULL_TEXT_LANGUAGE};
+pub use json::JsonPath;
 pub use performance_optimized::{acquire_query_builder, release_query_builder, QueryBuilderPool};
-pub use types::{OrderDirection, QueryOperator};
+pub use types::{JsonOperation, OrderDirection, OrderTarget, QueryOperator};
 pub use upsert::UpsertBuilder;
 pub use with::{QueryBuilderWithEagerLoading, QueryBuilderWithMethods};
 
@@ -8,14 +8,16 @@ impl<M> QueryBuilder<M> {
     /// Add ORDER BY clause (ascending)
     pub fn order_by(mut self, column: &str) -> Self {
         self.order_by
-            .push((column.to_string(), OrderDirection::Asc));
+            .push((OrderTarget::Column(column.to_string()), OrderDirection::Asc));
         self
     }
 
     /// Add ORDER BY clause (descending)
     pub fn order_by_desc(mut self, column: &str) -> Self {
-        self.order_by
-            .push((column.to_string(), OrderDirection::Desc));
+        self.order_by.push((
+            OrderTarget::Column(column.to_string()),
+            OrderDirection::Desc,
+        ));
         self
     }
 
@@ -32,6 +34,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Equal,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -48,6 +51,7 @@ impl<M> QueryBuilder<M> {
             operator,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -59,6 +63,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Raw,
             value: Some(Value::String(raw_condition.to_string())),
             values: Vec::new(),
+            json: None,
         });
         self
     }
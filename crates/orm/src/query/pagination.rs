@@ -45,7 +45,8 @@ impl<M> QueryBuilder<M> {
             }
         }
 
-        self.order_by.push((cursor_column.to_string(), direction));
+        self.order_by
+            .push((OrderTarget::Column(cursor_column.to_string()), direction));
 
         self
     }
@@ -4,6 +4,7 @@
 //! to reduce allocations and improve performance for hot paths.
 
 use super::builder::QueryBuilder;
+use super::json::{json_condition_sql, json_select_sql};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -57,6 +58,14 @@ impl<M> QueryBuilder<M> {
         placeholders
     }
 
+    /// Sequential placeholders in the query's dialect, starting from a 1-based index
+    fn dialect_placeholders(&self, start_index: usize, count: usize) -> String {
+        (start_index..start_index + count)
+            .map(|i| self.dialect.parameter_placeholder(i - 1))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     /// Optimized SQL generation with pre-allocated capacity
     pub fn to_sql_optimized(&self) -> String {
         // Pre-calculate approximate SQL length to reduce allocations
@@ -121,7 +130,10 @@ impl<M> QueryBuilder<M> {
                 if i > 0 {
                     sql.push_str(", ");
                 }
-                sql.push_str(field);
+                match json_select_sql(field, &self.dialect) {
+                    Some(json_field) => sql.push_str(&json_field),
+                    None => sql.push_str(field),
+                }
             }
         }
 
@@ -169,7 +181,18 @@ impl<M> QueryBuilder<M> {
                 }
 
                 // Handle special cases
-                if condition.column == "RAW" {
+                if let Some(operation) = condition.json {
+                    let placeholder = self.dialect.parameter_placeholder(param_counter - 1);
+                    sql.push_str(&json_condition_sql(
+                        condition,
+                        operation,
+                        &self.dialect,
+                        &placeholder,
+                    ));
+                    if self.json_condition_param(condition, operation).is_some() {
+                        param_counter += 1;
+                    }
+                } else if condition.column == "RAW" {
                     if let Some(ref value) = condition.value {
                         if let serde_json::Value::String(raw_sql) = value {
                             sql.push_str(raw_sql);
@@ -183,6 +206,11 @@ impl<M> QueryBuilder<M> {
                             sql.push_str(subquery);
                         }
                     }
+                } else if condition.operator == super::types::QueryOperator::FullText {
+                    let placeholder = self.dialect.parameter_placeholder(param_counter - 1);
+                    let search = self.full_text_for(condition);
+                    sql.push_str(&search.match_sql(&self.dialect, &placeholder));
+                    param_counter += 1;
                 } else {
                     // Regular conditions
                     sql.push_str(&condition.column);
@@ -200,10 +228,8 @@ impl<M> QueryBuilder<M> {
                             sql.push_str(" IN (");
                             let placeholder_count = condition.values.len();
                             if placeholder_count > 0 {
-                                let placeholders = Self::generate_sequential_placeholders(
-                                    param_counter,
-                                    placeholder_count,
-                                );
+                                let placeholders =
+                                    self.dialect_placeholders(param_counter, placeholder_count);
                                 sql.push_str(&placeholders);
                                 param_counter += placeholder_count;
                             }
@@ -214,10 +240,8 @@ impl<M> QueryBuilder<M> {
                             sql.push_str(" NOT IN (");
                             let placeholder_count = condition.values.len();
                             if placeholder_count > 0 {
-                                let placeholders = Self::generate_sequential_placeholders(
-                                    param_counter,
-                                    placeholder_count,
-                                );
+                                let placeholders =
+                                    self.dialect_placeholders(param_counter, placeholder_count);
                                 sql.push_str(&placeholders);
                                 param_counter += placeholder_count;
                             }
@@ -234,9 +258,9 @@ impl<M> QueryBuilder<M> {
                         }
                         super::types::QueryOperator::Between => {
                             sql.push_str(&format!(
-                                " BETWEEN ${} AND ${}",
-                                param_counter,
-                                param_counter + 1
+                                " BETWEEN {} AND {}",
+                                self.dialect.parameter_placeholder(param_counter - 1),
+                                self.dialect.parameter_placeholder(param_counter)
                             ));
                             param_counter += 2;
                             continue;
@@ -251,10 +275,11 @@ impl<M> QueryBuilder<M> {
                             }
                             continue;
                         }
+                        super::types::QueryOperator::FullText => continue, // Handled above
                     }
 
                     // Add parameter placeholder for regular operators
-                    sql.push_str(&format!("${}", param_counter));
+                    sql.push_str(&self.dialect.parameter_placeholder(param_counter - 1));
                     param_counter += 1;
                 }
             }
@@ -288,15 +313,24 @@ impl<M> QueryBuilder<M> {
                     _ => sql.push_str(" = "), // Default to equals
                 }
 
-                sql.push_str(&format!("${}", param_counter));
+                sql.push_str(&self.dialect.parameter_placeholder(param_counter - 1));
                 param_counter += 1;
             }
         }
 
         // ORDER BY
-        if !self.order_by.is_empty() {
+        // Relevance ordering needs the dialect-aware builder
+        let order_columns: Vec<_> = self
+            .order_by
+            .iter()
+            .filter_map(|(target, direction)| match target {
+                super::types::OrderTarget::Column(column) => Some((column, direction)),
+                super::types::OrderTarget::Relevance => None,
+            })
+            .collect();
+        if !order_columns.is_empty() {
             sql.push_str(" ORDER BY ");
-            for (i, (column, direction)) in self.order_by.iter().enumerate() {
+            for (i, (column, direction)) in order_columns.into_iter().enumerate() {
                 if i > 0 {
                     sql.push_str(", ");
                 }
@@ -400,6 +434,7 @@ pub fn release_query_builder(builder: QueryBuilder<()>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backends::SqlDialect;
 
     #[test]
     fn test_placeholder_caching() {
@@ -437,4 +472,75 @@ mod tests {
         assert!(sql.contains("FROM users"));
         assert!(sql.contains("WHERE"));
     }
+
+    #[test]
+    fn test_optimized_sql_json_conditions() {
+        let query: QueryBuilder<()> = QueryBuilder::new()
+            .select("id")
+            .select_json("settings->theme", Some("theme"))
+            .from("users")
+            .where_json("settings->notifications->email", true)
+            .where_json_contains("roles", serde_json::json!(["admin"]))
+            .where_json_length("tags", ">", 2)
+            .where_json("settings->deleted", serde_json::Value::Null)
+            .where_eq("active", true);
+
+        assert_eq!(
+            query.to_sql_optimized(),
+            "SELECT id, \"settings\"->>'theme' AS \"theme\" FROM users \
+             WHERE \"settings\"->'notifications'->>'email' = $1 \
+             AND \"roles\" @> $2::jsonb AND jsonb_array_length(\"tags\") > $3 \
+             AND \"settings\"->>'deleted' IS NULL AND active = $4"
+        );
+
+        let mysql = query
+            .clone()
+            .with_dialect(SqlDialect::MySQL)
+            .to_sql_optimized();
+        assert!(mysql.contains("JSON_UNQUOTE(JSON_EXTRACT(`settings`, '$.theme')) AS `theme`"));
+        assert!(
+            mysql.contains("JSON_UNQUOTE(JSON_EXTRACT(`settings`, '$.notifications.email')) = ?")
+        );
+        assert!(mysql.contains("JSON_CONTAINS(`roles`, ?)"));
+        assert!(mysql.contains("JSON_LENGTH(`tags`) > ?"));
+        assert!(mysql.contains("AND active = ?"));
+
+        let sqlite = query.with_dialect(SqlDialect::SQLite).to_sql_optimized();
+        assert!(sqlite.contains("json_extract(\"settings\", '$.theme') AS \"theme\""));
+        assert!(sqlite.contains("json_extract(\"settings\", '$.notifications.email') = ?"));
+        assert!(sqlite.contains("json_each(?) AS needle"));
+        assert!(sqlite.contains("json_array_length(\"tags\") > ?"));
+        assert!(sqlite.contains("json_extract(\"settings\", '$.deleted') IS NULL"));
+    }
+
+    #[test]
+    fn test_optimized_sql_full_text_uses_query_dialect() {
+        let query: QueryBuilder<()> = QueryBuilder::new()
+            .from("posts")
+            .where_full_text(&["title", "body"], "rust orm")
+            .where_eq("published", true);
+
+        assert_eq!(
+            query.to_sql_optimized(),
+            "SELECT * FROM posts WHERE to_tsvector('english', coalesce(\"title\", '') || ' ' || \
+             coalesce(\"body\", '')) @@ websearch_to_tsquery('english', $1) AND published = $2"
+        );
+
+        let mysql = query
+            .clone()
+            .with_dialect(SqlDialect::MySQL)
+            .to_sql_optimized();
+        assert_eq!(
+            mysql,
+            "SELECT * FROM posts WHERE MATCH (`title`, `body`) \
+             AGAINST (? IN NATURAL LANGUAGE MODE) AND published = ?"
+        );
+
+        let sqlite = query.with_dialect(SqlDialect::SQLite).to_sql_optimized();
+        assert_eq!(
+            sqlite,
+            "SELECT * FROM posts WHERE \"posts\".rowid IN \
+             (SELECT rowid FROM \"posts_fts\" WHERE \"posts_fts\" MATCH ?) AND published = ?"
+        );
+    }
 }
@@ -19,7 +19,25 @@ pub enum QueryOperator {
     IsNull,
     IsNotNull,
     Between,
-    Raw, // For raw SQL expressions
+    Raw,      // For raw SQL expressions
+    FullText, // Full-text search over the columns in `values`
+}
+
+impl QueryOperator {
+    /// Parse a comparison operator symbol, returning `None` for unsupported symbols
+    pub fn from_symbol(operator: &str) -> Option<Self> {
+        match operator {
+            "=" => Some(QueryOperator::Equal),
+            "!=" | "<>" => Some(QueryOperator::NotEqual),
+            ">" => Some(QueryOperator::GreaterThan),
+            ">=" => Some(QueryOperator::GreaterThanOrEqual),
+            "<" => Some(QueryOperator::LessThan),
+            "<=" => Some(QueryOperator::LessThanOrEqual),
+            "LIKE" => Some(QueryOperator::Like),
+            "NOT LIKE" => Some(QueryOperator::NotLike),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for QueryOperator {
@@ -39,6 +57,7 @@ impl fmt::Display for QueryOperator {
             QueryOperator::IsNotNull => write!(f, "IS NOT NULL"),
             QueryOperator::Between => write!(f, "BETWEEN"),
             QueryOperator::Raw => write!(f, "RAW"), // This won't be used in SQL generation
+            QueryOperator::FullText => write!(f, "MATCH"), // Rendered by FullTextSearch
         }
     }
 }
@@ -49,7 +68,19 @@ pub struct WhereCondition {
     pub column: String,
     pub operator: QueryOperator,
     pub value: Option<Value>,
-    pub values: Vec<Value>, // For IN, NOT IN, BETWEEN
+    pub values: Vec<Value>,          // For IN, NOT IN, BETWEEN
+    pub json: Option<JsonOperation>, // Set when `column` is a `column->path` JSON path
+}
+
+/// Operation applied to a JSON path in a WHERE condition
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsonOperation {
+    /// Compare the value at the path, extracted as text
+    Extract,
+    /// Check that the value at the path contains a JSON document
+    Contains,
+    /// Compare the length of the array at the path
+    Length,
 }
 
 /// Join types
@@ -80,6 +111,14 @@ pub struct JoinClause {
     pub on_conditions: Vec<(String, String)>, // (left_column, right_column)
 }
 
+/// What an ORDER BY entry sorts by
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderTarget {
+    Column(String),
+    /// Relevance to the first full-text condition
+    Relevance,
+}
+
 /// Order by direction
 #[derive(Debug, Clone, PartialEq)]
 pub enum OrderDirection {
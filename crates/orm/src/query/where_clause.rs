@@ -15,6 +15,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Equal,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -26,6 +27,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::NotEqual,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -37,6 +39,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::GreaterThan,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -48,6 +51,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::GreaterThanOrEqual,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -59,6 +63,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::LessThan,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -70,6 +75,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::LessThanOrEqual,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -81,6 +87,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Like,
             value: Some(Value::String(pattern.to_string())),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -92,6 +99,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::NotLike,
             value: Some(Value::String(pattern.to_string())),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -103,23 +111,15 @@ impl<M> QueryBuilder<M> {
         operator: &str,
         value: T,
     ) -> Self {
-        let query_operator = match operator {
-            "=" => QueryOperator::Equal,
-            "!=" | "<>" => QueryOperator::NotEqual,
-            ">" => QueryOperator::GreaterThan,
-            ">=" => QueryOperator::GreaterThanOrEqual,
-            "<" => QueryOperator::LessThan,
-            "<=" => QueryOperator::LessThanOrEqual,
-            "LIKE" => QueryOperator::Like,
-            "NOT LIKE" => QueryOperator::NotLike,
-            _ => QueryOperator::Equal, // Default fallback
-        };
+        // Unsupported operators fall back to `=`
+        let query_operator = QueryOperator::from_symbol(operator).unwrap_or(QueryOperator::Equal);
 
         self.where_conditions.push(WhereCondition {
             column: column.to_string(),
             operator: query_operator,
             value: Some(value.into()),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -131,6 +131,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::In,
             value: None,
             values: values.into_iter().map(|v| v.into()).collect(),
+            json: None,
         });
         self
     }
@@ -142,6 +143,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::NotIn,
             value: None,
             values: values.into_iter().map(|v| v.into()).collect(),
+            json: None,
         });
         self
     }
@@ -153,6 +155,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::IsNull,
             value: None,
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -164,6 +167,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::IsNotNull,
             value: None,
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -175,6 +179,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Between,
             value: None,
             values: vec![start.into(), end.into()],
+            json: None,
         });
         self
     }
@@ -186,6 +191,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Equal,
             value: Some(Value::String(raw_condition.to_string())),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -205,6 +211,7 @@ impl<M> QueryBuilder<M> {
             operator,
             value: Some(Value::String(formatted_value)),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -216,6 +223,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Equal,
             value: Some(Value::String(format!("({})", subquery.to_sql()))),
             values: Vec::new(),
+            json: None,
         });
         self
     }
@@ -227,6 +235,7 @@ impl<M> QueryBuilder<M> {
             operator: QueryOperator::Equal,
             value: Some(Value::String(format!("({})", subquery.to_sql()))),
             values: Vec::new(),
+            json: None,
         });
         self
     }
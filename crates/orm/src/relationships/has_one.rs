@@ -5,6 +5,7 @@ use sqlx::Pool;
 use sqlx::Postgres;
 
 use crate::error::ModelResult;
+use crate::loading::record_relationship_query;
 use crate::model::Model;
 use crate::query::QueryBuilder;
 
@@ -98,7 +99,10 @@ where
     }
 
     async fn load(&mut self, pool: &Pool<Postgres>) -> ModelResult<()> {
-        let results = self.query().get(pool).await?;
+        let query = self.query();
+        record_relationship_query(&self.relation_name(), &query.to_sql_with_params().0);
+
+        let results = query.get(pool).await?;
         self.related = results.into_iter().next();
         self.loaded = true;
         Ok(())
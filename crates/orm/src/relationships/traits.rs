@@ -38,6 +38,11 @@ where
     /// Build a query for this relationship
     fn query(&self) -> QueryBuilder<Related>;
 
+    /// Name used to identify this relationship in diagnostics (e.g. `users.posts`)
+    fn relation_name(&self) -> String {
+        format!("{}.{}", Parent::table_name(), self.meta().related_table)
+    }
+
     /// Load the relationship from the database
     async fn load(&mut self, pool: &Pool<Postgres>) -> ModelResult<()>;
 }
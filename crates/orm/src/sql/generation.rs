@@ -4,6 +4,7 @@
 
 use crate::error::ModelError;
 use crate::query::builder::QueryBuilder;
+use crate::query::json::{json_condition_sql, json_select_sql, JsonPath};
 use crate::query::types::*;
 use crate::security::{escape_identifier, validate_identifier, validate_parameter};
 use serde_json::Value;
@@ -31,6 +32,8 @@ impl<M> QueryBuilder<M> {
 
     /// Validate query security before SQL generation
     fn validate_query_security(&self) -> Result<(), ModelError> {
+        self.validate()?;
+
         // Validate table identifiers
         for table in &self.from_tables {
             validate_identifier(table)?;
@@ -51,14 +54,22 @@ impl<M> QueryBuilder<M> {
         // Validate select field identifiers
         for field in &self.select_fields {
             // Skip wildcard and function calls for now
-            if field != "*" && !field.contains('(') {
+            if JsonPath::is_json_path(field) {
+                validate_identifier(&JsonPath::parse(field).column)?;
+            } else if field != "*" && !field.contains('(') {
                 validate_identifier(field)?;
             }
         }
 
         // Validate column identifiers in WHERE clauses
         for condition in &self.where_conditions {
-            if condition.column != "RAW"
+            if condition.json.is_some() {
+                validate_identifier(&JsonPath::parse(&condition.column).column)?;
+            } else if condition.operator == QueryOperator::FullText {
+                for column in condition.values.iter().filter_map(Value::as_str) {
+                    validate_identifier(column)?;
+                }
+            } else if condition.column != "RAW"
                 && condition.column != "EXISTS"
                 && condition.column != "NOT EXISTS"
             {
@@ -108,7 +119,9 @@ impl<M> QueryBuilder<M> {
                 .select_fields
                 .iter()
                 .map(|field| {
-                    if field == "*" || field.contains('(') {
+                    if let Some(json_field) = json_select_sql(field, &self.dialect) {
+                        json_field
+                    } else if field == "*" || field.contains('(') {
                         // Keep wildcards and function calls as-is
                         field.clone()
                     } else {
@@ -150,7 +163,7 @@ impl<M> QueryBuilder<M> {
         }
 
         self.build_where_clause(&mut sql, &mut params, &mut param_counter);
-        self.build_order_limit_clause(&mut sql);
+        self.build_order_limit_clause(&mut sql, &mut params, &mut param_counter);
 
         (sql, params)
     }
@@ -180,7 +193,7 @@ impl<M> QueryBuilder<M> {
                         sql.push_str(", ");
                     }
                     if let Some(ref value) = clause.value {
-                        sql.push_str(&format!("${}", param_counter));
+                        sql.push_str(&self.placeholder(param_counter));
                         params.push(self.json_value_to_param_string(value));
                         param_counter += 1;
                     } else {
@@ -213,7 +226,7 @@ impl<M> QueryBuilder<M> {
                     sql.push_str(&escape_identifier(&clause.column));
                     sql.push_str(" = ");
                     if let Some(ref value) = clause.value {
-                        sql.push_str(&format!("${}", param_counter));
+                        sql.push_str(&self.placeholder(param_counter));
                         params.push(self.json_value_to_param_string(value));
                         param_counter += 1;
                     } else {
@@ -257,6 +270,32 @@ impl<M> QueryBuilder<M> {
                     sql.push_str(" AND ");
                 }
 
+                if let Some(operation) = condition.json {
+                    let placeholder = self.placeholder(*param_counter);
+                    sql.push_str(&json_condition_sql(
+                        condition,
+                        operation,
+                        &self.dialect,
+                        &placeholder,
+                    ));
+                    if let Some(param) = self.json_condition_param(condition, operation) {
+                        params.push(param);
+                        *param_counter += 1;
+                    }
+                    continue;
+                }
+
+                if condition.operator == QueryOperator::FullText {
+                    let placeholder = self.placeholder(*param_counter);
+                    let search = self.full_text_for(condition);
+                    sql.push_str(&search.match_sql(&self.dialect, &placeholder));
+                    if let Some(ref value) = condition.value {
+                        params.push(self.json_value_to_param_string(value));
+                        *param_counter += 1;
+                    }
+                    continue;
+                }
+
                 if condition.column == "RAW"
                     || condition.column == "EXISTS"
                     || condition.column == "NOT EXISTS"
@@ -276,7 +315,7 @@ impl<M> QueryBuilder<M> {
                             if j > 0 {
                                 sql.push_str(", ");
                             }
-                            sql.push_str(&format!("${}", param_counter));
+                            sql.push_str(&self.placeholder(*param_counter));
                             params.push(self.json_value_to_param_string(value));
                             *param_counter += 1;
                         }
@@ -284,7 +323,11 @@ impl<M> QueryBuilder<M> {
                     }
                     QueryOperator::Between => {
                         sql.push_str(&condition.operator.to_string());
-                        sql.push_str(&format!(" ${} AND ${}", param_counter, *param_counter + 1));
+                        sql.push_str(&format!(
+                            " {} AND {}",
+                            self.placeholder(*param_counter),
+                            self.placeholder(*param_counter + 1)
+                        ));
                         if condition.values.len() >= 2 {
                             params.push(self.json_value_to_param_string(&condition.values[0]));
                             params.push(self.json_value_to_param_string(&condition.values[1]));
@@ -297,7 +340,7 @@ impl<M> QueryBuilder<M> {
                     _ => {
                         sql.push_str(&condition.operator.to_string());
                         if let Some(ref value) = condition.value {
-                            sql.push_str(&format!(" ${}", param_counter));
+                            sql.push_str(&format!(" {}", self.placeholder(*param_counter)));
                             params.push(self.json_value_to_param_string(value));
                             *param_counter += 1;
                         }
@@ -308,17 +351,36 @@ impl<M> QueryBuilder<M> {
     }
 
     /// Helper method to build ORDER BY and LIMIT clauses
-    fn build_order_limit_clause(&self, sql: &mut String) {
+    fn build_order_limit_clause(
+        &self,
+        sql: &mut String,
+        params: &mut Vec<String>,
+        param_counter: &mut i32,
+    ) {
         // ORDER BY clause
-        if !self.order_by.is_empty() {
-            sql.push_str(" ORDER BY ");
-            for (i, (column, direction)) in self.order_by.iter().enumerate() {
-                if i > 0 {
-                    sql.push_str(", ");
+        let mut order_clauses = Vec::new();
+        for (target, direction) in &self.order_by {
+            match target {
+                OrderTarget::Relevance => {
+                    // Relevance is only meaningful with a full-text condition
+                    if let Some((search, query)) = self.full_text_search() {
+                        let placeholder = self.placeholder(*param_counter);
+                        let (relevance, direction) =
+                            search.relevance_sql(&self.dialect, &placeholder);
+                        order_clauses.push(format!("{} {}", relevance, direction));
+                        params.push(self.json_value_to_param_string(query));
+                        *param_counter += 1;
+                    }
+                }
+                OrderTarget::Column(column) => {
+                    order_clauses.push(format!("{} {}", escape_identifier(column), direction));
                 }
-                sql.push_str(&format!("{} {}", escape_identifier(column), direction));
             }
         }
+        if !order_clauses.is_empty() {
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&order_clauses.join(", "));
+        }
 
         // LIMIT clause
         if let Some(limit) = self.limit_count {
@@ -358,7 +420,12 @@ impl<M> QueryBuilder<M> {
         if self.select_fields.is_empty() {
             sql.push('*');
         } else {
-            sql.push_str(&self.select_fields.join(", "));
+            let fields: Vec<String> = self
+                .select_fields
+                .iter()
+                .map(|field| json_select_sql(field, &self.dialect).unwrap_or_else(|| field.clone()))
+                .collect();
+            sql.push_str(&fields.join(", "));
         }
 
         // FROM clause (no escaping for backward compatibility)
@@ -406,7 +473,15 @@ impl<M> QueryBuilder<M> {
             let order_clauses: Vec<String> = self
                 .order_by
                 .iter()
-                .map(|(column, direction)| format!("{} {}", column, direction))
+                .filter_map(|(target, direction)| match target {
+                    OrderTarget::Relevance => {
+                        let (search, query) = self.full_text_search()?;
+                        let (relevance, direction) =
+                            search.relevance_sql(&self.dialect, &self.format_value(query));
+                        Some(format!("{} {}", relevance, direction))
+                    }
+                    OrderTarget::Column(column) => Some(format!("{} {}", column, direction)),
+                })
                 .collect();
             sql.push_str(&order_clauses.join(", "));
         }
@@ -429,6 +504,30 @@ impl<M> QueryBuilder<M> {
         conditions
             .iter()
             .map(|condition| {
+                // Handle JSON path conditions
+                if let Some(operation) = condition.json {
+                    let rhs = self
+                        .json_condition_param(condition, operation)
+                        .map(|param| match operation {
+                            JsonOperation::Extract | JsonOperation::Length => condition
+                                .value
+                                .as_ref()
+                                .map(|value| self.format_value(value))
+                                .unwrap_or(param),
+                            JsonOperation::Contains => self.format_value(&Value::String(param)),
+                        })
+                        .unwrap_or_default();
+                    return json_condition_sql(condition, operation, &self.dialect, &rhs);
+                }
+
+                // Handle full-text search conditions
+                if condition.operator == QueryOperator::FullText {
+                    let query = condition.value.as_ref().unwrap_or(&Value::Null);
+                    return self
+                        .full_text_for(condition)
+                        .match_sql(&self.dialect, &self.format_value(query));
+                }
+
                 // Handle special raw conditions
                 if condition.column == "RAW" {
                     if let Some(Value::String(raw_sql)) = &condition.value {
@@ -535,6 +634,28 @@ impl<M> QueryBuilder<M> {
         }
     }
 
+    /// Get the parameter placeholder for a 1-based parameter counter
+    fn placeholder(&self, param_counter: i32) -> String {
+        self.dialect
+            .parameter_placeholder((param_counter - 1) as usize)
+    }
+
+    /// Get the bound parameter for a JSON condition, if it takes one
+    pub(crate) fn json_condition_param(
+        &self,
+        condition: &WhereCondition,
+        operation: JsonOperation,
+    ) -> Option<String> {
+        match (operation, condition.operator) {
+            (JsonOperation::Extract, QueryOperator::IsNull | QueryOperator::IsNotNull) => None,
+            (JsonOperation::Contains, _) => condition.value.as_ref().map(|value| value.to_string()),
+            _ => condition
+                .value
+                .as_ref()
+                .map(|value| self.json_value_to_param_string(value)),
+        }
+    }
+
     /// Convert JSON value to parameter string for SQL parameters
     fn json_value_to_param_string(&self, value: &Value) -> String {
         match value {
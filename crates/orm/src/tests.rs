@@ -345,6 +345,219 @@ mod query_builder_tests {
         let cloned = original.clone_for_subquery();
         assert_eq!(original.to_sql(), cloned.to_sql());
     }
+
+    #[test]
+    fn test_json_conditions_postgres() {
+        let query = QueryBuilder::<TestUser>::new()
+            .select("id")
+            .select_json("settings->theme", Some("theme"))
+            .from("users")
+            .where_json("settings->notifications->email", true)
+            .where_json_contains("roles", serde_json::json!(["admin"]))
+            .where_json_length("tags", ">", 2);
+
+        let (sql, params) = query.to_sql_with_params();
+        assert_eq!(
+            sql,
+            "SELECT \"id\", \"settings\"->>'theme' AS \"theme\" FROM \"users\" \
+             WHERE \"settings\"->'notifications'->>'email' = $1 \
+             AND \"roles\" @> $2::jsonb AND jsonb_array_length(\"tags\") > $3"
+        );
+        assert_eq!(params, vec!["true", "[\"admin\"]", "2"]);
+    }
+
+    #[test]
+    fn test_json_conditions_mysql_and_sqlite() {
+        use crate::backends::SqlDialect;
+
+        let query = QueryBuilder::<TestUser>::new()
+            .from("users")
+            .where_json("options->languages->0", "en")
+            .where_json_contains("options->roles", "admin")
+            .where_json_length("options->tags", ">=", 1);
+
+        let (mysql, params) = query
+            .clone()
+            .with_dialect(SqlDialect::MySQL)
+            .to_sql_with_params();
+        assert!(mysql.contains("JSON_UNQUOTE(JSON_EXTRACT(`options`, '$.languages[0]')) = ?"));
+        assert!(mysql.contains("JSON_CONTAINS(`options`, ?, '$.roles')"));
+        assert!(mysql.contains("JSON_LENGTH(`options`, '$.tags') >= ?"));
+        assert_eq!(params, vec!["en", "\"admin\"", "1"]);
+
+        let (sqlite, _) = query.with_dialect(SqlDialect::SQLite).to_sql_with_params();
+        assert!(sqlite.contains("json_extract(\"options\", '$.languages[0]') = ?"));
+        assert!(sqlite.contains("json_each(?) AS needle"));
+        assert!(sqlite.contains("json_each(\"options\", '$.roles')"));
+        assert!(sqlite.contains("json_array_length(\"options\", '$.tags') >= ?"));
+    }
+
+    #[test]
+    fn test_full_text_search() {
+        use crate::backends::SqlDialect;
+
+        let query = QueryBuilder::<TestUser>::new()
+            .from("posts")
+            .where_full_text(&["title", "body"], "rust orm")
+            .where_eq("published", true)
+            .order_by_relevance()
+            .limit(10);
+
+        let (sql, params) = query.to_sql_with_params();
+        let document =
+            "to_tsvector('english', coalesce(\"title\", '') || ' ' || coalesce(\"body\", ''))";
+        assert_eq!(
+            sql,
+            format!(
+                "SELECT * FROM \"posts\" WHERE {document} @@ websearch_to_tsquery('english', $1) \
+                 AND \"published\" = $2 \
+                 ORDER BY ts_rank({document}, websearch_to_tsquery('english', $3)) DESC LIMIT 10"
+            )
+        );
+        assert_eq!(params, vec!["rust orm", "true", "rust orm"]);
+
+        let (mysql, params) = query
+            .clone()
+            .with_dialect(SqlDialect::MySQL)
+            .to_sql_with_params();
+        assert!(mysql
+            .contains("WHERE MATCH (`title`, `body`) AGAINST (? IN NATURAL LANGUAGE MODE) AND"));
+        assert!(mysql.contains(
+            "ORDER BY MATCH (`title`, `body`) AGAINST (? IN NATURAL LANGUAGE MODE) DESC"
+        ));
+        assert_eq!(params.len(), 3);
+
+        let (sqlite, _) = query.with_dialect(SqlDialect::SQLite).to_sql_with_params();
+        assert!(sqlite.contains(
+            "\"posts\".rowid IN (SELECT rowid FROM \"posts_fts\" WHERE \"posts_fts\" MATCH ?)"
+        ));
+        assert!(sqlite.contains("ORDER BY (SELECT bm25(\"posts_fts\")"));
+        assert!(sqlite.contains(") ASC LIMIT 10"));
+    }
+
+    #[test]
+    fn test_full_text_language_and_relevance_without_search() {
+        let query = QueryBuilder::<TestUser>::new()
+            .from("posts")
+            .where_full_text(&["title"], "it's")
+            .full_text_language("simple");
+        assert!(query
+            .to_sql()
+            .contains("websearch_to_tsquery('simple', 'it''s')"));
+
+        let query = QueryBuilder::<TestUser>::new()
+            .from("posts")
+            .order_by_relevance()
+            .order_by("id");
+        assert_eq!(query.to_sql(), "SELECT * FROM posts ORDER BY id ASC");
+    }
+
+    #[test]
+    fn test_invalid_full_text_language() {
+        use crate::query::FullTextSearch;
+
+        match FullTextSearch::new("posts", &["title"]).language("english'); --") {
+            Err(ModelError::Query(message)) => {
+                assert!(message.contains("Invalid full-text language"))
+            }
+            other => panic!("expected a query error, got {:?}", other),
+        }
+        let search = FullTextSearch::new("posts", &["title"]).language("simple");
+        assert_eq!(search.unwrap().language, "simple");
+
+        let mut schema = crate::migrations::SchemaBuilder::new();
+        match schema.create_full_text_index_with_language("posts", &["title"], "x'; --", None) {
+            Err(ModelError::Query(message)) => {
+                assert!(message.contains("Invalid full-text language"))
+            }
+            other => panic!("expected a query error, got {:?}", other.map(|_| ())),
+        }
+        assert!(schema.to_sql().is_empty());
+
+        let query = QueryBuilder::<TestUser>::new()
+            .from("posts")
+            .where_full_text(&["title"], "rust")
+            .full_text_language("english'); --");
+        assert!(query
+            .to_sql()
+            .contains("websearch_to_tsquery('english', 'rust')"));
+        match query.validate() {
+            Err(ModelError::Query(message)) => {
+                assert!(message.contains("Invalid full-text language"))
+            }
+            other => panic!("expected a query error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_numbers_compare_numerically() {
+        use crate::backends::SqlDialect;
+
+        let query = QueryBuilder::<TestUser>::new()
+            .from("users")
+            .where_json_condition("stats->score", ">", 10)
+            .where_json_condition("stats->level", "<=", "9");
+
+        let (sql, params) = query.to_sql_with_params();
+        assert_eq!(
+            sql,
+            "SELECT * FROM \"users\" WHERE (\"stats\"->>'score')::numeric > ($1)::numeric \
+             AND \"stats\"->>'level' <= $2"
+        );
+        assert_eq!(params, vec!["10", "9"]);
+
+        let (mysql, _) = query
+            .clone()
+            .with_dialect(SqlDialect::MySQL)
+            .to_sql_with_params();
+        assert!(mysql.contains(
+            "CAST(JSON_UNQUOTE(JSON_EXTRACT(`stats`, '$.score')) AS DECIMAL(65,30)) > CAST(? AS DECIMAL(65,30))"
+        ));
+        let (sqlite, _) = query.with_dialect(SqlDialect::SQLite).to_sql_with_params();
+        assert!(sqlite
+            .contains("CAST(json_extract(\"stats\", '$.score') AS NUMERIC) > CAST(? AS NUMERIC)"));
+    }
+
+    #[test]
+    fn test_unsupported_operators_fall_back_to_equal() {
+        assert_eq!(
+            QueryOperator::from_symbol(">="),
+            Some(QueryOperator::GreaterThanOrEqual)
+        );
+        assert_eq!(QueryOperator::from_symbol("=>"), None);
+
+        let query = QueryBuilder::<TestUser>::new()
+            .from("users")
+            .where_condition("age", "=>", 18);
+        assert!(query.validate().is_ok());
+        let (sql, _) = query.to_sql_with_params_secure().unwrap();
+        assert!(sql.contains("\"age\" = $1"));
+
+        let query = QueryBuilder::<TestUser>::new()
+            .from("users")
+            .where_json_length("tags", "=>", 2);
+        assert!(query.validate().is_ok());
+        assert_eq!(
+            query.to_sql_with_params_secure().unwrap().0,
+            query.to_sql_with_params().0
+        );
+    }
+
+    #[test]
+    fn test_json_path_segments_are_escaped() {
+        let query = QueryBuilder::<TestUser>::new()
+            .from("users")
+            .where_json("meta->it's", "x'; DROP TABLE users; --")
+            .where_json("meta->deleted", Value::Null);
+
+        let (sql, params) = query.to_sql_with_params();
+        assert!(sql.contains("\"meta\"->>'it''s' = $1"));
+        assert!(sql.contains("\"meta\"->>'deleted' IS NULL"));
+        assert_eq!(params, vec!["x'; DROP TABLE users; --"]);
+
+        let simple = query.to_sql();
+        assert!(simple.contains("\"meta\"->>'it''s' = 'x''; DROP TABLE users; --'"));
+    }
 }
 
 #[cfg(test)]